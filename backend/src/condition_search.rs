@@ -0,0 +1,118 @@
+//! Conditional search (`UpMsg::SearchCondition`): the times where an expression over
+//! selected variables starts to hold.
+//!
+//! The expression is evaluated like a derived signal, over the whole files of its inputs,
+//! and a match is each time step where its value turns true. Edge conditions such as
+//! `rising(clk) && en` hold for a single time step, so every edge is a match of its own.
+
+use crate::derived_signals::{compile_expression, evaluate_expression, input_files_of};
+use crate::{run_blocking_signal_job, send_down_msg};
+use moon::*;
+use shared::expression::Bits;
+use shared::{
+    ConditionSearchRequest, DownMsg, MAX_CONDITION_MATCHES, SearchDirection, SignalTransition,
+};
+use std::sync::atomic::AtomicBool;
+
+/// Times where the value turns true, in order
+fn match_times(changes: &[SignalTransition]) -> impl Iterator<Item = u64> + '_ {
+    let mut was_true = false;
+    changes.iter().filter_map(move |change| {
+        let is_true = Bits::parse(&change.value).is_true();
+        let starts = is_true && !was_true;
+        was_true = is_true;
+        starts.then_some(change.time_ps)
+    })
+}
+
+/// Matches for the request's direction and whether more were left out
+fn select_matches(
+    changes: &[SignalTransition],
+    from_ps: u64,
+    direction: SearchDirection,
+) -> (Vec<u64>, bool) {
+    let mut times = match_times(changes);
+    match direction {
+        SearchDirection::Next => (
+            times
+                .find(|&time_ps| time_ps > from_ps)
+                .into_iter()
+                .collect(),
+            false,
+        ),
+        SearchDirection::Previous => (
+            times
+                .take_while(|&time_ps| time_ps < from_ps)
+                .last()
+                .into_iter()
+                .collect(),
+            false,
+        ),
+        SearchDirection::All => {
+            let matches: Vec<u64> = times.by_ref().take(MAX_CONDITION_MATCHES).collect();
+            let truncated = times.next().is_some();
+            (matches, truncated)
+        }
+    }
+}
+
+fn search(request: &ConditionSearchRequest) -> Result<(Vec<u64>, bool), String> {
+    let expr = compile_expression(&request.expression, &request.inputs)?;
+    let changes = evaluate_expression(&expr, &request.inputs, &AtomicBool::new(false))?;
+    Ok(select_matches(&changes, request.from_ps, request.direction))
+}
+
+pub(crate) async fn search_condition(
+    request: ConditionSearchRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let search_id = request.search_id.clone();
+    let files = input_files_of(&request.inputs);
+    let result = run_blocking_signal_job(files, "Search", move || search(&request)).await;
+
+    let msg = match result {
+        Ok((matches, truncated)) => DownMsg::ConditionSearchCompleted {
+            search_id,
+            matches,
+            truncated,
+        },
+        Err(error) => DownMsg::ConditionSearchFailed { search_id, error },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::select_matches;
+    use shared::{PackedTransitions, SearchDirection};
+
+    #[test]
+    fn matches_are_the_times_a_condition_turns_true() {
+        let changes = PackedTransitions::from_pairs(&[
+            (0, "0"),
+            (10, "1"),
+            (15, "1"),
+            (20, "0"),
+            (30, "x"),
+            (40, "1"),
+        ])
+        .to_transitions();
+        assert_eq!(
+            select_matches(&changes, 0, SearchDirection::All),
+            (vec![10, 40], false)
+        );
+        assert_eq!(
+            select_matches(&changes, 10, SearchDirection::Next),
+            (vec![40], false)
+        );
+        assert_eq!(
+            select_matches(&changes, 40, SearchDirection::Previous),
+            (vec![10], false)
+        );
+        assert_eq!(
+            select_matches(&changes, 5, SearchDirection::Previous),
+            (vec![], false)
+        );
+    }
+}
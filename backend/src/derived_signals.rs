@@ -0,0 +1,285 @@
+//! Derived signals (`UpMsg::DefineDerivedSignal`): expressions over loaded signals,
+//! served as signals of `shared::DERIVED_SIGNALS_FILE`.
+//!
+//! Definitions come from the workspace config and from the Derive dialog. The signal cache
+//! evaluates a derived signal on its first query and keeps the result like any other
+//! signal. Changing a definition or reloading any file drops the cached results.
+
+use crate::{
+    CANCELLATION_CHECK_INTERVAL, SIGNAL_CACHE_MANAGER, SIGNAL_QUERY_CANCELLED,
+    run_blocking_signal_job, send_down_msg,
+};
+use moon::*;
+use shared::expression::{Bits, Expr};
+use shared::{
+    DerivedSignal, DownMsg, PackedEncoding, PackedTransitions, SignalTransition,
+    UnifiedSignalRequest,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DERIVED_SIGNALS: Lazy<Mutex<HashMap<String, DerivedSignal>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Replace all definitions, e.g. from a loaded or saved workspace config
+pub(crate) fn set_derived_signals(signals: &[DerivedSignal]) {
+    let signals: HashMap<String, DerivedSignal> = signals
+        .iter()
+        .map(|signal| (signal.name.clone(), signal.clone()))
+        .collect();
+    let mut stored = match DERIVED_SIGNALS.lock() {
+        Ok(stored) => stored,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if *stored != signals {
+        *stored = signals;
+        SIGNAL_CACHE_MANAGER.invalidate_file(shared::DERIVED_SIGNALS_FILE);
+    }
+}
+
+fn derived_signal(name: &str) -> Option<DerivedSignal> {
+    let signals = match DERIVED_SIGNALS.lock() {
+        Ok(signals) => signals,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    signals.get(name).cloned()
+}
+
+/// Files the variables bound to expression operands come from
+pub(crate) fn input_files_of(inputs: &BTreeMap<String, String>) -> Vec<String> {
+    let mut files: Vec<String> = inputs
+        .values()
+        .filter_map(|unique_id| unique_id.split_once('|'))
+        .map(|(file_path, _)| file_path.to_string())
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Files whose bodies a signal is read from: its own file, or the inputs of a derived signal
+pub(crate) fn source_files(file_path: &str, variable_name: &str) -> Vec<String> {
+    if file_path != shared::DERIVED_SIGNALS_FILE {
+        return vec![file_path.to_string()];
+    }
+    derived_signal(variable_name)
+        .map(|signal| input_files_of(&signal.inputs))
+        .unwrap_or_default()
+}
+
+/// Parse an expression and check that each operand is bound to a variable of a file
+pub(crate) fn compile_expression(
+    expression: &str,
+    inputs: &BTreeMap<String, String>,
+) -> Result<Expr, String> {
+    let expr = shared::expression::parse(expression)?;
+    if let Some(operand) = expr
+        .signals()
+        .into_iter()
+        .find(|operand| !inputs.contains_key(operand))
+    {
+        return Err(format!("'{}' is not a selected variable", operand));
+    }
+    if inputs
+        .values()
+        .any(|unique_id| shared::derived_signal_name(unique_id).is_some())
+    {
+        return Err("Expressions cannot use derived signals".to_string());
+    }
+    Ok(expr)
+}
+
+fn compile(signal: &DerivedSignal) -> Result<Expr, String> {
+    if signal.name.is_empty()
+        || !signal
+            .name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err("Names may only use letters, digits and '_'".to_string());
+    }
+    compile_expression(&signal.expression, &signal.inputs)
+}
+
+fn load_input(
+    operand: &str,
+    unique_id: &str,
+    cancelled: &AtomicBool,
+) -> Result<PackedTransitions, String> {
+    let mut parts = unique_id.splitn(3, '|');
+    let (Some(file_path), Some(scope_path), Some(variable_name)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("Invalid signal id '{}'", unique_id));
+    };
+    let query = UnifiedSignalRequest {
+        file_path: file_path.to_string(),
+        scope_path: scope_path.to_string(),
+        variable_name: variable_name.to_string(),
+        time_range_ps: None,
+        max_transitions: None,
+        format: shared::VarFormat::default(),
+    };
+    let transitions = SIGNAL_CACHE_MANAGER
+        .get_or_load_signal_data(&query, cancelled)?
+        .packed_transitions
+        .unwrap_or_default();
+    if matches!(
+        transitions.encoding,
+        PackedEncoding::Real | PackedEncoding::Text
+    ) {
+        return Err(format!("'{}' is not a bit vector", operand));
+    }
+    Ok(transitions)
+}
+
+/// Evaluate the expression at every time step where an operand changes. The result is
+/// undefined until every operand has a value.
+fn evaluate_transitions(
+    expr: &Expr,
+    inputs: &[(String, PackedTransitions)],
+    cancelled: &AtomicBool,
+) -> Result<Vec<SignalTransition>, String> {
+    let mut next = vec![0usize; inputs.len()];
+    let mut values: HashMap<String, Bits> = HashMap::new();
+    // Values before the current time step, for edges
+    let mut previous: HashMap<String, Bits> = HashMap::new();
+    let mut changed: Vec<&str> = Vec::new();
+    let mut changes: Vec<SignalTransition> = Vec::new();
+
+    for step in 0usize.. {
+        if step % CANCELLATION_CHECK_INTERVAL == 0 && cancelled.load(Ordering::Relaxed) {
+            return Err(SIGNAL_QUERY_CANCELLED.to_string());
+        }
+        let Some(time_ps) = inputs
+            .iter()
+            .zip(&next)
+            .filter_map(|((_, transitions), &index)| transitions.times_ps.get(index).copied())
+            .min()
+        else {
+            break;
+        };
+        for operand in changed.drain(..) {
+            previous.insert(operand.to_string(), values[operand].clone());
+        }
+        // Several changes at one time step leave the last value
+        for ((operand, transitions), index) in inputs.iter().zip(next.iter_mut()) {
+            while transitions.times_ps.get(*index) == Some(&time_ps) {
+                *index += 1;
+            }
+            if *index > 0 && transitions.times_ps[*index - 1] == time_ps {
+                values.insert(
+                    operand.clone(),
+                    Bits::parse(&transitions.value_string(*index - 1)),
+                );
+                changed.push(operand);
+            }
+        }
+        if values.len() < inputs.len() {
+            continue;
+        }
+        let value = expr.evaluate_step(&values, &previous)?.to_string();
+        if changes.last().map(|change| &change.value) != Some(&value) {
+            changes.push(SignalTransition::new(time_ps, value));
+        }
+    }
+    Ok(changes)
+}
+
+/// Value changes of a compiled expression over the whole files of its inputs
+pub(crate) fn evaluate_expression(
+    expr: &Expr,
+    inputs: &BTreeMap<String, String>,
+    cancelled: &AtomicBool,
+) -> Result<Vec<SignalTransition>, String> {
+    let inputs = expr
+        .signals()
+        .into_iter()
+        .map(|operand| {
+            let transitions = load_input(&operand, &inputs[&operand], cancelled)?;
+            Ok((operand, transitions))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    evaluate_transitions(expr, &inputs, cancelled)
+}
+
+fn evaluate(signal: &DerivedSignal, cancelled: &AtomicBool) -> Result<PackedTransitions, String> {
+    let expr = compile(signal)?;
+    let changes = evaluate_expression(&expr, &signal.inputs, cancelled)?;
+    Ok(PackedTransitions::from_transitions(&changes))
+}
+
+/// Transitions of a registered derived signal, in timeline picoseconds
+pub(crate) fn evaluate_derived_signal(
+    name: &str,
+    cancelled: &AtomicBool,
+) -> Result<PackedTransitions, String> {
+    let signal =
+        derived_signal(name).ok_or_else(|| format!("Derived signal '{}' is not defined", name))?;
+    evaluate(&signal, cancelled)
+}
+
+/// Check a definition by evaluating it once, then register it
+pub(crate) async fn define_derived_signal(
+    signal: DerivedSignal,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let name = signal.name.clone();
+    let result = match compile(&signal) {
+        Ok(_) => {
+            let worker_signal = signal.clone();
+            run_blocking_signal_job(input_files_of(&signal.inputs), "Evaluation", move || {
+                evaluate(&worker_signal, &AtomicBool::new(false)).map(|_| ())
+            })
+            .await
+        }
+        Err(error) => Err(error),
+    };
+
+    let msg = match result {
+        Ok(()) => {
+            {
+                let mut signals = match DERIVED_SIGNALS.lock() {
+                    Ok(signals) => signals,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                signals.insert(name.clone(), signal);
+            }
+            SIGNAL_CACHE_MANAGER.invalidate_file(shared::DERIVED_SIGNALS_FILE);
+            DownMsg::DerivedSignalDefined { name }
+        }
+        Err(error) => DownMsg::DerivedSignalFailed { name, error },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::evaluate_transitions;
+    use shared::PackedTransitions;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn expression_changes_only_when_its_value_does() {
+        let expr = shared::expression::parse("valid && ready").unwrap();
+        let inputs = vec![
+            (
+                "valid".to_string(),
+                PackedTransitions::from_pairs(&[(0, "0"), (10, "1"), (30, "0")]),
+            ),
+            (
+                "ready".to_string(),
+                PackedTransitions::from_pairs(&[(5, "1"), (20, "0"), (20, "1"), (25, "0")]),
+            ),
+        ];
+        let changes = evaluate_transitions(&expr, &inputs, &AtomicBool::new(false)).unwrap();
+        let changes: Vec<(u64, &str)> = changes
+            .iter()
+            .map(|change| (change.time_ps, change.value.as_str()))
+            .collect();
+        // Undefined before `ready` has a value; the glitch at 20 nets out
+        assert_eq!(changes, vec![(5, "0"), (10, "1"), (25, "0")]);
+    }
+}
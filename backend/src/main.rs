@@ -1,17 +1,26 @@
+mod condition_search;
+mod derived_signals;
 mod plugins;
+mod signal_measurement;
+mod value_search;
+mod waveform_diff;
+mod waveform_export;
+mod waveform_import;
 
 use jwalk::WalkDir;
 use moon::*;
 use serde::{Deserialize, Serialize};
 use shared::{
     self, AppConfig, CanonicalPathPayload, DownMsg, FileError, FileFormat, FileHierarchy,
-    FileSystemItem, PlatformRoot, ScopeData, SignalStatistics, SignalTransition,
-    SignalTransitionQuery, SignalTransitionResult, SignalValue, SignalValueQuery,
-    SignalValueResult, UnifiedSignalData, UnifiedSignalRequest, UpMsg, WaveformFile,
+    FileSystemItem, LoadPhase, LoadProgress, PackedEncoding, PackedTransitions, PlatformRoot,
+    ScopeData, SignalPayloadEncoding, SignalStatistics, SignalTransition, SignalTransitionQuery,
+    SignalTransitionResult, SignalValue, SignalValueQuery, SignalValueResult, TransitionBucket,
+    UnifiedSignalData, UnifiedSignalRequest, UpMsg, WaveformFile,
 };
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use std::time::Instant;
 use tokio::sync::Notify;
@@ -43,24 +52,76 @@ static PARSING_SESSIONS: Lazy<Arc<Mutex<HashMap<String, Arc<Mutex<f32>>>>>> =
 
 // Storage for parsed waveform data to enable signal value queries
 struct WaveformData {
-    hierarchy: wellen::Hierarchy,
+    hierarchy: Arc<wellen::Hierarchy>,
     signal_source: Arc<Mutex<wellen::SignalSource>>,
-    time_table: Vec<wellen::Time>,
+    time_table: Arc<Vec<wellen::Time>>,
     signals: HashMap<String, wellen::SignalRef>, // scope_path|variable_name -> SignalRef
     file_format: wellen::FileFormat,             // Store file format for proper time conversion
     timescale_factor: f64, // Conversion factor from VCD native units to seconds
+    time_offset_ps: i64,   // Shift of the file on the timeline (see `FileTimeAlignment`)
+}
+
+impl WaveformData {
+    /// Timeline time of a time table entry
+    fn time_ps(&self, time_native: u64) -> u64 {
+        shift_ps(
+            native_time_to_ps(time_native, self.timescale_factor),
+            self.time_offset_ps,
+        )
+    }
+
+    /// File time in picoseconds of a timeline time
+    fn file_time_ps(&self, time_ps: u64) -> u64 {
+        shift_ps(time_ps, -self.time_offset_ps)
+    }
+
+    /// Native file time of a timeline time given in seconds
+    fn native_time_at(&self, time_seconds: f64) -> u64 {
+        let file_seconds = time_seconds - self.time_offset_ps as f64 / PS_PER_SECOND;
+        (file_seconds / self.timescale_factor).max(0.0) as u64
+    }
 }
 
 static WAVEFORM_DATA_STORE: Lazy<Arc<Mutex<HashMap<String, WaveformData>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+const PS_PER_SECOND: f64 = 1_000_000_000_000.0;
+
+/// Convert a native file timestamp to picoseconds.
+/// Timescales that are a whole number of picoseconds (1ps and coarser) are scaled
+/// with integer math so large timestamps keep their exact value; finer timescales
+/// (e.g. fs) are rounded to the nearest picosecond.
+fn native_time_to_ps(time_native: u64, timescale_factor: f64) -> u64 {
+    let ps_per_unit = timescale_factor * PS_PER_SECOND;
+    let whole_ps_per_unit = ps_per_unit.round();
+    if whole_ps_per_unit >= 1.0 && (ps_per_unit - whole_ps_per_unit).abs() <= 1e-6 {
+        time_native.saturating_mul(whole_ps_per_unit as u64)
+    } else {
+        (time_native as f64 * ps_per_unit).round() as u64
+    }
+}
+
+/// Move a time by a file's offset; times moved before zero stay at zero
+fn shift_ps(time_ps: u64, offset_ps: i64) -> u64 {
+    time_ps.saturating_add_signed(offset_ps)
+}
+
+/// Offset a file whose first timestamp is `first_ps` can move by. A negative shift stops
+/// once that timestamp reaches 0, so early transitions never pile up at the origin.
+fn clamped_offset_ps(offset_ps: i64, first_ps: u64) -> i64 {
+    offset_ps.max(-(first_ps.min(i64::MAX as u64) as i64))
+}
+
 // Lightweight metadata for deferred loading - avoids loading GB files into memory
 #[derive(Clone)]
 struct WaveformMetadata {
     _file_path: String, // Stored for future use
     file_format: wellen::FileFormat,
     timescale_factor: f64,
-    _time_bounds: (f64, f64), // Stored for future use
+    /// First and last time of the file in seconds, at the parsed timescale
+    time_bounds: (f64, f64),
+    /// Recovered prefix length of a truncated VCD; body loads stop there
+    vcd_body_limit: Option<u64>,
 }
 
 static WAVEFORM_METADATA_STORE: Lazy<Arc<Mutex<HashMap<String, WaveformMetadata>>>> =
@@ -74,10 +135,59 @@ static VCD_LOADING_IN_PROGRESS: Lazy<Arc<Mutex<std::collections::HashSet<String>
 static LOADING_NOTIFIERS: Lazy<Arc<Mutex<HashMap<String, Arc<Notify>>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+// Cancellation flags of in-flight header/body loads, keyed by file path
+static LOAD_CANCELLATIONS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 // Serialize config file read-modify-write operations to prevent lost updates
 // This protects global config file (workspace history) from concurrent modification
 static CONFIG_FILE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
+/// Give a starting load a fresh cancellation flag (the caller has marked it in VCD_LOADING_IN_PROGRESS)
+fn register_load(file_path: &str) {
+    let mut cancellations = match LOAD_CANCELLATIONS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    cancellations.insert(file_path.to_string(), Arc::new(AtomicBool::new(false)));
+}
+
+fn is_load_cancelled(file_path: &str) -> bool {
+    let cancellations = match LOAD_CANCELLATIONS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    cancellations
+        .get(file_path)
+        .is_some_and(|flag| flag.load(Ordering::Relaxed))
+}
+
+/// Cancellation flag of a running load, for stopping work that can't poll by file path
+fn load_cancel_flag(file_path: &str) -> Option<Arc<AtomicBool>> {
+    let cancellations = match LOAD_CANCELLATIONS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    cancellations.get(file_path).cloned()
+}
+
+/// Request cancellation of a running load; returns false when nothing was loading.
+/// A truncated VCD's body parse stops at its next read of the file (see `LoadInput`);
+/// other loads stop after their current parse stage and drop its result.
+fn cancel_load(file_path: &str) -> bool {
+    let cancellations = match LOAD_CANCELLATIONS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match cancellations.get(file_path) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
 /// Cleanup loading state and notify all waiters that loading is complete (success or failure)
 fn complete_loading(file_path: &str) {
     // ATOMIC CLEANUP: Acquire both locks before modifying any state
@@ -97,9 +207,20 @@ fn complete_loading(file_path: &str) {
         }
     }
     // Both locks released together here
+
+    match LOAD_CANCELLATIONS.lock() {
+        Ok(mut cancellations) => {
+            cancellations.remove(file_path);
+        }
+        Err(poisoned) => {
+            poisoned.into_inner().remove(file_path);
+        }
+    }
 }
 
-fn invalidate_waveform_resources(file_path: &str) {
+/// Drop every stored resource of a file.
+/// Returns the estimated freed bytes and whether a parsed waveform body was held.
+fn invalidate_waveform_resources(file_path: &str) -> (usize, bool) {
     // ATOMIC INVALIDATION: Acquire all locks before modifying any store
     // This prevents race conditions where another thread sees partial state
     // (e.g., data exists but metadata doesn't, or cache has stale entries)
@@ -108,26 +229,210 @@ fn invalidate_waveform_resources(file_path: &str) {
     let cache_guard = SIGNAL_CACHE_MANAGER.transition_cache.write();
 
     // Now perform all removals while holding all locks
+    let mut freed_bytes = 0;
+    let mut body_released = false;
     if let Ok(mut store) = data_guard {
-        store.remove(file_path);
+        if let Some(data) = store.remove(file_path) {
+            // The wellen signal source is opaque; count the parts whose size is known
+            freed_bytes += data.time_table.len() * std::mem::size_of::<wellen::Time>()
+                + data
+                    .signals
+                    .keys()
+                    .map(|key| key.len() + std::mem::size_of::<wellen::SignalRef>())
+                    .sum::<usize>();
+            body_released = true;
+        }
     }
     if let Ok(mut metadata) = metadata_guard {
         metadata.remove(file_path);
     }
     if let Ok(mut cache) = cache_guard {
-        let prefix = format!("{}|", file_path);
-        cache.retain(|key, _| !key.starts_with(&prefix));
+        freed_bytes += cache.remove_file(file_path);
+        // Derived signals may read the file; they are evaluated again on demand
+        freed_bytes += cache.remove_file(shared::DERIVED_SIGNALS_FILE);
     }
     // All locks released together here
+
+    (freed_bytes, body_released)
 }
 
 // ===== UNIFIED SIGNAL CACHE MANAGER =====
 
+/// Time steps between cancellation checks while extracting a signal
+const CANCELLATION_CHECK_INTERVAL: usize = 1 << 16;
+
+/// The frontend asks for four transitions per canvas pixel, so this yields one LOD bucket per pixel
+const LOD_TRANSITIONS_PER_BUCKET: usize = 4;
+
+/// Default byte budget for decoded transitions; override with NOVYWAVE_SIGNAL_CACHE_MB
+const DEFAULT_SIGNAL_CACHE_BUDGET_BYTES: usize = 1024 * 1024 * 1024;
+
+fn signal_cache_budget_bytes() -> usize {
+    std::env::var("NOVYWAVE_SIGNAL_CACHE_MB")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|megabytes| *megabytes > 0)
+        .map(|megabytes| megabytes.saturating_mul(1024 * 1024))
+        .unwrap_or(DEFAULT_SIGNAL_CACHE_BUDGET_BYTES)
+}
+
+struct CachedTransitions {
+    transitions: Arc<PackedTransitions>,
+    size_bytes: usize,
+    /// Logical clock of the latest access, the entry's key in `TransitionCache::recency`
+    last_access: AtomicU64,
+}
+
+/// Byte-bounded LRU store of packed transitions keyed by "file|scope|variable".
+/// Tracks memory per file and in total; the least recently used signals are
+/// evicted once the budget is exceeded.
+struct TransitionCache {
+    entries: HashMap<String, CachedTransitions>,
+    /// Keys ordered by last access, oldest first; behind a mutex so hits can
+    /// reorder it under a read lock
+    recency: Mutex<BTreeMap<u64, String>>,
+    file_bytes: BTreeMap<String, usize>,
+    total_bytes: usize,
+    budget_bytes: usize,
+    access_clock: AtomicU64,
+}
+
+impl TransitionCache {
+    fn new(budget_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            recency: Mutex::new(BTreeMap::new()),
+            file_bytes: BTreeMap::new(),
+            total_bytes: 0,
+            budget_bytes,
+            access_clock: AtomicU64::new(0),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn file_of(key: &str) -> &str {
+        key.split('|').next().unwrap_or(key)
+    }
+
+    fn estimate_size(key: &str, transitions: &PackedTransitions) -> usize {
+        key.len() + std::mem::size_of::<CachedTransitions>() + transitions.size_bytes()
+    }
+
+    fn tick(&self) -> u64 {
+        self.access_clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn recency_mut(&mut self) -> &mut BTreeMap<u64, String> {
+        match self.recency.get_mut() {
+            Ok(recency) => recency,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Look up a signal and mark it as most recently used
+    fn get(&self, key: &str) -> Option<&Arc<PackedTransitions>> {
+        let entry = self.entries.get(key)?;
+        let mut recency = match self.recency.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let now = self.tick();
+        let previous = entry.last_access.swap(now, Ordering::Relaxed);
+        let key = recency.remove(&previous).unwrap_or_else(|| key.to_string());
+        recency.insert(now, key);
+        Some(&entry.transitions)
+    }
+
+    /// Look up a signal without affecting its eviction order
+    fn peek(&self, key: &str) -> Option<&Arc<PackedTransitions>> {
+        self.entries.get(key).map(|entry| &entry.transitions)
+    }
+
+    /// Insert a signal and evict least recently used entries until the budget holds.
+    /// Returns the number of evicted entries and their total size in bytes.
+    fn insert(&mut self, key: String, transitions: Arc<PackedTransitions>) -> (usize, usize) {
+        self.remove(&key);
+
+        let size_bytes = Self::estimate_size(&key, &transitions);
+        *self
+            .file_bytes
+            .entry(Self::file_of(&key).to_string())
+            .or_insert(0) += size_bytes;
+        self.total_bytes += size_bytes;
+        let now = self.tick();
+        self.recency_mut().insert(now, key.clone());
+        self.entries.insert(
+            key,
+            CachedTransitions {
+                transitions,
+                size_bytes,
+                last_access: AtomicU64::new(now),
+            },
+        );
+
+        let mut evicted = 0;
+        let mut evicted_bytes = 0;
+        // The newest entry always stays, even if it alone exceeds the budget
+        while self.total_bytes > self.budget_bytes && self.entries.len() > 1 {
+            let Some((_, victim)) = self.recency_mut().pop_first() else {
+                break;
+            };
+            evicted_bytes += self.remove(&victim);
+            evicted += 1;
+        }
+        (evicted, evicted_bytes)
+    }
+
+    /// Remove a single signal; returns the freed bytes
+    fn remove(&mut self, key: &str) -> usize {
+        let Some(entry) = self.entries.remove(key) else {
+            return 0;
+        };
+        self.recency_mut()
+            .remove(&entry.last_access.load(Ordering::Relaxed));
+        self.total_bytes = self.total_bytes.saturating_sub(entry.size_bytes);
+        let file = Self::file_of(key);
+        if let Some(bytes) = self.file_bytes.get_mut(file) {
+            *bytes = bytes.saturating_sub(entry.size_bytes);
+            if *bytes == 0 {
+                self.file_bytes.remove(file);
+            }
+        }
+        entry.size_bytes
+    }
+
+    /// Remove every signal of a file; returns the freed bytes
+    fn remove_file(&mut self, file_path: &str) -> usize {
+        let prefix = format!("{}|", file_path);
+        let keys: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect();
+        keys.iter().map(|key| self.remove(key)).sum()
+    }
+
+    fn file_bytes(&self, file_path: &str) -> usize {
+        self.file_bytes.get(file_path).copied().unwrap_or(0)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency_mut().clear();
+        self.file_bytes.clear();
+        self.total_bytes = 0;
+    }
+}
+
 /// High-performance signal cache manager for desktop applications
-/// Uses Arc<RwLock<BTreeMap>> for efficient concurrent access
+/// Uses Arc<RwLock<TransitionCache>> for efficient concurrent access
 struct SignalCacheManager {
-    /// Complete signal transition data indexed by unique signal ID
-    transition_cache: Arc<RwLock<BTreeMap<String, Arc<[SignalTransition]>>>>,
+    /// Complete signal transition data indexed by unique signal ID, bounded by a byte budget
+    transition_cache: Arc<RwLock<TransitionCache>>,
     /// Cache statistics for performance monitoring
     cache_stats: Arc<RwLock<CacheStats>>,
 }
@@ -137,24 +442,27 @@ struct CacheStats {
     total_queries: usize,
     cache_hits: usize,
     cache_misses: usize,
+    evictions: usize,
 }
 
 impl SignalCacheManager {
     fn new() -> Self {
+        Self::with_budget(signal_cache_budget_bytes())
+    }
+
+    fn with_budget(budget_bytes: usize) -> Self {
         Self {
-            transition_cache: Arc::new(RwLock::new(BTreeMap::new())),
+            transition_cache: Arc::new(RwLock::new(TransitionCache::new(budget_bytes))),
             cache_stats: Arc::new(RwLock::new(CacheStats::default())),
         }
     }
 
-    #[allow(dead_code)]
-    fn invalidate_file(&self, file_path: &str) {
-        let prefix = format!("{}|", file_path);
+    fn invalidate_file(&self, file_path: &str) -> usize {
         let mut cache = match self.transition_cache.write() {
             Ok(guard) => guard,
             Err(poisoned) => poisoned.into_inner(),
         };
-        cache.retain(|key, _| !key.starts_with(&prefix));
+        cache.remove_file(file_path)
     }
 
     #[allow(dead_code)]
@@ -172,6 +480,7 @@ impl SignalCacheManager {
         &self,
         signal_requests: Vec<UnifiedSignalRequest>,
         cursor_time: Option<u64>,
+        cancelled: &AtomicBool,
     ) -> Result<
         (
             Vec<UnifiedSignalData>,
@@ -187,11 +496,15 @@ impl SignalCacheManager {
         // Ensure all referenced waveform bodies are loaded before processing requests
         let mut ensured_files = HashSet::new();
         for request in &signal_requests {
-            if ensured_files.insert(request.file_path.clone()) {
-                match ensure_waveform_body_loaded(&request.file_path).await {
-                    Ok(()) => {}
-                    Err(error) => {
-                        return Err(error);
+            for file_path in
+                derived_signals::source_files(&request.file_path, &request.variable_name)
+            {
+                if ensured_files.insert(file_path.clone()) {
+                    match ensure_waveform_body_loaded(&file_path).await {
+                        Ok(()) => {}
+                        Err(error) => {
+                            return Err(error);
+                        }
                     }
                 }
             }
@@ -202,12 +515,15 @@ impl SignalCacheManager {
         let mut missing_signals: Vec<(String, String, String, String, Option<(u64, u64)>)> =
             Vec::new();
         for request in &signal_requests {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(SIGNAL_QUERY_CANCELLED.to_string());
+            }
             let unique_id = format!(
                 "{}|{}|{}",
                 request.file_path, request.scope_path, request.variable_name
             );
 
-            match self.get_or_load_signal_data(request) {
+            match self.get_or_load_signal_data(request, cancelled) {
                 Ok(data) => signal_data.push(data),
                 Err(err) => {
                     if err.starts_with("Signal data not found:") {
@@ -222,7 +538,7 @@ impl SignalCacheManager {
                             request.file_path.clone(),
                             request.scope_path.clone(),
                             request.variable_name.clone(),
-                            request.time_range_ns,
+                            request.time_range_ps,
                         ));
                     } else {
                         return Err(err);
@@ -238,8 +554,11 @@ impl SignalCacheManager {
                 variable_name,
                 unique_id,
                 transitions: Vec::new(),
+                packed_transitions: None,
+                packed_binary: None,
                 total_transitions: 0,
-                actual_time_range_ns: requested_range,
+                actual_time_range_ps: requested_range,
+                busy_buckets: Vec::new(),
             });
         }
 
@@ -252,30 +571,48 @@ impl SignalCacheManager {
 
         // Update cache statistics (poison recovery)
         let query_time = start_time.elapsed().as_millis() as u64;
-        let (total_queries, cache_hits) = {
+        let (cache_hits, cache_misses, cache_evictions) = {
             let mut stats = match self.cache_stats.write() {
                 Ok(guard) => guard,
                 Err(poisoned) => poisoned.into_inner(),
             };
             stats.total_queries += 1;
-            (stats.total_queries, stats.cache_hits)
+            (stats.cache_hits, stats.cache_misses, stats.evictions)
         };
-        let cache_hit_ratio = if total_queries > 0 {
-            cache_hits as f64 / total_queries as f64
+        let lookups = cache_hits + cache_misses;
+        let cache_hit_ratio = if lookups > 0 {
+            cache_hits as f64 / lookups as f64
         } else {
             0.0
         };
+        let (cached_signals, cache_memory_bytes, cache_budget_bytes, cache_file_bytes) = {
+            let cache = match self.transition_cache.read() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            (
+                cache.len(),
+                cache.total_bytes,
+                cache.budget_bytes,
+                cache.file_bytes.clone(),
+            )
+        };
 
         let statistics = SignalStatistics {
             total_signals: signal_data.len(),
-            cached_signals: cache_hits,
+            cached_signals,
             query_time_ms: query_time,
             cache_hit_ratio,
+            cache_hits,
+            cache_evictions,
+            cache_memory_bytes,
+            cache_budget_bytes,
+            cache_file_bytes,
         };
 
         let sample_counts: Vec<_> = signal_data
             .iter()
-            .map(|data| (data.unique_id.as_str(), data.transitions.len()))
+            .map(|data| (data.unique_id.as_str(), data.transition_count()))
             .collect();
         debug_log!(
             DEBUG_BACKEND,
@@ -292,6 +629,7 @@ impl SignalCacheManager {
     fn get_or_load_signal_data(
         &self,
         request: &UnifiedSignalRequest,
+        cancelled: &AtomicBool,
     ) -> Result<UnifiedSignalData, String> {
         let unique_id = format!(
             "{}|{}|{}",
@@ -327,25 +665,7 @@ impl SignalCacheManager {
                 };
                 stats.cache_hits += 1;
 
-                let range_vec = self.collect_range(transitions, request.time_range_ns);
-
-                // Downsample if requested
-                let final_transitions = if let Some(max_transitions) = request.max_transitions {
-                    self.downsample_transitions(range_vec, max_transitions)
-                } else {
-                    range_vec
-                };
-
-                let total_transitions = transitions.len();
-                return Ok(UnifiedSignalData {
-                    file_path: request.file_path.clone(),
-                    scope_path: request.scope_path.clone(),
-                    variable_name: request.variable_name.clone(),
-                    unique_id: unique_id.clone(),
-                    transitions: final_transitions,
-                    total_transitions,
-                    actual_time_range_ns: self.compute_time_range(&transitions[..]),
-                });
+                return Ok(self.build_signal_data(request, &unique_id, transitions));
             }
         }
 
@@ -355,7 +675,7 @@ impl SignalCacheManager {
             "🔍 SIGNAL_CACHE_MANAGER: Cache MISS for '{}' - loading from waveform",
             unique_id
         );
-        self.load_signal_from_waveform(request, &unique_id)
+        self.load_signal_from_waveform(request, &unique_id, cancelled)
     }
 
     /// Load signal data from the waveform data store
@@ -364,6 +684,7 @@ impl SignalCacheManager {
         &self,
         request: &UnifiedSignalRequest,
         unique_id: &str,
+        cancelled: &AtomicBool,
     ) -> Result<UnifiedSignalData, String> {
         // DOUBLE-CHECKED LOCKING: Re-check cache with write lock before loading
         // Another thread may have inserted between our read check and now
@@ -387,26 +708,21 @@ impl SignalCacheManager {
                 };
                 stats.cache_hits += 1;
 
-                let range_vec = self.collect_range(transitions, request.time_range_ns);
-                let final_transitions = if let Some(max_transitions) = request.max_transitions {
-                    self.downsample_transitions(range_vec, max_transitions)
-                } else {
-                    range_vec
-                };
-
-                return Ok(UnifiedSignalData {
-                    file_path: request.file_path.clone(),
-                    scope_path: request.scope_path.clone(),
-                    variable_name: request.variable_name.clone(),
-                    unique_id: unique_id.to_string(),
-                    transitions: final_transitions,
-                    total_transitions: transitions.len(),
-                    actual_time_range_ns: self.compute_time_range(&transitions[..]),
-                });
+                return Ok(self.build_signal_data(request, unique_id, transitions));
             }
             // Drop cache lock before accessing waveform store (prevents lock ordering issues)
         }
 
+        if let Some(name) = shared::derived_signal_name(unique_id) {
+            let transitions_arc = match derived_signals::evaluate_derived_signal(name, cancelled) {
+                Ok(transitions) => Arc::new(transitions),
+                Err(error) if error == SIGNAL_QUERY_CANCELLED => return Err(error),
+                Err(error) => return Err(format!("Derived signal '{}' failed: {}", name, error)),
+            };
+            self.cache_loaded_transitions(unique_id, &transitions_arc);
+            return Ok(self.build_signal_data(request, unique_id, &transitions_arc));
+        }
+
         // Load from waveform store (poison recovery)
         let waveform_store = match WAVEFORM_DATA_STORE.lock() {
             Ok(guard) => guard,
@@ -444,50 +760,17 @@ impl SignalCacheManager {
                     "🔍 WAVEFORM_STORE: Found signal '{}' - extracting transitions",
                     signal_key
                 );
-                let transitions_vec = self.extract_transitions_from_wellen(
+                let transitions_arc = Arc::new(self.extract_transitions_from_wellen(
                     waveform_data,
                     signal_ref,
                     &request.format,
                     &signal_key,
-                )?;
+                    cancelled,
+                )?);
 
-                let transitions_arc: Arc<[SignalTransition]> = transitions_vec.into();
-
-                // Cache the loaded data AND update stats atomically (poison recovery)
-                {
-                    let mut cache = match self.transition_cache.write() {
-                        Ok(guard) => guard,
-                        Err(poisoned) => poisoned.into_inner(),
-                    };
-                    cache.insert(unique_id.to_string(), Arc::clone(&transitions_arc));
-
-                    // Update stats AFTER successful cache insert
-                    let mut stats = match self.cache_stats.write() {
-                        Ok(guard) => guard,
-                        Err(poisoned) => poisoned.into_inner(),
-                    };
-                    stats.cache_misses += 1;
-                }
-
-                // Filter by time range and downsample
-                let filtered_transitions =
-                    self.collect_range(&transitions_arc, request.time_range_ns);
-
-                let final_transitions = if let Some(max_transitions) = request.max_transitions {
-                    self.downsample_transitions(filtered_transitions, max_transitions)
-                } else {
-                    filtered_transitions
-                };
+                self.cache_loaded_transitions(unique_id, &transitions_arc);
 
-                return Ok(UnifiedSignalData {
-                    file_path: request.file_path.clone(),
-                    scope_path: request.scope_path.clone(),
-                    variable_name: request.variable_name.clone(),
-                    unique_id: unique_id.to_string(),
-                    transitions: final_transitions,
-                    total_transitions: transitions_arc.len(),
-                    actual_time_range_ns: self.compute_time_range(&transitions_arc[..]),
-                });
+                return Ok(self.build_signal_data(request, unique_id, &transitions_arc));
             } else {
                 debug_log!(
                     DEBUG_WAVEFORM_STORE,
@@ -506,6 +789,98 @@ impl SignalCacheManager {
         Err(format!("Signal data not found: {}", unique_id))
     }
 
+    /// Cache freshly loaded transitions AND update stats atomically (poison recovery)
+    fn cache_loaded_transitions(&self, unique_id: &str, transitions: &Arc<PackedTransitions>) {
+        let mut cache = match self.transition_cache.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let (evicted, evicted_bytes) = cache.insert(unique_id.to_string(), Arc::clone(transitions));
+        if evicted > 0 {
+            debug_log!(
+                DEBUG_SIGNAL_CACHE,
+                "🔍 SIGNAL_CACHE_MANAGER: Evicted {} signals ({} bytes) to fit '{}'",
+                evicted,
+                evicted_bytes,
+                unique_id
+            );
+        }
+
+        // Update stats AFTER successful cache insert
+        let mut stats = match self.cache_stats.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        stats.cache_misses += 1;
+        stats.evictions += evicted;
+    }
+
+    /// Full-resolution transitions of one signal for batch jobs that touch every signal of a
+    /// file. Served from the cache when present, otherwise extracted without caching, so the
+    /// scan neither evicts the working set nor reorders the LRU.
+    fn read_transitions_uncached(
+        &self,
+        file_path: &str,
+        signal_key: &str,
+        cancelled: &AtomicBool,
+    ) -> Result<Arc<PackedTransitions>, String> {
+        let unique_id = format!("{}|{}", file_path, signal_key);
+        {
+            let cache = match self.transition_cache.read() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if let Some(transitions) = cache.peek(&unique_id) {
+                return Ok(Arc::clone(transitions));
+            }
+        }
+
+        let waveform_store = match WAVEFORM_DATA_STORE.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let waveform_data = waveform_store
+            .get(file_path)
+            .ok_or_else(|| format!("Waveform data not loaded for '{}'", file_path))?;
+        let signal_ref = waveform_data
+            .signals
+            .get(signal_key)
+            .ok_or_else(|| format!("Signal data not found: {}", unique_id))?;
+        self.extract_transitions_from_wellen(
+            waveform_data,
+            signal_ref,
+            &shared::VarFormat::default(),
+            signal_key,
+            cancelled,
+        )
+        .map(Arc::new)
+    }
+
+    /// Filter cached transitions to the requested range and level of detail
+    fn build_signal_data(
+        &self,
+        request: &UnifiedSignalRequest,
+        unique_id: &str,
+        transitions: &PackedTransitions,
+    ) -> UnifiedSignalData {
+        let range = self.collect_range(transitions, request.time_range_ps);
+        let (final_transitions, busy_buckets) =
+            self.reduce_transitions_lod(range, request.time_range_ps, request.max_transitions);
+
+        UnifiedSignalData {
+            file_path: request.file_path.clone(),
+            scope_path: request.scope_path.clone(),
+            variable_name: request.variable_name.clone(),
+            unique_id: unique_id.to_string(),
+            transitions: Vec::new(),
+            packed_transitions: Some(final_transitions),
+            packed_binary: None,
+            total_transitions: transitions.len(),
+            actual_time_range_ps: self.compute_time_range(transitions),
+            busy_buckets,
+        }
+    }
+
     /// Extract transitions from wellen signal data
     fn extract_transitions_from_wellen(
         &self,
@@ -513,7 +888,8 @@ impl SignalCacheManager {
         signal_ref: &wellen::SignalRef,
         _requested_format: &shared::VarFormat,
         signal_key: &str,
-    ) -> Result<Vec<SignalTransition>, String> {
+        cancelled: &AtomicBool,
+    ) -> Result<PackedTransitions, String> {
         debug_log!(
             DEBUG_EXTRACT,
             "🔍 EXTRACT_TRANSITIONS: Loading real waveform data for {}",
@@ -521,7 +897,7 @@ impl SignalCacheManager {
         );
 
         if waveform_data.time_table.is_empty() {
-            return Ok(Vec::new());
+            return Ok(PackedTransitions::default());
         }
 
         // Load the signal once outside of the mutex guard for iteration performance
@@ -540,13 +916,16 @@ impl SignalCacheManager {
             ));
         };
 
-        let mut transitions = Vec::new();
+        let mut transitions: Option<PackedTransitions> = None;
         let mut last_value: Option<String> = None;
 
         for (index, &time_native) in waveform_data.time_table.iter().enumerate() {
-            // Convert native units to nanoseconds using the stored timescale factor
-            let time_seconds = time_native as f64 * waveform_data.timescale_factor;
-            let time_ns = (time_seconds * 1_000_000_000.0).round() as u64;
+            // Partial results are never cached, so bail out without a trace
+            if index % CANCELLATION_CHECK_INTERVAL == 0 && cancelled.load(Ordering::Relaxed) {
+                return Err(SIGNAL_QUERY_CANCELLED.to_string());
+            }
+            // Convert native units to timeline picoseconds
+            let time_ps = waveform_data.time_ps(time_native);
 
             if let Some(offset) = signal.get_offset(index as u32) {
                 let value = signal.get_value_at(&offset, 0);
@@ -559,20 +938,48 @@ impl SignalCacheManager {
                 };
 
                 if last_value.as_ref() != Some(&stored_value) {
-                    transitions.push(SignalTransition {
-                        time_ns,
-                        value: stored_value.clone(),
+                    // The first value picks the packed layout; values with more states widen it
+                    let packed = transitions.get_or_insert_with(|| match &value {
+                        wellen::SignalValue::Binary(..) => PackedTransitions::new(
+                            PackedEncoding::TwoState,
+                            stored_value.len() as u32,
+                        ),
+                        wellen::SignalValue::FourValue(..) => PackedTransitions::new(
+                            PackedEncoding::FourState,
+                            stored_value.len() as u32,
+                        ),
+                        wellen::SignalValue::NineValue(..) => PackedTransitions::new(
+                            PackedEncoding::NineState,
+                            stored_value.len() as u32,
+                        ),
+                        wellen::SignalValue::String(_) => {
+                            PackedTransitions::new(PackedEncoding::Text, 0)
+                        }
+                        wellen::SignalValue::Real(_) => {
+                            PackedTransitions::new(PackedEncoding::Real, 0)
+                        }
                     });
+                    // Sub-picosecond steps collapse onto the same timestamp; keep the latest value
+                    if packed.times_ps.last() == Some(&time_ps) {
+                        packed.pop();
+                    }
+                    match &value {
+                        wellen::SignalValue::Real(real) => packed.push_real(time_ps, *real),
+                        _ => packed.push_value(time_ps, &stored_value),
+                    }
                     last_value = Some(stored_value);
                 }
             }
         }
 
+        let transitions = transitions.unwrap_or_default();
         debug_log!(
             DEBUG_EXTRACT,
-            "🔍 EXTRACT_TRANSITIONS: Collected {} transitions for {}",
+            "🔍 EXTRACT_TRANSITIONS: Collected {} transitions for {} ({:?}, {} bytes)",
             transitions.len(),
-            signal_key
+            signal_key,
+            transitions.encoding,
+            transitions.size_bytes()
         );
 
         Ok(transitions)
@@ -592,41 +999,41 @@ impl SignalCacheManager {
 
         debug_log!(
             DEBUG_CURSOR,
-            "🔍 CURSOR: Computing cursor values at time {}ns for {} signals",
+            "🔍 CURSOR: Computing cursor values at time {}ps for {} signals",
             cursor_time,
             signal_data.len()
         );
 
         for signal in signal_data {
-            let transitions: &[SignalTransition] =
-                if let Some(transitions_arc) = cache_guard.get(&signal.unique_id) {
-                    &**transitions_arc
-                } else {
-                    &signal.transitions
-                };
+            let transitions = match (
+                cache_guard.peek(&signal.unique_id),
+                &signal.packed_transitions,
+            ) {
+                (Some(cached), _) => &**cached,
+                (None, Some(packed)) => packed,
+                (None, None) => {
+                    cursor_values.insert(signal.unique_id.clone(), SignalValue::Missing);
+                    continue;
+                }
+            };
             if transitions.is_empty() {
                 cursor_values.insert(signal.unique_id.clone(), SignalValue::Missing);
                 continue;
             }
 
-            let value = match transitions.binary_search_by(|t| t.time_ns.cmp(&cursor_time)) {
-                Ok(idx) => SignalValue::Present(transitions[idx].value.clone()),
-                Err(0) => SignalValue::Missing,
-                Err(idx) => {
-                    let prev = &transitions[idx - 1];
-                    SignalValue::Present(prev.value.clone())
-                }
+            let value = match transitions.index_at_or_before(cursor_time) {
+                Some(idx) => SignalValue::Present(transitions.value_string(idx)),
+                None => SignalValue::Missing,
             };
 
             if DEBUG_CURSOR && signal.unique_id.contains("wave_27.fst|TOP|clk") {
                 let idx = transitions
-                    .binary_search_by(|t| t.time_ns.cmp(&cursor_time))
-                    .unwrap_or_else(|i| i);
+                    .times_ps
+                    .partition_point(|&time| time < cursor_time);
                 let range_start = idx.saturating_sub(2);
                 let range_end = (idx + 2).min(transitions.len());
-                let window: Vec<String> = transitions[range_start..range_end]
-                    .iter()
-                    .map(|t| format!("{}:{}", t.time_ns, t.value))
+                let window: Vec<String> = (range_start..range_end)
+                    .map(|i| format!("{}:{}", transitions.time_ps(i), transitions.value_string(i)))
                     .collect();
                 debug_log!(
                     DEBUG_CURSOR,
@@ -646,129 +1053,128 @@ impl SignalCacheManager {
 
     fn collect_range(
         &self,
-        transitions: &Arc<[SignalTransition]>,
+        transitions: &PackedTransitions,
         range: Option<(u64, u64)>,
-    ) -> Vec<SignalTransition> {
-        if transitions.is_empty() {
-            return Vec::new();
+    ) -> PackedTransitions {
+        let Some((start, end)) = range else {
+            return transitions.clone();
+        };
+        let mut result = transitions.empty_like();
+        if transitions.is_empty() || start >= end {
+            return result;
         }
 
-        if let Some((start, end)) = range {
-            if start >= end {
-                return Vec::new();
-            }
-
-            let slice = &transitions[..];
-
-            let start_idx = slice
-                .binary_search_by(|transition| transition.time_ns.cmp(&start))
-                .unwrap_or_else(|idx| idx);
-            let end_idx = slice
-                .binary_search_by(|transition| transition.time_ns.cmp(&end))
-                .map(|idx| idx + 1)
-                .unwrap_or_else(|idx| idx)
-                .min(slice.len());
-
-            let mut result = Vec::new();
-
-            if start_idx > 0 {
-                let prev = &slice[start_idx - 1];
-                let needs_synthetic = slice
-                    .get(start_idx)
-                    .map(|transition| transition.time_ns != start)
-                    .unwrap_or(true);
-                if needs_synthetic {
-                    result.push(SignalTransition {
-                        time_ns: start,
-                        value: prev.value.clone(),
-                    });
-                }
-            }
-
-            if start_idx < end_idx {
-                result.extend(slice[start_idx..end_idx].iter().cloned());
-            }
+        let times = &transitions.times_ps;
+        let start_idx = times.partition_point(|&time| time < start);
+        let end_idx = times.partition_point(|&time| time <= end);
 
-            if let Some(first) = result.first() {
-                if first.time_ns > start {
-                    result.insert(
-                        0,
-                        SignalTransition {
-                            time_ns: start,
-                            value: first.value.clone(),
-                        },
-                    );
-                }
+        // Anchor the value held at the range start with a synthetic transition
+        if start_idx > 0 {
+            if times.get(start_idx) != Some(&start) {
+                result.push_from(transitions, start_idx - 1, start);
             }
+        } else if start_idx < end_idx && times[0] > start {
+            result.push_from(transitions, 0, start);
+        }
 
-            result
-        } else {
-            transitions.to_vec()
+        for index in start_idx..end_idx {
+            result.push_from(transitions, index, times[index]);
         }
+
+        result
     }
 
-    /// Downsample transitions for performance
-    fn downsample_transitions(
+    /// Reduce transitions to roughly one bucket per pixel without losing glitches.
+    /// Buckets with at most two transitions are kept verbatim; denser buckets keep their
+    /// first and last transition and are summarized as a `TransitionBucket`.
+    fn reduce_transitions_lod(
         &self,
-        transitions: Vec<SignalTransition>,
-        max_count: usize,
-    ) -> Vec<SignalTransition> {
+        transitions: PackedTransitions,
+        range: Option<(u64, u64)>,
+        max_count: Option<usize>,
+    ) -> (PackedTransitions, Vec<TransitionBucket>) {
+        let Some(max_count) = max_count else {
+            return (transitions, Vec::new());
+        };
         if transitions.len() <= max_count || max_count == 0 {
-            return transitions;
+            return (transitions, Vec::new());
         }
 
-        let mut result = Vec::with_capacity(max_count + 2);
-        let mut last_value = transitions[0].value.clone();
-        result.push(transitions[0].clone());
-
-        let stride = ((transitions.len() as f64 / max_count as f64).ceil() as usize).max(1);
-        let mut steps = 0usize;
+        let (span_start, span_end) = match range {
+            Some((start, end)) if end > start => (start, end),
+            _ => (
+                transitions.times_ps.first().copied().unwrap_or(0),
+                transitions.times_ps.last().copied().unwrap_or(0),
+            ),
+        };
+        let bucket_count = (max_count / LOD_TRANSITIONS_PER_BUCKET).max(1) as u64;
+        let bucket_width = span_end
+            .saturating_sub(span_start)
+            .div_ceil(bucket_count)
+            .max(1);
+        let bucket_of = |time_ps: u64| time_ps.saturating_sub(span_start) / bucket_width;
+
+        let mut result = transitions.empty_like();
+        let mut buckets = Vec::new();
+        let mut held_index: Option<usize> = None;
+        let mut group_start = 0usize;
+
+        while group_start < transitions.len() {
+            let bucket = bucket_of(transitions.time_ps(group_start));
+            let mut group_end = group_start + 1;
+            while group_end < transitions.len()
+                && bucket_of(transitions.time_ps(group_end)) == bucket
+            {
+                group_end += 1;
+            }
 
-        for transition in transitions
-            .iter()
-            .skip(1)
-            .take(transitions.len().saturating_sub(1))
-        {
-            steps += 1;
-            let value_changed = transition.value != last_value;
-            if value_changed || steps >= stride {
-                if result
-                    .last()
-                    .map(|prev| prev.time_ns != transition.time_ns)
-                    .unwrap_or(true)
-                {
-                    result.push(transition.clone());
+            let first = group_start;
+            let last = group_end - 1;
+            if group_end - group_start <= 2 {
+                for index in group_start..group_end {
+                    result.push_from(&transitions, index, transitions.time_ps(index));
                 }
-                last_value = transition.value.clone();
-                steps = 0;
-                if result.len() >= max_count {
-                    break;
+            } else {
+                let mut summary = TransitionBucket {
+                    start_ps: transitions.time_ps(first),
+                    end_ps: transitions.time_ps(last),
+                    transition_count: group_end - group_start,
+                    first_value: transitions.value_string(first),
+                    last_value: transitions.value_string(last),
+                    has_unknown: false,
+                    has_high_impedance: false,
+                    min_value: None,
+                    max_value: None,
+                };
+                // The value held into the bucket is drawn there too, so it counts for extremes
+                for index in held_index.into_iter().chain(group_start..group_end) {
+                    summary.has_unknown |= transitions.has_unknown(index);
+                    summary.has_high_impedance |= transitions.has_high_impedance(index);
+                    if let Some(analog) = transitions.real_value(index).filter(|v| v.is_finite()) {
+                        summary.min_value =
+                            Some(summary.min_value.map_or(analog, |min| min.min(analog)));
+                        summary.max_value =
+                            Some(summary.max_value.map_or(analog, |max| max.max(analog)));
+                    }
                 }
+                result.push_from(&transitions, first, transitions.time_ps(first));
+                result.push_from(&transitions, last, transitions.time_ps(last));
+                buckets.push(summary);
             }
-        }
 
-        if let Some(last) = transitions.last() {
-            if result
-                .last()
-                .map(|prev| prev.time_ns != last.time_ns)
-                .unwrap_or(true)
-            {
-                result.push(last.clone());
-            }
+            held_index = Some(last);
+            group_start = group_end;
         }
 
-        result
+        (result, buckets)
     }
 
     /// Compute time range from transitions
-    fn compute_time_range(&self, transitions: &[SignalTransition]) -> Option<(u64, u64)> {
-        if transitions.is_empty() {
-            None
-        } else {
-            let min_time = transitions.first().unwrap().time_ns;
-            let max_time = transitions.last().unwrap().time_ns;
-            Some((min_time, max_time))
-        }
+    fn compute_time_range(&self, transitions: &PackedTransitions) -> Option<(u64, u64)> {
+        Some((
+            *transitions.times_ps.first()?,
+            *transitions.times_ps.last()?,
+        ))
     }
 }
 
@@ -797,13 +1203,13 @@ mod tests {
             file_path: file_path.clone(),
             scope_path: "simple_tb.s".into(),
             variable_name: "A".into(),
-            time_range_ns: Some((0, 1_000_000)),
+            time_range_ps: Some((0, 1_000_000_000)),
             max_transitions: Some(1024),
             format: shared::VarFormat::Binary,
         };
 
         let (signal_data, cursor_values, _stats) = SignalCacheManager::new()
-            .query_unified_signals(vec![request], Some(0))
+            .query_unified_signals(vec![request], Some(0), &AtomicBool::new(false))
             .await
             .expect("query unified signals");
 
@@ -812,7 +1218,7 @@ mod tests {
             "expected at least one signal payload to be returned"
         );
         assert!(
-            !signal_data[0].transitions.is_empty(),
+            signal_data[0].transition_count() > 0,
             "expected transitions for simple_tb.s|A"
         );
         assert!(
@@ -833,13 +1239,13 @@ mod tests {
             file_path: file_path.clone(),
             scope_path: "TOP".into(),
             variable_name: "clk".into(),
-            time_range_ns: Some((0, 10_000_000)),
+            time_range_ps: Some((0, 10_000_000_000)),
             max_transitions: Some(4096),
             format: shared::VarFormat::Binary,
         };
 
         let (signal_data, _cursor_values, _stats) = SignalCacheManager::new()
-            .query_unified_signals(vec![request], Some(0))
+            .query_unified_signals(vec![request], Some(0), &AtomicBool::new(false))
             .await
             .expect("query unified signals");
 
@@ -848,11 +1254,345 @@ mod tests {
             "expected at least one signal payload for FST waveform"
         );
         assert!(
-            !signal_data[0].transitions.is_empty(),
+            signal_data[0].transition_count() > 0,
             "expected transitions for TOP|clk from FST waveform"
         );
     }
 
+    #[tokio::test]
+    async fn cancelled_unified_signal_query_stops_without_caching() {
+        let file_path = project_path("../test_files/simple.vcd");
+        let request = UnifiedSignalRequest {
+            file_path: file_path.clone(),
+            scope_path: "simple_tb.s".into(),
+            variable_name: "A".into(),
+            time_range_ps: None,
+            max_transitions: None,
+            format: shared::VarFormat::Binary,
+        };
+        let manager = SignalCacheManager::new();
+        let cancelled = AtomicBool::new(true);
+
+        let result = manager
+            .query_unified_signals(vec![request], None, &cancelled)
+            .await;
+
+        assert_eq!(result.err().as_deref(), Some(SIGNAL_QUERY_CANCELLED));
+        assert_eq!(manager.transition_cache.read().unwrap().len(), 0);
+
+        // A cancel that arrives before its query still wins
+        cancel_signal_query("early|timeline-1");
+        assert!(register_signal_query("early|timeline-1").load(Ordering::Relaxed));
+        finish_signal_query("early|timeline-1");
+        assert!(!register_signal_query("early|timeline-1").load(Ordering::Relaxed));
+        finish_signal_query("early|timeline-1");
+
+        // Cancels of finished queries are kept only up to the bound
+        for index in 0..MAX_UNMATCHED_SIGNAL_QUERY_CANCELS * 2 {
+            cancel_signal_query(&format!("late|timeline-{}", index));
+        }
+        let registry = signal_query_registry();
+        assert!(registry.unmatched_cancels.len() <= MAX_UNMATCHED_SIGNAL_QUERY_CANCELS);
+        assert!(!registry.active.contains_key("early|timeline-1"));
+    }
+
+    #[tokio::test]
+    async fn unloading_a_file_releases_its_body_and_cached_signals() {
+        let file_path = project_path("../test_files/simple_reload_test.vcd");
+        ensure_waveform_body_loaded(&file_path)
+            .await
+            .expect("load waveform body");
+        let request = UnifiedSignalRequest {
+            file_path: file_path.clone(),
+            scope_path: "simple_tb.s".into(),
+            variable_name: "A".into(),
+            time_range_ps: None,
+            max_transitions: None,
+            format: shared::VarFormat::Binary,
+        };
+        let (_, _, statistics) = SIGNAL_CACHE_MANAGER
+            .query_unified_signals(vec![request], None, &AtomicBool::new(false))
+            .await
+            .expect("query unified signals");
+        let cached_bytes = SIGNAL_CACHE_MANAGER
+            .transition_cache
+            .read()
+            .unwrap()
+            .file_bytes(&file_path);
+        assert!(cached_bytes > 0);
+        assert_eq!(
+            statistics.cache_file_bytes.get(&file_path).copied(),
+            Some(cached_bytes)
+        );
+
+        let (freed_bytes, body_released) = invalidate_waveform_resources(&file_path);
+
+        assert!(body_released);
+        assert!(freed_bytes > cached_bytes);
+        assert!(!WAVEFORM_DATA_STORE.lock().unwrap().contains_key(&file_path));
+        let cache = SIGNAL_CACHE_MANAGER.transition_cache.read().unwrap();
+        assert_eq!(cache.file_bytes(&file_path), 0);
+    }
+
+    #[test]
+    fn truncated_vcd_recovers_up_to_last_complete_timestamp() {
+        let file_path = project_path("../test_files/truncated.vcd");
+        let truncation = detect_vcd_truncation(&file_path)
+            .expect("read fixture")
+            .expect("fixture is cut mid-line");
+        assert_eq!(truncation.last_complete_time, 150);
+
+        let options = wellen::LoadOptions::default();
+        let header = wellen::viewers::read_header_from_file(&file_path, &options)
+            .expect("header is complete");
+        let (_, body) = WaveformBody::open(
+            &file_path,
+            &options,
+            Some(truncation.keep_bytes),
+            Arc::default(),
+        )
+        .expect("open recovered prefix");
+        let body = body.read(&header.hierarchy, None).expect("read body");
+        assert_eq!(body.time_table.last(), Some(&150));
+
+        // Complete files, even without a final newline, are left alone
+        for complete in [
+            "simple.vcd",
+            "complex.vcd",
+            "stress_test.vcd",
+            "corrupted.vcd",
+        ] {
+            let path = project_path(&format!("../test_files/{complete}"));
+            assert!(
+                detect_vcd_truncation(&path).unwrap().is_none(),
+                "{complete}"
+            );
+        }
+        assert!(find_vcd_truncation(b"#0\n1!\n#10\nb1x01 ").is_some());
+    }
+
+    #[test]
+    fn gzip_waveform_unpacks_to_the_plain_file() {
+        let file_path = project_path("../test_files/simple.vcd.gz");
+        let metadata = fs::metadata(&file_path).unwrap();
+        let target = decompression_cache_path(&file_path, &metadata);
+        assert!(target.to_string_lossy().ends_with("-simple.vcd"));
+
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        decompress_to_file(
+            &file_path,
+            shared::CompressionFormat::Gzip,
+            &target,
+            Arc::clone(&bytes_read),
+        )
+        .expect("decompress fixture");
+        assert_eq!(bytes_read.load(Ordering::Relaxed), metadata.len());
+        assert_eq!(
+            fs::read(&target).unwrap(),
+            fs::read(project_path("../test_files/simple.vcd")).unwrap()
+        );
+        fs::remove_file(&target).unwrap();
+    }
+
+    #[test]
+    fn decompression_cache_evicts_least_recently_used_copies() {
+        let dir = std::env::temp_dir().join(format!("novywave-eviction-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let now = std::time::SystemTime::now();
+        let copy = |name: &str, age_s: u64| {
+            let path = dir.join(name);
+            fs::write(&path, [0u8; 100]).unwrap();
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - Duration::from_secs(age_s))
+                .unwrap();
+            path
+        };
+        let oldest_open = copy("oldest_open.vcd", 40);
+        let old = copy("old.vcd", 30);
+        let partial = copy("unpacking.vcd.partial", 20);
+        let recent = copy("recent.vcd", 10);
+
+        let in_use = std::collections::HashSet::from([oldest_open.clone()]);
+        assert_eq!(evict_cached_copies(&dir, 300, &in_use), 100);
+        assert!(oldest_open.exists() && partial.exists() && recent.exists());
+        assert!(!old.exists());
+
+        // Within budget nothing more goes
+        assert_eq!(evict_cached_copies(&dir, 300, &in_use), 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cancel_load_flags_only_running_loads() {
+        let file_path = "cancel_test.vcd";
+        assert!(!cancel_load(file_path));
+
+        register_load(file_path);
+        assert!(!is_load_cancelled(file_path));
+        assert!(cancel_load(file_path));
+        assert!(is_load_cancelled(file_path));
+
+        complete_loading(file_path);
+        assert!(!is_load_cancelled(file_path));
+    }
+
+    #[test]
+    fn cancelled_load_stops_reading_the_file() {
+        let file_path = project_path("../test_files/simple.vcd");
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let len = fs::metadata(&file_path).unwrap().len();
+        let mut input = LoadInput::open(&file_path, len, Arc::clone(&cancelled)).unwrap();
+        let mut buffer = [0u8; 16];
+        assert_eq!(std::io::Read::read(&mut input, &mut buffer).unwrap(), 16);
+
+        cancelled.store(true, Ordering::Relaxed);
+        assert!(std::io::Read::read(&mut input, &mut buffer).is_err());
+        let options = wellen::LoadOptions::default();
+        assert!(WaveformBody::open(&file_path, &options, Some(len), cancelled).is_err());
+    }
+
+    #[test]
+    fn lod_reduction_keeps_glitches_in_dense_buckets() {
+        let mut transitions: Vec<SignalTransition> = (0..1_000u64)
+            .map(|i| SignalTransition::new(i * 10, if i % 2 == 0 { "0" } else { "1" }.into()))
+            .collect();
+        transitions[501].value = "X".into();
+
+        let (reduced, buckets) = SignalCacheManager::new().reduce_transitions_lod(
+            PackedTransitions::from_transitions(&transitions),
+            Some((0, 10_000)),
+            Some(40),
+        );
+
+        assert!(reduced.len() <= 40);
+        assert_eq!(buckets.len(), 10);
+        assert_eq!(buckets.iter().filter(|b| b.has_unknown).count(), 1);
+        assert!(buckets.iter().all(|b| b.transition_count == 100));
+        assert_eq!(reduced.times_ps.last(), Some(&9_990));
+    }
+
+    #[test]
+    fn lod_reduction_reports_analog_extremes() {
+        let values = ["1.000000", "1.500000", "-3.250000", "2.000000", "1.000000"];
+        let transitions: Vec<SignalTransition> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| SignalTransition::new(i as u64, v.to_string()))
+            .collect();
+
+        let (reduced, buckets) = SignalCacheManager::new().reduce_transitions_lod(
+            PackedTransitions::from_transitions(&transitions),
+            Some((0, 5)),
+            Some(4),
+        );
+
+        assert_eq!(reduced.len(), 2);
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].min_value, Some(-3.25));
+        assert_eq!(buckets[0].max_value, Some(2.0));
+        assert!(!buckets[0].has_unknown_states());
+    }
+
+    #[test]
+    fn transition_cache_evicts_least_recently_used_within_budget() {
+        let signal = |count: u64| -> Arc<PackedTransitions> {
+            let mut packed = PackedTransitions::new(PackedEncoding::TwoState, 4);
+            for i in 0..count {
+                packed.push_bits(i, "1010");
+            }
+            Arc::new(packed)
+        };
+        let entry_size = TransitionCache::estimate_size("a.vcd|top|x", &signal(100));
+        let mut cache = TransitionCache::new(entry_size * 2 + entry_size / 2);
+
+        assert_eq!(cache.insert("a.vcd|top|x".into(), signal(100)).0, 0);
+        assert_eq!(cache.insert("a.vcd|top|y".into(), signal(100)).0, 0);
+        // Touch x so y becomes the eviction candidate
+        assert!(cache.get("a.vcd|top|x").is_some());
+        let (evicted, evicted_bytes) = cache.insert("b.vcd|top|z".into(), signal(100));
+
+        assert_eq!(evicted, 1);
+        assert_eq!(evicted_bytes, entry_size);
+        assert!(cache.peek("a.vcd|top|y").is_none());
+        assert!(cache.peek("a.vcd|top|x").is_some());
+        assert!(cache.total_bytes <= cache.budget_bytes);
+        assert_eq!(cache.file_bytes("a.vcd"), entry_size);
+        assert_eq!(cache.remove_file("a.vcd"), entry_size);
+        assert_eq!(cache.file_bytes("a.vcd"), 0);
+        assert_eq!(cache.total_bytes, cache.file_bytes("b.vcd"));
+        assert_eq!(cache.recency_mut().len(), cache.len());
+    }
+
+    #[test]
+    fn aligned_bounds_move_in_picoseconds_and_stop_at_zero() {
+        let file_path = "alignment_test.vcd";
+        match WAVEFORM_METADATA_STORE.lock() {
+            Ok(mut store) => store,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+        .insert(
+            file_path.to_string(),
+            WaveformMetadata {
+                _file_path: file_path.to_string(),
+                file_format: wellen::FileFormat::Vcd,
+                timescale_factor: 1e-9,
+                time_bounds: (1.2345e-9, 5e-9),
+                vcd_body_limit: None,
+            },
+        );
+        let aligned = |offset_ps: i64| {
+            set_file_time_alignments(&BTreeMap::from([(
+                file_path.to_string(),
+                shared::FileTimeAlignment {
+                    offset_ps,
+                    timescale: Some("10ns".to_string()),
+                },
+            )]));
+            let hierarchy = FileHierarchy {
+                files: vec![WaveformFile {
+                    id: file_path.to_string(),
+                    filename: file_path.to_string(),
+                    format: FileFormat::VCD,
+                    scopes: Vec::new(),
+                    min_time_ns: Some(1),
+                    max_time_ns: Some(5),
+                    min_time_ps: Some(1_235),
+                    max_time_ps: Some(5_000),
+                    truncated_at_ns: None,
+                }],
+            };
+            let file = aligned_file_hierarchy(hierarchy, file_path).files.remove(0);
+            (file.min_time_ns, file.max_time_ns, file.time_range_ps())
+        };
+        // 12_345ps to 50_000ps once the timescale is ten times longer
+        assert_eq!(
+            aligned(-2_000),
+            (Some(10), Some(48), Some((10_345, 48_000)))
+        );
+        // The first timestamp stops at 0 instead of pulling later ones along
+        assert_eq!(aligned(-20_000), (Some(0), Some(37), Some((0, 37_655))));
+        assert_eq!(clamped_offset_ps(-20_000, 12_345), -12_345);
+        assert_eq!(clamped_offset_ps(300, 0), 300);
+        set_file_time_alignments(&BTreeMap::new());
+    }
+
+    #[test]
+    fn native_time_to_ps_keeps_sub_nanosecond_precision() {
+        // 1ps timescale: integer scaling, no rounding to nanoseconds
+        assert_eq!(native_time_to_ps(1_234, 1e-12), 1_234);
+        // 10ps timescale with a large timestamp stays exact
+        assert_eq!(
+            native_time_to_ps(123_456_789_012_345, 1e-11),
+            1_234_567_890_123_450
+        );
+        // 100fs timescale rounds to the nearest picosecond
+        assert_eq!(native_time_to_ps(25, 1e-13), 3);
+        assert_eq!(native_time_to_ps(0, 1e-15), 0);
+    }
+
     #[test]
     fn relativize_workspace_directory_path_normalizes_workspace_root() {
         let context = WorkspaceContext::new(PathBuf::from("/tmp/novywave_ai_workspace"));
@@ -887,6 +1627,75 @@ mod tests {
 /// Global signal cache manager instance
 static SIGNAL_CACHE_MANAGER: Lazy<SignalCacheManager> = Lazy::new(|| SignalCacheManager::new());
 
+/// Cancellation flags of in-flight unified signal queries, keyed by "session|request_id"
+static ACTIVE_SIGNAL_QUERIES: Lazy<Mutex<SignalQueryRegistry>> =
+    Lazy::new(|| Mutex::new(SignalQueryRegistry::default()));
+
+const SIGNAL_QUERY_CANCELLED: &str = "Signal query cancelled";
+/// Cancels remembered for queries that have not arrived (or have already finished)
+const MAX_UNMATCHED_SIGNAL_QUERY_CANCELS: usize = 64;
+
+#[derive(Default)]
+struct SignalQueryRegistry {
+    active: HashMap<String, Arc<AtomicBool>>,
+    /// Keys of cancels that matched no running query, oldest first
+    unmatched_cancels: VecDeque<String>,
+}
+
+fn signal_query_key(session_id: SessionId, request_id: &str) -> String {
+    format!("{}|{}", session_id, request_id)
+}
+
+fn signal_query_registry() -> std::sync::MutexGuard<'static, SignalQueryRegistry> {
+    match ACTIVE_SIGNAL_QUERIES.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Register a query and return its cancellation flag. A cancel that overtook its
+/// query is remembered for a while, so such a query stops right away.
+fn register_signal_query(key: &str) -> Arc<AtomicBool> {
+    let mut registry = signal_query_registry();
+    let overtaken = match registry
+        .unmatched_cancels
+        .iter()
+        .position(|cancel| cancel == key)
+    {
+        Some(position) => registry.unmatched_cancels.remove(position).is_some(),
+        None => false,
+    };
+    let cancelled = Arc::new(AtomicBool::new(overtaken));
+    registry
+        .active
+        .insert(key.to_string(), Arc::clone(&cancelled));
+    cancelled
+}
+
+fn finish_signal_query(key: &str) {
+    signal_query_registry().active.remove(key);
+}
+
+fn cancel_signal_query(key: &str) {
+    let mut registry = signal_query_registry();
+    if let Some(cancelled) = registry.active.get(key) {
+        cancelled.store(true, Ordering::Relaxed);
+        return;
+    }
+    // Either the query is still on its way or it has finished; only a bounded number of
+    // these are kept, so cancels of finished queries cannot pile up
+    if !registry
+        .unmatched_cancels
+        .iter()
+        .any(|cancel| cancel == key)
+    {
+        registry.unmatched_cancels.push_back(key.to_string());
+        if registry.unmatched_cancels.len() > MAX_UNMATCHED_SIGNAL_QUERY_CANCELS {
+            registry.unmatched_cancels.pop_front();
+        }
+    }
+}
+
 async fn up_msg_handler(req: UpMsgRequest<UpMsg>) {
     let (session_id, cor_id) = (req.session_id, req.cor_id);
 
@@ -910,6 +1719,38 @@ async fn up_msg_handler(req: UpMsgRequest<UpMsg>) {
                 file_path
             );
         }
+        UpMsg::CancelLoad(file_path) => {
+            debug_log!(DEBUG_BACKEND, "🛑 BACKEND: CancelLoad for '{}'", file_path);
+            // A running load reports LoadCancelled itself once its parse stage returns
+            let was_loading = cancel_load(file_path);
+            release_decompressed_copy(file_path);
+            if !was_loading {
+                invalidate_waveform_resources(file_path);
+                broadcast_down_msg(DownMsg::LoadCancelled {
+                    file_id: file_path.clone(),
+                })
+                .await;
+            }
+        }
+        UpMsg::UnloadFile(file_path) => {
+            cancel_load(file_path);
+            let (freed_bytes, body_released) = invalidate_waveform_resources(file_path);
+            let freed_disk_bytes = release_decompressed_copy(file_path);
+            debug_log!(
+                DEBUG_BACKEND,
+                "🧹 BACKEND: Unloaded '{}' (freed ~{} bytes, {} on disk, body_released={})",
+                file_path,
+                freed_bytes,
+                freed_disk_bytes,
+                body_released
+            );
+            broadcast_down_msg(DownMsg::FileUnloaded {
+                file_id: file_path.clone(),
+                freed_bytes: freed_bytes as u64,
+                body_released,
+            })
+            .await;
+        }
         UpMsg::GetParsingProgress(file_id) => {
             send_parsing_progress(file_id.clone(), session_id, cor_id).await;
         }
@@ -1013,14 +1854,15 @@ async fn up_msg_handler(req: UpMsgRequest<UpMsg>) {
         }
         UpMsg::UnifiedSignalQuery {
             signal_requests,
-            cursor_time_ns,
+            cursor_time_ps,
             request_id,
+            payload_encoding,
         } => {
             debug_log!(
                 DEBUG_BACKEND,
                 "🛰️ BACKEND: UnifiedSignalQuery len={} cursor={:?} id={}",
                 signal_requests.len(),
-                cursor_time_ns,
+                cursor_time_ps,
                 request_id
             );
             // Handle unified signal query using the new cache manager
@@ -1028,13 +1870,14 @@ async fn up_msg_handler(req: UpMsgRequest<UpMsg>) {
                 DEBUG_BACKEND,
                 "🔍 BACKEND: Processing UnifiedSignalQuery with {} requests, cursor_time: {:?}, request_id: {}",
                 signal_requests.len(),
-                cursor_time_ns,
+                cursor_time_ps,
                 request_id
             );
             handle_unified_signal_query(
                 signal_requests.clone(),
-                cursor_time_ns.clone(),
+                cursor_time_ps.clone(),
                 request_id.clone(),
+                payload_encoding,
                 session_id,
                 cor_id,
             )
@@ -1045,6 +1888,137 @@ async fn up_msg_handler(req: UpMsgRequest<UpMsg>) {
                 request_id
             );
         }
+        UpMsg::CancelSignalQuery { request_id } => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🛑 BACKEND: CancelSignalQuery id={}",
+                request_id
+            );
+            cancel_signal_query(&signal_query_key(session_id, request_id));
+        }
+        UpMsg::ExportWaveform(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "📤 BACKEND: ExportWaveform '{}' -> '{}'",
+                request.file_path,
+                request.output_path
+            );
+            waveform_export::export_waveform(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::CompareWaveforms(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: CompareWaveforms '{}' vs '{}'",
+                request.reference_file,
+                request.candidate_file
+            );
+            waveform_diff::compare_waveforms(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::DefineDerivedSignal(signal) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: DefineDerivedSignal '{}' = {}",
+                signal.name,
+                signal.expression
+            );
+            derived_signals::define_derived_signal(signal.clone(), session_id, cor_id).await;
+        }
+        UpMsg::SearchCondition(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: SearchCondition {:?} from {}ps: {}",
+                request.direction,
+                request.from_ps,
+                request.expression
+            );
+            condition_search::search_condition(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::SearchSignalValue(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: SearchSignalValue {}|{}|{} = {}",
+                request.file_path,
+                request.scope_path,
+                request.variable_name,
+                request.value
+            );
+            value_search::search_signal_value(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::MeasureSignal(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: MeasureSignal {}|{}|{} over {}..{}ps",
+                request.file_path,
+                request.scope_path,
+                request.variable_name,
+                request.start_ps,
+                request.end_ps
+            );
+            signal_measurement::measure_signal_range(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::CompareSignal(request) => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: CompareSignal {} vs {} over {:?}",
+                request.unique_id,
+                request.compare_unique_id,
+                request.range_ps
+            );
+            waveform_diff::compare_signal(request.clone(), session_id, cor_id).await;
+        }
+        UpMsg::SetFileTimeAlignment {
+            file_path,
+            alignment,
+        } => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: Time alignment for '{}': {:?}",
+                file_path,
+                alignment
+            );
+            let mut alignments = match FILE_TIME_ALIGNMENTS.lock() {
+                Ok(alignments) => alignments,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            alignments.insert(file_path.clone(), alignment.clone());
+        }
+        UpMsg::ConfigureCsvImport {
+            file_path,
+            settings,
+        } => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "🔍 BACKEND: CSV import settings for '{}': {:?}",
+                file_path,
+                settings
+            );
+            let mut imports = match CSV_IMPORT_SETTINGS.lock() {
+                Ok(imports) => imports,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            imports.insert(file_path.clone(), settings.clone());
+        }
+        UpMsg::SaveWaveformImage {
+            export_id,
+            output_path,
+            content,
+            row_count,
+        } => {
+            debug_log!(
+                DEBUG_BACKEND,
+                "📤 BACKEND: SaveWaveformImage -> '{}'",
+                output_path
+            );
+            waveform_export::save_waveform_image(
+                export_id.clone(),
+                output_path.clone(),
+                content.clone(),
+                *row_count,
+                session_id,
+                cor_id,
+            )
+            .await;
+        }
         UpMsg::GetPlatformRoots => {
             let roots = get_platform_roots();
             send_down_msg(DownMsg::PlatformRoots(roots), session_id, cor_id).await;
@@ -1161,6 +2135,7 @@ async fn load_waveform_file(file_path: String, session_id: SessionId, cor_id: Co
 
         // Mark this file as being loaded
         loading.insert(file_path.clone());
+        register_load(&file_path);
     }
 
     invalidate_waveform_resources(&file_path);
@@ -1197,6 +2172,29 @@ async fn load_waveform_file(file_path: String, session_id: SessionId, cor_id: Co
     })
     .await;
 
+    // Compressed dumps are parsed from an unpacked copy and imported formats from their
+    // VCD conversion; the progress reports end with them
+    let preparation = {
+        let load_progress = LoadProgressReporter::start(&file_path);
+        match ensure_decompressed(&file_path, &load_progress).await {
+            Ok(()) => ensure_converted(&file_path).await,
+            Err(error) => Err(error),
+        }
+    };
+    if let Err(error) = preparation {
+        if abort_if_load_cancelled(&file_path, &file_path).await {
+            return;
+        }
+        cleanup_parsing_session(&file_path);
+        complete_loading(&file_path);
+        let file_error = FileError::IoError {
+            path: file_path.clone(),
+            error,
+        };
+        send_structured_parsing_error(file_path, filename, file_error, session_id, cor_id).await;
+        return;
+    }
+
     // Use wellen's automatic file format detection instead of extension-based detection
     parse_waveform_file(
         file_path.clone(),
@@ -1245,6 +2243,22 @@ async fn send_structured_parsing_error(
     .await;
 }
 
+/// Stop a header load whose cancellation was requested; returns true when it was aborted
+async fn abort_if_load_cancelled(file_id: &str, file_path: &str) -> bool {
+    if !is_load_cancelled(file_path) {
+        return false;
+    }
+    debug_log!(DEBUG_PARSE, "🛑 PARSE: Load of '{}' cancelled", file_path);
+    cleanup_parsing_session(file_id);
+    invalidate_waveform_resources(file_path);
+    complete_loading(file_path);
+    broadcast_down_msg(DownMsg::LoadCancelled {
+        file_id: file_id.to_string(),
+    })
+    .await;
+    true
+}
+
 async fn parse_waveform_file(
     file_path: String,
     file_id: String,
@@ -1269,7 +2283,9 @@ async fn parse_waveform_file(
         "🔍 PARSE: Calling wellen::viewers::read_header_from_file for '{}'",
         file_path
     );
-    let file_path_clone = file_path.clone();
+    // Compressed files are read from their unpacked copy
+    let source_path = waveform_source_path(&file_path);
+    let file_path_clone = source_path.clone();
     let parse_result = tokio::task::spawn_blocking(move || {
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
             wellen::viewers::read_header_from_file(&file_path_clone, &options)
@@ -1323,6 +2339,10 @@ async fn parse_waveform_file(
         }
     };
 
+    if abort_if_load_cancelled(&file_id, &file_path).await {
+        return;
+    }
+
     {
         match progress.lock() {
             Ok(mut p) => *p = 0.3, // Header parsed
@@ -1351,7 +2371,7 @@ async fn parse_waveform_file(
 
             // For FST files, we need to get time bounds without loading full body
             // Try a quick scan first, return None if we can't determine actual bounds
-            let time_bounds_result = match extract_fst_time_bounds_fast(&file_path) {
+            let time_bounds_result = match extract_fst_time_bounds_fast(&source_path) {
                 Ok(result) => result,
                 Err(e) => {
                     broadcast_down_msg(DownMsg::ParsingError {
@@ -1533,6 +2553,10 @@ async fn parse_waveform_file(
                 Some((min_seconds * 1_000_000_000.0) as u64),
                 Some((max_seconds * 1_000_000_000.0) as u64),
             );
+            let (min_time_ps, max_time_ps) = (
+                Some((min_seconds * PS_PER_SECOND).round() as u64),
+                Some((max_seconds * PS_PER_SECOND).round() as u64),
+            );
 
             // Extract scopes from hierarchy (now owned from match result)
             let scopes = extract_scopes_from_hierarchy(&hierarchy, &file_path);
@@ -1545,7 +2569,8 @@ async fn parse_waveform_file(
                 _file_path: file_path.clone(),
                 file_format: header_result.file_format,
                 timescale_factor: final_timescale_factor, // Use inferred timescale for FST
-                _time_bounds: (min_seconds, max_seconds),
+                time_bounds: (min_seconds, max_seconds),
+                vcd_body_limit: None,
             };
 
             debug_log!(
@@ -1607,6 +2632,9 @@ async fn parse_waveform_file(
                 scopes,
                 min_time_ns,
                 max_time_ns,
+                min_time_ps,
+                max_time_ps,
+                truncated_at_ns: None,
             };
 
             let file_hierarchy = FileHierarchy {
@@ -1624,9 +2652,13 @@ async fn parse_waveform_file(
             }
             send_progress_update(file_id.clone(), 1.0, session_id, cor_id).await;
 
+            if abort_if_load_cancelled(&file_id, &file_path).await {
+                return;
+            }
+
             broadcast_down_msg(DownMsg::FileLoaded {
                 file_id: file_id.clone(),
-                hierarchy: file_hierarchy,
+                hierarchy: aligned_file_hierarchy(file_hierarchy, &file_path),
             })
             .await;
 
@@ -1636,9 +2668,31 @@ async fn parse_waveform_file(
         wellen::FileFormat::Vcd => {
             // VCD: Use progressive loading with quick time bounds extraction
 
+            // A crashed simulation leaves the VCD cut mid-line; keep the data up to the
+            // last complete timestamp instead of failing the whole file
+            let truncation = match detect_vcd_truncation(&source_path) {
+                Ok(truncation) => truncation,
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Failed to check '{}' for truncation: {}",
+                        file_path, e
+                    );
+                    None
+                }
+            };
+            if let Some(truncation) = &truncation {
+                println!(
+                    "VCD '{}' is truncated; recovering {} bytes up to t={}",
+                    file_path, truncation.keep_bytes, truncation.last_complete_time
+                );
+            }
+
             // First: Try quick time bounds extraction (much faster)
-            let (min_seconds, max_seconds) = match extract_vcd_time_bounds_fast(&file_path) {
+            let (min_seconds, max_seconds) = match extract_vcd_time_bounds_fast(&source_path) {
                 Ok((min_time, max_time)) => {
+                    let max_time = truncation
+                        .as_ref()
+                        .map_or(max_time, |t| t.last_complete_time as f64);
                     // Apply proper timescale conversion for VCD based on unit
                     let timescale_factor = match header_result.hierarchy.timescale() {
                         Some(ts) => {
@@ -1710,11 +2764,15 @@ async fn parse_waveform_file(
             // Extract scopes from header (immediate - no body parsing needed)
             let scopes = extract_scopes_from_hierarchy(&header_result.hierarchy, &file_path);
 
-            let format = FileFormat::VCD;
+            let format = waveform_import::import_format(&file_path).unwrap_or(FileFormat::VCD);
             let (min_time_ns, max_time_ns) = (
                 Some((min_seconds * 1_000_000_000.0) as u64),
                 Some((max_seconds * 1_000_000_000.0) as u64),
             );
+            let (min_time_ps, max_time_ps) = (
+                Some((min_seconds * PS_PER_SECOND).round() as u64),
+                Some((max_seconds * PS_PER_SECOND).round() as u64),
+            );
 
             debug_log!(
                 DEBUG_BACKEND,
@@ -1732,6 +2790,9 @@ async fn parse_waveform_file(
                 scopes,
                 min_time_ns,
                 max_time_ns,
+                min_time_ps,
+                max_time_ps,
+                truncated_at_ns: truncation.as_ref().and(max_time_ns),
             };
 
             let file_hierarchy = FileHierarchy {
@@ -1797,7 +2858,8 @@ async fn parse_waveform_file(
                 _file_path: file_path.clone(),
                 file_format: header_result.file_format,
                 timescale_factor,
-                _time_bounds: (min_seconds, max_seconds),
+                time_bounds: (min_seconds, max_seconds),
+                vcd_body_limit: truncation.as_ref().map(|t| t.keep_bytes),
             };
 
             debug_log!(
@@ -1828,9 +2890,13 @@ async fn parse_waveform_file(
                 }
             }
 
+            if abort_if_load_cancelled(&file_id, &file_path).await {
+                return;
+            }
+
             broadcast_down_msg(DownMsg::FileLoaded {
                 file_id: file_id.clone(),
-                hierarchy: file_hierarchy,
+                hierarchy: aligned_file_hierarchy(file_hierarchy, &file_path),
             })
             .await;
 
@@ -1954,6 +3020,10 @@ async fn parse_waveform_file(
                 Some((min_seconds * 1_000_000_000.0) as u64),
                 Some((max_seconds * 1_000_000_000.0) as u64),
             );
+            let (min_time_ps, max_time_ps) = (
+                Some((min_seconds * PS_PER_SECOND).round() as u64),
+                Some((max_seconds * PS_PER_SECOND).round() as u64),
+            );
 
             // Extract scopes from hierarchy (now owned from spawn_blocking result)
             let scopes = extract_scopes_from_hierarchy(&hierarchy, &file_path);
@@ -1963,7 +3033,8 @@ async fn parse_waveform_file(
                 _file_path: file_path.clone(),
                 file_format,
                 timescale_factor,
-                _time_bounds: (min_seconds, max_seconds),
+                time_bounds: (min_seconds, max_seconds),
+                vcd_body_limit: None,
             };
 
             {
@@ -2002,6 +3073,9 @@ async fn parse_waveform_file(
                 scopes,
                 min_time_ns,
                 max_time_ns,
+                min_time_ps,
+                max_time_ps,
+                truncated_at_ns: None,
             };
 
             let file_hierarchy = FileHierarchy {
@@ -2018,9 +3092,13 @@ async fn parse_waveform_file(
             }
             send_progress_update(file_id.clone(), 1.0, session_id, cor_id).await;
 
+            if abort_if_load_cancelled(&file_id, &file_path).await {
+                return;
+            }
+
             broadcast_down_msg(DownMsg::FileLoaded {
                 file_id: file_id.clone(),
-                hierarchy: file_hierarchy,
+                hierarchy: aligned_file_hierarchy(file_hierarchy, &file_path),
             })
             .await;
 
@@ -2067,7 +3145,14 @@ fn convert_wellen_error_to_file_error(wellen_error: &str, file_path: &str) -> Fi
         FileError::UnsupportedFormat {
             path,
             extension: extension.to_string(),
-            supported_formats: vec!["vcd".to_string(), "fst".to_string(), "ghw".to_string()],
+            supported_formats: vec![
+                "vcd".to_string(),
+                "fst".to_string(),
+                "ghw".to_string(),
+                "json".to_string(),
+                "raw".to_string(),
+                "csv".to_string(),
+            ],
         }
     } else {
         // Generic parsing error for everything else
@@ -2078,115 +3163,776 @@ fn convert_wellen_error_to_file_error(wellen_error: &str, file_path: &str) -> Fi
     }
 }
 
-/// Convert panic messages to structured FileError
-fn convert_panic_to_file_error(file_path: &str) -> FileError {
-    FileError::CorruptedFile {
-        path: file_path.to_string(),
-        details: "Critical error: Invalid waveform data or corrupted file".to_string(),
+/// Convert panic messages to structured FileError
+fn convert_panic_to_file_error(file_path: &str) -> FileError {
+    FileError::CorruptedFile {
+        path: file_path.to_string(),
+        details: "Critical error: Invalid waveform data or corrupted file".to_string(),
+    }
+}
+
+/// Intelligent FST timescale inference to handle files with incorrect embedded timescale
+fn infer_reasonable_fst_timescale(
+    body_result: &wellen::viewers::BodyResult,
+    embedded_factor: f64,
+    _filename: &str,
+) -> f64 {
+    if body_result.time_table.is_empty() {
+        return embedded_factor; // Can't infer from empty time table
+    }
+
+    let (raw_min, raw_max) = match (
+        body_result.time_table.first(),
+        body_result.time_table.last(),
+    ) {
+        (Some(&min), Some(&max)) => (min as f64, max as f64),
+        _ => {
+            // If time bounds can't be determined, return the embedded factor
+            return embedded_factor;
+        }
+    };
+    let raw_range = raw_max - raw_min;
+
+    // Check if embedded timescale produces unreasonably long duration (>1000 seconds)
+    let computed_duration = raw_range * embedded_factor;
+
+    if computed_duration > 1000.0 {
+        // FST inference: embedded timescale produces unreasonably long duration
+
+        // Heuristic inference based on value magnitude - optimized for typical FPGA/digital designs
+        let inferred_factor = if raw_range > 1e15 {
+            1e-15 // femtoseconds
+        } else if raw_range > 1e12 {
+            1e-12 // picoseconds
+        } else if raw_range > 1e6 {
+            1e-9 // nanoseconds - most common for FPGA/CPU designs (covers 1ms to 1000s of sim time)
+        } else if raw_range > 1e3 {
+            1e-6 // microseconds
+        } else {
+            1e-3 // milliseconds
+        };
+
+        return inferred_factor;
+    }
+    embedded_factor
+}
+
+fn _extract_fst_time_range(
+    body_result: &wellen::viewers::BodyResult,
+    timescale_factor: f64,
+) -> (f64, f64) {
+    if body_result.time_table.is_empty() {
+        // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s for empty FST
+        return (0.0, 1.0); // Minimal fallback for empty FST
+    }
+
+    let raw_min = match body_result.time_table.first() {
+        Some(time) => *time as f64,
+        None => {
+            eprintln!("Warning: Empty time table in FST file");
+            // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s
+            return (0.0, 1.0);
+        }
+    };
+
+    let raw_max = match body_result.time_table.last() {
+        Some(time) => *time as f64,
+        None => {
+            eprintln!("Warning: Empty time table in FST file");
+            // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s
+            return (0.0, 1.0);
+        }
+    };
+
+    // Removed spammy FST time extraction debug logging
+
+    // Convert FST time values to seconds using the proper timescale factor
+    let min_seconds = raw_min * timescale_factor;
+    let max_seconds = raw_max * timescale_factor;
+
+    debug_log!(
+        DEBUG_BACKEND,
+        "🔧 BACKEND TIME RANGE: FST file time range: {:.3}s to {:.3}s (span: {:.3}s)",
+        min_seconds,
+        max_seconds,
+        max_seconds - min_seconds
+    );
+
+    (min_seconds, max_seconds)
+}
+
+fn extract_fst_time_bounds_fast(
+    _file_path: &str,
+) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>> {
+    // FST files need to be parsed to get time bounds
+    // However, we can use a callback to stop early once we have min/max
+    // For now, we cannot determine bounds without parsing the body
+    // This could be improved with FST-specific scanning in the future
+
+    // NO FALLBACKS: Return None if we can't determine actual time bounds
+    // The frontend should show "Loading..." or appropriate state
+    Ok(None)
+}
+
+/// Recovery point of a VCD that was cut off mid-line
+struct VcdTruncation {
+    /// Bytes before the last, incomplete timestamp block
+    keep_bytes: u64,
+    /// Last timestamp whose value changes are complete, in file time units
+    last_complete_time: u64,
+}
+
+fn detect_vcd_truncation(file_path: &str) -> std::io::Result<Option<VcdTruncation>> {
+    let file = fs::File::open(file_path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(find_vcd_truncation(&mmap))
+}
+
+/// A VCD whose last line is incomplete was cut off; everything from the last
+/// timestamp line on is dropped, as that block may miss value changes.
+fn find_vcd_truncation(data: &[u8]) -> Option<VcdTruncation> {
+    let end = data.iter().rposition(|byte| !byte.is_ascii_whitespace())? + 1;
+    let last_line_start = data[..end]
+        .iter()
+        .rposition(|byte| matches!(byte, b'\n' | b'\r'))
+        .map_or(0, |pos| pos + 1);
+    if is_complete_vcd_line(&data[last_line_start..end]) {
+        return None;
+    }
+
+    let cut = rfind_timestamp_line(data, data.len())?;
+    let previous = rfind_timestamp_line(data, cut)?;
+    let time_end = data[previous..cut]
+        .iter()
+        .position(|byte| byte.is_ascii_whitespace())
+        .map_or(cut, |offset| previous + offset);
+    let last_complete_time = std::str::from_utf8(&data[previous + 1..time_end])
+        .ok()?
+        .parse()
+        .ok()?;
+    Some(VcdTruncation {
+        keep_bytes: cut as u64,
+        last_complete_time,
+    })
+}
+
+/// Whether a VCD body line is complete. A cut that happens to leave a valid
+/// line (e.g. "#2" of "#250") can't be told apart and counts as complete.
+fn is_complete_vcd_line(line: &[u8]) -> bool {
+    let Ok(line) = std::str::from_utf8(line) else {
+        return false;
+    };
+    let mut tokens = line.split_whitespace();
+    let Some(first) = tokens.next() else {
+        return true;
+    };
+    let second = tokens.next();
+    match first.as_bytes()[0] {
+        b'#' => first.len() > 1 && first[1..].bytes().all(|byte| byte.is_ascii_digit()),
+        // Vector, real and string changes: value, space, identifier
+        b'b' | b'B' | b'r' | b'R' | b's' | b'S' => second.is_some(),
+        b'0' | b'1' | b'x' | b'X' | b'z' | b'Z' | b'u' | b'U' | b'w' | b'W' | b'l' | b'L'
+        | b'h' | b'H' | b'-' => first.len() > 1 && second.is_none(),
+        b'$' => {
+            line.trim_end().ends_with("$end")
+                || matches!(
+                    first,
+                    "$dumpvars" | "$dumpall" | "$dumpon" | "$dumpoff" | "$end"
+                )
+        }
+        _ => false,
+    }
+}
+
+/// Start of the last line before `end` that begins with a `#` timestamp
+fn rfind_timestamp_line(data: &[u8], end: usize) -> Option<usize> {
+    (0..end)
+        .rev()
+        .find(|&pos| data[pos] == b'#' && (pos == 0 || matches!(data[pos - 1], b'\n' | b'\r')))
+}
+
+/// Input of a truncated VCD's body parse. Reads stop after `len` bytes, so wellen sees the
+/// recovered part, and fail once the load is cancelled; wellen's progress counter cannot
+/// stop a parse, but a failing read ends it.
+struct LoadInput {
+    file: fs::File,
+    len: u64,
+    pos: u64,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl LoadInput {
+    fn open(file_path: &str, len: u64, cancelled: Arc<AtomicBool>) -> std::io::Result<Self> {
+        Ok(Self {
+            file: fs::File::open(file_path)?,
+            len,
+            pos: 0,
+            cancelled,
+        })
+    }
+}
+
+impl std::io::Read for LoadInput {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(std::io::Error::other("load cancelled"));
+        }
+        let remaining = self.len.saturating_sub(self.pos);
+        let max = (buf.len() as u64).min(remaining) as usize;
+        if max == 0 {
+            return Ok(0);
+        }
+        let read = std::io::Read::read(&mut self.file, &mut buf[..max])?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl std::io::Seek for LoadInput {
+    fn seek(&mut self, from: std::io::SeekFrom) -> std::io::Result<u64> {
+        let target = match from {
+            std::io::SeekFrom::Start(offset) => Some(offset),
+            std::io::SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            std::io::SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        }
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "seek before file start")
+        })?;
+        self.pos = std::io::Seek::seek(&mut self.file, std::io::SeekFrom::Start(target))?;
+        Ok(self.pos)
+    }
+}
+
+/// Body continuation of a re-opened waveform, either the whole file or a recovered prefix
+enum WaveformBody {
+    File(wellen::viewers::ReadBodyContinuation<std::io::BufReader<fs::File>>),
+    Prefix(wellen::viewers::ReadBodyContinuation<std::io::BufReader<LoadInput>>),
+}
+
+impl WaveformBody {
+    /// Whole files go through wellen's own file reader, which can mmap and parse in
+    /// parallel; `cancelled` only interrupts the parse of a recovered prefix
+    fn open(
+        file_path: &str,
+        options: &wellen::LoadOptions,
+        vcd_body_limit: Option<u64>,
+        cancelled: Arc<AtomicBool>,
+    ) -> wellen::Result<(u64, Self)> {
+        match vcd_body_limit {
+            Some(limit) => {
+                let reader = std::io::BufReader::new(LoadInput::open(file_path, limit, cancelled)?);
+                let header = wellen::viewers::read_header(reader, options)?;
+                Ok((header.body_len, WaveformBody::Prefix(header.body)))
+            }
+            None => {
+                let header = wellen::viewers::read_header_from_file(file_path, options)?;
+                Ok((header.body_len, WaveformBody::File(header.body)))
+            }
+        }
+    }
+
+    fn read(
+        self,
+        hierarchy: &wellen::Hierarchy,
+        progress: Option<Arc<AtomicU64>>,
+    ) -> wellen::Result<wellen::viewers::BodyResult> {
+        match self {
+            WaveformBody::File(body) => wellen::viewers::read_body(body, hierarchy, progress),
+            WaveformBody::Prefix(body) => wellen::viewers::read_body(body, hierarchy, progress),
+        }
+    }
+}
+
+// ===== COMPRESSED WAVEFORM FILES =====
+
+/// Directory under the system temp dir holding unpacked copies of compressed files;
+/// wellen mmaps and seeks its input, which a decompressing stream cannot offer
+const DECOMPRESSION_CACHE_DIR: &str = "novywave-decompressed";
+
+/// Most bytes the decompression cache keeps on disk. Beyond it the least recently used
+/// copies that no open file reads from are deleted.
+const DECOMPRESSION_CACHE_BUDGET_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Compressed file path -> its unpacked copy in the decompression cache
+static DECOMPRESSED_SOURCES: Lazy<Mutex<HashMap<String, PathBuf>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Imported file path (see `waveform_import`) -> its VCD conversion in the decompression cache
+static CONVERTED_SOURCES: Lazy<Mutex<HashMap<String, PathBuf>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Time offsets and timescale overrides of files, from the workspace config and
+/// `UpMsg::SetFileTimeAlignment`
+static FILE_TIME_ALIGNMENTS: Lazy<Mutex<HashMap<String, shared::FileTimeAlignment>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn set_file_time_alignments(alignments: &BTreeMap<String, shared::FileTimeAlignment>) {
+    let mut stored = match FILE_TIME_ALIGNMENTS.lock() {
+        Ok(stored) => stored,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *stored = alignments
+        .iter()
+        .map(|(path, alignment)| (path.clone(), alignment.clone()))
+        .collect();
+}
+
+fn file_time_alignment(file_path: &str) -> shared::FileTimeAlignment {
+    let alignments = match FILE_TIME_ALIGNMENTS.lock() {
+        Ok(alignments) => alignments,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    alignments.get(file_path).cloned().unwrap_or_default()
+}
+
+/// Move the time bounds of a parsed file the way its times move once the body loads
+fn aligned_file_hierarchy(mut hierarchy: FileHierarchy, file_path: &str) -> FileHierarchy {
+    let alignment = file_time_alignment(file_path);
+    if alignment.is_identity() {
+        return hierarchy;
+    }
+    let metadata = match WAVEFORM_METADATA_STORE.lock() {
+        Ok(store) => store.get(file_path).cloned(),
+        Err(poisoned) => poisoned.into_inner().get(file_path).cloned(),
+    };
+    let scale = match (alignment.timescale_seconds(), &metadata) {
+        (Some(override_factor), Some(metadata)) => override_factor / metadata.timescale_factor,
+        _ => 1.0,
+    };
+    // Bounds are moved in picoseconds and only rounded for the nanosecond fields
+    let scaled_ps = |time_seconds: f64| (time_seconds * scale * PS_PER_SECOND).round() as u64;
+    for file in &mut hierarchy.files {
+        let (min_ps, max_ps) = match &metadata {
+            Some(metadata) => (
+                file.min_time_ns.map(|_| scaled_ps(metadata.time_bounds.0)),
+                file.max_time_ns.map(|_| scaled_ps(metadata.time_bounds.1)),
+            ),
+            None => match file.time_range_ps() {
+                Some((min, max)) => (
+                    Some(scaled_ps(min as f64 / PS_PER_SECOND)),
+                    Some(scaled_ps(max as f64 / PS_PER_SECOND)),
+                ),
+                None => (None, None),
+            },
+        };
+        let offset_ps = clamped_offset_ps(alignment.offset_ps, min_ps.unwrap_or(0));
+        let align = |time_ps: u64| shift_ps(time_ps, offset_ps) / 1000;
+        file.min_time_ps = min_ps.map(|time_ps| shift_ps(time_ps, offset_ps));
+        file.max_time_ps = max_ps.map(|time_ps| shift_ps(time_ps, offset_ps));
+        file.min_time_ns = min_ps.map(align);
+        file.max_time_ns = max_ps.map(align);
+        file.truncated_at_ns = file
+            .truncated_at_ns
+            .map(|time_ns| align(scaled_ps(time_ns as f64 * 1e-9)));
+    }
+    hierarchy
+}
+
+/// Import settings of logic-analyzer CSV files, from the workspace config and
+/// `UpMsg::ConfigureCsvImport`
+static CSV_IMPORT_SETTINGS: Lazy<Mutex<HashMap<String, shared::CsvImportSettings>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn set_csv_import_settings(csv_imports: &BTreeMap<String, shared::CsvImportSettings>) {
+    let mut imports = match CSV_IMPORT_SETTINGS.lock() {
+        Ok(imports) => imports,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *imports = csv_imports
+        .iter()
+        .map(|(path, settings)| (path.clone(), settings.clone()))
+        .collect();
+}
+
+fn csv_import_settings(file_path: &str) -> shared::CsvImportSettings {
+    let imports = match CSV_IMPORT_SETTINGS.lock() {
+        Ok(imports) => imports,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    imports.get(file_path).cloned().unwrap_or_default()
+}
+
+/// Path wellen reads for a file: the VCD conversion of an imported format, the unpacked
+/// copy of a compressed file, otherwise the file itself
+fn waveform_source_path(file_path: &str) -> String {
+    let converted = match CONVERTED_SOURCES.lock() {
+        Ok(sources) => sources.get(file_path).cloned(),
+        Err(poisoned) => poisoned.into_inner().get(file_path).cloned(),
+    };
+    converted
+        .map(|source| source.to_string_lossy().to_string())
+        .unwrap_or_else(|| unpacked_source_path(file_path))
+}
+
+/// The unpacked copy of a compressed file, otherwise the file itself
+fn unpacked_source_path(file_path: &str) -> String {
+    let sources = match DECOMPRESSED_SOURCES.lock() {
+        Ok(sources) => sources,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    sources
+        .get(file_path)
+        .map(|source| source.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_path.to_string())
+}
+
+/// Cache location of a compressed file's unpacked copy. The key covers size and
+/// modification time, so a rewritten archive never reuses a stale copy.
+fn decompression_cache_path(file_path: &str, metadata: &fs::Metadata) -> PathBuf {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    file_path.hash(&mut hasher);
+    metadata.len().hash(&mut hasher);
+    metadata.modified().ok().hash(&mut hasher);
+    let inner_name = Path::new(shared::strip_compression_extension(file_path))
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("waveform");
+    decompression_cache_dir().join(format!("{:016x}-{}", hasher.finish(), inner_name))
+}
+
+fn decompression_cache_dir() -> PathBuf {
+    std::env::temp_dir().join(DECOMPRESSION_CACHE_DIR)
+}
+
+/// Mark a cached copy as just used; eviction goes by modification time
+fn touch_cached_copy(copy: &Path) {
+    if let Ok(file) = fs::File::options().write(true).open(copy) {
+        let _ = file.set_modified(std::time::SystemTime::now());
+    }
+}
+
+/// Cached copies the open files are read from
+fn cached_copies_in_use() -> std::collections::HashSet<PathBuf> {
+    let mut in_use = std::collections::HashSet::new();
+    for sources in [&DECOMPRESSED_SOURCES, &CONVERTED_SOURCES] {
+        let sources = match sources.lock() {
+            Ok(sources) => sources,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        in_use.extend(sources.values().cloned());
+    }
+    in_use
+}
+
+/// Delete the least recently used copies in `dir` until it fits `budget_bytes`, sparing
+/// `in_use` and unfinished `.partial` files; returns the bytes freed
+fn evict_cached_copies(
+    dir: &Path,
+    budget_bytes: u64,
+    in_use: &std::collections::HashSet<PathBuf>,
+) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut copies: Vec<(std::time::SystemTime, u64, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| {
+                let used = metadata.modified().unwrap_or(std::time::UNIX_EPOCH);
+                (used, metadata.len(), entry.path())
+            })
+        })
+        .collect();
+    let mut total_bytes: u64 = copies.iter().map(|(_, size, _)| size).sum();
+    copies.sort();
+
+    let mut freed = 0;
+    for (_, _, copy) in copies {
+        if total_bytes <= budget_bytes {
+            break;
+        }
+        let partial = copy
+            .extension()
+            .is_some_and(|extension| extension == "partial");
+        if partial || in_use.contains(&copy) {
+            continue;
+        }
+        let removed = remove_cached_copy(&copy);
+        total_bytes = total_bytes.saturating_sub(removed);
+        freed += removed;
+    }
+    freed
+}
+
+/// Keep the decompression cache within its budget after a copy was added or reused
+fn enforce_decompression_cache_budget(copy: &Path) {
+    touch_cached_copy(copy);
+    let freed = evict_cached_copies(
+        &decompression_cache_dir(),
+        DECOMPRESSION_CACHE_BUDGET_BYTES,
+        &cached_copies_in_use(),
+    );
+    if freed > 0 {
+        debug_log!(
+            DEBUG_PARSE,
+            "🗜️ PARSE: Evicted {} bytes from the decompression cache",
+            freed
+        );
     }
 }
 
-/// Intelligent FST timescale inference to handle files with incorrect embedded timescale
-fn infer_reasonable_fst_timescale(
-    body_result: &wellen::viewers::BodyResult,
-    embedded_factor: f64,
-    _filename: &str,
-) -> f64 {
-    if body_result.time_table.is_empty() {
-        return embedded_factor; // Can't infer from empty time table
+/// Startup cleanup: drop copies left unfinished by an earlier run and trim the cache to
+/// its budget, since no file is open yet
+fn sweep_decompression_cache() {
+    let dir = decompression_cache_dir();
+    if let Ok(entries) = fs::read_dir(&dir) {
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "partial")
+            {
+                remove_cached_copy(&path);
+            }
+        }
     }
+    evict_cached_copies(
+        &dir,
+        DECOMPRESSION_CACHE_BUDGET_BYTES,
+        &std::collections::HashSet::new(),
+    );
+}
 
-    let (raw_min, raw_max) = match (
-        body_result.time_table.first(),
-        body_result.time_table.last(),
-    ) {
-        (Some(&min), Some(&max)) => (min as f64, max as f64),
-        _ => {
-            // If time bounds can't be determined, return the embedded factor
-            return embedded_factor;
-        }
+/// Unpack a compressed file into the cache unless an up-to-date copy is already there.
+/// Plain files need no preparation.
+async fn ensure_decompressed(
+    file_path: &str,
+    load_progress: &LoadProgressReporter,
+) -> Result<(), String> {
+    let Some(format) = shared::CompressionFormat::from_path(file_path) else {
+        return Ok(());
     };
-    let raw_range = raw_max - raw_min;
+    let metadata = fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read metadata of '{}': {}", file_path, e))?;
+    let target = decompression_cache_path(file_path, &metadata);
 
-    // Check if embedded timescale produces unreasonably long duration (>1000 seconds)
-    let computed_duration = raw_range * embedded_factor;
+    let previous = {
+        let sources = match DECOMPRESSED_SOURCES.lock() {
+            Ok(sources) => sources,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        sources.get(file_path).cloned()
+    };
+    if let Some(previous) = previous.filter(|previous| *previous != target) {
+        // The archive changed on disk since it was unpacked
+        let _ = fs::remove_file(previous);
+    }
 
-    if computed_duration > 1000.0 {
-        // FST inference: embedded timescale produces unreasonably long duration
+    if !target.exists() {
+        debug_log!(
+            DEBUG_PARSE,
+            "🗜️ PARSE: Decompressing '{}' into '{}'",
+            file_path,
+            target.display()
+        );
+        let bytes_read = load_progress.begin_counted(LoadPhase::Decompressing, metadata.len());
+        let source = file_path.to_string();
+        let destination = target.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            decompress_to_file(&source, format, &destination, bytes_read)
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(format!("Failed to decompress '{}': {}", file_path, e)),
+            Err(join_error) => {
+                return Err(format!(
+                    "Blocking task failed for decompression: {}",
+                    join_error
+                ));
+            }
+        }
+        load_progress.set_phase(LoadPhase::Header);
+    }
 
-        // Heuristic inference based on value magnitude - optimized for typical FPGA/digital designs
-        let inferred_factor = if raw_range > 1e15 {
-            1e-15 // femtoseconds
-        } else if raw_range > 1e12 {
-            1e-12 // picoseconds
-        } else if raw_range > 1e6 {
-            1e-9 // nanoseconds - most common for FPGA/CPU designs (covers 1ms to 1000s of sim time)
-        } else if raw_range > 1e3 {
-            1e-6 // microseconds
-        } else {
-            1e-3 // milliseconds
+    {
+        let mut sources = match DECOMPRESSED_SOURCES.lock() {
+            Ok(sources) => sources,
+            Err(poisoned) => poisoned.into_inner(),
         };
-
-        return inferred_factor;
+        sources.insert(file_path.to_string(), target.clone());
     }
-    embedded_factor
+    enforce_decompression_cache_budget(&target);
+    Ok(())
 }
 
-fn _extract_fst_time_range(
-    body_result: &wellen::viewers::BodyResult,
-    timescale_factor: f64,
-) -> (f64, f64) {
-    if body_result.time_table.is_empty() {
-        // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s for empty FST
-        return (0.0, 1.0); // Minimal fallback for empty FST
+/// Write the VCD conversion of an imported format into the cache unless an up-to-date
+/// copy is already there. Files wellen reads directly need no preparation.
+async fn ensure_converted(file_path: &str) -> Result<(), String> {
+    let Some(format) = waveform_import::import_format(file_path) else {
+        return Ok(());
+    };
+    let metadata = fs::metadata(file_path)
+        .map_err(|e| format!("Failed to read metadata of '{}': {}", file_path, e))?;
+    let csv_settings = csv_import_settings(file_path);
+    // CSV import settings are part of the cache key, so changing them converts the file again
+    let settings_key = {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        csv_settings.hash(&mut hasher);
+        hasher.finish()
+    };
+    let target = PathBuf::from(format!(
+        "{}-{:08x}.vcd",
+        decompression_cache_path(file_path, &metadata).display(),
+        settings_key as u32
+    ));
+
+    let previous = match CONVERTED_SOURCES.lock() {
+        Ok(sources) => sources.get(file_path).cloned(),
+        Err(poisoned) => poisoned.into_inner().get(file_path).cloned(),
+    };
+    if let Some(previous) = previous.filter(|previous| *previous != target) {
+        // The file changed on disk since it was converted
+        let _ = fs::remove_file(previous);
     }
 
-    let raw_min = match body_result.time_table.first() {
-        Some(time) => *time as f64,
-        None => {
-            eprintln!("Warning: Empty time table in FST file");
-            // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s
-            return (0.0, 1.0);
-        }
+    if !target.exists() {
+        debug_log!(
+            DEBUG_PARSE,
+            "🔄 PARSE: Converting {:?} file '{}' into '{}'",
+            format,
+            file_path,
+            target.display()
+        );
+        let original = file_path.to_string();
+        let source = unpacked_source_path(file_path);
+        let destination = target.clone();
+        tokio::task::spawn_blocking(move || {
+            waveform_import::convert_to_vcd(
+                &original,
+                &source,
+                &format,
+                &csv_settings,
+                &destination,
+            )
+        })
+        .await
+        .map_err(|join_error| format!("Blocking task failed for conversion: {}", join_error))??;
+    }
+
+    {
+        let mut sources = match CONVERTED_SOURCES.lock() {
+            Ok(sources) => sources,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        sources.insert(file_path.to_string(), target.clone());
+    }
+    enforce_decompression_cache_budget(&target);
+    Ok(())
+}
+
+/// Delete the unpacked copy of a compressed file and the conversion of an imported one;
+/// returns their size on disk
+fn release_decompressed_copy(file_path: &str) -> u64 {
+    let converted = match CONVERTED_SOURCES.lock() {
+        Ok(mut sources) => sources.remove(file_path),
+        Err(poisoned) => poisoned.into_inner().remove(file_path),
+    };
+    let unpacked = match DECOMPRESSED_SOURCES.lock() {
+        Ok(mut sources) => sources.remove(file_path),
+        Err(poisoned) => poisoned.into_inner().remove(file_path),
     };
+    converted
+        .into_iter()
+        .chain(unpacked)
+        .map(|copy| remove_cached_copy(&copy))
+        .sum()
+}
 
-    let raw_max = match body_result.time_table.last() {
-        Some(time) => *time as f64,
-        None => {
-            eprintln!("Warning: Empty time table in FST file");
-            // ❌ FALLBACK ELIMINATION: Use minimal 1-second range instead of 100s
-            return (0.0, 1.0);
+fn remove_cached_copy(copy: &Path) -> u64 {
+    let size = fs::metadata(copy)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    match fs::remove_file(copy) {
+        Ok(()) => size,
+        Err(e) => {
+            eprintln!(
+                "Warning: Failed to remove cached copy '{}': {}",
+                copy.display(),
+                e
+            );
+            0
         }
-    };
+    }
+}
 
-    // Removed spammy FST time extraction debug logging
+/// Counts the compressed bytes consumed, which is what decompression progress is measured in
+struct CountingReader<R> {
+    inner: R,
+    bytes_read: Arc<AtomicU64>,
+}
 
-    // Convert FST time values to seconds using the proper timescale factor
-    let min_seconds = raw_min * timescale_factor;
-    let max_seconds = raw_max * timescale_factor;
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.bytes_read.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
 
-    debug_log!(
-        DEBUG_BACKEND,
-        "🔧 BACKEND TIME RANGE: FST file time range: {:.3}s to {:.3}s (span: {:.3}s)",
-        min_seconds,
-        max_seconds,
-        max_seconds - min_seconds
-    );
+/// Stream-decompress into `target`, going through a `.partial` file so an
+/// interrupted run never leaves a copy that looks complete
+fn decompress_to_file(
+    file_path: &str,
+    format: shared::CompressionFormat,
+    target: &Path,
+    bytes_read: Arc<AtomicU64>,
+) -> std::io::Result<()> {
+    use std::io::Read;
+
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let input = CountingReader {
+        inner: fs::File::open(file_path)?,
+        bytes_read,
+    };
+    let mut decoder: Box<dyn Read> = match format {
+        shared::CompressionFormat::Gzip => Box::new(flate2::read::MultiGzDecoder::new(input)),
+        shared::CompressionFormat::Zstd => Box::new(zstd::stream::read::Decoder::new(input)?),
+        shared::CompressionFormat::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(input)),
+    };
 
-    (min_seconds, max_seconds)
+    let partial = PathBuf::from(format!("{}.partial", target.display()));
+    match copy_until_cancelled(file_path, &mut decoder, &partial) {
+        Ok(()) => fs::rename(&partial, target),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
 }
 
-fn extract_fst_time_bounds_fast(
-    _file_path: &str,
-) -> Result<Option<(f64, f64)>, Box<dyn std::error::Error>> {
-    // FST files need to be parsed to get time bounds
-    // However, we can use a callback to stop early once we have min/max
-    // For now, we cannot determine bounds without parsing the body
-    // This could be improved with FST-specific scanning in the future
-
-    // NO FALLBACKS: Return None if we can't determine actual time bounds
-    // The frontend should show "Loading..." or appropriate state
-    Ok(None)
+fn copy_until_cancelled(
+    file_path: &str,
+    decoder: &mut dyn std::io::Read,
+    destination: &Path,
+) -> std::io::Result<()> {
+    use std::io::{Read, Write};
+
+    let mut output = std::io::BufWriter::new(fs::File::create(destination)?);
+    let mut buffer = vec![0u8; 256 * 1024];
+    loop {
+        if is_load_cancelled(file_path) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                "decompression cancelled",
+            ));
+        }
+        let read = decoder.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        output.write_all(&buffer[..read])?;
+    }
+    output.flush()
 }
 
 fn extract_vcd_time_bounds_fast(file_path: &str) -> Result<(f64, f64), Box<dyn std::error::Error>> {
@@ -2402,6 +4148,104 @@ async fn send_progress_update(
     broadcast_down_msg(DownMsg::ParsingProgress { file_id, progress }).await;
 }
 
+/// Time between `DownMsg::LoadProgress` updates; loads finishing sooner report nothing
+const LOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+struct LoadProgressState {
+    phase: Mutex<LoadPhase>,
+    /// When the current phase began; the ETA only extrapolates the time spent in it
+    phase_started: Mutex<Instant>,
+    /// Counts compressed bytes while decompressing; handed to wellen, which adds
+    /// the body bytes it has parsed (VCD only)
+    bytes_processed: Arc<AtomicU64>,
+    total_bytes: AtomicU64,
+    finished: AtomicBool,
+}
+
+/// Broadcasts the progress of a load until dropped, so every early return ends the reports
+struct LoadProgressReporter {
+    state: Arc<LoadProgressState>,
+}
+
+impl LoadProgressReporter {
+    fn start(file_path: &str) -> Self {
+        let state = Arc::new(LoadProgressState {
+            phase: Mutex::new(LoadPhase::Header),
+            phase_started: Mutex::new(Instant::now()),
+            bytes_processed: Arc::new(AtomicU64::new(0)),
+            total_bytes: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+        });
+        let ticker_state = Arc::clone(&state);
+        let file_id = file_path.to_string();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let mut reported = false;
+            loop {
+                tokio::time::sleep(LOAD_PROGRESS_INTERVAL).await;
+                let finished = ticker_state.finished.load(Ordering::Relaxed);
+                if finished && !reported {
+                    break;
+                }
+                let phase = if finished {
+                    LoadPhase::Done
+                } else {
+                    match ticker_state.phase.lock() {
+                        Ok(phase) => *phase,
+                        Err(poisoned) => *poisoned.into_inner(),
+                    }
+                };
+                let phase_started = match ticker_state.phase_started.lock() {
+                    Ok(phase_started) => *phase_started,
+                    Err(poisoned) => *poisoned.into_inner(),
+                };
+                let progress = LoadProgress::new(
+                    phase,
+                    ticker_state.bytes_processed.load(Ordering::Relaxed),
+                    ticker_state.total_bytes.load(Ordering::Relaxed),
+                    started.elapsed().as_millis() as u64,
+                    phase_started.elapsed().as_millis() as u64,
+                );
+                broadcast_down_msg(DownMsg::LoadProgress {
+                    file_id: file_id.clone(),
+                    progress,
+                })
+                .await;
+                reported = true;
+                if finished {
+                    break;
+                }
+            }
+        });
+        Self { state }
+    }
+
+    fn set_phase(&self, phase: LoadPhase) {
+        match self.state.phase_started.lock() {
+            Ok(mut started) => *started = Instant::now(),
+            Err(poisoned) => *poisoned.into_inner() = Instant::now(),
+        }
+        match self.state.phase.lock() {
+            Ok(mut current) => *current = phase,
+            Err(poisoned) => *poisoned.into_inner() = phase,
+        }
+    }
+
+    /// Enter a phase measured in bytes; the returned counter starts from zero
+    fn begin_counted(&self, phase: LoadPhase, total_bytes: u64) -> Arc<AtomicU64> {
+        self.state.bytes_processed.store(0, Ordering::Relaxed);
+        self.state.total_bytes.store(total_bytes, Ordering::Relaxed);
+        self.set_phase(phase);
+        Arc::clone(&self.state.bytes_processed)
+    }
+}
+
+impl Drop for LoadProgressReporter {
+    fn drop(&mut self) {
+        self.state.finished.store(true, Ordering::Relaxed);
+    }
+}
+
 async fn send_down_msg(msg: DownMsg, session_id: SessionId, cor_id: CorId) {
     if let Some(session) = sessions::by_session_id().wait_for(session_id).await {
         session.send_down_msg(&msg, cor_id).await;
@@ -2729,6 +4573,29 @@ fn expand_config_paths(config: &mut AppConfig) {
             cloned
         })
         .collect();
+
+    config.workspace.csv_imports = std::mem::take(&mut config.workspace.csv_imports)
+        .into_iter()
+        .map(|(path, settings)| {
+            let absolute = context.to_absolute(&PathBuf::from(&path));
+            (absolute.to_string_lossy().to_string(), settings)
+        })
+        .collect();
+
+    config.workspace.file_time_alignments =
+        std::mem::take(&mut config.workspace.file_time_alignments)
+            .into_iter()
+            .map(|(path, alignment)| {
+                let absolute = context.to_absolute(&PathBuf::from(&path));
+                (absolute.to_string_lossy().to_string(), alignment)
+            })
+            .collect();
+
+    for signal in &mut config.workspace.derived_signals {
+        for unique_id in signal.inputs.values_mut() {
+            *unique_id = expand_unique_id(unique_id, context);
+        }
+    }
 }
 
 fn relativize_config_paths(config: &mut AppConfig) {
@@ -2776,6 +4643,35 @@ fn relativize_config_paths(config: &mut AppConfig) {
             cloned
         })
         .collect();
+
+    config.workspace.csv_imports = std::mem::take(&mut config.workspace.csv_imports)
+        .into_iter()
+        .map(|(path, settings)| {
+            let path_buf = PathBuf::from(&path);
+            let normalized = context
+                .to_relative_if_in_workspace(&path_buf)
+                .unwrap_or(path_buf);
+            (normalized.to_string_lossy().to_string(), settings)
+        })
+        .collect();
+
+    config.workspace.file_time_alignments =
+        std::mem::take(&mut config.workspace.file_time_alignments)
+            .into_iter()
+            .map(|(path, alignment)| {
+                let path_buf = PathBuf::from(&path);
+                let normalized = context
+                    .to_relative_if_in_workspace(&path_buf)
+                    .unwrap_or(path_buf);
+                (normalized.to_string_lossy().to_string(), alignment)
+            })
+            .collect();
+
+    for signal in &mut config.workspace.derived_signals {
+        for unique_id in signal.inputs.values_mut() {
+            *unique_id = relativize_unique_id(unique_id, context);
+        }
+    }
 }
 
 fn relativize_workspace_directory_path(dir: &str, context: &WorkspaceContext) -> String {
@@ -2805,6 +4701,9 @@ fn relativize_workspace_directory_path(dir: &str, context: &WorkspaceContext) ->
 }
 
 fn expand_unique_id(unique_id: &str, context: &WorkspaceContext) -> String {
+    if shared::derived_signal_name(unique_id).is_some() {
+        return unique_id.to_string();
+    }
     if let Some((path_part, rest)) = unique_id.split_once('|') {
         let path_buf = PathBuf::from(path_part);
         let absolute = context.to_absolute(&path_buf);
@@ -2988,6 +4887,9 @@ async fn handle_loaded_config(
     workspace_event_root: Option<String>,
 ) {
     let config_for_messages = config;
+    set_csv_import_settings(&config_for_messages.workspace.csv_imports);
+    set_file_time_alignments(&config_for_messages.workspace.file_time_alignments);
+    derived_signals::set_derived_signals(&config_for_messages.workspace.derived_signals);
 
     spawn_directory_preload(
         config_for_messages
@@ -3116,6 +5018,9 @@ async fn load_config(session_id: SessionId, cor_id: CorId) {
 async fn save_config(config: AppConfig, _session_id: SessionId, _cor_id: CorId) {
     let mut runtime_config = config.clone();
     expand_config_paths(&mut runtime_config);
+    set_csv_import_settings(&runtime_config.workspace.csv_imports);
+    set_file_time_alignments(&runtime_config.workspace.file_time_alignments);
+    derived_signals::set_derived_signals(&runtime_config.workspace.derived_signals);
 
     let plugin_reload = plugins::reload_plugins(
         &runtime_config.plugins,
@@ -3649,14 +5554,7 @@ async fn scan_directory_async(
                         let path_str = entry_path.to_string_lossy().to_string();
 
                         // Only include directories and waveform files for cleaner file dialog
-                        let is_waveform = if !is_directory {
-                            let name_lower = name.to_lowercase();
-                            name_lower.ends_with(".vcd")
-                                || name_lower.ends_with(".fst")
-                                || name_lower.ends_with(".ghw")
-                        } else {
-                            false
-                        };
+                        let is_waveform = !is_directory && shared::is_waveform_file(&name);
 
                         // Skip non-waveform files to reduce clutter
                         if !is_directory && !is_waveform {
@@ -3799,6 +5697,7 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
             } else {
                 // Mark this file as being loaded (while holding both locks)
                 loading_in_progress.insert(file_path.to_string());
+                register_load(file_path);
                 LoadAction::LoadNow
             }
         }
@@ -3838,6 +5737,16 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
         }
     }
 
+    let load_progress = LoadProgressReporter::start(file_path);
+    let preparation = match ensure_decompressed(file_path, &load_progress).await {
+        Ok(()) => ensure_converted(file_path).await,
+        Err(error) => Err(error),
+    };
+    if let Err(error) = preparation {
+        complete_loading(file_path);
+        return Err(error);
+    }
+
     // Check if we have metadata for this file
     let metadata = {
         let metadata_store = match WAVEFORM_METADATA_STORE.lock() {
@@ -3874,7 +5783,7 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
         // Re-parse the header to get hierarchy
         // CRITICAL: Use spawn_blocking to avoid blocking the async runtime
         let options = wellen::LoadOptions::default();
-        let file_path_clone = file_path.to_string();
+        let file_path_clone = waveform_source_path(file_path);
         let spawn_result = tokio::task::spawn_blocking(move || {
             std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 wellen::viewers::read_header_from_file(&file_path_clone, &options)
@@ -3917,7 +5826,7 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
 
         // Catch panics from header parsing
         // CRITICAL: Use spawn_blocking to avoid blocking the async runtime
-        let file_path_clone = file_path.to_string();
+        let file_path_clone = waveform_source_path(file_path);
         let spawn_result = tokio::task::spawn_blocking(move || {
             std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                 wellen::viewers::read_header_from_file(&file_path_clone, &options)
@@ -3976,6 +5885,11 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
         )
     };
 
+    if is_load_cancelled(file_path) {
+        complete_loading(file_path);
+        return Err(format!("Loading '{}' was cancelled", file_path));
+    }
+
     // Now parse the body on-demand
     // NOTE: This still loads the entire body into memory, but only when signals are actually requested
     // This defers the memory load until necessary, not during file selection
@@ -3988,16 +5902,22 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
     // Re-parse the file to get the body
     // CRITICAL: Use spawn_blocking to avoid blocking the async runtime
     let options = wellen::LoadOptions::default();
-    let file_path_clone = file_path.to_string();
+    let file_path_clone = waveform_source_path(file_path);
+    let vcd_body_limit = metadata.as_ref().and_then(|m| m.vcd_body_limit);
+    let cancelled = load_cancel_flag(file_path).unwrap_or_default();
     let spawn_result = tokio::task::spawn_blocking(move || {
         std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            wellen::viewers::read_header_from_file(&file_path_clone, &options)
+            WaveformBody::open(&file_path_clone, &options, vcd_body_limit, cancelled)
         }))
     })
     .await;
 
-    let header_with_body = match spawn_result {
-        Ok(Ok(Ok(header))) => header,
+    let (body_len, body) = match spawn_result {
+        Ok(Ok(Ok(_))) if is_load_cancelled(file_path) => {
+            complete_loading(file_path);
+            return Err(format!("Loading '{}' was cancelled", file_path));
+        }
+        Ok(Ok(Ok(opened))) => opened,
         Ok(Ok(Err(e))) => {
             complete_loading(file_path);
             return Err(format!("Failed to reparse file for body loading: {}", e));
@@ -4017,10 +5937,10 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
 
     // Parse body (this is where the memory is used, but only on-demand)
     // CRITICAL: Use spawn_blocking to avoid blocking the async runtime
-    let body = header_with_body.body;
+    let bytes_processed = load_progress.begin_counted(LoadPhase::Body, body_len);
     let spawn_result = tokio::task::spawn_blocking(move || {
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            wellen::viewers::read_body(body, &hierarchy, None)
+            body.read(&hierarchy, Some(bytes_processed))
         }));
         (result, hierarchy)
     })
@@ -4039,6 +5959,10 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
 
     let body_result = match body_parse_result {
         Ok(Ok(body)) => body,
+        Ok(Err(_)) if is_load_cancelled(file_path) => {
+            complete_loading(file_path);
+            return Err(format!("Loading '{}' was cancelled", file_path));
+        }
         Ok(Err(e)) => {
             complete_loading(file_path);
             return Err(format!(
@@ -4054,6 +5978,14 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
         }
     };
 
+    // The parsed body is dropped here instead of being stored
+    if is_load_cancelled(file_path) {
+        complete_loading(file_path);
+        return Err(format!("Loading '{}' was cancelled", file_path));
+    }
+
+    load_progress.set_phase(LoadPhase::Indexing);
+
     // Build signal reference map (hierarchy returned from spawn_blocking)
     let mut signals: HashMap<String, wellen::SignalRef> = HashMap::new();
     build_signal_reference_map(&hierarchy, &mut signals);
@@ -4076,14 +6008,25 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
         timescale_factor = inferred;
     }
 
+    let alignment = file_time_alignment(file_path);
+    if let Some(override_factor) = alignment.timescale_seconds() {
+        timescale_factor = override_factor;
+    }
+
+    let first_ps = native_time_to_ps(
+        body_result.time_table.first().copied().unwrap_or(0),
+        timescale_factor,
+    );
+
     // Store waveform data
     let waveform_data = WaveformData {
-        hierarchy,
+        hierarchy: Arc::new(hierarchy),
         signal_source: Arc::new(Mutex::new(body_result.source)),
-        time_table: body_result.time_table.clone(),
+        time_table: Arc::new(body_result.time_table.clone()),
         signals,
         file_format,
         timescale_factor,
+        time_offset_ps: clamped_offset_ps(alignment.offset_ps, first_ps),
     };
 
     debug_log!(
@@ -4123,6 +6066,21 @@ async fn ensure_waveform_body_loaded(file_path: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Load the bodies of `file_paths`, then run `job` on the blocking pool. A failed load or
+/// a panicking job comes back as the job's error; `task` names the job in that message.
+pub(crate) async fn run_blocking_signal_job<T: Send + 'static>(
+    file_paths: impl IntoIterator<Item = String>,
+    task: &str,
+    job: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    for file_path in file_paths {
+        ensure_waveform_body_loaded(&file_path).await?;
+    }
+    tokio::task::spawn_blocking(job)
+        .await
+        .unwrap_or_else(|join_error| Err(format!("{} task failed: {}", task, join_error)))
+}
+
 /// Generate alternative scope path formats to handle format mismatches between frontend and backend
 fn generate_scope_path_fallbacks(scope_path: &str, variable_name: &str) -> Vec<String> {
     let mut fallbacks = Vec::new();
@@ -4265,11 +6223,11 @@ async fn query_signal_values(
                 let target_time = match waveform_data.file_format {
                     wellen::FileFormat::Vcd => {
                         // For VCD: Convert from seconds to VCD native units using stored timescale
-                        (query.time_seconds / waveform_data.timescale_factor) as u64
+                        waveform_data.native_time_at(query.time_seconds)
                     }
                     _ => {
                         // For other formats, use proper timescale conversion
-                        (query.time_seconds / waveform_data.timescale_factor) as u64
+                        waveform_data.native_time_at(query.time_seconds)
                     }
                 };
 
@@ -4483,11 +6441,11 @@ async fn process_signal_value_queries_internal(
         let target_time = match waveform_data.file_format {
             wellen::FileFormat::Vcd => {
                 // For VCD: Convert from seconds to VCD native units using stored timescale
-                (query.time_seconds / waveform_data.timescale_factor) as u64
+                waveform_data.native_time_at(query.time_seconds)
             }
             _ => {
                 // For other formats, use proper timescale conversion
-                (query.time_seconds / waveform_data.timescale_factor) as u64
+                waveform_data.native_time_at(query.time_seconds)
             }
         };
 
@@ -4750,8 +6708,8 @@ async fn query_signal_transitions(
                         // time_range is in nanoseconds, convert to seconds then to VCD native units
                         let start_seconds = time_range.0 as f64 / 1_000_000_000.0;
                         let end_seconds = time_range.1 as f64 / 1_000_000_000.0;
-                        let start_native = (start_seconds / waveform_data.timescale_factor) as u64;
-                        let end_native = (end_seconds / waveform_data.timescale_factor) as u64;
+                        let start_native = waveform_data.native_time_at(start_seconds);
+                        let end_native = waveform_data.native_time_at(end_seconds);
                         (start_native, end_native)
                     }
                     _ => {
@@ -4759,8 +6717,8 @@ async fn query_signal_transitions(
                         let start_seconds = time_range.0 as f64 / 1_000_000_000.0;
                         let end_seconds = time_range.1 as f64 / 1_000_000_000.0;
                         (
-                            (start_seconds / waveform_data.timescale_factor) as u64,
-                            (end_seconds / waveform_data.timescale_factor) as u64,
+                            waveform_data.native_time_at(start_seconds),
+                            waveform_data.native_time_at(end_seconds),
                         )
                     }
                 };
@@ -4826,20 +6784,8 @@ async fn query_signal_transitions(
                     let mut idx = start_idx;
                     while idx < end_idx.min(waveform_data.time_table.len()) {
                         if let Some(&time_val) = waveform_data.time_table.get(idx) {
-                            // Convert time to nanoseconds for frontend using proper timescale
-                            let time_ns = match waveform_data.file_format {
-                                wellen::FileFormat::Vcd => {
-                                    // Convert VCD native units to seconds, then to nanoseconds
-                                    let time_seconds =
-                                        time_val as f64 * waveform_data.timescale_factor;
-                                    (time_seconds * 1_000_000_000.0) as u64
-                                }
-                                _ => {
-                                    let time_seconds =
-                                        time_val as f64 * waveform_data.timescale_factor;
-                                    (time_seconds * 1_000_000_000.0) as u64
-                                }
-                            };
+                            // Convert time to picoseconds for frontend using proper timescale
+                            let time_ps = waveform_data.time_ps(time_val);
 
                             // Get signal value at this time index
                             if let Some(offset) = signal.get_offset(idx as u32) {
@@ -4851,11 +6797,11 @@ async fn query_signal_transitions(
                                 // TRANSITION DETECTION: Only send when value actually changes
                                 if last_value.as_ref() != Some(&value_str) {
                                     transitions.push(SignalTransition {
-                                        time_ns,
+                                        time_ps,
                                         value: value_str.clone(),
                                     });
                                     last_value = Some(value_str);
-                                    last_transition_time = Some(time_ns);
+                                    last_transition_time = Some(time_ps);
                                 }
                             }
                         }
@@ -4868,24 +6814,12 @@ async fn query_signal_transitions(
                     if let (Some(last_val), Some(last_time)) = (&last_value, last_transition_time) {
                         if last_val != "0" {
                             // Calculate actual file end time for proper filler timing using proper timescale
-                            let file_end_time_ns = match waveform_data.file_format {
-                                wellen::FileFormat::Vcd => {
-                                    // Convert VCD native units to seconds, then to nanoseconds
-                                    let time_seconds =
-                                        end_time as f64 * waveform_data.timescale_factor;
-                                    (time_seconds * 1_000_000_000.0) as u64
-                                }
-                                _ => {
-                                    let time_seconds =
-                                        end_time as f64 * waveform_data.timescale_factor;
-                                    (time_seconds * 1_000_000_000.0) as u64
-                                }
-                            };
+                            let file_end_time_ps = waveform_data.time_ps(end_time);
 
                             // Add "0" filler at actual signal end time (not viewing window end)
-                            if last_time < file_end_time_ns {
+                            if last_time < file_end_time_ps {
                                 transitions.push(SignalTransition {
-                                    time_ns: file_end_time_ns,
+                                    time_ps: file_end_time_ps,
                                     value: "0".to_string(),
                                 });
                             }
@@ -4964,8 +6898,9 @@ fn format_non_binary_signal_value(value: &wellen::SignalValue) -> String {
 /// Handle unified signal query using the new cache manager
 async fn handle_unified_signal_query(
     signal_requests: Vec<UnifiedSignalRequest>,
-    cursor_time_ns: Option<u64>,
+    cursor_time_ps: Option<u64>,
     request_id: String,
+    payload_encoding: SignalPayloadEncoding,
     session_id: SessionId,
     cor_id: CorId,
 ) {
@@ -4974,11 +6909,17 @@ async fn handle_unified_signal_query(
         "🔍 BACKEND: About to call SIGNAL_CACHE_MANAGER.query_unified_signals for request_id: {}",
         request_id
     );
-    match SIGNAL_CACHE_MANAGER
-        .query_unified_signals(signal_requests, cursor_time_ns)
-        .await
-    {
-        Ok((signal_data, cursor_values, statistics)) => {
+    let query_key = signal_query_key(session_id, &request_id);
+    let cancelled = register_signal_query(&query_key);
+    let result = SIGNAL_CACHE_MANAGER
+        .query_unified_signals(signal_requests, cursor_time_ps, &cancelled)
+        .await;
+    finish_signal_query(&query_key);
+    match result {
+        Ok((mut signal_data, cursor_values, statistics)) => {
+            for data in &mut signal_data {
+                data.encode_payload(payload_encoding);
+            }
             debug_log!(
                 DEBUG_BACKEND,
                 "🔍 BACKEND: SIGNAL_CACHE_MANAGER success - {} signal_data items, {} cursor_values",
@@ -4992,8 +6933,12 @@ async fn handle_unified_signal_query(
                     request_id,
                     signal_data,
                     cursor_values,
-                    cached_time_range_ns: None, // Cache time range would be computed from signal data bounds
+                    cached_time_range_ps: None, // Cache time range would be computed from signal data bounds
                     statistics: Some(statistics),
+                    payload_encodings: vec![
+                        SignalPayloadEncoding::Json,
+                        SignalPayloadEncoding::Binary,
+                    ],
                 },
                 session_id,
                 cor_id,
@@ -5006,6 +6951,14 @@ async fn handle_unified_signal_query(
                 cursor_len
             );
         }
+        Err(error) if error == SIGNAL_QUERY_CANCELLED => {
+            // The frontend has moved on and drops late responses anyway
+            debug_log!(
+                DEBUG_BACKEND,
+                "🛑 BACKEND: UnifiedSignalQuery {} cancelled",
+                request_id
+            );
+        }
         Err(error) => {
             debug_log!(
                 DEBUG_BACKEND,
@@ -5050,6 +7003,8 @@ async fn main() -> std::io::Result<()> {
         }
     }));
 
+    sweep_decompression_cache();
+
     start(frontend, up_msg_handler, |_error| {
         // Error logging removed to reduce log spam
     })
@@ -130,8 +130,11 @@ impl DiscoveryHostConfig {
         .map_err(|_| "panic while building discovery matcher".to_string())?
     }
 
+    /// Compressed dumps such as `run.vcd.gz` are filtered by the format they wrap
     fn extension_allowed(&self, path: &Path) -> bool {
-        path.extension()
+        let path_text = path.to_string_lossy();
+        Path::new(shared::strip_compression_extension(&path_text))
+            .extension()
             .and_then(|ext| ext.to_str())
             .map(|ext| self.allow_extensions.contains(&ext.to_ascii_lowercase()))
             .unwrap_or(false)
@@ -1143,6 +1146,8 @@ mod tests {
         let matcher = config.build_matcher().expect("matcher builds");
         assert!(config.extension_allowed(&file_path));
         assert!(config.matches(&matcher, &file_path));
+        assert!(config.extension_allowed(&target_dir.join("sample.vcd.gz")));
+        assert!(!config.extension_allowed(&target_dir.join("notes.txt.zst")));
 
         fs::remove_dir_all(&temp_root).unwrap();
     }
@@ -0,0 +1,174 @@
+//! Signal measurements (`UpMsg::MeasureSignal`): period, frequency, duty cycle, pulse widths
+//! and edge counts of a 1-bit signal between two times.
+//!
+//! Transitions come from `SignalCacheManager`, so the range may reach beyond the part of
+//! the signal the frontend has loaded.
+
+use crate::derived_signals::source_files;
+use crate::{SIGNAL_CACHE_MANAGER, run_blocking_signal_job, send_down_msg};
+use moon::*;
+use shared::{
+    DownMsg, DurationStats, PackedTransitions, SignalMeasurement, SignalMeasurementRequest,
+    UnifiedSignalRequest, VarFormat,
+};
+use std::sync::atomic::AtomicBool;
+
+/// Logic level of a 1-bit value; `None` for `x`, `z` and the other unknown states
+fn level(value: &str) -> Option<bool> {
+    match value {
+        "1" | "h" | "H" => Some(true),
+        "0" | "l" | "L" => Some(false),
+        _ => None,
+    }
+}
+
+fn measure(packed: &PackedTransitions, start_ps: u64, end_ps: u64) -> SignalMeasurement {
+    let first_in_range = packed
+        .times_ps
+        .partition_point(|&time_ps| time_ps < start_ps);
+    let mut current = first_in_range
+        .checked_sub(1)
+        .and_then(|index| level(&packed.value_string(index)));
+
+    let mut measurement = SignalMeasurement::default();
+    let mut periods = Vec::new();
+    let mut high_pulses = Vec::new();
+    let mut low_pulses = Vec::new();
+    let (mut high_in_periods_ps, mut periods_ps) = (0u64, 0u64);
+    // Times of the last edges since the last unknown value
+    let mut last_rise: Option<u64> = None;
+    let mut last_fall: Option<u64> = None;
+
+    for index in first_in_range..packed.len() {
+        let time_ps = packed.times_ps[index];
+        if time_ps > end_ps {
+            break;
+        }
+        let next = level(&packed.value_string(index));
+        match (current, next) {
+            (Some(false), Some(true)) => {
+                measurement.rising_edges += 1;
+                if let Some(fall_ps) = last_fall {
+                    low_pulses.push(time_ps - fall_ps);
+                }
+                if let (Some(rise_ps), Some(fall_ps)) = (last_rise, last_fall) {
+                    periods.push(time_ps - rise_ps);
+                    periods_ps += time_ps - rise_ps;
+                    high_in_periods_ps += fall_ps - rise_ps;
+                }
+                last_rise = Some(time_ps);
+            }
+            (Some(true), Some(false)) => {
+                measurement.falling_edges += 1;
+                if let Some(rise_ps) = last_rise {
+                    high_pulses.push(time_ps - rise_ps);
+                }
+                last_fall = Some(time_ps);
+            }
+            (_, None) => {
+                last_rise = None;
+                last_fall = None;
+            }
+            _ => {}
+        }
+        current = next;
+    }
+
+    measurement.period = DurationStats::from_durations(&periods);
+    measurement.high_pulses = DurationStats::from_durations(&high_pulses);
+    measurement.low_pulses = DurationStats::from_durations(&low_pulses);
+    measurement.duty_cycle =
+        (periods_ps > 0).then(|| high_in_periods_ps as f64 / periods_ps as f64);
+    measurement
+}
+
+fn measure_signal(request: &SignalMeasurementRequest) -> Result<SignalMeasurement, String> {
+    if request.end_ps <= request.start_ps {
+        return Err("The measurement range is empty".to_string());
+    }
+    let query = UnifiedSignalRequest {
+        file_path: request.file_path.clone(),
+        scope_path: request.scope_path.clone(),
+        variable_name: request.variable_name.clone(),
+        time_range_ps: None,
+        max_transitions: None,
+        format: VarFormat::default(),
+    };
+    let packed = SIGNAL_CACHE_MANAGER
+        .get_or_load_signal_data(&query, &AtomicBool::new(false))?
+        .packed_transitions
+        .unwrap_or_default();
+    if !packed.is_empty() && !(packed.encoding.is_logic() && packed.width == 1) {
+        return Err(format!("'{}' is not a 1-bit signal", request.variable_name));
+    }
+    Ok(measure(&packed, request.start_ps, request.end_ps))
+}
+
+pub(crate) async fn measure_signal_range(
+    request: SignalMeasurementRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let measurement_id = request.measurement_id.clone();
+    let files = source_files(&request.file_path, &request.variable_name);
+    let result =
+        run_blocking_signal_job(files, "Measurement", move || measure_signal(&request)).await;
+
+    let msg = match result {
+        Ok(measurement) => DownMsg::SignalMeasured {
+            measurement_id,
+            measurement,
+        },
+        Err(error) => DownMsg::SignalMeasurementFailed {
+            measurement_id,
+            error,
+        },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::measure;
+    use shared::PackedTransitions;
+
+    #[test]
+    fn clock_measurements_use_complete_pulses_in_range() {
+        // 25% duty cycle with a period of 40ps, a glitch to x, then 60ps periods
+        let packed = PackedTransitions::from_pairs(&[
+            (0, "0"),
+            (10, "1"),
+            (20, "0"),
+            (50, "1"),
+            (60, "0"),
+            (90, "1"),
+            (100, "x"),
+            (110, "1"),
+            (140, "0"),
+            (170, "1"),
+            (200, "0"),
+            (230, "1"),
+        ]);
+
+        let clock = measure(&packed, 0, 95);
+        assert_eq!((clock.rising_edges, clock.falling_edges), (3, 2));
+        let period = clock.period.unwrap();
+        assert_eq!((period.count, period.min_ps, period.max_ps), (2, 40, 40));
+        assert_eq!(clock.duty_cycle, Some(0.25));
+        assert_eq!(clock.frequency_hz(), Some(25e9));
+        assert_eq!(clock.high_pulses.unwrap().max_ps, 10);
+        assert_eq!(clock.low_pulses.unwrap().min_ps, 30);
+
+        // The x value breaks the period that spans it
+        let after_glitch = measure(&packed, 95, 240);
+        let period = after_glitch.period.unwrap();
+        assert_eq!((period.count, period.min_ps, period.max_ps), (1, 60, 60));
+        assert_eq!(after_glitch.high_pulses.unwrap().count, 1);
+        assert_eq!(after_glitch.duty_cycle, Some(0.5));
+
+        let jittery = measure(&packed, 0, 240);
+        let period = jittery.period.unwrap();
+        assert_eq!(period.peak_to_peak_ps(), 20);
+        assert!(period.std_dev_ps > 0.0);
+    }
+}
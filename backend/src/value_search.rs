@@ -0,0 +1,236 @@
+//! Value search within one signal (`UpMsg::SearchSignalValue`).
+//!
+//! The value is typed in the row's format. Binary, octal and hexadecimal values become bit
+//! patterns where `x` (or `?`) digits match any bit; decimal values are compared
+//! numerically, ASCII values by their characters. Transitions come from
+//! `SignalCacheManager`, so the whole signal is searched, not just the loaded viewport.
+
+use crate::derived_signals::source_files;
+use crate::{SIGNAL_CACHE_MANAGER, run_blocking_signal_job, send_down_msg};
+use moon::*;
+use shared::{
+    DownMsg, MAX_VALUE_MATCH_INTERVALS, PackedEncoding, PackedTransitions, UnifiedSignalRequest,
+    ValueSearchRequest, VarFormat,
+};
+use std::sync::atomic::AtomicBool;
+
+#[derive(Debug, PartialEq)]
+enum ValuePattern {
+    /// Value characters, least significant bit first; `None` matches any bit
+    Bits(Vec<Option<char>>),
+    Integer(i128),
+    Real(f64),
+    /// Formatted value as shown in the row
+    Text(String),
+}
+
+fn parse_pattern(
+    value: &str,
+    format: VarFormat,
+    encoding: PackedEncoding,
+) -> Result<ValuePattern, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Type a value to search for".to_string());
+    }
+    match encoding {
+        PackedEncoding::Real => {
+            return value
+                .parse()
+                .map(ValuePattern::Real)
+                .map_err(|_| format!("'{}' is not a number", value));
+        }
+        PackedEncoding::Text => return Ok(ValuePattern::Text(value.to_string())),
+        _ => {}
+    }
+
+    let (prefix, bits_per_digit) = match format {
+        VarFormat::ASCII => return Ok(ValuePattern::Text(value.to_string())),
+        VarFormat::Signed | VarFormat::Unsigned => {
+            return value
+                .parse()
+                .map(ValuePattern::Integer)
+                .map_err(|_| format!("'{}' is not a decimal number", value));
+        }
+        VarFormat::Binary | VarFormat::BinaryWithGroups => ("0b", 1),
+        VarFormat::Octal => ("0o", 3),
+        VarFormat::Hexadecimal => ("0x", 4),
+    };
+    let digits: String = value
+        .strip_prefix(prefix)
+        .unwrap_or(value)
+        .chars()
+        .filter(|c| *c != '_' && !c.is_whitespace())
+        .collect();
+    let mut bits = Vec::new();
+    for digit in digits.chars().rev() {
+        match digit.to_ascii_lowercase() {
+            'x' | '?' => bits.extend(std::iter::repeat_n(None, bits_per_digit)),
+            'z' => bits.extend(std::iter::repeat_n(Some('z'), bits_per_digit)),
+            digit => {
+                let digit_value = digit.to_digit(1 << bits_per_digit).ok_or_else(|| {
+                    format!("'{}' is not a {} digit", digit, format.as_static_str())
+                })?;
+                bits.extend((0..bits_per_digit).map(|index| {
+                    Some(if digit_value >> index & 1 == 1 {
+                        '1'
+                    } else {
+                        '0'
+                    })
+                }));
+            }
+        }
+    }
+    if bits.is_empty() {
+        return Err(format!("'{}' has no digits", value));
+    }
+    Ok(ValuePattern::Bits(bits))
+}
+
+fn matches(
+    pattern: &ValuePattern,
+    format: VarFormat,
+    packed: &PackedTransitions,
+    index: usize,
+) -> bool {
+    match pattern {
+        ValuePattern::Bits(bits) => {
+            let value: Vec<char> = packed
+                .value_string(index)
+                .chars()
+                .rev()
+                .map(|c| c.to_ascii_lowercase())
+                .collect();
+            // Both sides are zero-extended to the wider one
+            (0..bits.len().max(value.len())).all(|position| {
+                match bits.get(position).copied().unwrap_or(Some('0')) {
+                    None => true,
+                    Some(bit) => value.get(position).copied().unwrap_or('0') == bit,
+                }
+            })
+        }
+        ValuePattern::Integer(expected) => format
+            .format_packed(packed, index)
+            .parse::<i128>()
+            .is_ok_and(|value| value == *expected),
+        ValuePattern::Real(expected) => packed.reals.get(index) == Some(expected),
+        ValuePattern::Text(expected) => match packed.encoding {
+            PackedEncoding::Text => packed.value_string(index) == *expected,
+            _ => format.format_packed(packed, index) == *expected,
+        },
+    }
+}
+
+/// Number of intervals holding the pattern and the earliest of them. Consecutive matching
+/// values form one interval; the last value is held to `u64::MAX`.
+fn match_intervals(
+    pattern: &ValuePattern,
+    format: VarFormat,
+    packed: &PackedTransitions,
+) -> (usize, Vec<(u64, u64)>) {
+    let mut match_count = 0;
+    let mut intervals = Vec::new();
+    let mut open_start: Option<u64> = None;
+    for (index, &time_ps) in packed.times_ps.iter().enumerate() {
+        let is_match = matches(pattern, format, packed, index);
+        match (open_start, is_match) {
+            (None, true) => open_start = Some(time_ps),
+            (Some(start_ps), false) => {
+                if intervals.len() < MAX_VALUE_MATCH_INTERVALS {
+                    intervals.push((start_ps, time_ps));
+                }
+                match_count += 1;
+                open_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start_ps) = open_start {
+        if intervals.len() < MAX_VALUE_MATCH_INTERVALS {
+            intervals.push((start_ps, u64::MAX));
+        }
+        match_count += 1;
+    }
+    (match_count, intervals)
+}
+
+fn search(request: &ValueSearchRequest) -> Result<(usize, Vec<(u64, u64)>), String> {
+    let query = UnifiedSignalRequest {
+        file_path: request.file_path.clone(),
+        scope_path: request.scope_path.clone(),
+        variable_name: request.variable_name.clone(),
+        time_range_ps: None,
+        max_transitions: None,
+        format: VarFormat::default(),
+    };
+    let packed = SIGNAL_CACHE_MANAGER
+        .get_or_load_signal_data(&query, &AtomicBool::new(false))?
+        .packed_transitions
+        .unwrap_or_default();
+    let pattern = parse_pattern(&request.value, request.format, packed.encoding)?;
+    Ok(match_intervals(&pattern, request.format, &packed))
+}
+
+pub(crate) async fn search_signal_value(
+    request: ValueSearchRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let search_id = request.search_id.clone();
+    let files = source_files(&request.file_path, &request.variable_name);
+    let result = run_blocking_signal_job(files, "Search", move || search(&request)).await;
+
+    let msg = match result {
+        Ok((match_count, intervals)) => DownMsg::ValueSearchCompleted {
+            search_id,
+            match_count,
+            intervals,
+        },
+        Err(error) => DownMsg::ValueSearchFailed { search_id, error },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_intervals, parse_pattern};
+    use shared::{PackedEncoding, PackedTransitions, VarFormat};
+
+    fn intervals(value: &str, format: VarFormat, packed: &PackedTransitions) -> Vec<(u64, u64)> {
+        let pattern = parse_pattern(value, format, packed.encoding).unwrap();
+        match_intervals(&pattern, format, packed).1
+    }
+
+    #[test]
+    fn values_match_in_the_row_format() {
+        let bus = PackedTransitions::from_pairs(&[
+            (0, "00000000"),
+            (10, "10100101"),
+            (20, "10101111"),
+            (30, "11111111"),
+            (40, "1010xxxx"),
+            (50, "00000101"),
+        ]);
+        assert_eq!(
+            intervals("0xa5", VarFormat::Hexadecimal, &bus),
+            vec![(10, 20)]
+        );
+        // Don't-care digits join neighbouring matches into one interval
+        assert_eq!(
+            intervals("ax", VarFormat::Hexadecimal, &bus),
+            vec![(10, 30), (40, 50)]
+        );
+        assert_eq!(
+            intervals("1010_0101", VarFormat::Binary, &bus),
+            vec![(10, 20)]
+        );
+        assert_eq!(intervals("-1", VarFormat::Signed, &bus), vec![(30, 40)]);
+        assert_eq!(
+            intervals("5", VarFormat::Unsigned, &bus),
+            vec![(50, u64::MAX)]
+        );
+        assert_eq!(intervals("0", VarFormat::Octal, &bus), vec![(0, 10)]);
+        assert!(parse_pattern("0xg1", VarFormat::Hexadecimal, PackedEncoding::TwoState).is_err());
+        assert!(parse_pattern("five", VarFormat::Unsigned, PackedEncoding::TwoState).is_err());
+    }
+}
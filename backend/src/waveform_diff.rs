@@ -0,0 +1,299 @@
+//! Signal-by-signal comparison of two loaded waveform files (`UpMsg::CompareWaveforms`).
+//!
+//! Reference signals are matched to candidate signals by their "scope_path|variable_name"
+//! key, after the request's path remaps are applied. Transitions are read at full
+//! resolution in timeline picoseconds, so files shifted with a time alignment are compared
+//! where they are shown; they are not added to the signal cache, so a diff leaves the
+//! user's working set alone. Values are compared as bit strings (or real / text
+//! values) up to the end of the shorter file.
+//!
+//! `UpMsg::CompareSignal` runs the same comparison for one selected variable over the
+//! visible range, for the difference shading of its waveform row.
+
+use crate::derived_signals::source_files;
+use crate::{
+    LOAD_PROGRESS_INTERVAL, SIGNAL_CACHE_MANAGER, WAVEFORM_DATA_STORE, ensure_waveform_body_loaded,
+    run_blocking_signal_job, send_down_msg,
+};
+use moon::*;
+use shared::{
+    DownMsg, PackedTransitions, SignalDiffFailure, SignalDifferenceRequest, SignalMismatch,
+    UnifiedSignalRequest, WaveformDiffRequest, WaveformDiffSummary,
+};
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+struct DiffCounters {
+    compared: AtomicU64,
+    total: AtomicU64,
+    finished: AtomicBool,
+}
+
+/// Signal keys of a loaded file and the timeline time of its last time step
+fn file_signals(file_path: &str) -> Result<(BTreeSet<String>, u64), String> {
+    let store = match WAVEFORM_DATA_STORE.lock() {
+        Ok(store) => store,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let data = store
+        .get(file_path)
+        .ok_or_else(|| format!("Waveform data not loaded for '{}'", file_path))?;
+    let end_ps = data.time_table.last().map_or(0, |&time| data.time_ps(time));
+    Ok((data.signals.keys().cloned().collect(), end_ps))
+}
+
+/// Both sides of one signal pair; read without caching, a diff visits every signal once
+fn load_pair(
+    request: &WaveformDiffRequest,
+    reference_key: &str,
+    candidate_key: &str,
+) -> Result<(Arc<PackedTransitions>, Arc<PackedTransitions>), String> {
+    let not_cancelled = AtomicBool::new(false);
+    let reference = SIGNAL_CACHE_MANAGER.read_transitions_uncached(
+        &request.reference_file,
+        reference_key,
+        &not_cancelled,
+    )?;
+    let candidate = SIGNAL_CACHE_MANAGER.read_transitions_uncached(
+        &request.candidate_file,
+        candidate_key,
+        &not_cancelled,
+    )?;
+    Ok((reference, candidate))
+}
+
+fn diff_waveforms(
+    request: &WaveformDiffRequest,
+    counters: &DiffCounters,
+) -> Result<WaveformDiffSummary, String> {
+    let (reference_keys, reference_end_ps) = file_signals(&request.reference_file)?;
+    let (candidate_keys, candidate_end_ps) = file_signals(&request.candidate_file)?;
+    let end_ps = reference_end_ps.min(candidate_end_ps);
+
+    let mut summary = WaveformDiffSummary {
+        reference_file: request.reference_file.clone(),
+        candidate_file: request.candidate_file.clone(),
+        compared_until_ps: end_ps,
+        ..WaveformDiffSummary::default()
+    };
+    let mut matched_candidates = BTreeSet::new();
+    counters
+        .total
+        .store(reference_keys.len() as u64, Ordering::Relaxed);
+
+    for (compared, reference_key) in reference_keys.iter().enumerate() {
+        counters.compared.store(compared as u64, Ordering::Relaxed);
+        let candidate_key = shared::remap_signal_key(reference_key, &request.path_remaps);
+        if !candidate_keys.contains(&candidate_key) {
+            summary.missing_in_candidate.push(reference_key.clone());
+            continue;
+        }
+        matched_candidates.insert(candidate_key.clone());
+        let (reference, candidate) = match load_pair(request, reference_key, &candidate_key) {
+            Ok(pair) => pair,
+            Err(error) => {
+                summary.failed_signals.push(SignalDiffFailure {
+                    reference_key: reference_key.clone(),
+                    error,
+                });
+                continue;
+            }
+        };
+        summary.compared_signals += 1;
+        // The values held at `end_ps` count, so the range ends just past it
+        let intervals =
+            shared::difference_intervals(&reference, &candidate, 0, end_ps.saturating_add(1));
+        if let Some(&(first_divergence_ps, _)) = intervals.first() {
+            summary.mismatches.push(SignalMismatch {
+                reference_key: reference_key.clone(),
+                candidate_key: candidate_key.clone(),
+                first_divergence_ps,
+                mismatch_intervals: intervals.len(),
+            });
+        }
+    }
+    summary.missing_in_reference = candidate_keys
+        .difference(&matched_candidates)
+        .cloned()
+        .collect();
+    summary
+        .mismatches
+        .sort_by(|a, b| a.first_divergence_ps.cmp(&b.first_divergence_ps));
+    Ok(summary)
+}
+
+pub(crate) async fn compare_waveforms(
+    request: WaveformDiffRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let diff_id = request.diff_id.clone();
+    let loaded = match ensure_waveform_body_loaded(&request.reference_file).await {
+        Ok(()) => ensure_waveform_body_loaded(&request.candidate_file).await,
+        Err(error) => Err(error),
+    };
+    let result = match loaded {
+        Ok(()) => {
+            let counters = Arc::new(DiffCounters {
+                compared: AtomicU64::new(0),
+                total: AtomicU64::new(0),
+                finished: AtomicBool::new(false),
+            });
+            let ticker_counters = Arc::clone(&counters);
+            let ticker_diff_id = diff_id.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(LOAD_PROGRESS_INTERVAL).await;
+                    if ticker_counters.finished.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let total = ticker_counters.total.load(Ordering::Relaxed);
+                    if total == 0 {
+                        continue;
+                    }
+                    let compared = ticker_counters.compared.load(Ordering::Relaxed);
+                    send_down_msg(
+                        DownMsg::WaveformDiffProgress {
+                            diff_id: ticker_diff_id.clone(),
+                            progress: (compared as f32 / total as f32).min(1.0),
+                        },
+                        session_id,
+                        cor_id,
+                    )
+                    .await;
+                }
+            });
+
+            let worker_counters = Arc::clone(&counters);
+            let worker_request = request.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                diff_waveforms(&worker_request, &worker_counters)
+            })
+            .await
+            .unwrap_or_else(|join_error| Err(format!("Diff task failed: {}", join_error)));
+            counters.finished.store(true, Ordering::Relaxed);
+            result
+        }
+        Err(error) => Err(error),
+    };
+
+    let msg = match result {
+        Ok(summary) => {
+            println!(
+                "Compared {} signals of '{}' and '{}': {} differ",
+                summary.compared_signals,
+                request.reference_file,
+                request.candidate_file,
+                summary.mismatches.len()
+            );
+            DownMsg::WaveformDiffCompleted { diff_id, summary }
+        }
+        Err(error) => {
+            eprintln!(
+                "Diff of '{}' and '{}' failed: {}",
+                request.reference_file, request.candidate_file, error
+            );
+            DownMsg::WaveformDiffFailed { diff_id, error }
+        }
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+/// Join intervals separated by less than `resolution_ps`
+fn merge_close_intervals(intervals: Vec<(u64, u64)>, resolution_ps: u64) -> Vec<(u64, u64)> {
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+    for (start_ps, end_ps) in intervals {
+        match merged.last_mut() {
+            Some(last) if start_ps - last.1 < resolution_ps => last.1 = end_ps,
+            _ => merged.push((start_ps, end_ps)),
+        }
+    }
+    merged
+}
+
+fn full_signal(unique_id: &str) -> Result<PackedTransitions, String> {
+    let mut parts = unique_id.splitn(3, '|');
+    let (Some(file_path), Some(scope_path), Some(variable_name)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("Invalid signal id '{}'", unique_id));
+    };
+    let query = UnifiedSignalRequest {
+        file_path: file_path.to_string(),
+        scope_path: scope_path.to_string(),
+        variable_name: variable_name.to_string(),
+        time_range_ps: None,
+        max_transitions: None,
+        format: shared::VarFormat::default(),
+    };
+    Ok(SIGNAL_CACHE_MANAGER
+        .get_or_load_signal_data(&query, &AtomicBool::new(false))?
+        .packed_transitions
+        .unwrap_or_default())
+}
+
+fn signal_differences(request: &SignalDifferenceRequest) -> Result<Vec<(u64, u64)>, String> {
+    let reference = full_signal(&request.unique_id)?;
+    let candidate = full_signal(&request.compare_unique_id)?;
+    let (start_ps, end_ps) = request.range_ps;
+    Ok(merge_close_intervals(
+        shared::difference_intervals(&reference, &candidate, start_ps, end_ps),
+        request.resolution_ps,
+    ))
+}
+
+pub(crate) async fn compare_signal(
+    request: SignalDifferenceRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let (unique_id, compare_unique_id) =
+        (request.unique_id.clone(), request.compare_unique_id.clone());
+    let files: Vec<String> = [&unique_id, &compare_unique_id]
+        .into_iter()
+        .flat_map(|unique_id| {
+            let mut parts = unique_id.splitn(3, '|');
+            let file_path = parts.next().unwrap_or_default();
+            source_files(file_path, parts.nth(1).unwrap_or_default())
+        })
+        .collect();
+    let range_ps = request.range_ps;
+    let result =
+        run_blocking_signal_job(files, "Comparison", move || signal_differences(&request)).await;
+
+    let msg = match result {
+        Ok(intervals) => DownMsg::SignalDifferencesCompleted {
+            unique_id,
+            compare_unique_id,
+            range_ps,
+            intervals,
+        },
+        Err(error) => DownMsg::SignalDifferencesFailed {
+            unique_id,
+            compare_unique_id,
+            range_ps,
+            error,
+        },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_close_intervals;
+
+    #[test]
+    fn differences_closer_than_the_resolution_merge() {
+        let intervals = vec![(0, 10), (12, 15), (30, 31), (40, u64::MAX)];
+        assert_eq!(
+            merge_close_intervals(intervals.clone(), 1),
+            intervals.clone()
+        );
+        assert_eq!(
+            merge_close_intervals(intervals.clone(), 5),
+            vec![(0, 15), (30, 31), (40, u64::MAX)]
+        );
+        assert_eq!(merge_close_intervals(intervals, 50), vec![(0, u64::MAX)]);
+    }
+}
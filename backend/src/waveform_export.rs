@@ -0,0 +1,1465 @@
+//! Standalone VCD / FST excerpts, CSV / JSON tables and WaveDrom diagrams of loaded
+//! waveform files (`UpMsg::ExportWaveform`).
+//!
+//! Signals are read from the `WaveformData` store, so an export first makes sure the
+//! waveform body is loaded. Scope names, variable declarations and the timescale are
+//! copied from the source file; timestamps keep their original values. CSV / JSON /
+//! WaveDrom values come from `SignalCacheManager` and are rendered with each signal's
+//! `VarFormat`, the same text the Selected Variables panel shows.
+
+use crate::{
+    LOAD_PROGRESS_INTERVAL, SIGNAL_CACHE_MANAGER, WAVEFORM_DATA_STORE, ensure_waveform_body_loaded,
+    native_time_to_ps, send_down_msg,
+};
+use moon::*;
+use serde::Serialize;
+use shared::{
+    DownMsg, ExportFormat, PackedTransitions, TabularLayout, UnifiedSignalRequest, VarFormat,
+    WaveformExportRequest,
+};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Time steps written between progress counter updates
+const PROGRESS_STRIDE: u64 = 4096;
+
+/// Upper bound on sampled CSV / JSON rows, guarding against tiny sampling periods
+const MAX_SAMPLED_ROWS: u64 = 10_000_000;
+
+/// Upper bound on WaveDrom steps; larger diagrams are unreadable anyway
+const MAX_WAVEDROM_STEPS: usize = 2048;
+
+const VCD_SCOPE_TYPES: &[&str] = &[
+    "module",
+    "task",
+    "function",
+    "begin",
+    "fork",
+    "generate",
+    "struct",
+    "union",
+    "class",
+    "interface",
+    "package",
+    "program",
+];
+
+const VCD_VAR_TYPES: &[&str] = &[
+    "event",
+    "integer",
+    "parameter",
+    "real",
+    "realtime",
+    "reg",
+    "supply0",
+    "supply1",
+    "time",
+    "tri",
+    "triand",
+    "trior",
+    "trireg",
+    "tri0",
+    "tri1",
+    "wand",
+    "wire",
+    "wor",
+    "string",
+    "logic",
+    "bit",
+    "int",
+    "shortint",
+    "longint",
+    "byte",
+];
+
+struct ExportCounters {
+    processed: AtomicU64,
+    total: AtomicU64,
+    finished: AtomicBool,
+}
+
+/// Variable declaration copied out of the hierarchy
+struct ExportVar {
+    name: String,
+    /// VCD `$var` keyword
+    var_type: String,
+    width: u32,
+    index: Option<(i64, i64)>,
+    encoding: wellen::SignalEncoding,
+    signal_ref: wellen::SignalRef,
+}
+
+/// Scope of the source hierarchy that holds at least one exported variable
+struct ExportScope {
+    name: String,
+    /// VCD `$scope` keyword
+    scope_type: String,
+    vars: Vec<ExportVar>,
+    children: Vec<ExportScope>,
+}
+
+impl ExportScope {
+    fn all_vars(&self) -> Vec<&ExportVar> {
+        let mut vars: Vec<&ExportVar> = self.vars.iter().collect();
+        for child in &self.children {
+            vars.extend(child.all_vars());
+        }
+        vars
+    }
+}
+
+/// Time table indices covered by an export window
+#[derive(Debug, PartialEq)]
+struct ExportWindow {
+    /// Last index at or before the window start; its values form the initial dump
+    initial_idx: Option<usize>,
+    /// First index past the window end
+    end_idx: usize,
+    /// Window start in file time units, the first timestamp of the excerpt
+    start_native: u64,
+}
+
+impl ExportWindow {
+    fn new(time_table: &[u64], timescale_factor: f64, start_ps: u64, end_ps: u64) -> Self {
+        let to_ps = |time: &u64| native_time_to_ps(*time, timescale_factor);
+        let up_to_start = time_table.partition_point(|time| to_ps(time) <= start_ps);
+        let up_to_end = time_table.partition_point(|time| to_ps(time) <= end_ps);
+        Self {
+            initial_idx: up_to_start.checked_sub(1),
+            end_idx: up_to_end.max(up_to_start),
+            start_native: ps_to_native_time(start_ps, timescale_factor),
+        }
+    }
+
+    /// Time indices after the initial dump whose changes are written
+    fn change_indices(&self) -> std::ops::Range<usize> {
+        self.initial_idx.map_or(0, |idx| idx + 1)..self.end_idx
+    }
+}
+
+/// Inverse of `native_time_to_ps`, rounding down to whole file time units
+fn ps_to_native_time(time_ps: u64, timescale_factor: f64) -> u64 {
+    let ps_per_unit = timescale_factor * 1e12;
+    let whole_ps_per_unit = ps_per_unit.round();
+    if whole_ps_per_unit >= 1.0 && (ps_per_unit - whole_ps_per_unit).abs() <= 1e-6 {
+        time_ps / whole_ps_per_unit as u64
+    } else {
+        (time_ps as f64 / ps_per_unit).floor() as u64
+    }
+}
+
+/// Split seconds per file time unit into the VCD form `factor` x 10^`exponent` s,
+/// with `factor` one of 1, 10, 100 and `exponent` a multiple of 3 (fs at the finest)
+fn timescale_parts(timescale_factor: f64) -> (u32, i32) {
+    let power = (timescale_factor.log10().round() as i32).max(-15);
+    let factor_power = power.rem_euclid(3);
+    (10u32.pow(factor_power as u32), power - factor_power)
+}
+
+pub(crate) fn vcd_timescale(timescale_factor: f64) -> String {
+    let (factor, exponent) = timescale_parts(timescale_factor);
+    let unit = match exponent {
+        -15 => "fs",
+        -12 => "ps",
+        -9 => "ns",
+        -6 => "us",
+        -3 => "ms",
+        _ => "s",
+    };
+    format!("{}{}", factor, unit)
+}
+
+/// Short VCD identifier codes: `!`, `"`, ... `~`, then two characters and so on
+pub(crate) fn vcd_identifier(mut n: usize) -> String {
+    const FIRST: u8 = b'!';
+    const COUNT: usize = (b'~' - b'!' + 1) as usize;
+    let mut id = String::new();
+    loop {
+        id.push((FIRST + (n % COUNT) as u8) as char);
+        n /= COUNT;
+        if n == 0 {
+            break;
+        }
+        n -= 1;
+    }
+    id
+}
+
+fn vcd_value_change(value: &wellen::SignalValue, width: u32, id: &str) -> String {
+    match value {
+        wellen::SignalValue::Real(real) => format!("r{} {}", real, id),
+        wellen::SignalValue::String(text) => {
+            // VCD string values end at whitespace
+            let escaped: String = text
+                .chars()
+                .map(|c| if c.is_whitespace() { '_' } else { c })
+                .collect();
+            format!("s{} {}", escaped, id)
+        }
+        _ => {
+            let bits = value
+                .to_bit_string()
+                .unwrap_or_else(|| "x".repeat(width as usize));
+            if width == 1 {
+                format!("{}{}", bits, id)
+            } else {
+                format!("b{} {}", bits, id)
+            }
+        }
+    }
+}
+
+/// Collect the requested variables below `scope_ref`; `whole_subtree` exports every variable
+fn collect_export_scope(
+    hierarchy: &wellen::Hierarchy,
+    scope_ref: wellen::ScopeRef,
+    variables: &HashSet<&str>,
+    scopes: &[String],
+    whole_subtree: bool,
+) -> Option<ExportScope> {
+    let scope = &hierarchy[scope_ref];
+    let scope_path = scope.full_name(hierarchy);
+    let whole_subtree = whole_subtree || scopes.iter().any(|path| *path == scope_path);
+
+    let vars: Vec<ExportVar> = scope
+        .vars(hierarchy)
+        .map(|var_ref| &hierarchy[var_ref])
+        .filter(|var| {
+            whole_subtree
+                || variables.contains(format!("{}|{}", scope_path, var.name(hierarchy)).as_str())
+        })
+        .map(|var| {
+            let encoding = var.signal_encoding();
+            let fallback_type = match encoding {
+                wellen::SignalEncoding::Real => "real",
+                wellen::SignalEncoding::String => "string",
+                _ => "wire",
+            };
+            let declared_type = format!("{:?}", var.var_type()).to_lowercase();
+            ExportVar {
+                name: var.name(hierarchy).to_string(),
+                var_type: if VCD_VAR_TYPES.contains(&declared_type.as_str()) {
+                    declared_type
+                } else {
+                    fallback_type.to_string()
+                },
+                width: match encoding {
+                    wellen::SignalEncoding::BitVector(width) => width.get(),
+                    wellen::SignalEncoding::Real => 64,
+                    _ => 1,
+                },
+                index: var.index().map(|index| (index.msb(), index.lsb())),
+                encoding,
+                signal_ref: var.signal_ref(),
+            }
+        })
+        .collect();
+
+    let children: Vec<ExportScope> = scope
+        .scopes(hierarchy)
+        .filter_map(|child| {
+            collect_export_scope(hierarchy, child, variables, scopes, whole_subtree)
+        })
+        .collect();
+
+    if vars.is_empty() && children.is_empty() {
+        return None;
+    }
+    let declared_type = format!("{:?}", scope.scope_type()).to_lowercase();
+    Some(ExportScope {
+        name: scope.name(hierarchy).to_string(),
+        scope_type: if VCD_SCOPE_TYPES.contains(&declared_type.as_str()) {
+            declared_type
+        } else {
+            "module".to_string()
+        },
+        vars,
+        children,
+    })
+}
+
+/// Comparable form of a value, used to skip time steps where a signal did not change
+fn value_key(value: &wellen::SignalValue) -> String {
+    match value {
+        // wellen panics if Real values are converted to bit strings.
+        wellen::SignalValue::Real(real) => real.to_string(),
+        wellen::SignalValue::String(text) => text.to_string(),
+        _ => value.to_bit_string().unwrap_or_default(),
+    }
+}
+
+/// Everything an export writer needs, detached from the request handling
+struct Excerpt<'a> {
+    request: &'a WaveformExportRequest,
+    scopes: Vec<ExportScope>,
+    signals: HashMap<wellen::SignalRef, wellen::Signal>,
+    time_table: &'a [u64],
+    timescale_factor: f64,
+    window: ExportWindow,
+}
+
+impl Excerpt<'_> {
+    /// Distinct signals in declaration order; aliased variables share one signal
+    fn declared_signals(&self) -> Vec<(wellen::SignalRef, u32)> {
+        let mut seen = HashSet::new();
+        self.scopes
+            .iter()
+            .flat_map(|scope| scope.all_vars())
+            .filter(|var| seen.insert(var.signal_ref))
+            .map(|var| (var.signal_ref, var.width))
+            .collect()
+    }
+
+    fn initial_value<'s>(&self, signal: &'s wellen::Signal) -> Option<wellen::SignalValue<'s>> {
+        let idx = self.window.initial_idx?;
+        let offset = signal.get_offset(idx as u32)?;
+        Some(signal.get_value_at(&offset, 0))
+    }
+
+    /// Visit the value changes after the initial dump in time order. The callback gets the
+    /// time table index, the position in `order` and the new value; `new_time` is true for
+    /// the first change of a time step.
+    fn for_each_change<F>(
+        &self,
+        order: &[wellen::SignalRef],
+        counters: &ExportCounters,
+        mut on_change: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(usize, usize, bool, &wellen::SignalValue) -> Result<(), String>,
+    {
+        let signals: Vec<&wellen::Signal> = order.iter().map(|r| &self.signals[r]).collect();
+        let mut last: Vec<Option<String>> = signals
+            .iter()
+            .map(|signal| self.initial_value(signal).map(|value| value_key(&value)))
+            .collect();
+
+        for (processed, time_idx) in self.window.change_indices().enumerate() {
+            let mut new_time = true;
+            for (position, signal) in signals.iter().enumerate() {
+                let Some(offset) = signal.get_offset(time_idx as u32) else {
+                    continue;
+                };
+                let value = signal.get_value_at(&offset, 0);
+                let key = value_key(&value);
+                if last[position].as_ref() == Some(&key) {
+                    continue;
+                }
+                on_change(time_idx, position, new_time, &value)?;
+                new_time = false;
+                last[position] = Some(key);
+            }
+            if processed as u64 % PROGRESS_STRIDE == 0 {
+                counters
+                    .processed
+                    .store(processed as u64, Ordering::Relaxed);
+            }
+        }
+        counters
+            .processed
+            .store(self.window.change_indices().len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+fn write_vcd_scope<W: Write>(
+    out: &mut W,
+    scope: &ExportScope,
+    ids: &HashMap<wellen::SignalRef, String>,
+) -> std::io::Result<()> {
+    writeln!(out, "$scope {} {} $end", scope.scope_type, scope.name)?;
+    for var in &scope.vars {
+        let index = match var.index {
+            Some((msb, lsb)) if msb == lsb => format!(" [{}]", msb),
+            Some((msb, lsb)) => format!(" [{}:{}]", msb, lsb),
+            None => String::new(),
+        };
+        writeln!(
+            out,
+            "$var {} {} {} {}{} $end",
+            var.var_type, var.width, ids[&var.signal_ref], var.name, index
+        )?;
+    }
+    for child in &scope.children {
+        write_vcd_scope(out, child, ids)?;
+    }
+    writeln!(out, "$upscope $end")
+}
+
+fn write_vcd<W: Write>(
+    out: &mut W,
+    excerpt: &Excerpt,
+    counters: &ExportCounters,
+) -> Result<(), String> {
+    let request = excerpt.request;
+    let io_error = |e: std::io::Error| format!("Failed to write '{}': {}", request.output_path, e);
+
+    let declared = excerpt.declared_signals();
+    let ids: HashMap<wellen::SignalRef, String> = declared
+        .iter()
+        .enumerate()
+        .map(|(n, (signal_ref, _))| (*signal_ref, vcd_identifier(n)))
+        .collect();
+
+    writeln!(out, "$version\n\tNovyWave waveform export\n$end").map_err(io_error)?;
+    writeln!(
+        out,
+        "$comment\n\tExcerpt of {} from {} ps to {} ps\n$end",
+        request.file_path, request.start_ps, request.end_ps
+    )
+    .map_err(io_error)?;
+    writeln!(
+        out,
+        "$timescale\n\t{}\n$end",
+        vcd_timescale(excerpt.timescale_factor)
+    )
+    .map_err(io_error)?;
+    for scope in &excerpt.scopes {
+        write_vcd_scope(out, scope, &ids).map_err(io_error)?;
+    }
+    writeln!(out, "$enddefinitions $end").map_err(io_error)?;
+
+    writeln!(out, "#{}\n$dumpvars", excerpt.window.start_native).map_err(io_error)?;
+    for (signal_ref, width) in &declared {
+        let id = &ids[signal_ref];
+        match excerpt.initial_value(&excerpt.signals[signal_ref]) {
+            Some(value) => {
+                writeln!(out, "{}", vcd_value_change(&value, *width, id)).map_err(io_error)?
+            }
+            None if *width == 1 => writeln!(out, "x{}", id).map_err(io_error)?,
+            None => writeln!(out, "b{} {}", "x".repeat(*width as usize), id).map_err(io_error)?,
+        }
+    }
+    writeln!(out, "$end").map_err(io_error)?;
+
+    let order: Vec<wellen::SignalRef> =
+        declared.iter().map(|(signal_ref, _)| *signal_ref).collect();
+    excerpt.for_each_change(&order, counters, |time_idx, position, new_time, value| {
+        if new_time {
+            writeln!(out, "#{}", excerpt.time_table[time_idx]).map_err(io_error)?;
+        }
+        let (signal_ref, width) = &declared[position];
+        writeln!(out, "{}", vcd_value_change(value, *width, &ids[signal_ref])).map_err(io_error)
+    })
+}
+
+/// Hierarchy declarations in file order, so the FST header is written without recursion
+enum HeaderItem<'a> {
+    Scope(&'a ExportScope),
+    Var(&'a ExportVar),
+    UpScope,
+}
+
+fn flatten_scope<'a>(scope: &'a ExportScope, items: &mut Vec<HeaderItem<'a>>) {
+    items.push(HeaderItem::Scope(scope));
+    items.extend(scope.vars.iter().map(HeaderItem::Var));
+    for child in &scope.children {
+        flatten_scope(child, items);
+    }
+    items.push(HeaderItem::UpScope);
+}
+
+fn write_fst(excerpt: &Excerpt, counters: &ExportCounters) -> Result<(), String> {
+    let fst_error = |e: fst_writer::FstWriteError| format!("Failed to write FST: {:?}", e);
+
+    if let Some(var) = excerpt
+        .scopes
+        .iter()
+        .flat_map(|scope| scope.all_vars())
+        .find(|var| !matches!(var.encoding, wellen::SignalEncoding::BitVector(_)))
+    {
+        return Err(format!(
+            "FST export supports bit-vector signals only; '{}' is a {} signal",
+            var.name, var.var_type
+        ));
+    }
+
+    let (factor, exponent) = timescale_parts(excerpt.timescale_factor);
+    let info = fst_writer::FstInfo {
+        start_time: excerpt.window.start_native,
+        timescale_exponent: (exponent + factor.ilog10() as i32) as i8,
+        version: "NovyWave waveform export".to_string(),
+        date: String::new(),
+        file_type: fst_writer::FstFileType::Verilog,
+    };
+    let mut header =
+        fst_writer::open_fst(&excerpt.request.output_path, &info).map_err(fst_error)?;
+
+    let mut items = Vec::new();
+    for scope in &excerpt.scopes {
+        flatten_scope(scope, &mut items);
+    }
+    let mut ids = HashMap::new();
+    let mut order = Vec::new();
+    for item in items {
+        match item {
+            HeaderItem::Scope(scope) => header
+                .scope(&scope.name, "", fst_writer::FstScopeType::Module)
+                .map_err(fst_error)?,
+            HeaderItem::Var(var) => {
+                let name = match var.index {
+                    Some((msb, lsb)) if msb == lsb => format!("{} [{}]", var.name, msb),
+                    Some((msb, lsb)) => format!("{} [{}:{}]", var.name, msb, lsb),
+                    None => var.name.clone(),
+                };
+                let alias = ids.get(&var.signal_ref).cloned();
+                let id = header
+                    .var(
+                        name,
+                        fst_writer::FstSignalType::bit_vec(var.width),
+                        fst_writer::FstVarType::Wire,
+                        fst_writer::FstVarDirection::Implicit,
+                        alias,
+                    )
+                    .map_err(fst_error)?;
+                if !ids.contains_key(&var.signal_ref) {
+                    ids.insert(var.signal_ref, id);
+                    order.push(var.signal_ref);
+                }
+            }
+            HeaderItem::UpScope => header.up_scope().map_err(fst_error)?,
+        }
+    }
+    let mut body = header.finish().map_err(fst_error)?;
+
+    body.time_change(excerpt.window.start_native)
+        .map_err(fst_error)?;
+    for signal_ref in &order {
+        let initial = excerpt.initial_value(&excerpt.signals[signal_ref]);
+        if let Some(bits) = initial.and_then(|value| value.to_bit_string()) {
+            body.signal_change(ids[signal_ref], bits.as_bytes())
+                .map_err(fst_error)?;
+        }
+    }
+    excerpt.for_each_change(&order, counters, |time_idx, position, new_time, value| {
+        if new_time {
+            body.time_change(excerpt.time_table[time_idx])
+                .map_err(fst_error)?;
+        }
+        match value.to_bit_string() {
+            Some(bits) => body
+                .signal_change(ids[&order[position]], bits.as_bytes())
+                .map_err(fst_error),
+            None => Ok(()),
+        }
+    })?;
+    body.finish().map_err(fst_error)
+}
+
+/// Signal of a CSV / JSON / WaveDrom export with its transitions inside the window
+struct TabularSignal {
+    /// "scope_path|variable_name"
+    name: String,
+    format: VarFormat,
+    /// Starts with the value held at the window start
+    transitions: PackedTransitions,
+}
+
+impl TabularSignal {
+    fn value_at(&self, time_ps: u64) -> String {
+        self.transitions
+            .index_at_or_before(time_ps)
+            .map(|index| self.format.format_packed(&self.transitions, index))
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Serialize)]
+struct JsonChange {
+    time_ps: u64,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct JsonSignalChanges {
+    name: String,
+    format: VarFormat,
+    changes: Vec<JsonChange>,
+}
+
+#[derive(Serialize)]
+struct JsonSample {
+    time_ps: u64,
+    values: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    file: &'a str,
+    start_ps: u64,
+    end_ps: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    signals: Vec<JsonSignalChanges>,
+    /// Column names of `samples`, for sampled layouts
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    columns: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    samples: Vec<JsonSample>,
+}
+
+/// Requested variables plus every variable below the requested scopes
+fn tabular_variable_keys(request: &WaveformExportRequest) -> Result<Vec<String>, String> {
+    let mut keys = request.variables.clone();
+    if request.scopes.is_empty() {
+        return Ok(keys);
+    }
+    let store = match WAVEFORM_DATA_STORE.lock() {
+        Ok(store) => store,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let data = store
+        .get(&request.file_path)
+        .ok_or_else(|| format!("Waveform data not loaded for '{}'", request.file_path))?;
+    let mut scoped: Vec<&String> = data
+        .signals
+        .keys()
+        .filter(|key| {
+            key.split_once('|').is_some_and(|(scope_path, _)| {
+                request.scopes.iter().any(|path| {
+                    scope_path == path
+                        || scope_path
+                            .strip_prefix(path.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                })
+            })
+        })
+        .collect();
+    scoped.sort();
+    for key in scoped {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    Ok(keys)
+}
+
+/// Load transitions through the shared signal cache, so repeated exports stay cheap
+fn load_tabular_transitions(
+    request: &WaveformExportRequest,
+    key: &str,
+    time_range_ps: Option<(u64, u64)>,
+) -> Result<PackedTransitions, String> {
+    let (scope_path, variable_name) = key
+        .split_once('|')
+        .ok_or_else(|| format!("Invalid signal key '{}'", key))?;
+    let query = UnifiedSignalRequest {
+        file_path: request.file_path.clone(),
+        scope_path: scope_path.to_string(),
+        variable_name: variable_name.to_string(),
+        time_range_ps,
+        max_transitions: None,
+        format: request
+            .variable_formats
+            .get(key)
+            .copied()
+            .unwrap_or_default(),
+    };
+    let data = SIGNAL_CACHE_MANAGER.get_or_load_signal_data(&query, &AtomicBool::new(false))?;
+    Ok(data.packed_transitions.unwrap_or_default())
+}
+
+/// Timestamps of the rows of a sampled layout
+fn sample_times(request: &WaveformExportRequest) -> Result<Vec<u64>, String> {
+    match &request.layout {
+        TabularLayout::Changes => Ok(Vec::new()),
+        TabularLayout::SampledPeriod { period_ps } => {
+            if *period_ps == 0 {
+                return Err("The sampling period must be positive".to_string());
+            }
+            let rows = (request.end_ps.saturating_sub(request.start_ps)) / period_ps + 1;
+            if rows > MAX_SAMPLED_ROWS {
+                return Err(format!(
+                    "Sampling every {} ps would write {} rows (limit {})",
+                    period_ps, rows, MAX_SAMPLED_ROWS
+                ));
+            }
+            Ok((0..rows)
+                .map(|row| request.start_ps + row * period_ps)
+                .collect())
+        }
+        TabularLayout::ClockEdges { clock } => {
+            let clock_transitions = load_tabular_transitions(request, clock, None)?;
+            // The first transition is the initial value, not an edge
+            Ok(clock_transitions
+                .times_ps
+                .iter()
+                .skip(1)
+                .copied()
+                .filter(|time| (request.start_ps..=request.end_ps).contains(time))
+                .collect())
+        }
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn write_tabular<W: Write>(
+    out: &mut W,
+    request: &WaveformExportRequest,
+    signals: &[TabularSignal],
+    counters: &ExportCounters,
+) -> Result<(), String> {
+    let io_error = |e: std::io::Error| format!("Failed to write '{}': {}", request.output_path, e);
+    let times = sample_times(request)?;
+    let sampled = !matches!(request.layout, TabularLayout::Changes);
+    let total = if sampled {
+        times.len()
+    } else {
+        signals.iter().map(|signal| signal.transitions.len()).sum()
+    };
+    counters.total.store(total as u64, Ordering::Relaxed);
+
+    let mut json = JsonExport {
+        file: &request.file_path,
+        start_ps: request.start_ps,
+        end_ps: request.end_ps,
+        signals: Vec::new(),
+        columns: Vec::new(),
+        samples: Vec::new(),
+    };
+    let mut processed = 0u64;
+    let mut step = || {
+        processed += 1;
+        if processed % PROGRESS_STRIDE == 0 {
+            counters.processed.store(processed, Ordering::Relaxed);
+        }
+    };
+
+    if sampled {
+        let names: Vec<String> = signals.iter().map(|signal| signal.name.clone()).collect();
+        if request.format == ExportFormat::Csv {
+            let header: Vec<String> = names.iter().map(|name| csv_field(name)).collect();
+            writeln!(out, "time_ps,{}", header.join(",")).map_err(io_error)?;
+        }
+        for &time_ps in &times {
+            let values: Vec<String> = signals
+                .iter()
+                .map(|signal| signal.value_at(time_ps))
+                .collect();
+            if request.format == ExportFormat::Csv {
+                let fields: Vec<String> = values.iter().map(|value| csv_field(value)).collect();
+                writeln!(out, "{},{}", time_ps, fields.join(",")).map_err(io_error)?;
+            } else {
+                json.samples.push(JsonSample { time_ps, values });
+            }
+            step();
+        }
+        json.columns = names;
+    } else {
+        if request.format == ExportFormat::Csv {
+            writeln!(out, "signal,time_ps,value").map_err(io_error)?;
+        }
+        for signal in signals {
+            let mut changes = Vec::new();
+            for index in 0..signal.transitions.len() {
+                let time_ps = signal.transitions.time_ps(index);
+                let value = signal.format.format_packed(&signal.transitions, index);
+                if request.format == ExportFormat::Csv {
+                    writeln!(
+                        out,
+                        "{},{},{}",
+                        csv_field(&signal.name),
+                        time_ps,
+                        csv_field(&value)
+                    )
+                    .map_err(io_error)?;
+                } else {
+                    changes.push(JsonChange { time_ps, value });
+                }
+                step();
+            }
+            if request.format == ExportFormat::Json {
+                json.signals.push(JsonSignalChanges {
+                    name: signal.name.clone(),
+                    format: signal.format,
+                    changes,
+                });
+            }
+        }
+    }
+
+    if request.format == ExportFormat::Json {
+        serde_json::to_writer_pretty(&mut *out, &json)
+            .map_err(|e| format!("Failed to write '{}': {}", request.output_path, e))?;
+        writeln!(out).map_err(io_error)?;
+    }
+    counters.processed.store(total as u64, Ordering::Relaxed);
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct WaveDromSignal {
+    name: String,
+    wave: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    data: Vec<String>,
+    /// Steps per wave character, for clocks slower than the step grid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    period: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct WaveDromDiagram {
+    signal: Vec<WaveDromSignal>,
+}
+
+/// Step times of a WaveDrom diagram: every value change, every sampling period or
+/// every rising clock edge inside the window
+fn wavedrom_steps(
+    request: &WaveformExportRequest,
+    signals: &[TabularSignal],
+) -> Result<Vec<u64>, String> {
+    let window = request.start_ps..=request.end_ps;
+    let steps: Vec<u64> = match &request.layout {
+        TabularLayout::Changes => {
+            let mut times: Vec<u64> = signals
+                .iter()
+                .flat_map(|signal| signal.transitions.times_ps.iter().copied())
+                .filter(|time| window.contains(time))
+                .collect();
+            times.push(request.start_ps);
+            times.sort_unstable();
+            times.dedup();
+            times
+        }
+        TabularLayout::SampledPeriod { .. } => sample_times(request)?,
+        TabularLayout::ClockEdges { clock } => {
+            let clock_transitions = load_tabular_transitions(request, clock, None)?;
+            (1..clock_transitions.len())
+                .filter(|&index| clock_transitions.value_string(index) == "1")
+                .map(|index| clock_transitions.time_ps(index))
+                .filter(|time| window.contains(time))
+                .collect()
+        }
+    };
+    if steps.is_empty() {
+        return Err("The selected range contains no diagram steps".to_string());
+    }
+    if steps.len() > MAX_WAVEDROM_STEPS {
+        return Err(format!(
+            "The diagram would have {} steps (limit {}); choose a shorter range or a longer period",
+            steps.len(),
+            MAX_WAVEDROM_STEPS
+        ));
+    }
+    Ok(steps)
+}
+
+/// Distance between steps when they are evenly spaced
+fn uniform_step(steps: &[u64]) -> Option<u64> {
+    let step_ps = steps.get(1)?.checked_sub(steps[0])?;
+    (step_ps > 0 && steps.windows(2).all(|pair| pair[1] - pair[0] == step_ps)).then_some(step_ps)
+}
+
+/// `p` / `n` pattern and its period in steps for a 1-bit signal toggling at a fixed rate
+/// in phase with the step grid
+fn clock_pattern(signal: &TabularSignal, steps: &[u64], step_ps: u64) -> Option<(char, u64)> {
+    let transitions = &signal.transitions;
+    if transitions.width != 1 || !transitions.encoding.is_logic() {
+        return None;
+    }
+    let (first, last) = (*steps.first()?, *steps.last()?);
+    // Index 0 holds the value at the window start, not an edge
+    let edges: Vec<usize> = (1..transitions.len())
+        .filter(|&index| (first..=last).contains(&transitions.time_ps(index)))
+        .collect();
+    if edges.len() < 2 {
+        return None;
+    }
+    let half_period = transitions.time_ps(edges[1]) - transitions.time_ps(edges[0]);
+    let regular = edges
+        .windows(2)
+        .all(|pair| transitions.time_ps(pair[1]) - transitions.time_ps(pair[0]) == half_period);
+    let two_state = edges
+        .iter()
+        .all(|&index| matches!(transitions.value_string(index).as_str(), "0" | "1"));
+    let period = 2 * half_period;
+    if half_period == 0 || !regular || !two_state || period % step_ps != 0 {
+        return None;
+    }
+
+    let first_edge_rises = transitions.value_string(edges[0]) == "1";
+    let rises_at_steps = match (transitions.time_ps(edges[0]) - first) % period {
+        0 => first_edge_rises,
+        phase if phase == half_period => !first_edge_rises,
+        _ => return None,
+    };
+    Some((if rises_at_steps { 'p' } else { 'n' }, period / step_ps))
+}
+
+/// Wave character of a value, with its data label for buses, reals and strings
+fn wave_state(signal: &TabularSignal, index: Option<usize>) -> (char, Option<String>) {
+    let Some(index) = index else {
+        return ('x', None);
+    };
+    let transitions = &signal.transitions;
+    let raw = transitions.value_string(index);
+    if !transitions.encoding.is_logic() {
+        return ('=', Some(raw));
+    }
+    if transitions.width == 1 {
+        let state = match raw.as_str() {
+            "0" => '0',
+            "1" => '1',
+            "z" => 'z',
+            "l" => 'l',
+            "h" => 'h',
+            _ => 'x',
+        };
+        return (state, None);
+    }
+    if raw.chars().all(|state| state == 'z') {
+        ('z', None)
+    } else if raw.chars().all(|state| state == 'x') {
+        ('x', None)
+    } else {
+        ('=', Some(signal.format.format_packed(transitions, index)))
+    }
+}
+
+fn wavedrom_signal(signal: &TabularSignal, steps: &[u64], step_ps: Option<u64>) -> WaveDromSignal {
+    let name = signal
+        .name
+        .rsplit('|')
+        .next()
+        .unwrap_or(&signal.name)
+        .to_string();
+    if let Some((pattern, period)) =
+        step_ps.and_then(|step_ps| clock_pattern(signal, steps, step_ps))
+    {
+        let cycles = (steps.len() as u64).div_ceil(period) as usize;
+        return WaveDromSignal {
+            name,
+            wave: format!("{}{}", pattern, ".".repeat(cycles - 1)),
+            data: Vec::new(),
+            period: (period > 1).then_some(period),
+        };
+    }
+
+    let mut wave = String::with_capacity(steps.len());
+    let mut data = Vec::new();
+    let mut previous: Option<Option<String>> = None;
+    for &time_ps in steps {
+        let index = signal.transitions.index_at_or_before(time_ps);
+        let raw = index.map(|index| signal.transitions.value_string(index));
+        if previous.as_ref() == Some(&raw) {
+            wave.push('.');
+            continue;
+        }
+        let (state, label) = wave_state(signal, index);
+        wave.push(state);
+        data.extend(label);
+        previous = Some(raw);
+    }
+    WaveDromSignal {
+        name,
+        wave,
+        data,
+        period: None,
+    }
+}
+
+fn write_wavedrom<W: Write>(
+    out: &mut W,
+    request: &WaveformExportRequest,
+    signals: &[TabularSignal],
+    counters: &ExportCounters,
+) -> Result<(), String> {
+    let steps = wavedrom_steps(request, signals)?;
+    let step_ps = uniform_step(&steps);
+    counters
+        .total
+        .store(signals.len() as u64, Ordering::Relaxed);
+    let diagram = WaveDromDiagram {
+        signal: signals
+            .iter()
+            .map(|signal| wavedrom_signal(signal, &steps, step_ps))
+            .collect(),
+    };
+    serde_json::to_writer_pretty(&mut *out, &diagram)
+        .map_err(|e| format!("Failed to write '{}': {}", request.output_path, e))?;
+    writeln!(out).map_err(|e| format!("Failed to write '{}': {}", request.output_path, e))?;
+    counters
+        .processed
+        .store(signals.len() as u64, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Signals of a text export, loaded through the signal cache
+fn load_tabular_signals(request: &WaveformExportRequest) -> Result<Vec<TabularSignal>, String> {
+    let keys = tabular_variable_keys(request)?;
+    if keys.is_empty() {
+        return Err("None of the requested signals exist in the file".to_string());
+    }
+    let window = Some((request.start_ps, request.end_ps));
+    let signals = keys
+        .into_iter()
+        .map(|name| {
+            Ok(TabularSignal {
+                transitions: load_tabular_transitions(request, &name, window)?,
+                format: request
+                    .variable_formats
+                    .get(&name)
+                    .copied()
+                    .unwrap_or_default(),
+                name,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(signals)
+}
+
+fn write_text<W: Write>(
+    out: &mut W,
+    request: &WaveformExportRequest,
+    signals: &[TabularSignal],
+    counters: &ExportCounters,
+) -> Result<(), String> {
+    if request.format == ExportFormat::WaveDrom {
+        write_wavedrom(out, request, signals, counters)
+    } else {
+        write_tabular(out, request, signals, counters)
+    }
+}
+
+/// CSV / JSON / WaveDrom export; returns the number of exported variables
+fn write_tabular_export(
+    request: &WaveformExportRequest,
+    counters: &ExportCounters,
+) -> Result<usize, String> {
+    let signals = load_tabular_signals(request)?;
+    let file = fs::File::create(&request.output_path)
+        .map_err(|e| format!("Failed to create '{}': {}", request.output_path, e))?;
+    let mut out = std::io::BufWriter::new(file);
+    write_text(&mut out, request, &signals, counters)?;
+    out.flush()
+        .map_err(|e| format!("Failed to write '{}': {}", request.output_path, e))?;
+    Ok(signals.len())
+}
+
+/// Text export kept in memory for the clipboard; returns the number of exported variables
+/// and the text
+fn export_to_text(
+    request: &WaveformExportRequest,
+    counters: &ExportCounters,
+) -> Result<(usize, String), String> {
+    if !request.format.is_tabular() {
+        return Err(format!(
+            "{} exports cannot be copied to the clipboard",
+            request.format.label()
+        ));
+    }
+    let signals = load_tabular_signals(request)?;
+    let mut out = Vec::new();
+    write_text(&mut out, request, &signals, counters)?;
+    let text = String::from_utf8(out).map_err(|e| format!("Export is not valid UTF-8: {}", e))?;
+    Ok((signals.len(), text))
+}
+
+/// Write the export; returns the number of exported variables
+fn write_export(
+    request: &WaveformExportRequest,
+    counters: &ExportCounters,
+) -> Result<usize, String> {
+    let same_file = match (
+        fs::canonicalize(&request.file_path),
+        fs::canonicalize(&request.output_path),
+    ) {
+        (Ok(source), Ok(output)) => source == output,
+        _ => request.file_path == request.output_path,
+    };
+    if same_file {
+        return Err("The export would overwrite its source file".to_string());
+    }
+    if request.format.is_image() {
+        Err(format!(
+            "{} exports are rendered by the frontend",
+            request.format.label()
+        ))
+    } else if request.format.is_tabular() {
+        write_tabular_export(request, counters)
+    } else {
+        write_waveform_export(request, counters)
+    }
+}
+
+/// VCD / FST export; returns the number of exported variables
+fn write_waveform_export(
+    request: &WaveformExportRequest,
+    counters: &ExportCounters,
+) -> Result<usize, String> {
+    let variables: HashSet<&str> = request.variables.iter().map(String::as_str).collect();
+    // Share the parsed file out of the store, so it stays unlocked while the export is written
+    let (hierarchy, signal_source, time_table, timescale_factor, start_ps, end_ps) = {
+        let store = match WAVEFORM_DATA_STORE.lock() {
+            Ok(store) => store,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let data = store
+            .get(&request.file_path)
+            .ok_or_else(|| format!("Waveform data not loaded for '{}'", request.file_path))?;
+        (
+            Arc::clone(&data.hierarchy),
+            Arc::clone(&data.signal_source),
+            Arc::clone(&data.time_table),
+            data.timescale_factor,
+            data.file_time_ps(request.start_ps),
+            data.file_time_ps(request.end_ps),
+        )
+    };
+
+    let scopes: Vec<ExportScope> = hierarchy
+        .scopes()
+        .filter_map(|scope_ref| {
+            collect_export_scope(&hierarchy, scope_ref, &variables, &request.scopes, false)
+        })
+        .collect();
+    let var_count = scopes.iter().map(|scope| scope.all_vars().len()).sum();
+    if var_count == 0 {
+        return Err("None of the requested signals exist in the file".to_string());
+    }
+
+    let mut signal_refs: Vec<wellen::SignalRef> = scopes
+        .iter()
+        .flat_map(|scope| scope.all_vars())
+        .map(|var| var.signal_ref)
+        .collect();
+    signal_refs.sort();
+    signal_refs.dedup();
+    let signals = {
+        let mut source = signal_source
+            .lock()
+            .map_err(|_| "Signal source unavailable".to_string())?;
+        source
+            .load_signals(&signal_refs, &hierarchy, true)
+            .into_iter()
+            .collect()
+    };
+
+    let excerpt = Excerpt {
+        request,
+        scopes,
+        signals,
+        time_table: &time_table,
+        timescale_factor,
+        window: ExportWindow::new(&time_table, timescale_factor, start_ps, end_ps),
+    };
+    counters.total.store(
+        excerpt.window.change_indices().len() as u64,
+        Ordering::Relaxed,
+    );
+
+    if request.format == ExportFormat::Fst {
+        write_fst(&excerpt, counters)?;
+    } else {
+        let file = fs::File::create(&request.output_path)
+            .map_err(|e| format!("Failed to create '{}': {}", request.output_path, e))?;
+        let mut out = std::io::BufWriter::new(file);
+        write_vcd(&mut out, &excerpt, counters)?;
+        out.flush()
+            .map_err(|e| format!("Failed to write '{}': {}", request.output_path, e))?;
+    }
+    Ok(var_count)
+}
+
+pub(crate) async fn export_waveform(
+    request: WaveformExportRequest,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let export_id = request.export_id.clone();
+    let result = match ensure_waveform_body_loaded(&request.file_path).await {
+        Ok(()) => {
+            let counters = Arc::new(ExportCounters {
+                processed: AtomicU64::new(0),
+                total: AtomicU64::new(0),
+                finished: AtomicBool::new(false),
+            });
+            let ticker_counters = Arc::clone(&counters);
+            let ticker_export_id = export_id.clone();
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(LOAD_PROGRESS_INTERVAL).await;
+                    if ticker_counters.finished.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let total = ticker_counters.total.load(Ordering::Relaxed);
+                    if total == 0 {
+                        continue;
+                    }
+                    let processed = ticker_counters.processed.load(Ordering::Relaxed);
+                    send_down_msg(
+                        DownMsg::ExportProgress {
+                            export_id: ticker_export_id.clone(),
+                            progress: (processed as f32 / total as f32).min(1.0),
+                        },
+                        session_id,
+                        cor_id,
+                    )
+                    .await;
+                }
+            });
+
+            let worker_counters = Arc::clone(&counters);
+            let worker_request = request.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                if worker_request.copy_to_clipboard {
+                    export_to_text(&worker_request, &worker_counters)
+                        .map(|(signal_count, text)| (signal_count, Some(text)))
+                } else {
+                    write_export(&worker_request, &worker_counters)
+                        .map(|signal_count| (signal_count, None))
+                }
+            })
+            .await
+            .unwrap_or_else(|join_error| Err(format!("Export task failed: {}", join_error)));
+            counters.finished.store(true, Ordering::Relaxed);
+            result
+        }
+        Err(error) => Err(error),
+    };
+
+    let msg = match result {
+        Ok((signal_count, clipboard_text)) => {
+            let bytes_written = match &clipboard_text {
+                Some(text) => text.len() as u64,
+                None => fs::metadata(&request.output_path)
+                    .map(|metadata| metadata.len())
+                    .unwrap_or(0),
+            };
+            println!(
+                "Exported {} signals of '{}' to '{}' ({} bytes)",
+                signal_count,
+                request.file_path,
+                if clipboard_text.is_some() {
+                    "clipboard"
+                } else {
+                    &request.output_path
+                },
+                bytes_written
+            );
+            DownMsg::ExportCompleted {
+                export_id,
+                output_path: request.output_path,
+                signal_count,
+                bytes_written,
+                clipboard_text,
+            }
+        }
+        Err(error) => {
+            eprintln!("Export of '{}' failed: {}", request.file_path, error);
+            if Path::new(&request.output_path).exists() && !error.starts_with("The export would") {
+                let _ = fs::remove_file(&request.output_path);
+            }
+            DownMsg::ExportFailed { export_id, error }
+        }
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+/// Write a picture of the waveform view rendered by the frontend
+pub(crate) async fn save_waveform_image(
+    export_id: String,
+    output_path: String,
+    content: String,
+    row_count: usize,
+    session_id: SessionId,
+    cor_id: CorId,
+) {
+    let msg = match tokio::fs::write(&output_path, content.as_bytes()).await {
+        Ok(()) => {
+            println!(
+                "Saved waveform image with {} rows to '{}' ({} bytes)",
+                row_count,
+                output_path,
+                content.len()
+            );
+            DownMsg::ExportCompleted {
+                export_id,
+                output_path,
+                signal_count: row_count,
+                bytes_written: content.len() as u64,
+                clipboard_text: None,
+            }
+        }
+        Err(e) => DownMsg::ExportFailed {
+            export_id,
+            error: format!("Failed to write '{}': {}", output_path, e),
+        },
+    };
+    send_down_msg(msg, session_id, cor_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simple_vcd_path() -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../test_files/simple.vcd")
+            .display()
+            .to_string()
+    }
+
+    #[test]
+    fn timescales_and_identifiers_use_vcd_notation() {
+        assert_eq!(vcd_timescale(1.0), "1s");
+        assert_eq!(vcd_timescale(1e-9), "1ns");
+        assert_eq!(vcd_timescale(1e-10), "100ps");
+        assert_eq!(vcd_timescale(1e-14), "10fs");
+        assert_eq!(ps_to_native_time(2_500, 1e-9), 2);
+        assert_eq!(vcd_identifier(0), "!");
+        assert_eq!(vcd_identifier(93), "~");
+        assert_eq!(vcd_identifier(94), "!!");
+    }
+
+    #[tokio::test]
+    async fn vcd_excerpt_keeps_hierarchy_and_window_values() {
+        let file_path = simple_vcd_path();
+        ensure_waveform_body_loaded(&file_path)
+            .await
+            .expect("load waveform body");
+
+        let output_path = std::env::temp_dir()
+            .join(format!("novywave_export_test_{}.vcd", std::process::id()))
+            .display()
+            .to_string();
+        let request = WaveformExportRequest {
+            export_id: "test".to_string(),
+            file_path,
+            variables: vec!["simple_tb.s|A".to_string()],
+            scopes: Vec::new(),
+            // simple.vcd uses a 1s timescale
+            start_ps: 40_000_000_000_000,
+            end_ps: 160_000_000_000_000,
+            format: ExportFormat::Vcd,
+            output_path: output_path.clone(),
+            layout: TabularLayout::Changes,
+            variable_formats: Default::default(),
+            copy_to_clipboard: false,
+        };
+        let counters = ExportCounters {
+            processed: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+        };
+        assert_eq!(write_export(&request, &counters), Ok(1));
+        assert_eq!(counters.total.load(Ordering::Relaxed), 2);
+        assert_eq!(counters.processed.load(Ordering::Relaxed), 2);
+
+        let options = wellen::LoadOptions::default();
+        let header =
+            wellen::viewers::read_header_from_file(&output_path, &options).expect("read export");
+        let body = wellen::viewers::read_body(header.body, &header.hierarchy, None)
+            .expect("read export body");
+        assert_eq!(body.time_table, vec![40, 50, 150]);
+
+        let mut signals = HashMap::new();
+        crate::build_signal_reference_map(&header.hierarchy, &mut signals);
+        assert_eq!(signals.len(), 1);
+        let signal_ref = signals["simple_tb.s|A"];
+        let mut source = body.source;
+        let signal = source
+            .load_signals(&[signal_ref], &header.hierarchy, true)
+            .pop()
+            .expect("signal A")
+            .1;
+        let values: Vec<String> = (0..body.time_table.len())
+            .filter_map(|idx| signal.get_offset(idx as u32))
+            .filter_map(|offset| signal.get_value_at(&offset, 0).to_bit_string())
+            .collect();
+        assert_eq!(values, ["1010", "1100", "0000"]);
+
+        fs::remove_file(&output_path).unwrap();
+    }
+
+    #[test]
+    fn wavedrom_waves_detect_clocks_and_keep_x_and_z() {
+        let mut clock = PackedTransitions::new(shared::PackedEncoding::TwoState, 1);
+        for (index, time_ps) in (0..=30).step_by(5).enumerate() {
+            clock.push_bits(time_ps, if index % 2 == 1 { "1" } else { "0" });
+        }
+        let mut bus = PackedTransitions::new(shared::PackedEncoding::FourState, 4);
+        bus.push_bits(0, "xxxx");
+        bus.push_bits(12, "1010");
+        bus.push_bits(22, "zzzz");
+        let signal = |name: &str, transitions: PackedTransitions| TabularSignal {
+            name: name.to_string(),
+            format: VarFormat::Hexadecimal,
+            transitions,
+        };
+
+        let steps = [5, 15, 25];
+        let clock = wavedrom_signal(&signal("tb|clk", clock), &steps, uniform_step(&steps));
+        assert_eq!((clock.name.as_str(), clock.wave.as_str()), ("clk", "p.."));
+        assert_eq!(clock.period, None);
+
+        let bus = wavedrom_signal(&signal("tb|data", bus), &steps, uniform_step(&steps));
+        assert_eq!(bus.wave, "x=z");
+        assert_eq!(bus.data, ["a"]);
+    }
+
+    #[tokio::test]
+    async fn csv_exports_use_variable_formats() {
+        let file_path = simple_vcd_path();
+        ensure_waveform_body_loaded(&file_path)
+            .await
+            .expect("load waveform body");
+        let output_path = std::env::temp_dir()
+            .join(format!("novywave_export_test_{}.csv", std::process::id()))
+            .display()
+            .to_string();
+        let counters = ExportCounters {
+            processed: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            finished: AtomicBool::new(false),
+        };
+        let mut request = WaveformExportRequest {
+            export_id: "test".to_string(),
+            file_path,
+            variables: vec!["simple_tb.s|A".to_string()],
+            scopes: Vec::new(),
+            start_ps: 40_000_000_000_000,
+            end_ps: 160_000_000_000_000,
+            format: ExportFormat::Csv,
+            output_path: output_path.clone(),
+            layout: TabularLayout::Changes,
+            variable_formats: [("simple_tb.s|A".to_string(), VarFormat::Binary)].into(),
+            copy_to_clipboard: false,
+        };
+
+        assert_eq!(write_export(&request, &counters), Ok(1));
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "signal,time_ps,value\n\
+             simple_tb.s|A,40000000000000,1010\n\
+             simple_tb.s|A,50000000000000,1100\n\
+             simple_tb.s|A,150000000000000,0000\n"
+        );
+
+        request.start_ps = 0;
+        request.end_ps = 150_000_000_000_000;
+        request.layout = TabularLayout::SampledPeriod {
+            period_ps: 50_000_000_000_000,
+        };
+        request.variable_formats.clear();
+        assert_eq!(write_export(&request, &counters), Ok(1));
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            "time_ps,simple_tb.s|A\n0,a\n50000000000000,c\n100000000000000,c\n150000000000000,0\n"
+        );
+        assert_eq!(counters.processed.load(Ordering::Relaxed), 4);
+
+        fs::remove_file(&output_path).unwrap();
+    }
+}
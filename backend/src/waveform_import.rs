@@ -0,0 +1,1137 @@
+//! Waveform sources wellen cannot read: WaveDrom diagrams, SPICE rawfiles and
+//! logic-analyzer CSV captures.
+//!
+//! An importer parses a file into an `ImportedWaveform`, a `ScopeData` tree plus the value
+//! changes of each `Signal`. `write_vcd` turns that model into a VCD in the decompression
+//! cache, which is then loaded like any other dump, so signal queries, the signal cache
+//! and exports work on imported files without a second code path.
+
+use crate::waveform_export::{vcd_identifier, vcd_timescale};
+use shared::{CsvImportSettings, FileFormat, ScopeData, Signal};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Length of one WaveDrom step in `WAVEDROM_TIMESCALE` units; even, so clocks can
+/// toggle in the middle of a step
+const WAVEDROM_STEP: u64 = 10;
+
+const WAVEDROM_TIMESCALE: f64 = 1e-9;
+
+/// WaveDrom diagrams are written by hand; larger `.json` files are not parsed to find out
+const MAX_WAVEDROM_BYTES: u64 = 16 * 1024 * 1024;
+
+/// SPICE timesteps are non-uniform and often sub-picosecond
+const SPICE_TIMESCALE: f64 = 1e-15;
+
+/// AC analyses sweep a frequency axis instead, in millihertz, so 1 Hz is shown as 1 s
+const SPICE_FREQUENCY_TIMESCALE: f64 = 1e-3;
+
+/// Latest time the picosecond timeline holds, about 5 hours; caps AC sweeps at ~18 MHz
+const MAX_TIMELINE_SECONDS: f64 = u64::MAX as f64 * 1e-12;
+
+/// Logic analyzers sample at a few GHz at most
+const CSV_TIMESCALE: f64 = 1e-12;
+
+pub(crate) struct ImportedWaveform {
+    /// Seconds per time unit of the transitions
+    pub timescale_factor: f64,
+    pub scopes: Vec<ScopeData>,
+    /// Value changes by `Signal::id`, in time order. Values use VCD notation: bit strings
+    /// for wires, decimal text for reals and free text for strings.
+    pub transitions: HashMap<String, Vec<(u64, String)>>,
+    /// Last timestamp of the waveform, at or after its last change
+    pub end_time: u64,
+}
+
+/// Format of a file read through an importer, by its extension. `.json` files only count
+/// as WaveDrom once their (unpacked) content turns out to be a diagram.
+pub(crate) fn import_format(file_path: &str) -> Option<FileFormat> {
+    match shared::get_file_extension(shared::strip_compression_extension(file_path))?.as_str() {
+        "json" if is_wavedrom_json(&crate::unpacked_source_path(file_path)) => {
+            Some(FileFormat::WaveDrom)
+        }
+        "raw" => Some(FileFormat::SpiceRaw),
+        "csv" => Some(FileFormat::LogicCsv),
+        _ => None,
+    }
+}
+
+/// Whether a JSON file holds a top-level `"signal"` array, as every WaveDrom diagram does
+fn is_wavedrom_json(source_path: &str) -> bool {
+    let small =
+        fs::metadata(source_path).is_ok_and(|metadata| metadata.len() <= MAX_WAVEDROM_BYTES);
+    small
+        && fs::read(source_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+            .is_some_and(|document| {
+                document
+                    .get("signal")
+                    .is_some_and(|signal| signal.is_array())
+            })
+}
+
+/// Parse `source_path` (the file itself or its unpacked copy) and write it out as a VCD
+pub(crate) fn convert_to_vcd(
+    file_path: &str,
+    source_path: &str,
+    format: &FileFormat,
+    csv_settings: &CsvImportSettings,
+    target: &Path,
+) -> Result<(), String> {
+    let root_name = Path::new(shared::strip_compression_extension(file_path))
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("top");
+    let waveform = match format {
+        FileFormat::WaveDrom => {
+            let text = fs::read_to_string(source_path)
+                .map_err(|e| format!("Failed to read '{}': {}", file_path, e))?;
+            parse_wavedrom(&text, root_name)?
+        }
+        FileFormat::SpiceRaw => {
+            let bytes = fs::read(source_path)
+                .map_err(|e| format!("Failed to read '{}': {}", file_path, e))?;
+            parse_spice_raw(&bytes, root_name)?
+        }
+        FileFormat::LogicCsv => {
+            let text = fs::read_to_string(source_path)
+                .map_err(|e| format!("Failed to read '{}': {}", file_path, e))?;
+            parse_logic_csv(&text, root_name, csv_settings)?
+        }
+        other => return Err(format!("{:?} files are read by wellen directly", other)),
+    };
+
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create cache dir: {}", e))?;
+    }
+    let partial = format!("{}.partial", target.display());
+    let written = fs::File::create(&partial).and_then(|file| {
+        let mut out = std::io::BufWriter::new(file);
+        write_vcd(&mut out, &waveform)?;
+        out.flush()
+    });
+    match written.and_then(|()| fs::rename(&partial, target)) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(format!("Failed to write converted '{}': {}", file_path, e))
+        }
+    }
+}
+
+/// VCD with the scopes, declarations and value changes of an imported waveform
+pub(crate) fn write_vcd<W: Write>(out: &mut W, waveform: &ImportedWaveform) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "$timescale {} $end",
+        vcd_timescale(waveform.timescale_factor)
+    )?;
+    let mut declared: Vec<(&Signal, String)> = Vec::new();
+    for scope in &waveform.scopes {
+        write_vcd_scope(out, scope, &mut declared)?;
+    }
+    writeln!(out, "$enddefinitions $end")?;
+
+    let mut changes: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+    for (signal, id) in &declared {
+        for (time, value) in waveform.transitions.get(&signal.id).into_iter().flatten() {
+            let line = match signal.signal_type.as_str() {
+                "Real" => format!("r{} {}", value, id),
+                "String" => format!("s{} {}", value, id),
+                _ if signal.width == 1 => format!("{}{}", value, id),
+                _ => format!("b{} {}", value, id),
+            };
+            changes.entry(*time).or_default().push(line);
+        }
+    }
+    for (time, lines) in &changes {
+        writeln!(out, "#{}", time)?;
+        for line in lines {
+            writeln!(out, "{}", line)?;
+        }
+    }
+    if changes
+        .last_key_value()
+        .is_none_or(|(time, _)| *time < waveform.end_time)
+    {
+        writeln!(out, "#{}", waveform.end_time)?;
+    }
+    Ok(())
+}
+
+fn write_vcd_scope<'a, W: Write>(
+    out: &mut W,
+    scope: &'a ScopeData,
+    declared: &mut Vec<(&'a Signal, String)>,
+) -> std::io::Result<()> {
+    writeln!(out, "$scope module {} $end", vcd_name(&scope.name))?;
+    for signal in &scope.variables {
+        let id = vcd_identifier(declared.len());
+        let var_type = match signal.signal_type.as_str() {
+            "Real" => "real",
+            "String" => "string",
+            _ => "wire",
+        };
+        writeln!(
+            out,
+            "$var {} {} {} {} $end",
+            var_type,
+            signal.width,
+            id,
+            vcd_name(&signal.name)
+        )?;
+        declared.push((signal, id));
+    }
+    for child in &scope.children {
+        write_vcd_scope(out, child, declared)?;
+    }
+    writeln!(out, "$upscope $end")
+}
+
+/// VCD names and string values end at whitespace
+fn vcd_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+fn new_scope(name: &str, parent: Option<&str>) -> ScopeData {
+    let full_name = match parent {
+        Some(parent) => format!("{}.{}", parent, name),
+        None => name.to_string(),
+    };
+    ScopeData {
+        id: full_name.clone(),
+        name: name.to_string(),
+        full_name,
+        children: Vec::new(),
+        variables: Vec::new(),
+        scope_type: Some("Module".to_string()),
+    }
+}
+
+/// Level of a WaveDrom lane during one step
+#[derive(Clone, PartialEq)]
+enum WaveLevel {
+    /// `0`, `1`, `x` or `z`
+    Bit(char),
+    /// Data segment (`=`, `2`..`9`) with its label
+    Data(String),
+}
+
+/// Parse a WaveDrom diagram. Top-level lanes go into a scope named `root_name`, groups
+/// (`["name", lanes...]`) become nested scopes. Every step lasts `WAVEDROM_STEP` time units.
+fn parse_wavedrom(text: &str, root_name: &str) -> Result<ImportedWaveform, String> {
+    let document: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| format!("Invalid WaveDrom JSON: {}", e))?;
+    let lanes = document
+        .get("signal")
+        .and_then(|signal| signal.as_array())
+        .ok_or_else(|| "Not a WaveDrom diagram: missing \"signal\" array".to_string())?;
+
+    let mut waveform = ImportedWaveform {
+        timescale_factor: WAVEDROM_TIMESCALE,
+        scopes: Vec::new(),
+        transitions: HashMap::new(),
+        end_time: 0,
+    };
+    let mut root = new_scope(root_name, None);
+    add_wavedrom_lanes(lanes, &mut root, &mut waveform);
+    if waveform.transitions.is_empty() {
+        return Err("The WaveDrom diagram contains no waves".to_string());
+    }
+    waveform.scopes.push(root);
+    Ok(waveform)
+}
+
+fn add_wavedrom_lanes(
+    lanes: &[serde_json::Value],
+    scope: &mut ScopeData,
+    waveform: &mut ImportedWaveform,
+) {
+    for lane in lanes {
+        if let Some(group) = lane.as_array() {
+            match group.split_first() {
+                Some((serde_json::Value::String(name), lanes)) if !name.trim().is_empty() => {
+                    let mut child = new_scope(&vcd_name(name), Some(&scope.full_name));
+                    add_wavedrom_lanes(lanes, &mut child, waveform);
+                    scope.children.push(child);
+                }
+                // Unnamed groups only indent their lanes
+                Some((serde_json::Value::String(_), lanes)) => {
+                    add_wavedrom_lanes(lanes, scope, waveform)
+                }
+                _ => add_wavedrom_lanes(group, scope, waveform),
+            }
+            continue;
+        }
+        // `{}` spacers and lanes without a wave carry no signal
+        let Some(wave) = lane.get("wave").and_then(|wave| wave.as_str()) else {
+            continue;
+        };
+        let data: Vec<String> = match lane.get("data") {
+            Some(serde_json::Value::Array(labels)) => labels
+                .iter()
+                .map(|label| match label {
+                    serde_json::Value::String(label) => label.clone(),
+                    other => other.to_string(),
+                })
+                .collect(),
+            Some(serde_json::Value::String(labels)) => {
+                labels.split_whitespace().map(str::to_string).collect()
+            }
+            _ => Vec::new(),
+        };
+        let period = lane
+            .get("period")
+            .and_then(|period| period.as_f64())
+            .filter(|period| *period >= 1.0)
+            .map_or(1, |period| period.round() as u64);
+
+        let mut name = lane
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(vcd_name)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("signal{}", scope.variables.len()));
+        if scope.variables.iter().any(|signal| signal.name == name) {
+            name = format!("{}_{}", name, scope.variables.len());
+        }
+
+        let (levels, end_time) = wavedrom_levels(wave, &data, period);
+        waveform.end_time = waveform.end_time.max(end_time);
+        let id = format!("{}|{}", scope.full_name, name);
+        let (signal_type, width, changes) = wavedrom_changes(&levels);
+        waveform.transitions.insert(id.clone(), changes);
+        scope.variables.push(Signal {
+            id,
+            name,
+            signal_type: signal_type.to_string(),
+            width,
+        });
+    }
+}
+
+/// Levels of a lane at each change, plus the time its last step ends
+fn wavedrom_levels(wave: &str, data: &[String], period: u64) -> (Vec<(u64, WaveLevel)>, u64) {
+    let step = period * WAVEDROM_STEP;
+    let mut labels = data.iter();
+    let mut levels = Vec::new();
+    let mut clock: Option<(char, char)> = None;
+    let mut steps = 0;
+    for (index, symbol) in wave.chars().filter(|c| !c.is_whitespace()).enumerate() {
+        let time = index as u64 * step;
+        steps = index as u64 + 1;
+        let level = match symbol {
+            // Continuations repeat a clock cycle and keep any other level
+            '.' | '|' => {
+                if let Some((first, second)) = clock {
+                    levels.push((time, WaveLevel::Bit(first)));
+                    levels.push((time + step / 2, WaveLevel::Bit(second)));
+                }
+                continue;
+            }
+            'p' | 'P' | 'n' | 'N' => {
+                let cycle = if symbol.eq_ignore_ascii_case(&'p') {
+                    ('1', '0')
+                } else {
+                    ('0', '1')
+                };
+                clock = Some(cycle);
+                levels.push((time, WaveLevel::Bit(cycle.0)));
+                levels.push((time + step / 2, WaveLevel::Bit(cycle.1)));
+                continue;
+            }
+            '0' | 'l' | 'L' | 'd' => WaveLevel::Bit('0'),
+            '1' | 'h' | 'H' | 'u' => WaveLevel::Bit('1'),
+            'z' => WaveLevel::Bit('z'),
+            '=' | '2'..='9' => WaveLevel::Data(labels.next().cloned().unwrap_or_default()),
+            _ => WaveLevel::Bit('x'),
+        };
+        clock = None;
+        levels.push((time, level));
+    }
+    (levels, steps * step)
+}
+
+/// VCD signal type, width and value changes of a lane. Data lanes whose labels are all
+/// numbers become buses; other labels make a string signal.
+fn wavedrom_changes(levels: &[(u64, WaveLevel)]) -> (&'static str, u32, Vec<(u64, String)>) {
+    let labels: Vec<&str> = levels
+        .iter()
+        .filter_map(|(_, level)| match level {
+            WaveLevel::Data(label) => Some(label.as_str()),
+            WaveLevel::Bit(_) => None,
+        })
+        .collect();
+    let numbers: Option<Vec<u128>> = labels.iter().map(|label| parse_number(label)).collect();
+    let (signal_type, width) = match &numbers {
+        _ if labels.is_empty() => ("Wire", 1),
+        Some(numbers) => {
+            let bits = numbers
+                .iter()
+                .map(|number| 128 - number.leading_zeros())
+                .max()
+                .unwrap_or(1);
+            ("Wire", bits.max(1))
+        }
+        None => ("String", 1),
+    };
+
+    let mut changes: Vec<(u64, String)> = Vec::new();
+    for (time, level) in levels {
+        let value = match (level, signal_type) {
+            (WaveLevel::Bit(bit), "Wire") if width > 1 => match bit {
+                '0' | '1' => format!(
+                    "{:0width$b}",
+                    bit.to_digit(2).unwrap_or(0),
+                    width = width as usize
+                ),
+                _ => bit.to_string().repeat(width as usize),
+            },
+            (WaveLevel::Bit(bit), _) => bit.to_string(),
+            (WaveLevel::Data(label), "Wire") => format!(
+                "{:0width$b}",
+                parse_number(label).unwrap_or(0),
+                width = width as usize
+            ),
+            (WaveLevel::Data(label), _) => {
+                let label = vcd_name(label);
+                if label.is_empty() {
+                    "-".to_string()
+                } else {
+                    label
+                }
+            }
+        };
+        push_change(&mut changes, *time, value);
+    }
+    (signal_type, width, changes)
+}
+
+/// `0x1F`, `0b101`, `0o17` or decimal
+fn parse_number(label: &str) -> Option<u128> {
+    let label = label.trim().replace('_', "");
+    let lower = label.to_lowercase();
+    if let Some(hex) = lower.strip_prefix("0x") {
+        u128::from_str_radix(hex, 16).ok()
+    } else if let Some(binary) = lower.strip_prefix("0b") {
+        u128::from_str_radix(binary, 2).ok()
+    } else if let Some(octal) = lower.strip_prefix("0o") {
+        u128::from_str_radix(octal, 8).ok()
+    } else {
+        lower.parse().ok()
+    }
+}
+
+/// Header text of a rawfile up to its data section, the data offset and whether the data
+/// is binary. LTspice writes the header (and ASCII data) as UTF-16LE, ngspice as ASCII.
+fn raw_header(bytes: &[u8], utf16: bool) -> Result<(String, usize, bool), String> {
+    let unit = if utf16 { 2 } else { 1 };
+    let mut header = String::new();
+    let mut line = String::new();
+    let mut position = 0;
+    while position + unit <= bytes.len() {
+        let code = if utf16 {
+            u16::from_le_bytes([bytes[position], bytes[position + 1]]) as u32
+        } else {
+            bytes[position] as u32
+        };
+        position += unit;
+        match char::from_u32(code) {
+            Some('\n') => {
+                let trimmed = line.trim();
+                header.push_str(trimmed);
+                header.push('\n');
+                if trimmed.eq_ignore_ascii_case("binary:") {
+                    return Ok((header, position, true));
+                }
+                if trimmed.eq_ignore_ascii_case("values:") {
+                    return Ok((header, position, false));
+                }
+                line.clear();
+            }
+            Some(character) => line.push(character),
+            None => line.push('?'),
+        }
+    }
+    Err("Not a SPICE rawfile: no Values: or Binary: section".to_string())
+}
+
+/// Parse the first plot of an ngspice or LTspice rawfile. The first vector is the time
+/// axis (frequency for AC analyses); every other vector becomes a Real signal, complex
+/// vectors as magnitude plus `_phase` in degrees.
+fn parse_spice_raw(bytes: &[u8], root_name: &str) -> Result<ImportedWaveform, String> {
+    let utf16 = bytes.get(1) == Some(&0);
+    let (header, data_start, binary) = raw_header(bytes, utf16)?;
+
+    let mut flags = String::new();
+    let mut vector_count: Option<usize> = None;
+    let mut point_count: Option<usize> = None;
+    // Vector names; of the type column (time, voltage, current, ...) only the axis type matters
+    let mut vectors: Vec<String> = Vec::new();
+    let mut axis_type = String::new();
+    let mut lines = header.lines();
+    while let Some(line) = lines.next() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim().to_lowercase().as_str() {
+            "flags" => flags = value.trim().to_lowercase(),
+            "no. variables" => vector_count = value.trim().parse().ok(),
+            "no. points" => point_count = value.trim().parse().ok(),
+            "variables" => {
+                let count = vector_count
+                    .ok_or_else(|| "Rawfile lists variables before their count".to_string())?;
+                for line in lines.by_ref().take(count) {
+                    let mut columns = line.split_whitespace().skip(1);
+                    let name = columns.next().unwrap_or_default();
+                    if vectors.is_empty() {
+                        axis_type = columns.next().unwrap_or_default().to_lowercase();
+                    }
+                    vectors.push(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    if vectors.len() < 2 || vectors.len() != vector_count.unwrap_or(0) {
+        return Err("Rawfile declares no vectors besides the time axis".to_string());
+    }
+    if flags.contains("fastaccess") {
+        return Err(
+            "LTspice fastaccess rawfiles are not supported; save the file again without fastaccess"
+                .to_string(),
+        );
+    }
+    let complex = flags.contains("complex");
+
+    // Values per point as (real, imaginary); stops at the last complete point
+    let data = &bytes[data_start..];
+    let mut points: Vec<Vec<(f64, f64)>> = Vec::new();
+    let max_points = point_count.unwrap_or(usize::MAX);
+    if binary {
+        // LTspice stores real vectors other than the time axis as f32 unless flagged "double"
+        let single_precision = utf16 && !complex && !flags.contains("double");
+        let mut position = 0;
+        let mut read_f64 = |size: usize| -> Option<f64> {
+            let field = data.get(position..position + size)?;
+            position += size;
+            Some(match size {
+                4 => f32::from_le_bytes(field.try_into().ok()?) as f64,
+                _ => f64::from_le_bytes(field.try_into().ok()?),
+            })
+        };
+        'points: while points.len() < max_points {
+            let mut point = Vec::with_capacity(vectors.len());
+            for index in 0..vectors.len() {
+                let value = if complex {
+                    read_f64(8).zip(read_f64(8))
+                } else if single_precision && index > 0 {
+                    read_f64(4).map(|real| (real, 0.0))
+                } else {
+                    read_f64(8).map(|real| (real, 0.0))
+                };
+                let Some(value) = value else {
+                    break 'points;
+                };
+                point.push(value);
+            }
+            points.push(point);
+        }
+    } else {
+        let text = if utf16 {
+            let units: Vec<u16> = data
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(data).into_owned()
+        };
+        let mut tokens = text.split_whitespace();
+        'points: while points.len() < max_points {
+            // Each point starts with its index
+            if tokens.next().is_none() {
+                break;
+            }
+            let mut point = Vec::with_capacity(vectors.len());
+            for _ in 0..vectors.len() {
+                let Some(token) = tokens.next() else {
+                    break 'points;
+                };
+                let (real, imaginary) = token.split_once(',').unwrap_or((token, "0"));
+                match (real.parse::<f64>(), imaginary.parse::<f64>()) {
+                    (Ok(real), Ok(imaginary)) => point.push((real, imaginary)),
+                    _ => return Err(format!("Invalid rawfile value '{}'", token)),
+                }
+            }
+            points.push(point);
+        }
+    }
+    if points.is_empty() {
+        return Err("The rawfile contains no data points".to_string());
+    }
+
+    let timescale_factor = if axis_type == "frequency" {
+        SPICE_FREQUENCY_TIMESCALE
+    } else {
+        SPICE_TIMESCALE
+    };
+    let mut root = new_scope(root_name, None);
+    let mut waveform = ImportedWaveform {
+        timescale_factor,
+        scopes: Vec::new(),
+        transitions: HashMap::new(),
+        end_time: 0,
+    };
+    // LTspice marks compressed points with a negative time
+    let times: Vec<u64> = points
+        .iter()
+        .map(|point| {
+            let axis = point[0].0.abs();
+            if axis >= MAX_TIMELINE_SECONDS {
+                return Err(if timescale_factor == SPICE_FREQUENCY_TIMESCALE {
+                    format!(
+                        "The AC sweep reaches {} Hz; frequencies from {:.0} Hz on do not fit \
+                         the timeline, which shows 1 Hz as 1 s",
+                        axis, MAX_TIMELINE_SECONDS
+                    )
+                } else {
+                    format!(
+                        "The rawfile time axis reaches {} s, beyond the timeline",
+                        axis
+                    )
+                });
+            }
+            Ok((axis / timescale_factor).round() as u64)
+        })
+        .collect::<Result<_, _>>()?;
+    waveform.end_time = times.iter().copied().max().unwrap_or(0);
+
+    for (index, vector) in vectors.iter().enumerate().skip(1) {
+        let name = vcd_name(vector);
+        let values = points.iter().map(|point| point[index]);
+        let columns: Vec<(String, Vec<f64>)> = if complex {
+            vec![
+                (
+                    name.clone(),
+                    values
+                        .clone()
+                        .map(|(real, imaginary)| real.hypot(imaginary))
+                        .collect(),
+                ),
+                (
+                    format!("{}_phase", name),
+                    values
+                        .map(|(real, imaginary)| imaginary.atan2(real).to_degrees())
+                        .collect(),
+                ),
+            ]
+        } else {
+            vec![(name, values.map(|(real, _)| real).collect())]
+        };
+        for (name, column) in columns {
+            let mut changes: Vec<(u64, String)> = Vec::new();
+            for (time, value) in times.iter().zip(column) {
+                push_change(&mut changes, *time, value.to_string());
+            }
+            let id = format!("{}|{}", root.full_name, name);
+            waveform.transitions.insert(id.clone(), changes);
+            root.variables.push(Signal {
+                id,
+                name,
+                signal_type: "Real".to_string(),
+                width: 64,
+            });
+        }
+    }
+    waveform.scopes.push(root);
+    Ok(waveform)
+}
+
+/// Kind of a CSV channel column, from all its values
+#[derive(Clone, Copy, PartialEq)]
+enum CsvColumn {
+    /// Only `0`, `1`, `x` and `z`
+    Bit,
+    /// Numbers (decimal, `0x` hex, ...) of up to this many bits
+    Bus(u32),
+    Text,
+}
+
+/// Parse a logic-analyzer CSV capture (Saleae "Export Raw Data", sigrok `-O csv`): a header
+/// row, a time column and one column per channel. Columns holding only 0/1/x/z become
+/// 1-bit signals, numeric columns buses and anything else string signals; `settings` pick
+/// the time column and unit and combine 1-bit channels into buses. Captures with samples
+/// before the trigger (negative times) are shifted to start at 0.
+fn parse_logic_csv(
+    text: &str,
+    root_name: &str,
+    settings: &CsvImportSettings,
+) -> Result<ImportedWaveform, String> {
+    // sigrok starts its output with `;` comment lines
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with(';') && !line.starts_with('#'));
+    let (_, header_line) = lines
+        .next()
+        .ok_or_else(|| "The CSV file is empty".to_string())?;
+    // On a tie the comma wins
+    let delimiter = ['\t', ';', ',']
+        .into_iter()
+        .max_by_key(|delimiter| header_line.matches(*delimiter).count())
+        .unwrap_or(',');
+    let headers = csv_fields(header_line, delimiter);
+    if headers.len() < 2 {
+        return Err("The CSV file needs a time column and at least one channel".to_string());
+    }
+    let find_column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("The CSV file has no column '{}'", name))
+    };
+
+    let time_index = match &settings.time_column {
+        Some(name) => find_column(name)?,
+        None => headers
+            .iter()
+            .position(|header| header.to_lowercase().contains("time"))
+            .unwrap_or(0),
+    };
+    // `Time [s]`, `Time (us)`
+    let header_unit = headers[time_index]
+        .split(['[', '('])
+        .nth(1)
+        .and_then(|rest| rest.split([']', ')']).next())
+        .map(str::trim);
+    let unit = settings.time_unit.as_deref().or(header_unit).unwrap_or("s");
+    let unit_seconds = time_unit_seconds(unit)
+        .ok_or_else(|| format!("Unknown time unit '{}' (use s, ms, us, ns, ps or fs)", unit))?;
+
+    let mut times: Vec<f64> = Vec::new();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for (line_number, line) in lines {
+        let mut fields = csv_fields(line, delimiter);
+        if fields.len() != headers.len() {
+            return Err(format!(
+                "Line {} has {} columns instead of {}",
+                line_number,
+                fields.len(),
+                headers.len()
+            ));
+        }
+        let time = fields[time_index].parse::<f64>().map_err(|_| {
+            format!(
+                "Invalid time '{}' on line {}",
+                fields[time_index], line_number
+            )
+        })?;
+        fields.remove(time_index);
+        times.push(time * unit_seconds);
+        rows.push(fields);
+    }
+    if rows.is_empty() {
+        return Err("The CSV file contains no samples".to_string());
+    }
+    let shift = -times.iter().copied().fold(0.0, f64::min);
+    let times: Vec<u64> = times
+        .iter()
+        .map(|time| ((time + shift) / CSV_TIMESCALE).round() as u64)
+        .collect();
+    if times.windows(2).any(|pair| pair[1] < pair[0]) {
+        return Err("The time column of the CSV file is not in ascending order".to_string());
+    }
+
+    let mut channels = headers.clone();
+    channels.remove(time_index);
+    let kinds: Vec<CsvColumn> = (0..channels.len())
+        .map(|column| csv_column_kind(rows.iter().map(|row| row[column].as_str())))
+        .collect();
+
+    let mut root = new_scope(root_name, None);
+    let mut waveform = ImportedWaveform {
+        timescale_factor: CSV_TIMESCALE,
+        scopes: Vec::new(),
+        transitions: HashMap::new(),
+        end_time: times.last().copied().unwrap_or(0),
+    };
+    let mut bus_channels: Vec<Vec<usize>> = Vec::new();
+    for bus in &settings.buses {
+        let mut columns = Vec::new();
+        for channel in &bus.channels {
+            let column = channels
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(channel.trim()))
+                .ok_or_else(|| {
+                    format!(
+                        "Bus '{}': the CSV file has no channel '{}'",
+                        bus.name, channel
+                    )
+                })?;
+            if kinds[column] != CsvColumn::Bit {
+                return Err(format!(
+                    "Bus '{}': channel '{}' is not a 1-bit signal",
+                    bus.name, channel
+                ));
+            }
+            columns.push(column);
+        }
+        if columns.is_empty() {
+            return Err(format!("Bus '{}' has no channels", bus.name));
+        }
+        bus_channels.push(columns);
+    }
+
+    for (column, header) in channels.iter().enumerate() {
+        if bus_channels
+            .iter()
+            .flatten()
+            .any(|grouped| *grouped == column)
+        {
+            continue;
+        }
+        let mut changes = Vec::new();
+        for (time, row) in times.iter().zip(&rows) {
+            let cell = row[column].as_str();
+            if cell.is_empty() {
+                continue;
+            }
+            let value = match kinds[column] {
+                CsvColumn::Bit => cell.to_lowercase(),
+                CsvColumn::Bus(width) => match parse_number(cell) {
+                    Some(number) => format!("{:0width$b}", number, width = width as usize),
+                    None => cell.to_lowercase().repeat(width as usize),
+                },
+                CsvColumn::Text => vcd_name(cell),
+            };
+            push_change(&mut changes, *time, value);
+        }
+        let (signal_type, width) = match kinds[column] {
+            CsvColumn::Bit => ("Wire", 1),
+            CsvColumn::Bus(width) => ("Wire", width),
+            CsvColumn::Text => ("String", 1),
+        };
+        add_csv_signal(
+            &mut root,
+            &mut waveform,
+            vcd_name(header),
+            signal_type,
+            width,
+            changes,
+        );
+    }
+
+    for (bus, columns) in settings.buses.iter().zip(&bus_channels) {
+        let mut changes = Vec::new();
+        // Empty cells keep the previous level of their channel
+        let mut bits = vec!['x'; columns.len()];
+        for (time, row) in times.iter().zip(&rows) {
+            for (bit, column) in bits.iter_mut().zip(columns) {
+                if let Some(level) = row[*column].chars().next() {
+                    *bit = level.to_ascii_lowercase();
+                }
+            }
+            push_change(&mut changes, *time, bits.iter().collect());
+        }
+        add_csv_signal(
+            &mut root,
+            &mut waveform,
+            vcd_name(&bus.name),
+            "Wire",
+            columns.len() as u32,
+            changes,
+        );
+    }
+
+    waveform.scopes.push(root);
+    Ok(waveform)
+}
+
+fn add_csv_signal(
+    scope: &mut ScopeData,
+    waveform: &mut ImportedWaveform,
+    name: String,
+    signal_type: &str,
+    width: u32,
+    changes: Vec<(u64, String)>,
+) {
+    let mut name = if name.is_empty() {
+        format!("signal{}", scope.variables.len())
+    } else {
+        name
+    };
+    if scope.variables.iter().any(|signal| signal.name == name) {
+        name = format!("{}_{}", name, scope.variables.len());
+    }
+    let id = format!("{}|{}", scope.full_name, name);
+    waveform.transitions.insert(id.clone(), changes);
+    scope.variables.push(Signal {
+        id,
+        name,
+        signal_type: signal_type.to_string(),
+        width,
+    });
+}
+
+/// Fields of a CSV line with surrounding quotes removed; delimiters inside quotes are kept
+/// and `""` inside quotes stands for one quote
+fn csv_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if quoted && characters.peek() == Some(&'"') => {
+                characters.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            _ if character == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            _ => field.push(character),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+fn csv_column_kind<'a>(cells: impl Iterator<Item = &'a str>) -> CsvColumn {
+    let mut kind = CsvColumn::Bit;
+    for cell in cells.filter(|cell| !cell.is_empty()) {
+        if matches!(cell, "0" | "1" | "x" | "X" | "z" | "Z") {
+            continue;
+        }
+        match (kind, parse_number(cell)) {
+            (CsvColumn::Text, _) | (_, None) => return CsvColumn::Text,
+            (CsvColumn::Bit, Some(number)) => kind = CsvColumn::Bus(bit_width(number)),
+            (CsvColumn::Bus(width), Some(number)) => {
+                kind = CsvColumn::Bus(width.max(bit_width(number)))
+            }
+        }
+    }
+    kind
+}
+
+fn bit_width(number: u128) -> u32 {
+    (128 - number.leading_zeros()).max(1)
+}
+
+fn time_unit_seconds(unit: &str) -> Option<f64> {
+    match unit.trim().to_lowercase().as_str() {
+        "s" => Some(1.0),
+        "ms" => Some(1e-3),
+        "us" | "µs" | "μs" => Some(1e-6),
+        "ns" => Some(1e-9),
+        "ps" => Some(1e-12),
+        "fs" => Some(1e-15),
+        _ => None,
+    }
+}
+
+/// Append a value change, dropping repeats of the current value
+fn push_change(changes: &mut Vec<(u64, String)>, time: u64, value: String) {
+    match changes.last_mut() {
+        Some(last) if last.0 == time => last.1 = value,
+        Some(last) if last.1 == value => {}
+        _ => changes.push((time, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_json_with_a_signal_array_counts_as_wavedrom() {
+        let dir = std::env::temp_dir().join(format!("novywave-sniff-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let sniff = |name: &str, text: &str| {
+            let path = dir.join(name);
+            fs::write(&path, text).unwrap();
+            is_wavedrom_json(&path.to_string_lossy())
+        };
+        assert!(sniff(
+            "timing.json",
+            r#"{"signal": [{"name": "clk", "wave": "p."}]}"#
+        ));
+        assert!(!sniff(
+            "package.json",
+            r#"{"name": "app", "signal": "none"}"#
+        ));
+        assert!(!sniff("nested.json", r#"{"config": {"signal": []}}"#));
+        assert!(!sniff("broken.json", "{signal: ["));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn wavedrom_diagram_converts_to_vcd() {
+        let diagram = r#"{"signal": [
+            {"name": "clk", "wave": "p..."},
+            {},
+            ["bus",
+                {"name": "addr", "wave": "x=.z", "data": ["0x12"]},
+                {"name": "cmd", "wave": "=.=.", "data": "read write"}
+            ]
+        ]}"#;
+        let waveform = parse_wavedrom(diagram, "spec").unwrap();
+        assert_eq!(waveform.end_time, 40);
+        assert_eq!(
+            waveform.transitions["spec|clk"],
+            [
+                (0, "1"),
+                (5, "0"),
+                (10, "1"),
+                (15, "0"),
+                (20, "1"),
+                (25, "0"),
+                (30, "1"),
+                (35, "0")
+            ]
+            .map(|(time, value)| (time, value.to_string()))
+        );
+        assert_eq!(
+            waveform.transitions["spec.bus|addr"],
+            [(0, "xxxxx"), (10, "10010"), (30, "zzzzz")]
+                .map(|(time, value)| (time, value.to_string()))
+        );
+        assert_eq!(
+            waveform.scopes[0].children[0].variables[1].signal_type,
+            "String"
+        );
+
+        let mut vcd = Vec::new();
+        write_vcd(&mut vcd, &waveform).unwrap();
+        let vcd = String::from_utf8(vcd).unwrap();
+        assert!(vcd.starts_with(
+            "$timescale 1ns $end\n\
+             $scope module spec $end\n\
+             $var wire 1 ! clk $end\n\
+             $scope module bus $end\n\
+             $var wire 5 \" addr $end\n\
+             $var string 1 # cmd $end\n\
+             $upscope $end\n\
+             $upscope $end\n\
+             $enddefinitions $end\n\
+             #0\n1!\nbxxxxx \"\nsread #\n"
+        ));
+        assert!(vcd.ends_with("#35\n0!\n#40\n"));
+    }
+
+    #[test]
+    fn ascii_rawfile_keeps_non_uniform_timesteps() {
+        let raw = "Title: rc\nPlotname: Transient Analysis\nFlags: real\nNo. Variables: 3\n\
+                   No. Points: 3\nVariables:\n\t0\ttime\ttime\n\t1\tv(out)\tvoltage\n\
+                   \t2\ti(v1)\tcurrent\nValues:\n 0\t0.0\n\t0.0\n\t1.0e-3\n\
+                   1\t1.5e-9\n\t0.5\n\t1.0e-3\n 2\t4e-9\n\t0.75\n\t0.5e-3\n";
+        let waveform = parse_spice_raw(raw.as_bytes(), "rc").unwrap();
+        assert_eq!(waveform.end_time, 4_000_000);
+        let names: Vec<&str> = waveform.scopes[0]
+            .variables
+            .iter()
+            .map(|signal| signal.name.as_str())
+            .collect();
+        assert_eq!(names, ["v(out)", "i(v1)"]);
+        assert_eq!(
+            waveform.transitions["rc|v(out)"],
+            [(0, "0"), (1_500_000, "0.5"), (4_000_000, "0.75")]
+                .map(|(time, value)| (time, value.to_string()))
+        );
+        assert_eq!(
+            waveform.transitions["rc|i(v1)"],
+            [(0, "0.001"), (4_000_000, "0.0005")].map(|(time, value)| (time, value.to_string()))
+        );
+    }
+
+    #[test]
+    fn ltspice_complex_rawfile_splits_magnitude_and_phase() {
+        let header = "Title: ac\nPlotname: AC Analysis\nFlags: complex forward log\n\
+                      No. Variables: 2\nNo. Points: 2\nVariables:\n\t0\tfrequency\tfrequency\n\
+                      \t1\tV(out)\tvoltage\nBinary:\n";
+        let mut raw: Vec<u8> = header.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        for value in [1.0, 0.0, 1.0, 0.0, 1e6, 0.0, 0.0, 1.0f64] {
+            raw.extend(value.to_le_bytes());
+        }
+        // The frequency axis counts millihertz, shown as milliseconds
+        let waveform = parse_spice_raw(&raw, "ac").unwrap();
+        assert_eq!(waveform.timescale_factor, 1e-3);
+        assert_eq!(
+            waveform.transitions["ac|V(out)"],
+            [(1_000, "1".to_string())]
+        );
+        assert_eq!(
+            waveform.transitions["ac|V(out)_phase"],
+            [(1_000, "0"), (1_000_000_000, "90")].map(|(time, value)| (time, value.to_string()))
+        );
+
+        // A GHz point would overflow the picosecond timeline
+        let last_frequency = raw.len() - 4 * 8;
+        raw[last_frequency..last_frequency + 8].copy_from_slice(&1e9f64.to_le_bytes());
+        let error = parse_spice_raw(&raw, "ac").err().unwrap();
+        assert!(error.contains("AC sweep reaches 1000000000 Hz"), "{error}");
+    }
+
+    #[test]
+    fn csv_fields_unescape_doubled_quotes() {
+        assert_eq!(
+            csv_fields(r#"1.5,"say ""hi"", then go",,"""""#, ','),
+            ["1.5", r#"say "hi", then go"#, "", r#"""#]
+        );
+    }
+
+    #[test]
+    fn saleae_csv_shifts_pre_trigger_samples_to_zero() {
+        let csv = "Time [s],Channel 0,Channel 1\n-0.000001,0,1\n0,1,1\n0.0000025,1,0\n";
+        let waveform = parse_logic_csv(csv, "capture", &CsvImportSettings::default()).unwrap();
+        assert_eq!(waveform.end_time, 3_500_000);
+        assert_eq!(
+            waveform.transitions["capture|Channel_0"],
+            [(0, "0"), (1_000_000, "1")].map(|(time, value)| (time, value.to_string()))
+        );
+        assert_eq!(
+            waveform.transitions["capture|Channel_1"],
+            [(0, "1"), (3_500_000, "0")].map(|(time, value)| (time, value.to_string()))
+        );
+    }
+
+    #[test]
+    fn sigrok_csv_groups_channels_into_buses() {
+        let csv = "; CSV generated by libsigrok\n; Samplerate: 1 MHz\n\
+                   Time [us],D0,D1,D2,data\n0,0,0,1,0x1f\n1,1,0,1,0x20\n2,1,1,0,0x20\n";
+        let mut settings = CsvImportSettings {
+            buses: vec![shared::CsvBusGroup {
+                name: "nibble".to_string(),
+                channels: vec!["D2".to_string(), "D1".to_string(), "D0".to_string()],
+            }],
+            ..CsvImportSettings::default()
+        };
+        let waveform = parse_logic_csv(csv, "la", &settings).unwrap();
+        let signals: Vec<(&str, u32)> = waveform.scopes[0]
+            .variables
+            .iter()
+            .map(|signal| (signal.name.as_str(), signal.width))
+            .collect();
+        assert_eq!(signals, [("data", 6), ("nibble", 3)]);
+        assert_eq!(
+            waveform.transitions["la|nibble"],
+            [(0, "100"), (1_000_000, "101"), (2_000_000, "011")]
+                .map(|(time, value)| (time, value.to_string()))
+        );
+        assert_eq!(
+            waveform.transitions["la|data"],
+            [(0, "011111"), (1_000_000, "100000")].map(|(time, value)| (time, value.to_string()))
+        );
+
+        settings.buses[0].channels.push("data".to_string());
+        assert!(parse_logic_csv(csv, "la", &settings).is_err());
+    }
+}
@@ -238,6 +238,21 @@ impl ConnectionMessageActor {
                             }
                             // FileLoaded, ParsingError, ParsingStarted are handled directly
                             // in the Connection callback via tf.update_file_state()
+                            DownMsg::FileLoaded { file_id, hierarchy } => {
+                                // Only the recovery warning for truncated files is raised here
+                                for file in hierarchy.files {
+                                    if let Some(truncated_ns) = file.truncated_at_ns {
+                                        use crate::visualizer::timeline::time_domain::TimePs;
+                                        let alert =
+                                            crate::error_display::ErrorAlert::new_file_truncated(
+                                                file_id.clone(),
+                                                file.filename,
+                                                TimePs::from_nanos(truncated_ns).to_string(),
+                                            );
+                                        crate::error_display::add_error_alert(alert, &config);
+                                    }
+                                }
+                            }
                             DownMsg::BatchSignalValues { file_results, .. } => {
                                 let mut values = Vec::new();
 
@@ -270,6 +285,7 @@ impl ConnectionMessageActor {
                                 request_id,
                                 signal_data,
                                 cursor_values,
+                                payload_encodings,
                                 ..
                             } => {
                                 zoon::println!(
@@ -277,6 +293,7 @@ impl ConnectionMessageActor {
                                     signal_data.len(),
                                     cursor_values.len()
                                 );
+                                waveform_timeline.negotiate_payload_encoding(&payload_encodings);
                                 waveform_timeline.apply_unified_signal_response(
                                     &request_id,
                                     signal_data,
@@ -296,6 +313,166 @@ impl ConnectionMessageActor {
                                     tracked_files_for_reload.load_new_paths(file_paths);
                                 }
                             }
+                            DownMsg::LoadProgress { file_id, progress } => {
+                                tracked_files.apply_load_progress(file_id, progress);
+                            }
+                            DownMsg::LoadCancelled { file_id } => {
+                                zoon::println!("frontend: load of '{file_id}' cancelled");
+                            }
+                            DownMsg::FileUnloaded {
+                                file_id,
+                                freed_bytes,
+                                body_released,
+                            } => {
+                                crate::error_display::add_error_alert(
+                                    crate::error_display::ErrorAlert::new_file_unloaded(
+                                        file_id,
+                                        freed_bytes,
+                                        body_released,
+                                    ),
+                                    &config,
+                                );
+                            }
+                            DownMsg::ExportProgress {
+                                export_id,
+                                progress,
+                            } => {
+                                config.error_display.update_toast(
+                                    crate::error_display::ErrorAlert::new_export_progress(
+                                        export_id, progress,
+                                    ),
+                                );
+                            }
+                            DownMsg::ExportCompleted {
+                                export_id,
+                                output_path,
+                                signal_count,
+                                bytes_written,
+                                clipboard_text,
+                            } => {
+                                config.error_display.dismiss_toast(&export_id);
+                                let alert = match clipboard_text {
+                                    Some(text) => {
+                                        crate::clipboard::copy_to_clipboard(text, &config);
+                                        crate::error_display::ErrorAlert::new_export_copied(
+                                            signal_count,
+                                            bytes_written,
+                                        )
+                                    }
+                                    None => crate::error_display::ErrorAlert::new_export_completed(
+                                        output_path,
+                                        signal_count,
+                                        bytes_written,
+                                    ),
+                                };
+                                crate::error_display::add_error_alert(alert, &config);
+                            }
+                            DownMsg::ExportFailed { export_id, error } => {
+                                config.error_display.dismiss_toast(&export_id);
+                                crate::error_display::add_error_alert(
+                                    crate::error_display::ErrorAlert::new_export_failed(
+                                        export_id, error,
+                                    ),
+                                    &config,
+                                );
+                            }
+                            DownMsg::WaveformDiffProgress { diff_id, progress } => {
+                                config.waveform_diff.apply_progress(&diff_id, progress);
+                            }
+                            DownMsg::WaveformDiffCompleted { diff_id, summary } => {
+                                config.waveform_diff.apply_summary(
+                                    &diff_id,
+                                    summary,
+                                    &waveform_timeline,
+                                    &config,
+                                );
+                            }
+                            DownMsg::WaveformDiffFailed { diff_id, error } => {
+                                config.waveform_diff.apply_failure(&diff_id, error);
+                            }
+                            DownMsg::DerivedSignalDefined { name } => {
+                                config.derived_signals.apply_defined(
+                                    &name,
+                                    &selected_variables,
+                                    &waveform_timeline,
+                                    &config,
+                                );
+                            }
+                            DownMsg::DerivedSignalFailed { name, error } => {
+                                config.derived_signals.apply_failure(&name, error);
+                            }
+                            DownMsg::ConditionSearchCompleted {
+                                search_id,
+                                matches,
+                                truncated,
+                            } => {
+                                config.condition_search.apply_matches(
+                                    &search_id,
+                                    matches,
+                                    truncated,
+                                    &waveform_timeline,
+                                );
+                            }
+                            DownMsg::ConditionSearchFailed { search_id, error } => {
+                                config.condition_search.apply_failure(&search_id, error);
+                            }
+                            DownMsg::ValueSearchCompleted {
+                                search_id,
+                                match_count,
+                                intervals,
+                            } => {
+                                config.value_search.apply_matches(
+                                    &search_id,
+                                    match_count,
+                                    intervals,
+                                    &waveform_timeline,
+                                );
+                            }
+                            DownMsg::ValueSearchFailed { search_id, error } => {
+                                config.value_search.apply_failure(&search_id, error);
+                            }
+                            DownMsg::SignalMeasured {
+                                measurement_id,
+                                measurement,
+                            } => {
+                                config
+                                    .signal_measurements
+                                    .apply_measurement(&measurement_id, measurement);
+                            }
+                            DownMsg::SignalMeasurementFailed {
+                                measurement_id,
+                                error,
+                            } => {
+                                config
+                                    .signal_measurements
+                                    .apply_failure(&measurement_id, error);
+                            }
+                            DownMsg::SignalDifferencesCompleted {
+                                unique_id,
+                                compare_unique_id,
+                                range_ps,
+                                intervals,
+                            } => {
+                                waveform_timeline.apply_signal_differences(
+                                    &unique_id,
+                                    &compare_unique_id,
+                                    range_ps,
+                                    Ok(intervals),
+                                );
+                            }
+                            DownMsg::SignalDifferencesFailed {
+                                unique_id,
+                                compare_unique_id,
+                                range_ps,
+                                error,
+                            } => {
+                                waveform_timeline.apply_signal_differences(
+                                    &unique_id,
+                                    &compare_unique_id,
+                                    range_ps,
+                                    Err(error),
+                                );
+                            }
                             DownMsg::TestNotification {
                                 variant,
                                 title,
@@ -886,6 +1063,13 @@ impl NovyWaveApp {
                                             .set(Some(timeline_for_keydown.marker_target_time()));
                                         marker_dialog_visible_for_keydown.set(true);
                                     }
+                                    // Second cursor
+                                    "b" | "B" => {
+                                        event.prevent_default();
+                                        let time = (!event.shift_key())
+                                            .then(|| timeline_for_keydown.cursor_actor().get());
+                                        timeline_for_keydown.set_secondary_cursor(time);
+                                    }
                                     "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" => {
                                         let digit: usize = event.key().parse().unwrap_or(1);
                                         timeline_for_keydown.jump_to_marker(digit - 1);
@@ -0,0 +1,160 @@
+//! Conditional search (`UpMsg::SearchCondition`): jump to the next or previous time where
+//! an expression over the selected variables starts to hold, or list every such time.
+//!
+//! The backend evaluates the expression over the whole files, so matches outside the
+//! loaded viewport are found too. Listed matches can be turned into timeline markers; new
+//! markers replace those of the previous search.
+
+use crate::pending_request::PendingRequest;
+use crate::visualizer::timeline::time_domain::TimePs;
+use shared::{ConditionSearchRequest, SearchDirection};
+use zoon::*;
+
+/// Name prefix of the markers placed at matches
+const MATCH_MARKER_PREFIX: &str = "⌕ ";
+
+/// Markers go to the first matches only; the dialog still lists all of them
+const MAX_MATCH_MARKERS: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchMatches {
+    pub expression: String,
+    pub times_ps: Vec<u64>,
+    pub truncated: bool,
+}
+
+#[derive(Clone)]
+pub struct ConditionSearch {
+    pub dialog_visible: Mutable<bool>,
+    pub expression_input: Mutable<String>,
+    /// Search in flight, with its direction
+    pub request: PendingRequest<SearchDirection>,
+    /// Result of the last search for all matches
+    pub matches: Mutable<Option<SearchMatches>>,
+}
+
+impl ConditionSearch {
+    pub fn new() -> Self {
+        Self {
+            dialog_visible: Mutable::new(false),
+            expression_input: Mutable::new(String::new()),
+            request: PendingRequest::new(),
+            matches: Mutable::new(None),
+        }
+    }
+
+    pub fn open(&self) {
+        self.request.error_message.set(None);
+        self.dialog_visible.set(true);
+    }
+
+    pub fn start(
+        &self,
+        direction: SearchDirection,
+        selected_variables: &crate::selected_variables::SelectedVariables,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+    ) {
+        let expression = self.expression_input.get_cloned().trim().to_string();
+        let candidates = crate::derived_signals::operand_candidates(selected_variables);
+        let inputs = match crate::derived_signals::bind_operands(&expression, &candidates) {
+            Ok(inputs) => inputs,
+            Err(error) => {
+                self.request.error_message.set(Some(error));
+                return;
+            }
+        };
+
+        let from_ps = timeline.cursor_actor().get().picoseconds();
+        self.request.send("search", direction, |search_id| {
+            shared::UpMsg::SearchCondition(ConditionSearchRequest {
+                search_id,
+                expression,
+                inputs,
+                from_ps,
+                direction,
+            })
+        });
+    }
+
+    pub fn apply_matches(
+        &self,
+        search_id: &str,
+        times_ps: Vec<u64>,
+        truncated: bool,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+    ) {
+        let Some(direction) = self.request.finish(search_id) else {
+            return;
+        };
+        match direction {
+            SearchDirection::All => self.matches.set(Some(SearchMatches {
+                expression: self.expression_input.get_cloned().trim().to_string(),
+                times_ps,
+                truncated,
+            })),
+            SearchDirection::Next | SearchDirection::Previous => match times_ps.first() {
+                Some(&time_ps) => timeline.jump_to_time(TimePs::from_picoseconds(time_ps)),
+                None => self.request.error_message.set(Some(
+                    if direction == SearchDirection::Next {
+                        "No match after the cursor."
+                    } else {
+                        "No match before the cursor."
+                    }
+                    .to_string(),
+                )),
+            },
+        }
+    }
+
+    pub fn apply_failure(&self, search_id: &str, error: String) {
+        self.request.fail(search_id, error);
+    }
+
+    /// Replace the markers of the previous search with one per listed match
+    pub fn add_markers(
+        &self,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+        app_config: &crate::config::AppConfig,
+    ) {
+        let Some(matches) = self.matches.get_cloned() else {
+            return;
+        };
+        app_config.replace_prefixed_markers(timeline, MATCH_MARKER_PREFIX, match_markers(&matches));
+    }
+}
+
+fn match_markers(matches: &SearchMatches) -> Vec<shared::MarkerConfig> {
+    matches
+        .times_ps
+        .iter()
+        .take(MAX_MATCH_MARKERS)
+        .enumerate()
+        .map(|(index, &time_ps)| shared::MarkerConfig {
+            time_ps,
+            name: format!(
+                "{}{} #{}",
+                MATCH_MARKER_PREFIX,
+                matches.expression,
+                index + 1
+            ),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_MATCH_MARKERS, SearchMatches, match_markers};
+
+    #[test]
+    fn only_the_earliest_matches_get_markers() {
+        let matches = SearchMatches {
+            expression: "irq becomes 1".to_string(),
+            times_ps: (0..250).map(|index| index * 10).collect(),
+            truncated: false,
+        };
+        let markers = match_markers(&matches);
+        assert_eq!(markers.len(), MAX_MATCH_MARKERS);
+        assert_eq!(markers[0].name, "⌕ irq becomes 1 #1");
+        assert_eq!(markers[1].time_ps, 10);
+    }
+}
@@ -7,6 +7,7 @@ use shared::UpMsg;
 use shared::{
     self, AppConfig as SharedAppConfig, CanonicalPathPayload, DockMode, Theme as SharedTheme,
 };
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use zoon::*;
 
@@ -39,6 +40,10 @@ pub(crate) fn compose_shared_app_config(
     variables_search_filter: &Mutable<String>,
     markers_config: &Mutable<Vec<shared::MarkerConfig>>,
     signal_groups_config: &Mutable<Vec<shared::SignalGroupConfig>>,
+    csv_imports_config: &Mutable<BTreeMap<String, shared::CsvImportSettings>>,
+    file_time_alignments_config: &Mutable<BTreeMap<String, shared::FileTimeAlignment>>,
+    diff_path_remaps_config: &Mutable<Vec<shared::PathRemap>>,
+    derived_signals_config: &Mutable<Vec<shared::DerivedSignal>>,
 ) -> Option<shared::AppConfig> {
     let theme = theme.get();
     let dock_mode = dock_mode.get_cloned();
@@ -82,6 +87,9 @@ pub(crate) fn compose_shared_app_config(
 
     let timeline_config = shared::TimelineConfig {
         cursor_position_ps,
+        secondary_cursor_ps: timeline_state
+            .secondary_cursor
+            .map(|time| time.picoseconds()),
         visible_range_start_ps: visible_start_ps,
         visible_range_end_ps: visible_end_ps,
         zoom_center_ps,
@@ -105,6 +113,7 @@ pub(crate) fn compose_shared_app_config(
         app: shared::AppSection::default(),
         workspace: shared::WorkspaceSection {
             opened_files,
+            file_time_alignments: file_time_alignments_config.get_cloned(),
             docked_bottom_dimensions: shared::DockedBottomDimensions {
                 files_and_scopes_panel_width: files_width_bottom as f64,
                 files_and_scopes_panel_height: files_height_bottom as f64,
@@ -126,6 +135,9 @@ pub(crate) fn compose_shared_app_config(
             selected_variables: selected_variables_snapshot,
             signal_groups: signal_groups_config.get_cloned(),
             timeline: timeline_config,
+            csv_imports: csv_imports_config.get_cloned(),
+            diff_path_remaps: diff_path_remaps_config.get_cloned(),
+            derived_signals: derived_signals_config.get_cloned(),
             ..shared::WorkspaceSection::default()
         },
         ui: shared::UiSection {
@@ -140,6 +152,7 @@ pub(crate) fn compose_shared_app_config(
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct TimelineState {
     pub cursor_position: Option<TimePs>,
+    pub secondary_cursor: Option<TimePs>,
     pub visible_range: Option<TimeRange>,
     pub zoom_center: Option<TimePs>,
     pub tooltip_enabled: bool,
@@ -168,6 +181,7 @@ impl Default for TimelineState {
     fn default() -> Self {
         Self {
             cursor_position: None,
+            secondary_cursor: None,
             visible_range: None,
             zoom_center: None,
             tooltip_enabled: true,
@@ -526,6 +540,19 @@ pub struct AppConfig {
 
     pub markers_config: Mutable<Vec<shared::MarkerConfig>>,
     pub signal_groups_config: Mutable<Vec<shared::SignalGroupConfig>>,
+    /// Import settings of logic-analyzer CSV files by canonical path
+    pub csv_imports_config: Mutable<BTreeMap<String, shared::CsvImportSettings>>,
+    /// Time offsets and timescale overrides of files by canonical path
+    pub file_time_alignments_config: Mutable<BTreeMap<String, shared::FileTimeAlignment>>,
+    /// Scope path remaps of the last file comparison
+    pub diff_path_remaps_config: Mutable<Vec<shared::PathRemap>>,
+    pub waveform_diff: crate::waveform_diff::WaveformDiff,
+    /// Definitions of the derived signals in the workspace
+    pub derived_signals_config: Mutable<Vec<shared::DerivedSignal>>,
+    pub derived_signals: crate::derived_signals::DerivedSignals,
+    pub condition_search: crate::condition_search::ConditionSearch,
+    pub value_search: crate::value_search::ValueSearch,
+    pub signal_measurements: crate::signal_measurement::SignalMeasurements,
 
     // Task handles to keep processors alive
     _config_save_debouncer_task: Arc<TaskHandle>,
@@ -596,6 +623,24 @@ impl AppConfig {
         let _ = self.config_save_request_sender.unbounded_send(());
     }
 
+    /// Swap the timeline markers whose names start with `prefix` for `markers` and save
+    pub fn replace_prefixed_markers(
+        &self,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+        prefix: &str,
+        markers: Vec<shared::MarkerConfig>,
+    ) {
+        let mut kept: Vec<shared::MarkerConfig> = timeline
+            .markers_as_config()
+            .into_iter()
+            .filter(|marker| !marker.name.starts_with(prefix))
+            .collect();
+        kept.extend(markers);
+        timeline.restore_markers(kept);
+        self.markers_config.set(timeline.markers_as_config());
+        self.request_save();
+    }
+
     pub fn is_config_loaded(&self) -> bool {
         self.config_loaded_flag.get()
     }
@@ -628,6 +673,10 @@ impl AppConfig {
             &selected_variables.search_filter,
             &self.markers_config,
             &self.signal_groups_config,
+            &self.csv_imports_config,
+            &self.file_time_alignments_config,
+            &self.diff_path_remaps_config,
+            &self.derived_signals_config,
         )
     }
 
@@ -905,6 +954,11 @@ impl AppConfig {
 
         let markers_config = Mutable::new(Vec::<shared::MarkerConfig>::new());
         let signal_groups_config = Mutable::new(Vec::<shared::SignalGroupConfig>::new());
+        let csv_imports_config = Mutable::new(BTreeMap::<String, shared::CsvImportSettings>::new());
+        let file_time_alignments_config =
+            Mutable::new(BTreeMap::<String, shared::FileTimeAlignment>::new());
+        let diff_path_remaps_config = Mutable::new(Vec::<shared::PathRemap>::new());
+        let derived_signals_config = Mutable::new(Vec::<shared::DerivedSignal>::new());
         let (config_save_request_sender, config_save_request_receiver) =
             futures::channel::mpsc::unbounded::<()>();
 
@@ -937,6 +991,10 @@ impl AppConfig {
             let variables_search_filter_clone = selected_variables.search_filter.clone();
             let markers_config_clone = markers_config.clone();
             let signal_groups_config_clone = signal_groups_config.clone();
+            let csv_imports_config_clone = csv_imports_config.clone();
+            let file_time_alignments_config_clone = file_time_alignments_config.clone();
+            let diff_path_remaps_config_clone = diff_path_remaps_config.clone();
+            let derived_signals_config_clone = derived_signals_config.clone();
             let debug_metrics_clone = debug_metrics.clone();
             let last_saved_config_clone = last_saved_config.clone();
 
@@ -967,6 +1025,10 @@ impl AppConfig {
             let picker_expanded_for_signal = file_picker_domain.expanded_directories.clone();
             let markers_for_signal = markers_config.clone();
             let signal_groups_for_signal = signal_groups_config.clone();
+            let csv_imports_for_signal = csv_imports_config.clone();
+            let file_time_alignments_for_signal = file_time_alignments_config.clone();
+            let diff_path_remaps_for_signal = diff_path_remaps_config.clone();
+            let derived_signals_for_signal = derived_signals_config.clone();
 
             Arc::new(Task::start_droppable(async move {
                 // Combine all config-relevant signals into one trigger signal
@@ -996,7 +1058,11 @@ impl AppConfig {
                     let _ = picker_scroll_for_signal.signal(),
                     let _ = picker_expanded_for_signal.signal_cloned(),
                     let _ = markers_for_signal.signal_cloned(),
-                    let _ = signal_groups_for_signal.signal_cloned()
+                    let _ = signal_groups_for_signal.signal_cloned(),
+                    let _ = csv_imports_for_signal.signal_cloned(),
+                    let _ = file_time_alignments_for_signal.signal_cloned(),
+                    let _ = diff_path_remaps_for_signal.signal_cloned(),
+                    let _ = derived_signals_for_signal.signal_cloned()
                     => ()
                 };
 
@@ -1061,6 +1127,10 @@ impl AppConfig {
                                                     &variables_search_filter_clone,
                                                     &markers_config_clone,
                                                     &signal_groups_config_clone,
+                                                    &csv_imports_config_clone,
+                                                    &file_time_alignments_config_clone,
+                                                    &diff_path_remaps_config_clone,
+                                                    &derived_signals_config_clone,
                                                 ) {
                                                     let should_send = {
                                                         let last_saved = last_saved_config_clone.get_cloned();
@@ -1138,6 +1208,10 @@ impl AppConfig {
                                                     &variables_search_filter_clone,
                                                     &markers_config_clone,
                                                     &signal_groups_config_clone,
+                                                    &csv_imports_config_clone,
+                                                    &file_time_alignments_config_clone,
+                                                    &diff_path_remaps_config_clone,
+                                                    &derived_signals_config_clone,
                                                 ) {
                                                     let should_send = {
                                                         let last_saved = last_saved_config_clone.get_cloned();
@@ -1260,6 +1334,15 @@ impl AppConfig {
             last_saved_config,
             markers_config,
             signal_groups_config,
+            csv_imports_config,
+            file_time_alignments_config,
+            diff_path_remaps_config,
+            waveform_diff: crate::waveform_diff::WaveformDiff::new(),
+            derived_signals_config,
+            derived_signals: crate::derived_signals::DerivedSignals::new(),
+            condition_search: crate::condition_search::ConditionSearch::new(),
+            value_search: crate::value_search::ValueSearch::new(),
+            signal_measurements: crate::signal_measurement::SignalMeasurements::new(),
             _config_save_debouncer_task,
             _workspace_history_task,
             _selected_variables_snapshot_task,
@@ -1520,6 +1603,9 @@ impl AppConfig {
 
         let timeline_state = TimelineState {
             cursor_position: Some(TimePs::from_picoseconds(cursor_position_ps)),
+            secondary_cursor: timeline_cfg
+                .secondary_cursor_ps
+                .map(TimePs::from_picoseconds),
             visible_range: Some(visible_range),
             zoom_center: Some(TimePs::from_picoseconds(zoom_center_ps)),
             tooltip_enabled: timeline_cfg.tooltip_enabled,
@@ -1612,6 +1698,15 @@ impl AppConfig {
             self.file_picker_domain.request_directory_browse(dir);
         }
 
+        self.csv_imports_config
+            .set(loaded_config.workspace.csv_imports.clone());
+        self.file_time_alignments_config
+            .set(loaded_config.workspace.file_time_alignments.clone());
+        self.diff_path_remaps_config
+            .set(loaded_config.workspace.diff_path_remaps.clone());
+        self.derived_signals_config
+            .set(loaded_config.workspace.derived_signals.clone());
+
         // Send file load requests directly after browse replay is queued.
         if opened_files.is_empty() {
             tracked_files.clear_all_files();
@@ -1678,6 +1773,10 @@ impl AppConfig {
             &selected_variables.search_filter,
             &self.markers_config,
             &self.signal_groups_config,
+            &self.csv_imports_config,
+            &self.file_time_alignments_config,
+            &self.diff_path_remaps_config,
+            &self.derived_signals_config,
         ) {
             self.last_saved_config.set_neq(Some(config));
         }
@@ -0,0 +1,183 @@
+//! Derived signals (`UpMsg::DefineDerivedSignal`): expressions over selected variables.
+//!
+//! Operands are resolved to selected variables here, by the tail of their path, and the
+//! backend evaluates the expression into a signal of `shared::DERIVED_SIGNALS_FILE`. The
+//! derived variable is added once the backend accepts the definition.
+
+use crate::selected_variables::SelectedVariables;
+use shared::DerivedSignal;
+use std::collections::BTreeMap;
+use zoon::*;
+
+#[derive(Clone)]
+pub struct DerivedSignals {
+    pub dialog_visible: Mutable<bool>,
+    pub name_input: Mutable<String>,
+    pub expression_input: Mutable<String>,
+    /// Definition waiting for the backend's verdict
+    pub pending: Mutable<Option<DerivedSignal>>,
+    pub error_message: Mutable<Option<String>>,
+}
+
+impl DerivedSignals {
+    pub fn new() -> Self {
+        Self {
+            dialog_visible: Mutable::new(false),
+            name_input: Mutable::new(String::new()),
+            expression_input: Mutable::new(String::new()),
+            pending: Mutable::new(None),
+            error_message: Mutable::new(None),
+        }
+    }
+
+    pub fn open(&self) {
+        self.error_message.set(None);
+        self.dialog_visible.set(true);
+    }
+
+    pub fn define(&self, selected_variables: &SelectedVariables) {
+        let name = self.name_input.get_cloned().trim().to_string();
+        if name.is_empty() {
+            self.error_message
+                .set(Some("Give the signal a name.".to_string()));
+            return;
+        }
+        let selected_ids = operand_candidates(selected_variables);
+        let signal = match bind_inputs(name, self.expression_input.get_cloned(), &selected_ids) {
+            Ok(signal) => signal,
+            Err(error) => {
+                self.error_message.set(Some(error));
+                return;
+            }
+        };
+        self.error_message.set(None);
+        self.pending.set(Some(signal.clone()));
+        Task::start(send_define_request(signal));
+    }
+
+    fn take_pending(&self, name: &str) -> Option<DerivedSignal> {
+        let mut pending = self.pending.lock_mut();
+        if pending.as_ref().is_some_and(|signal| signal.name == name) {
+            pending.take()
+        } else {
+            None
+        }
+    }
+
+    pub fn apply_defined(
+        &self,
+        name: &str,
+        selected_variables: &SelectedVariables,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+        app_config: &crate::config::AppConfig,
+    ) {
+        let Some(signal) = self.take_pending(name) else {
+            return;
+        };
+        let unique_id = signal.unique_id();
+        let selected = selected_variables.variables_vec_actor.get_cloned();
+        let is_selected = selected
+            .iter()
+            .any(|variable| variable.unique_id == unique_id);
+
+        // Definitions of removed derived variables are dropped along the way
+        app_config.derived_signals_config.update_mut(|signals| {
+            signals.retain(|existing| {
+                existing.name != signal.name
+                    && selected
+                        .iter()
+                        .any(|variable| variable.unique_id == existing.unique_id())
+            });
+            signals.push(signal);
+        });
+        app_config.request_save();
+
+        if is_selected {
+            timeline.refresh_variable_data(&unique_id);
+        } else {
+            selected_variables.add_variable(unique_id);
+        }
+        self.dialog_visible.set(false);
+    }
+
+    pub fn apply_failure(&self, name: &str, error: String) {
+        if self.take_pending(name).is_some() {
+            self.error_message.set(Some(error));
+        }
+    }
+}
+
+/// Selected variables an expression may name; derived signals are not among them
+pub fn operand_candidates(selected_variables: &SelectedVariables) -> Vec<String> {
+    selected_variables
+        .variables_vec_actor
+        .get_cloned()
+        .into_iter()
+        .map(|variable| variable.unique_id)
+        .filter(|unique_id| shared::derived_signal_name(unique_id).is_none())
+        .collect()
+}
+
+/// Parse the expression and bind each operand to one of the selected variables
+fn bind_inputs(
+    name: String,
+    expression: String,
+    selected_ids: &[String],
+) -> Result<DerivedSignal, String> {
+    let expression = expression.trim().to_string();
+    let inputs = bind_operands(&expression, selected_ids)?;
+    Ok(DerivedSignal {
+        name,
+        expression,
+        inputs,
+    })
+}
+
+/// Parse an expression and bind each operand to one of the candidate variables
+pub fn bind_operands(
+    expression: &str,
+    candidates: &[String],
+) -> Result<BTreeMap<String, String>, String> {
+    shared::expression::parse(expression)?
+        .signals()
+        .into_iter()
+        .map(|operand| {
+            let unique_id = shared::expression::resolve_signal(&operand, candidates)?;
+            Ok((operand, unique_id))
+        })
+        .collect()
+}
+
+async fn send_define_request(signal: DerivedSignal) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    if let Err(e) = CurrentPlatform::send_message(shared::UpMsg::DefineDerivedSignal(signal)).await
+    {
+        zoon::eprintln!("🚨 Derived signals: Failed to send definition: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bind_inputs;
+
+    #[test]
+    fn operands_bind_to_selected_variables_by_path_tail() {
+        let selected_ids = vec![
+            "/runs/a.vcd|tb.dut|valid".to_string(),
+            "/runs/a.vcd|tb.dut|ready".to_string(),
+            "/runs/a.vcd|tb.mon|ready".to_string(),
+        ];
+        let signal = bind_inputs(
+            "fire".to_string(),
+            " valid && dut.ready ".to_string(),
+            &selected_ids,
+        )
+        .unwrap();
+        assert_eq!(signal.expression, "valid && dut.ready");
+        assert_eq!(signal.inputs["valid"], "/runs/a.vcd|tb.dut|valid");
+        assert_eq!(signal.inputs["dut.ready"], "/runs/a.vcd|tb.dut|ready");
+        assert!(bind_inputs("x".to_string(), "ready".to_string(), &selected_ids).is_err());
+        assert!(bind_inputs("x".to_string(), "grant".to_string(), &selected_ids).is_err());
+    }
+}
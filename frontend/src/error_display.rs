@@ -5,6 +5,7 @@ use zoon::*;
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum NotificationVariant {
     Error,
+    Warning,
     Info,
     Success,
 }
@@ -83,6 +84,120 @@ impl ErrorAlert {
         }
     }
 
+    pub fn new_file_truncated(file_id: String, filename: String, truncated_at: String) -> Self {
+        Self {
+            id: format!("file_truncated_{}", file_id),
+            title: "Truncated Waveform".to_string(),
+            message: format!(
+                "{} ends mid-line; showing data up to t={}",
+                filename, truncated_at
+            ),
+            technical_error: format!("File {} truncated at t={}", file_id, truncated_at),
+            auto_dismiss_ms: 5000,
+            variant: NotificationVariant::Warning,
+            action_label: None,
+            progress: None,
+        }
+    }
+
+    pub fn new_file_unloaded(file_id: String, freed_bytes: u64, body_released: bool) -> Self {
+        let filename = file_id
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(&file_id)
+            .to_string();
+        let freed = shared::format_byte_size(freed_bytes);
+        let message = if body_released {
+            format!("{}: released {} of waveform data", filename, freed)
+        } else {
+            format!("{}: released {} of cached signal data", filename, freed)
+        };
+        Self {
+            id: format!("file_unloaded_{}", file_id),
+            title: "File Unloaded".to_string(),
+            message,
+            technical_error: format!(
+                "Unloaded {} (freed ~{} bytes, body_released={})",
+                file_id, freed_bytes, body_released
+            ),
+            auto_dismiss_ms: 5000,
+            variant: NotificationVariant::Success,
+            action_label: None,
+            progress: None,
+        }
+    }
+
+    pub fn new_export_progress(export_id: String, progress: f32) -> Self {
+        Self {
+            id: export_id.clone(),
+            title: "Exporting Signals".to_string(),
+            message: format!("{:.0}% written", progress * 100.0),
+            technical_error: format!("Export {} at {:.1}%", export_id, progress * 100.0),
+            auto_dismiss_ms: 0,
+            variant: NotificationVariant::Info,
+            action_label: None,
+            progress: None,
+        }
+    }
+
+    pub fn new_export_completed(
+        output_path: String,
+        signal_count: usize,
+        bytes_written: u64,
+    ) -> Self {
+        Self {
+            id: format!("export_done_{}", js_sys::Date::now() as u64),
+            title: "Export Finished".to_string(),
+            message: format!(
+                "{} signals written to {} ({})",
+                signal_count,
+                output_path,
+                shared::format_byte_size(bytes_written)
+            ),
+            technical_error: format!(
+                "Exported {} signals to {} ({} bytes)",
+                signal_count, output_path, bytes_written
+            ),
+            auto_dismiss_ms: 5000,
+            variant: NotificationVariant::Success,
+            action_label: None,
+            progress: None,
+        }
+    }
+
+    pub fn new_export_copied(signal_count: usize, bytes_written: u64) -> Self {
+        Self {
+            id: format!("export_copied_{}", js_sys::Date::now() as u64),
+            title: "Export Copied".to_string(),
+            message: format!(
+                "{} signals copied to the clipboard ({})",
+                signal_count,
+                shared::format_byte_size(bytes_written)
+            ),
+            technical_error: format!(
+                "Copied {} signals to the clipboard ({} bytes)",
+                signal_count, bytes_written
+            ),
+            auto_dismiss_ms: 5000,
+            variant: NotificationVariant::Success,
+            action_label: None,
+            progress: None,
+        }
+    }
+
+    pub fn new_export_failed(export_id: String, error: String) -> Self {
+        Self {
+            id: format!("export_error_{}", export_id),
+            title: "Export Failed".to_string(),
+            message: make_error_user_friendly(&error),
+            technical_error: format!("Export {} failed: {}", export_id, error),
+            auto_dismiss_ms: 5000,
+            variant: NotificationVariant::Error,
+            action_label: None,
+            progress: None,
+        }
+    }
+
     pub fn new_update_available(
         current_version: String,
         new_version: String,
@@ -9,6 +9,7 @@ use zoon::*;
 fn variant_icon(variant: NotificationVariant) -> IconName {
     match variant {
         NotificationVariant::Error => IconName::TriangleAlert,
+        NotificationVariant::Warning => IconName::TriangleAlert,
         NotificationVariant::Info => IconName::Info,
         NotificationVariant::Success => IconName::CircleCheck,
     }
@@ -17,6 +18,7 @@ fn variant_icon(variant: NotificationVariant) -> IconName {
 fn variant_icon_color(variant: NotificationVariant) -> IconColor {
     match variant {
         NotificationVariant::Error => IconColor::Error,
+        NotificationVariant::Warning => IconColor::Custom("oklch(75% 0.15 75)"),
         NotificationVariant::Info => IconColor::Primary,
         NotificationVariant::Success => IconColor::Success,
     }
@@ -36,6 +38,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(12% 0.03 30)",
+            NotificationVariant::Warning => "oklch(14% 0.03 75)",
             NotificationVariant::Info => "oklch(20% 0.01 250)",
             NotificationVariant::Success => "oklch(12% 0.03 145)",
         })
@@ -45,6 +48,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(70% 0.21 30)",
+            NotificationVariant::Warning => "oklch(75% 0.15 75)",
             NotificationVariant::Info => "oklch(65% 0.16 250)",
             NotificationVariant::Success => "oklch(70% 0.15 145)",
         })
@@ -54,6 +58,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(88% 0.18 30)",
+            NotificationVariant::Warning => "oklch(88% 0.14 75)",
             NotificationVariant::Info => "oklch(85% 0.14 250)",
             NotificationVariant::Success => "oklch(88% 0.13 145)",
         })
@@ -63,6 +68,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(80% 0.21 30)",
+            NotificationVariant::Warning => "oklch(80% 0.15 75)",
             NotificationVariant::Info => "oklch(75% 0.16 250)",
             NotificationVariant::Success => "oklch(80% 0.15 145)",
         })
@@ -72,6 +78,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(30% 0.09 30)",
+            NotificationVariant::Warning => "oklch(30% 0.07 75)",
             NotificationVariant::Info => "oklch(30% 0.05 250)",
             NotificationVariant::Success => "oklch(30% 0.07 145)",
         })
@@ -81,6 +88,7 @@ impl VariantColors {
         let v = self.variant;
         signal::always(()).map(move |_| match v {
             NotificationVariant::Error => "oklch(70% 0.21 30)",
+            NotificationVariant::Warning => "oklch(75% 0.15 75)",
             NotificationVariant::Info => "oklch(65% 0.16 250)",
             NotificationVariant::Success => "oklch(70% 0.15 145)",
         })
@@ -1,6 +1,7 @@
+use crate::visualizer::timeline::time_domain::TimePs;
 use moonzoon_novyui::tokens::color::neutral_8;
 use moonzoon_novyui::*;
-use shared::{ScopeData, TrackedFile, generate_smart_labels};
+use shared::{LoadPhase, LoadProgress, ScopeData, TrackedFile, generate_smart_labels};
 use std::collections::HashMap;
 use std::sync::Arc;
 use zoon::*;
@@ -229,16 +230,23 @@ pub fn render_tracked_file_as_tree_item_with_label_and_expanded_state(
                 .iter()
                 .filter_map(|scope| convert_scope_to_tree_data(scope))
                 .collect();
+            let mut item = TreeViewItemData::new(tracked_file.id.clone(), display_name.clone())
+                .item_type(TreeViewItemType::File)
+                .icon("file");
+            if let Some(truncated_ns) = file_data.truncated_at_ns {
+                item = item.icon("triangle-alert").tooltip(format!(
+                    "{}\nFile is truncated: loaded up to the last complete timestamp t={}",
+                    tracked_file.path,
+                    TimePs::from_nanos(truncated_ns)
+                ));
+            }
             vec![
-                TreeViewItemData::new(tracked_file.id.clone(), display_name.clone())
-                    .item_type(TreeViewItemType::File)
-                    .icon("file")
-                    .on_remove(create_enhanced_file_remove_handler(
-                        tracked_file.id.clone(),
-                        tracked_files_domain.clone(),
-                        selected_variables.clone(),
-                    ))
-                    .with_children(children),
+                item.on_remove(create_enhanced_file_remove_handler(
+                    tracked_file.id.clone(),
+                    tracked_files_domain.clone(),
+                    selected_variables.clone(),
+                ))
+                .with_children(children),
             ]
         }
         shared::FileState::Failed(file_error) => {
@@ -292,7 +300,16 @@ pub fn render_tracked_file_as_tree_item_with_label_and_expanded_state(
         }
     };
 
-    tree_view()
+    let settings = (!matches!(tracked_file.state, shared::FileState::Loading(_))).then(|| {
+        file_settings(
+            tracked_file.clone(),
+            tracked_files_domain.clone(),
+            app_config.clone(),
+        )
+    });
+    let progress_line = load_progress_line(tracked_file.id.clone(), tracked_files_domain);
+
+    let tree = tree_view()
         .data(tree_data)
         .size(TreeViewSize::Medium)
         .variant(TreeViewVariant::Basic)
@@ -302,7 +319,336 @@ pub fn render_tracked_file_as_tree_item_with_label_and_expanded_state(
         .single_scope_selection(true)
         .external_expanded(app_config.files_expanded_scopes.clone())
         .external_selected_vec(app_config.files_selected_scope.clone())
-        .build()
+        .build();
+
+    Column::new()
+        .s(Width::fill())
+        .item(tree)
+        .item(progress_line)
+        .item(settings)
+}
+
+/// Collapsible editor of a file's time alignment and, for logic-analyzer CSV files, of how
+/// the file is imported; applying it reloads the file
+fn file_settings(
+    tracked_file: TrackedFile,
+    tracked_files: crate::tracked_files::TrackedFiles,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let file_path = tracked_file.canonical_path.clone();
+    let is_csv = shared::get_file_extension(shared::strip_compression_extension(&file_path))
+        .is_some_and(|extension| extension == "csv");
+    let alignment = app_config
+        .file_time_alignments_config
+        .lock_ref()
+        .get(&file_path)
+        .cloned()
+        .unwrap_or_default();
+    let csv_import = app_config
+        .csv_imports_config
+        .lock_ref()
+        .get(&file_path)
+        .cloned()
+        .unwrap_or_default();
+    let expanded = Mutable::new(false);
+    let offset = Mutable::new(format_offset(alignment.offset_ps));
+    let timescale = Mutable::new(alignment.timescale.clone().unwrap_or_default());
+    let time_column = Mutable::new(csv_import.time_column.clone().unwrap_or_default());
+    let time_unit = Mutable::new(csv_import.time_unit.clone().unwrap_or_default());
+    let buses = Mutable::new(format_bus_groups(&csv_import.buses));
+    let error = Mutable::new(None::<String>);
+
+    let labeled_input = |label: &'static str, placeholder: &'static str, value: Mutable<String>| {
+        Row::new()
+            .s(Align::new().center_y())
+            .s(Gap::new().x(SPACING_8))
+            .item(
+                El::new()
+                    .s(Width::exact(80))
+                    .s(Font::new().size(12).color_signal(neutral_8()))
+                    .child(label),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder(placeholder)
+                    .value_signal(value.signal_cloned())
+                    .on_change(move |text| value.set(text))
+                    .build(),
+            )
+    };
+
+    let apply = {
+        let error = error.clone();
+        let offset = offset.clone();
+        let timescale = timescale.clone();
+        let time_column = time_column.clone();
+        let time_unit = time_unit.clone();
+        let buses = buses.clone();
+        move || {
+            let Some(offset_ps) = parse_offset(&offset.get_cloned()) else {
+                error.set(Some(
+                    "Time offset must look like 10ns or -2.5us".to_string(),
+                ));
+                return;
+            };
+            let timescale = Some(timescale.get_cloned().trim().to_string())
+                .filter(|timescale| !timescale.is_empty());
+            if timescale
+                .as_deref()
+                .is_some_and(|timescale| shared::parse_timescale_seconds(timescale).is_none())
+            {
+                error.set(Some("Timescale must look like 1ns or 10ps".to_string()));
+                return;
+            }
+            let alignment = shared::FileTimeAlignment {
+                offset_ps,
+                timescale,
+            };
+            let csv_import = if is_csv {
+                match parse_bus_groups(&buses.get_cloned()) {
+                    Ok(bus_groups) => Some(shared::CsvImportSettings {
+                        time_column: Some(time_column.get_cloned().trim().to_string())
+                            .filter(|name| !name.is_empty()),
+                        time_unit: Some(time_unit.get_cloned().trim().to_string())
+                            .filter(|unit| !unit.is_empty()),
+                        buses: bus_groups,
+                    }),
+                    Err(message) => {
+                        error.set(Some(message));
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            error.set(None);
+
+            {
+                let mut alignments = app_config.file_time_alignments_config.lock_mut();
+                if alignment.is_identity() {
+                    alignments.remove(&file_path);
+                } else {
+                    alignments.insert(file_path.clone(), alignment.clone());
+                }
+            }
+            if let Some(settings) = &csv_import {
+                app_config
+                    .csv_imports_config
+                    .lock_mut()
+                    .insert(file_path.clone(), settings.clone());
+            }
+            Task::start(send_file_settings(
+                file_path.clone(),
+                alignment,
+                csv_import,
+                tracked_files.clone(),
+            ));
+        }
+    };
+
+    Column::new()
+        .s(Padding::new().left(SPACING_24))
+        .s(Gap::new().y(SPACING_4))
+        .item(
+            button()
+                .label_signal(expanded.signal().map(|expanded| {
+                    if expanded {
+                        "Hide file settings".to_string()
+                    } else {
+                        "File settings".to_string()
+                    }
+                }))
+                .variant(ButtonVariant::Ghost)
+                .size(ButtonSize::Small)
+                .on_press({
+                    let expanded = expanded.clone();
+                    move || expanded.set(!expanded.get())
+                })
+                .build(),
+        )
+        .item_signal(expanded.signal().map(move |expanded| {
+            expanded.then(|| {
+                Column::new()
+                    .s(Gap::new().y(SPACING_4))
+                    .item(labeled_input("Time offset", "0ns", offset.clone()))
+                    .item(labeled_input("Timescale", "from file", timescale.clone()))
+                    .items(is_csv.then(|| {
+                        Column::new()
+                            .s(Gap::new().y(SPACING_4))
+                            .item(labeled_input("Time column", "auto", time_column.clone()))
+                            .item(labeled_input(
+                                "Time unit",
+                                "from header or s",
+                                time_unit.clone(),
+                            ))
+                            .item(labeled_input(
+                                "Buses",
+                                "addr: D3 D2 D1 D0; cs: D5 D4",
+                                buses.clone(),
+                            ))
+                            .item(
+                                El::new()
+                                    .s(Font::new().size(11).color_signal(neutral_8()))
+                                    .child("Bus channels are listed most significant bit first."),
+                            )
+                    }))
+                    .item_signal(error.signal_cloned().map(|error| {
+                        error.map(|message| {
+                            El::new()
+                                .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                                .child(message)
+                        })
+                    }))
+                    .item(
+                        button()
+                            .label("Apply and reload")
+                            .variant(ButtonVariant::Outline)
+                            .size(ButtonSize::Small)
+                            .on_press(apply.clone())
+                            .build(),
+                    )
+            })
+        }))
+}
+
+async fn send_file_settings(
+    file_path: String,
+    alignment: shared::FileTimeAlignment,
+    csv_import: Option<shared::CsvImportSettings>,
+    tracked_files: crate::tracked_files::TrackedFiles,
+) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    let mut messages = vec![shared::UpMsg::SetFileTimeAlignment {
+        file_path: file_path.clone(),
+        alignment,
+    }];
+    if let Some(settings) = csv_import {
+        messages.push(shared::UpMsg::ConfigureCsvImport {
+            file_path: file_path.clone(),
+            settings,
+        });
+    }
+    for msg in messages {
+        if let Err(e) = CurrentPlatform::send_message(msg).await {
+            zoon::eprintln!("🚨 File settings: Failed to send settings for {file_path}: {e}");
+            return;
+        }
+    }
+    // The backend applies the settings when it loads the file again
+    tracked_files.reload_file(shared::CanonicalPathPayload::new(file_path));
+}
+
+/// `-2.5us`, `+10ns` or `0`; an empty text is no offset
+fn parse_offset(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Some(0);
+    }
+    let (sign, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (-1, magnitude),
+        None => (1, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let magnitude = i64::try_from(shared::parse_duration_ps(magnitude)?).ok()?;
+    Some(sign * magnitude)
+}
+
+/// Offset in the largest unit that keeps it exact, so it parses back unchanged
+fn format_offset(offset_ps: i64) -> String {
+    const UNITS: [(i64, &str); 5] = [
+        (1_000_000_000_000, "s"),
+        (1_000_000_000, "ms"),
+        (1_000_000, "us"),
+        (1_000, "ns"),
+        (1, "ps"),
+    ];
+    if offset_ps == 0 {
+        return String::new();
+    }
+    let (scale, unit) = UNITS
+        .into_iter()
+        .find(|(scale, _)| offset_ps % scale == 0)
+        .unwrap_or((1, "ps"));
+    format!("{}{}", offset_ps / scale, unit)
+}
+
+/// `name: ch ch ...` groups separated by `;`
+fn parse_bus_groups(text: &str) -> Result<Vec<shared::CsvBusGroup>, String> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            let (name, channels) = group
+                .split_once(':')
+                .ok_or_else(|| format!("Bus '{}' needs a name followed by ':'", group))?;
+            let channels: Vec<String> = channels
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|channel| !channel.is_empty())
+                .map(str::to_string)
+                .collect();
+            if name.trim().is_empty() || channels.is_empty() {
+                return Err(format!("Bus '{}' needs a name and its channels", group));
+            }
+            Ok(shared::CsvBusGroup {
+                name: name.trim().to_string(),
+                channels,
+            })
+        })
+        .collect()
+}
+
+fn format_bus_groups(buses: &[shared::CsvBusGroup]) -> String {
+    buses
+        .iter()
+        .map(|bus| format!("{}: {}", bus.name, bus.channels.join(" ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Status line shown under a file while it is decompressed or its waveform body loads
+fn load_progress_line(
+    file_id: String,
+    tracked_files: crate::tracked_files::TrackedFiles,
+) -> impl Element {
+    El::new().child_signal(tracked_files.load_progress.signal_ref(move |loads| {
+        loads.get(&file_id).map(|progress| {
+            El::new()
+                .s(Padding::new().left(SPACING_20).bottom(SPACING_2))
+                .s(Font::new().size(12).color_signal(neutral_8()).no_wrap())
+                .child(format_load_progress(progress))
+        })
+    }))
+}
+
+/// e.g. "Loading body 42% · 1.2 GB / 2.9 GB · ETA 12s"
+fn format_load_progress(progress: &LoadProgress) -> String {
+    let mut parts = vec![match progress.fraction() {
+        Some(fraction) if progress.phase == LoadPhase::Body => {
+            format!("Loading body {:.0}%", fraction * 100.0)
+        }
+        Some(fraction) if progress.phase == LoadPhase::Decompressing => {
+            format!("Decompressing {:.0}%", fraction * 100.0)
+        }
+        None if progress.phase == LoadPhase::Decompressing => "Decompressing...".to_string(),
+        _ => format!("Loading {}...", progress.phase.label()),
+    }];
+    if progress.bytes_processed > 0 && progress.total_bytes > 0 {
+        parts.push(format!(
+            "{} / {}",
+            shared::format_byte_size(progress.bytes_processed),
+            shared::format_byte_size(progress.total_bytes)
+        ));
+    }
+    if let Some(eta_ms) = progress.eta_ms {
+        let eta_seconds = eta_ms.div_ceil(1000);
+        if eta_seconds >= 60 {
+            parts.push(format!("ETA {}m {}s", eta_seconds / 60, eta_seconds % 60));
+        } else {
+            parts.push(format!("ETA {}s", eta_seconds));
+        }
+    }
+    parts.join(" · ")
 }
 
 /// Compute smart label for a single file with duplicate detection AND time intervals
@@ -368,7 +714,15 @@ pub fn compute_smart_label_for_file(
                     }
                 };
 
-                format!("{} ({})", base_name, time_range)
+                match waveform_file.truncated_at_ns {
+                    Some(truncated_ns) => format!(
+                        "{} ({}, truncated at t={})",
+                        base_name,
+                        time_range,
+                        TimePs::from_nanos(truncated_ns)
+                    ),
+                    None => format!("{} ({})", base_name, time_range),
+                }
             } else {
                 base_name
             }
@@ -453,3 +807,29 @@ pub fn empty_state_hint(text: &str) -> impl Element {
         .s(Font::new().color_signal(neutral_8()).italic())
         .child(text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bus_groups, format_offset, parse_bus_groups, parse_offset};
+
+    #[test]
+    fn bus_groups_round_trip_through_text() {
+        let buses = parse_bus_groups(" addr: D3 D2,D1 D0 ;cs:D5;").unwrap();
+        assert_eq!(buses.len(), 2);
+        assert_eq!(buses[0].channels, ["D3", "D2", "D1", "D0"]);
+        assert_eq!(format_bus_groups(&buses), "addr: D3 D2 D1 D0; cs: D5");
+        assert!(parse_bus_groups("D3 D2").is_err());
+        assert!(parse_bus_groups("addr:").is_err());
+    }
+
+    #[test]
+    fn time_offsets_round_trip_through_text() {
+        assert_eq!(parse_offset(""), Some(0));
+        assert_eq!(parse_offset("-2.5us"), Some(-2_500_000));
+        assert_eq!(parse_offset("+10"), Some(10_000));
+        assert_eq!(parse_offset("later"), None);
+        assert_eq!(format_offset(-2_500_000), "-2500ns");
+        assert_eq!(format_offset(3_000_000_000), "3ms");
+        assert_eq!(parse_offset(&format_offset(-1_234_567)), Some(-1_234_567));
+    }
+}
@@ -9,17 +9,23 @@ static MAIN_TASK: OnceLock<TaskHandle> = OnceLock::new();
 // Core modules
 mod app;
 mod clipboard;
+mod condition_search;
 mod config;
 mod connection;
+mod derived_signals;
 mod error_display;
 mod error_ui;
+mod pending_request;
 mod platform;
 mod selected_variables;
 mod selected_variables_layout;
+mod signal_measurement;
 mod test_api;
 mod tracked_files;
+mod value_search;
 mod virtual_list;
 mod visualizer;
+mod waveform_diff;
 
 mod action_buttons;
 mod dragging;
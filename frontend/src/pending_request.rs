@@ -0,0 +1,77 @@
+//! Requests answered by a DownMsg carrying the id they were sent with.
+//!
+//! Only the request sent last is in flight; answers to earlier or abandoned ones are
+//! dropped, so a slow reply never overwrites the result of a newer request.
+
+use shared::UpMsg;
+use zoon::*;
+
+#[derive(Clone)]
+pub struct PendingRequest<T> {
+    /// Id of the request in flight and what its answer needs to be applied
+    running: Mutable<Option<(String, T)>>,
+    pub error_message: Mutable<Option<String>>,
+}
+
+impl<T: Clone + 'static> PendingRequest<T> {
+    pub fn new() -> Self {
+        Self {
+            running: Mutable::new(None),
+            error_message: Mutable::new(None),
+        }
+    }
+
+    /// Send the message built for a fresh id; `kind` prefixes the id and names the
+    /// request in logs
+    pub fn send(&self, kind: &'static str, context: T, up_msg: impl FnOnce(String) -> UpMsg) {
+        let id = format!("{}_{}", kind, js_sys::Date::now() as u64);
+        self.running.set(Some((id.clone(), context)));
+        self.error_message.set(None);
+        Task::start(send_request(kind, up_msg(id)));
+    }
+
+    pub fn is_running(&self, id: &str) -> bool {
+        self.running
+            .lock_ref()
+            .as_ref()
+            .is_some_and(|(running_id, _)| running_id == id)
+    }
+
+    /// Context of the request with this id, if it is the one in flight; it no longer is
+    pub fn finish(&self, id: &str) -> Option<T> {
+        let mut running = self.running.lock_mut();
+        match running.take() {
+            Some((running_id, context)) if running_id == id => Some(context),
+            other => {
+                *running = other;
+                None
+            }
+        }
+    }
+
+    /// Show the error of the request with this id, if it is the one in flight
+    pub fn fail(&self, id: &str, error: String) -> bool {
+        let failed = self.finish(id).is_some();
+        if failed {
+            self.error_message.set(Some(error));
+        }
+        failed
+    }
+
+    /// Drop the request in flight; its answer will be ignored
+    pub fn abandon(&self) {
+        self.running.set(None);
+    }
+
+    pub fn running_signal(&self) -> impl Signal<Item = bool> + use<T> {
+        self.running.signal_ref(Option::is_some)
+    }
+}
+
+async fn send_request(kind: &'static str, up_msg: UpMsg) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    if let Err(e) = CurrentPlatform::send_message(up_msg).await {
+        zoon::eprintln!("🚨 Failed to send {kind} request: {e}");
+    }
+}
@@ -35,6 +35,8 @@ fn platform_log_label() -> &'static str {
 fn message_name(msg: &UpMsg) -> &'static str {
     match msg {
         UpMsg::LoadWaveformFile(_) => "LoadWaveformFile",
+        UpMsg::CancelLoad(_) => "CancelLoad",
+        UpMsg::UnloadFile(_) => "UnloadFile",
         UpMsg::GetParsingProgress(_) => "GetParsingProgress",
         UpMsg::LoadConfig => "LoadConfig",
         UpMsg::SelectWorkspace { .. } => "SelectWorkspace",
@@ -47,6 +49,17 @@ fn message_name(msg: &UpMsg) -> &'static str {
         UpMsg::BatchQuerySignalValues { .. } => "BatchQuerySignalValues",
         UpMsg::QuerySignalTransitions { .. } => "QuerySignalTransitions",
         UpMsg::UnifiedSignalQuery { .. } => "UnifiedSignalQuery",
+        UpMsg::CancelSignalQuery { .. } => "CancelSignalQuery",
+        UpMsg::ExportWaveform(_) => "ExportWaveform",
+        UpMsg::CompareWaveforms(_) => "CompareWaveforms",
+        UpMsg::DefineDerivedSignal(_) => "DefineDerivedSignal",
+        UpMsg::SearchCondition(_) => "SearchCondition",
+        UpMsg::SearchSignalValue(_) => "SearchSignalValue",
+        UpMsg::MeasureSignal(_) => "MeasureSignal",
+        UpMsg::CompareSignal(_) => "CompareSignal",
+        UpMsg::SetFileTimeAlignment { .. } => "SetFileTimeAlignment",
+        UpMsg::ConfigureCsvImport { .. } => "ConfigureCsvImport",
+        UpMsg::SaveWaveformImage { .. } => "SaveWaveformImage",
         UpMsg::TriggerTestNotifications => "TriggerTestNotifications",
         UpMsg::GetPlatformRoots => "GetPlatformRoots",
     }
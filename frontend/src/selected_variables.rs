@@ -166,6 +166,7 @@ impl SelectedVariables {
             signal_type: None,
             row_height: None,
             analog_limits: None,
+            compare_file: None,
         };
 
         self.variables.lock_mut().push_cloned(placeholder_var);
@@ -374,6 +375,12 @@ impl SelectedVariables {
         });
     }
 
+    pub fn set_compare_file(&self, unique_id: &str, compare_file: Option<String>) {
+        self.update_variable_with_visibility_refresh(unique_id, true, |var| {
+            var.compare_file = compare_file.clone();
+        });
+    }
+
     pub fn synchronize_metadata_from_files(&self, files: &[shared::TrackedFile]) {
         let mut updated_any = false;
         let mut current = {
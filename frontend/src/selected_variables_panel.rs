@@ -12,9 +12,12 @@ use crate::dragging::{variables_name_column_width_signal, variables_value_column
 use crate::visualizer::timeline::TimePerPixel;
 use moonzoon_novyui::components::input::{InputSize, input};
 use moonzoon_novyui::components::{KbdSize, KbdVariant, kbd};
-use moonzoon_novyui::tokens::color::{neutral_2, neutral_4, neutral_8, neutral_11};
+use moonzoon_novyui::tokens::color::{neutral_2, neutral_4, neutral_8, neutral_11, warning_7};
 use moonzoon_novyui::*;
-use shared::{AnalogLimits, SelectedVariable, SignalValue, TrackedFile, VarFormat};
+use shared::{
+    AnalogLimits, ExportFormat, SelectedVariable, SignalValue, TabularLayout, TrackedFile,
+    VarFormat, WaveformExportRequest,
+};
 use std::rc::Rc;
 use zoon::*;
 
@@ -39,6 +42,51 @@ struct AnalogLimitsDialogState {
     error_message: Mutable<Option<String>>,
 }
 
+#[derive(Clone)]
+struct ExportDialogState {
+    visible: Mutable<bool>,
+    format: Mutable<ExportFormat>,
+    /// Export the range between the first two markers instead of the visible range
+    between_markers: Mutable<bool>,
+    /// Export the subtree of the scope selected in the Files panel instead of the variables
+    whole_scope: Mutable<bool>,
+    /// Output path, used when the export covers a single file
+    output_input: Mutable<String>,
+    /// CSV / JSON row layout
+    rows: Mutable<TabularRows>,
+    period_input: Mutable<String>,
+    /// Unique id of the selected variable whose edges are sampled
+    clock: Mutable<Option<String>>,
+    /// SVG image width in pixels
+    width_input: Mutable<String>,
+    error_message: Mutable<Option<String>>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TabularRows {
+    Changes,
+    Period,
+    ClockEdges,
+}
+
+impl TabularRows {
+    fn next(self) -> Self {
+        match self {
+            TabularRows::Changes => TabularRows::Period,
+            TabularRows::Period => TabularRows::ClockEdges,
+            TabularRows::ClockEdges => TabularRows::Changes,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TabularRows::Changes => "Rows: value changes",
+            TabularRows::Period => "Rows: every period",
+            TabularRows::ClockEdges => "Rows: clock edges",
+        }
+    }
+}
+
 /// Enhanced Selected Variables Panel with proper three-column layout
 pub fn selected_variables_panel(
     selected_variables: crate::selected_variables::SelectedVariables,
@@ -66,6 +114,20 @@ pub fn selected_variables_panel(
         error_message: Mutable::new(None),
     };
     let marker_manager_visible = Mutable::new(false);
+    let export_dialog = ExportDialogState {
+        visible: Mutable::new(false),
+        format: Mutable::new(ExportFormat::default()),
+        between_markers: Mutable::new(false),
+        whole_scope: Mutable::new(false),
+        output_input: Mutable::new(String::new()),
+        rows: Mutable::new(TabularRows::Changes),
+        period_input: Mutable::new("10ns".to_string()),
+        clock: Mutable::new(None),
+        width_input: Mutable::new("1600".to_string()),
+        error_message: Mutable::new(None),
+    };
+    let waveform_canvas_for_export = waveform_canvas.clone();
+    let tracked_files_for_diff = tracked_files.clone();
 
     Stack::new()
         .s(Width::fill())
@@ -78,6 +140,8 @@ pub fn selected_variables_panel(
                     &app_config_for_header,
                     group_dialog.clone(),
                     marker_manager_visible.clone(),
+                    export_dialog.clone(),
+                    &tracked_files_for_diff,
                 ),
                 selected_variables_panel_content(
                     selected_variables,
@@ -124,6 +188,93 @@ pub fn selected_variables_panel(
                 )
             }
         }))
+        .layer_signal(export_dialog.visible.signal().map_true({
+            let selected_variables = selected_variables_for_header.clone();
+            let timeline = waveform_timeline_for_header.clone();
+            move || {
+                export_dialog_view(
+                    selected_variables.clone(),
+                    timeline.clone(),
+                    waveform_canvas_for_export.clone(),
+                    export_dialog.clone(),
+                )
+            }
+        }))
+        .layer_signal(
+            app_config_for_header
+                .waveform_diff
+                .dialog_visible
+                .signal()
+                .map_true({
+                    let timeline = waveform_timeline_for_header.clone();
+                    let app_config = app_config_for_header.clone();
+                    move || {
+                        waveform_diff_dialog(
+                            tracked_files_for_diff.clone(),
+                            timeline.clone(),
+                            app_config.clone(),
+                        )
+                    }
+                }),
+        )
+        .layer_signal(
+            app_config_for_header
+                .derived_signals
+                .dialog_visible
+                .signal()
+                .map_true({
+                    let selected_variables = selected_variables_for_header.clone();
+                    let app_config = app_config_for_header.clone();
+                    move || derived_signal_dialog(selected_variables.clone(), app_config.clone())
+                }),
+        )
+        .layer_signal(
+            app_config_for_header
+                .condition_search
+                .dialog_visible
+                .signal()
+                .map_true({
+                    let selected_variables = selected_variables_for_header.clone();
+                    let timeline = waveform_timeline_for_header.clone();
+                    let app_config = app_config_for_header.clone();
+                    move || {
+                        condition_search_dialog(
+                            selected_variables.clone(),
+                            timeline.clone(),
+                            app_config.clone(),
+                        )
+                    }
+                }),
+        )
+        .layer_signal(
+            app_config_for_header
+                .value_search
+                .dialog_visible
+                .signal()
+                .map_true({
+                    let timeline = waveform_timeline_for_header.clone();
+                    let app_config = app_config_for_header.clone();
+                    move || value_search_dialog(timeline.clone(), app_config.clone())
+                }),
+        )
+        .layer_signal(
+            app_config_for_header
+                .signal_measurements
+                .dialog_visible
+                .signal()
+                .map_true({
+                    let selected_variables = selected_variables_for_header.clone();
+                    let timeline = waveform_timeline_for_header.clone();
+                    let app_config = app_config_for_header.clone();
+                    move || {
+                        signal_measurement_dialog(
+                            selected_variables.clone(),
+                            timeline.clone(),
+                            app_config.clone(),
+                        )
+                    }
+                }),
+        )
 }
 
 /// Panel header with title and action buttons
@@ -133,10 +284,13 @@ fn selected_variables_panel_header(
     app_config: &crate::config::AppConfig,
     group_dialog: GroupDialogState,
     marker_manager_visible: Mutable<bool>,
+    export_dialog: ExportDialogState,
+    tracked_files: &crate::tracked_files::TrackedFiles,
 ) -> impl Element {
     let sv_for_group_toggle = selected_variables.clone();
     let sv_for_group_create = selected_variables.clone();
     let timeline_for_markers = waveform_timeline.clone();
+    let sv_for_export = selected_variables.clone();
 
     Row::new()
         .s(Gap::new().x(SPACING_8))
@@ -232,6 +386,76 @@ fn selected_variables_panel_header(
                         })
                         .build(),
                 )
+                .item(
+                    button()
+                        .label("Export")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press(move || {
+                            let sources = export_source_files(&sv_for_export, &export_dialog);
+                            if let [source] = sources.as_slice() {
+                                export_dialog.output_input.set(shared::default_export_path(
+                                    source,
+                                    export_dialog.format.get(),
+                                ));
+                            }
+                            export_dialog.error_message.set(None);
+                            export_dialog.visible.set(true);
+                        })
+                        .build(),
+                )
+                .item(
+                    button()
+                        .label("Compare")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let tracked_files = tracked_files.clone();
+                            let app_config = app_config.clone();
+                            move || {
+                                app_config.waveform_diff.open(
+                                    &loaded_file_paths(&tracked_files),
+                                    &app_config.diff_path_remaps_config.get_cloned(),
+                                );
+                            }
+                        })
+                        .build(),
+                )
+                .item(
+                    button()
+                        .label("Derive")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let app_config = app_config.clone();
+                            move || app_config.derived_signals.open()
+                        })
+                        .build(),
+                )
+                .item(
+                    button()
+                        .label("Find")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let app_config = app_config.clone();
+                            move || app_config.condition_search.open()
+                        })
+                        .build(),
+                )
+                .item(
+                    button()
+                        .label("Measure")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let app_config = app_config.clone();
+                            let selected_variables = selected_variables.clone();
+                            move || app_config.signal_measurements.open(&selected_variables)
+                        })
+                        .build(),
+                )
+                .item(secondary_cursor_readout(waveform_timeline.clone()))
                 .item(
                     // Version display with less contrast
                     El::new()
@@ -253,6 +477,45 @@ fn selected_variables_panel_header(
         ))
 }
 
+/// Δt and 1/Δt between the cursor and the second cursor, once the second cursor is placed
+fn secondary_cursor_readout(
+    waveform_timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+) -> impl Element {
+    use crate::visualizer::timeline::time_domain::DurationPs;
+
+    let delta_signal = map_ref! {
+        let cursor = waveform_timeline.cursor_actor().signal(),
+        let secondary = waveform_timeline.secondary_cursor_actor().signal()
+        => secondary.map(|secondary| cursor.picoseconds().abs_diff(secondary.picoseconds()))
+    };
+
+    Row::new()
+        .s(Gap::new().x(SPACING_6))
+        .s(Align::new().center_y())
+        .s(Font::new().size(12).no_wrap().color_signal(neutral_11()))
+        .item(
+            kbd("B")
+                .size(KbdSize::Small)
+                .variant(KbdVariant::Outlined)
+                .title("Press B to place the second cursor at the cursor, or Alt+click the waveform. Press Shift+B to remove it.")
+                .build(),
+        )
+        .item_signal(delta_signal.map(|delta_ps| {
+            delta_ps.map(|delta_ps| {
+                let frequency = if delta_ps > 0 {
+                    format_frequency(1e12 / delta_ps as f64)
+                } else {
+                    "–".to_string()
+                };
+                format!(
+                    "Δt {}  1/Δt {}",
+                    DurationPs::from_picoseconds(delta_ps),
+                    frequency
+                )
+            })
+        }))
+}
+
 /// Three-column content area with proper layout
 fn selected_variables_panel_content(
     selected_variables: crate::selected_variables::SelectedVariables,
@@ -363,6 +626,7 @@ fn selected_variables_name_column(
                     let tf = tracked_files.clone();
                     let cfg = app_config.clone();
                     let ds = dragging_system.clone();
+                    let wt = waveform_timeline.clone();
                     sv.visible_items.signal_cloned().map(move |items| {
                         let sv = sv.clone();
                         let tf = tf.clone();
@@ -374,7 +638,14 @@ fn selected_variables_name_column(
                         crate::selected_variables::SelectedVariableOrGroup::Variable(var) => {
                             let uid = var.unique_id.clone();
                             elements.push(
-                                name_column_variable_row(var, sv.clone(), tf.clone()).into_raw(),
+                                name_column_variable_row(
+                                    var,
+                                    sv.clone(),
+                                    tf.clone(),
+                                    wt.clone(),
+                                    cfg.clone(),
+                                )
+                                .into_raw(),
                             );
                             elements.push(signal_row_divider(uid, ds.clone()).into_raw());
                         }
@@ -406,11 +677,13 @@ fn selected_variables_name_column(
         .item(name_column_footer(waveform_timeline))
 }
 
-/// Individual variable row in Name Column
+/// Individual variable row in Name Column; right-click opens value search for it
 fn name_column_variable_row(
     selected_var: SelectedVariable,
     selected_variables: crate::selected_variables::SelectedVariables,
     tracked_files: crate::tracked_files::TrackedFiles,
+    waveform_timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    app_config: crate::config::AppConfig,
 ) -> impl Element {
     let unique_id = selected_var.unique_id.clone();
     let selected_variables_for_remove = selected_variables.clone();
@@ -440,7 +713,19 @@ fn name_column_variable_row(
         .s(Gap::new().x(SPACING_4))
         .update_raw_el({
             let indent = if is_grouped { "16px" } else { "0" };
-            move |raw_el| raw_el.style("padding-left", indent)
+            let selected_var = selected_var.clone();
+            let waveform_timeline = waveform_timeline.clone();
+            move |raw_el| {
+                raw_el.style("padding-left", indent).event_handler_with_options(
+                    EventOptions::new().preventable(),
+                    move |event: events::ContextMenu| {
+                        event.prevent_default();
+                        app_config
+                            .value_search
+                            .open(selected_var.clone(), &waveform_timeline);
+                    },
+                )
+            }
         })
         .item_signal({
             let sv = selected_variables.clone();
@@ -537,6 +822,80 @@ fn name_column_variable_row(
                     }
                 })
         })
+        .item_signal(tracked_files_broadcaster.signal_cloned().map({
+            let selected_var = selected_var.clone();
+            move |files: Vec<TrackedFile>| {
+                let own_file = selected_var.file_path();
+                // Derived signals have no counterpart in another file
+                if own_file.as_deref() == Some(shared::DERIVED_SIGNALS_FILE) {
+                    return None;
+                }
+                let candidates: Vec<String> = files
+                    .into_iter()
+                    .filter(|file| matches!(file.state, shared::FileState::Loaded(_)))
+                    .map(|file| file.canonical_path)
+                    .filter(|path| Some(path) != own_file.as_ref())
+                    .collect();
+                (selected_var.compare_file.is_some() || !candidates.is_empty()).then(|| {
+                    compare_file_button(
+                        selected_var.clone(),
+                        candidates,
+                        selected_variables.clone(),
+                        waveform_timeline.clone(),
+                    )
+                })
+            }
+        }))
+}
+
+/// Cycles the file the variable is compared with through the other loaded files and off
+fn compare_file_button(
+    selected_var: SelectedVariable,
+    candidates: Vec<String>,
+    selected_variables: crate::selected_variables::SelectedVariables,
+    waveform_timeline: crate::visualizer::timeline::WaveformTimeline,
+) -> impl Element {
+    let compare_name = selected_var
+        .compare_file
+        .as_deref()
+        .and_then(|path| std::path::Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    let title = match &compare_name {
+        Some(name) => format!("Shading differences from {name}; click for the next file"),
+        None => "Compare with the same variable in another file".to_string(),
+    };
+    let title_signal = waveform_timeline
+        .signal_difference_error_signal(selected_var.unique_id.clone())
+        .map(move |error| match error {
+            Some(error) => format!("Comparison failed: {error}"),
+            None => title.clone(),
+        });
+    El::new()
+        .update_raw_el(move |raw_el| raw_el.attr_signal("title", title_signal))
+        .child(
+            button()
+                .label(match compare_name {
+                    Some(name) => format!("≠ {name}"),
+                    None => "≠".to_string(),
+                })
+                .variant(if selected_var.compare_file.is_some() {
+                    ButtonVariant::Outline
+                } else {
+                    ButtonVariant::Ghost
+                })
+                .size(ButtonSize::Small)
+                .custom_padding(4, 2)
+                .on_press(move || {
+                    let next = selected_var
+                        .compare_file
+                        .as_ref()
+                        .and_then(|path| candidates.iter().position(|candidate| candidate == path))
+                        .map_or(0, |position| position + 1);
+                    selected_variables
+                        .set_compare_file(&selected_var.unique_id, candidates.get(next).cloned());
+                })
+                .build(),
+        )
 }
 
 fn name_column_group_header(
@@ -865,29 +1224,76 @@ fn value_column_variable_row(
             .unwrap_or(SELECTED_VARIABLES_ROW_HEIGHT),
     );
     let is_real_signal = selected_var.signal_type.as_deref() == Some("Real");
-    El::new()
+    let secondary_value = secondary_cursor_value(selected_var.clone(), waveform_timeline.clone());
+    Row::new()
         .s(Height::exact_signal(row_height_signal))
         .s(Width::fill())
-        .child(if is_real_signal {
-            analog_value_row(
-                selected_var,
-                selected_variables,
-                waveform_timeline,
-                tracked_files,
-                app_config,
-                analog_dialog,
-            )
-            .into_raw()
-        } else {
-            digital_value_row(
-                selected_var,
-                selected_variables,
-                waveform_timeline,
-                tracked_files,
-                app_config,
-            )
-            .into_raw()
+        .item(
+            El::new()
+                .s(Width::fill())
+                .s(Height::fill())
+                .child(if is_real_signal {
+                    analog_value_row(
+                        selected_var,
+                        selected_variables,
+                        waveform_timeline,
+                        tracked_files,
+                        app_config,
+                        analog_dialog,
+                    )
+                    .into_raw()
+                } else {
+                    digital_value_row(
+                        selected_var,
+                        selected_variables,
+                        waveform_timeline,
+                        tracked_files,
+                        app_config,
+                    )
+                    .into_raw()
+                }),
+        )
+        .item_signal(secondary_value)
+}
+
+/// Value at the second cursor, beside the cursor value while the second cursor is placed
+fn secondary_cursor_value(
+    selected_var: SelectedVariable,
+    waveform_timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+) -> impl Signal<Item = Option<RawElOrText>> {
+    let unique_id = selected_var.unique_id;
+    let signal_type = selected_var.signal_type;
+    let format = selected_var.formatter.unwrap_or(VarFormat::Hexadecimal);
+    let value_signal = waveform_timeline
+        .secondary_cursor_values_actor()
+        .signal_ref(move |values| values.get(&unique_id).cloned());
+    map_ref! {
+        let secondary = waveform_timeline.secondary_cursor_actor().signal(),
+        let value = value_signal
+        => secondary.map(|_| value.clone().unwrap_or(SignalValue::Loading))
+    }
+    .map(move |value| {
+        value.map(|value| {
+            let formatted = crate::format_selection::format_signal_value_for_display(
+                &value,
+                signal_type.as_deref(),
+                format,
+            );
+            let title = format!("At the second cursor: {formatted}");
+            El::new()
+                .s(Width::exact(90))
+                .s(Padding::new().right(SPACING_8).top(SPACING_4))
+                .s(Font::new().size(13).no_wrap().color_signal(warning_7()))
+                .update_raw_el(move |raw_el| {
+                    raw_el
+                        .attr("title", &title)
+                        .style("overflow", "hidden")
+                        .style("text-overflow", "ellipsis")
+                })
+                .child(formatted)
+                .into_raw()
         })
+    })
 }
 
 fn digital_value_row(
@@ -918,19 +1324,25 @@ fn value_column_footer(
 ) -> impl Element {
     let viewport_actor = waveform_timeline.viewport_actor();
     let start_signal = viewport_actor.clone().signal().map(|viewport| {
-        format_time_with_range(viewport.start.nanos(), viewport.duration().nanos())
+        format_time_with_range(
+            viewport.start.picoseconds(),
+            viewport.duration().picoseconds(),
+        )
     });
 
-    let end_signal = viewport_actor
-        .signal()
-        .map(|viewport| format_time_with_range(viewport.end.nanos(), viewport.duration().nanos()));
+    let end_signal = viewport_actor.signal().map(|viewport| {
+        format_time_with_range(
+            viewport.end.picoseconds(),
+            viewport.duration().picoseconds(),
+        )
+    });
 
     let cursor_signal = {
         let viewport_actor = viewport_actor.clone();
         map_ref! {
             let cursor = waveform_timeline.cursor_actor().signal(),
             let viewport = viewport_actor.signal() => {
-                format_time_with_range(cursor.nanos(), viewport.duration().nanos())
+                format_time_with_range(cursor.picoseconds(), viewport.duration().picoseconds())
             }
         }
     };
@@ -1047,9 +1459,9 @@ fn selected_variables_wave_column(
         ))
 }
 
-fn format_time_with_range(ns: u64, range_ns: u64) -> String {
-    let unit = TimeDisplayUnit::from_range(range_ns);
-    let value = ns as f64 / unit.base_ns();
+fn format_time_with_range(ps: u64, range_ps: u64) -> String {
+    let unit = TimeDisplayUnit::from_range(range_ps);
+    let value = ps as f64 / unit.base_ps();
     let mut formatted = format_axis_number(value);
     formatted.push_str(unit.suffix());
     formatted
@@ -1084,27 +1496,31 @@ enum TimeDisplayUnit {
     Milliseconds,
     Microseconds,
     Nanoseconds,
+    Picoseconds,
 }
 
 impl TimeDisplayUnit {
-    fn from_range(range_ns: u64) -> Self {
-        if range_ns >= 1_000_000_000 {
+    fn from_range(range_ps: u64) -> Self {
+        if range_ps >= 1_000_000_000_000 {
             TimeDisplayUnit::Seconds
-        } else if range_ns >= 1_000_000 {
+        } else if range_ps >= 1_000_000_000 {
             TimeDisplayUnit::Milliseconds
-        } else if range_ns >= 1_000 {
+        } else if range_ps >= 1_000_000 {
             TimeDisplayUnit::Microseconds
-        } else {
+        } else if range_ps >= 1_000 {
             TimeDisplayUnit::Nanoseconds
+        } else {
+            TimeDisplayUnit::Picoseconds
         }
     }
 
-    fn base_ns(self) -> f64 {
+    fn base_ps(self) -> f64 {
         match self {
-            TimeDisplayUnit::Seconds => 1_000_000_000.0,
-            TimeDisplayUnit::Milliseconds => 1_000_000.0,
-            TimeDisplayUnit::Microseconds => 1_000.0,
-            TimeDisplayUnit::Nanoseconds => 1.0,
+            TimeDisplayUnit::Seconds => 1_000_000_000_000.0,
+            TimeDisplayUnit::Milliseconds => 1_000_000_000.0,
+            TimeDisplayUnit::Microseconds => 1_000_000.0,
+            TimeDisplayUnit::Nanoseconds => 1_000.0,
+            TimeDisplayUnit::Picoseconds => 1.0,
         }
     }
 
@@ -1114,6 +1530,7 @@ impl TimeDisplayUnit {
             TimeDisplayUnit::Milliseconds => "ms",
             TimeDisplayUnit::Microseconds => "us",
             TimeDisplayUnit::Nanoseconds => "ns",
+            TimeDisplayUnit::Picoseconds => "ps",
         }
     }
 }
@@ -1142,8 +1559,8 @@ fn analog_compressed_zoom_hint(
     })?;
 
     let visible_width_px = analog_visible_span_width_px(
-        series.transitions.as_ref(),
-        series.actual_time_range_ns,
+        &series.transitions.times_ps,
+        series.actual_time_range_ps,
         state.viewport_start.picoseconds(),
         state.viewport_end.picoseconds(),
         state.canvas_width_px,
@@ -1157,26 +1574,25 @@ fn analog_compressed_zoom_hint(
 }
 
 fn analog_visible_span_width_px(
-    transitions: &[shared::SignalTransition],
-    actual_time_range_ns: Option<(u64, u64)>,
+    times_ps: &[u64],
+    actual_time_range_ps: Option<(u64, u64)>,
     viewport_start_ps: u64,
     viewport_end_ps: u64,
     canvas_width_px: u32,
 ) -> Option<f64> {
-    if transitions.is_empty() || viewport_end_ps <= viewport_start_ps || canvas_width_px == 0 {
+    if times_ps.is_empty() || viewport_end_ps <= viewport_start_ps || canvas_width_px == 0 {
         return None;
     }
 
     let mut visible_start_ps = u64::MAX;
     let mut visible_end_ps = 0_u64;
     let mut found_segment = false;
-    let actual_end_ps = actual_time_range_ns.map(|(_, end_ns)| end_ns.saturating_mul(1_000));
+    let actual_end_ps = actual_time_range_ps.map(|(_, end_ps)| end_ps);
 
-    for (index, transition) in transitions.iter().enumerate() {
-        let segment_start_ps = transition.time_ns.saturating_mul(1_000);
-        let segment_end_ps = transitions
+    for (index, &segment_start_ps) in times_ps.iter().enumerate() {
+        let segment_end_ps = times_ps
             .get(index + 1)
-            .map(|next| next.time_ns.saturating_mul(1_000))
+            .copied()
             .or(actual_end_ps)
             .unwrap_or(viewport_end_ps);
 
@@ -1747,8 +2163,8 @@ fn marker_manager_dialog(
                                                         .no_wrap())
                                                     .s(Width::exact(90))
                                                     .child(format_time_with_range(
-                                                        marker.time_ps / 1_000,
-                                                        1_000_000_000,
+                                                        marker.time_ps,
+                                                        1_000_000_000_000,
                                                     )),
                                             )
                                             .item(
@@ -1826,6 +2242,1362 @@ fn marker_manager_dialog(
     )
 }
 
+/// Canonical paths of the files whose hierarchy finished loading
+fn loaded_file_paths(tracked_files: &crate::tracked_files::TrackedFiles) -> Vec<String> {
+    tracked_files
+        .files_vec_signal
+        .get_cloned()
+        .into_iter()
+        .filter(|file| matches!(file.state, shared::FileState::Loaded(_)))
+        .map(|file| file.canonical_path)
+        .collect()
+}
+
+/// Button cycling `choice` through the loaded files
+fn diff_file_button(
+    role: &'static str,
+    choice: Mutable<Option<String>>,
+    tracked_files: crate::tracked_files::TrackedFiles,
+) -> impl Element {
+    button()
+        .label_signal(choice.signal_cloned().map(move |path| {
+            let filename = path
+                .as_deref()
+                .and_then(|path| std::path::Path::new(path).file_name())
+                .map(|name| name.to_string_lossy().to_string());
+            match filename {
+                Some(filename) => format!("{role}: {filename}"),
+                None => format!("{role}: none"),
+            }
+        }))
+        .variant(ButtonVariant::Ghost)
+        .size(ButtonSize::Small)
+        .on_press({
+            let choice = choice.clone();
+            move || {
+                let candidates = loaded_file_paths(&tracked_files);
+                let next = choice
+                    .get_cloned()
+                    .and_then(|path| candidates.iter().position(|candidate| *candidate == path))
+                    .map_or(0, |position| position + 1);
+                choice.set(candidates.get(next % candidates.len().max(1)).cloned());
+            }
+        })
+        .build()
+}
+
+fn waveform_diff_dialog(
+    tracked_files: crate::tracked_files::TrackedFiles,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let diff = app_config.waveform_diff.clone();
+    let close_diff = diff.clone();
+    let status_signal = map_ref! {
+        let running = diff.request.running_signal(),
+        let progress = diff.progress.signal(),
+        let summary = diff.summary.signal_cloned()
+        => (*running, *progress, summary.clone())
+    };
+
+    centered_modal(
+        move || close_diff.dialog_visible.set(false),
+        Column::new()
+            .s(Width::exact(560))
+            .s(Height::exact(480))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child("Compare Files"),
+            )
+            .item(
+                Row::new()
+                    .s(Align::new().center_y())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(diff_file_button(
+                        "Reference",
+                        diff.reference_file.clone(),
+                        tracked_files.clone(),
+                    ))
+                    .item(diff_file_button(
+                        "Candidate",
+                        diff.candidate_file.clone(),
+                        tracked_files,
+                    )),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder("Path remaps, e.g. tb.dut => top.core")
+                    .value_signal(diff.remaps_input.signal_cloned())
+                    .on_change({
+                        let diff = diff.clone();
+                        move |text| diff.remaps_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                El::new()
+                    .s(Font::new().size(11).color_signal(neutral_8()))
+                    .child("Signals are matched by scope path; a remap rewrites a reference scope prefix."),
+            )
+            .item_signal(diff.request.error_message.signal_cloned().map(|message| {
+                message.map(|message| {
+                    El::new()
+                        .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                        .child(message)
+                        .into_raw()
+                })
+            }))
+            .item(
+                El::new()
+                    .s(Width::fill())
+                    .s(Height::fill())
+                    .s(Scrollbars::both())
+                    .child_signal(status_signal.map(move |(running, progress, summary)| {
+                        if running {
+                            return Some(
+                                El::new()
+                                    .s(Font::new().size(12).color_signal(neutral_8()))
+                                    .child(format!("Comparing... {:.0}%", progress * 100.0))
+                                    .into_raw(),
+                            );
+                        }
+                        summary.map(|summary| diff_summary_view(summary, timeline.clone()).into_raw())
+                    })),
+            )
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(
+                        button()
+                            .label("Close")
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let diff = diff.clone();
+                                move || diff.dialog_visible.set(false)
+                            })
+                            .build(),
+                    )
+                    .item(
+                        button()
+                            .label("Compare")
+                            .size(ButtonSize::Small)
+                            .on_press(move || diff.start(&app_config))
+                            .build(),
+                    ),
+            ),
+    )
+}
+
+fn derived_signal_dialog(
+    selected_variables: crate::selected_variables::SelectedVariables,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let derived = app_config.derived_signals.clone();
+    let close_derived = derived.clone();
+
+    centered_modal(
+        move || close_derived.dialog_visible.set(false),
+        Column::new()
+            .s(Width::exact(480))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child("Derived Signal"),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder("Name, e.g. fire")
+                    .value_signal(derived.name_input.signal_cloned())
+                    .on_change({
+                        let derived = derived.clone();
+                        move |text| derived.name_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder("Expression, e.g. valid && ready")
+                    .value_signal(derived.expression_input.signal_cloned())
+                    .on_change({
+                        let derived = derived.clone();
+                        move |text| derived.expression_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                El::new()
+                    .s(Font::new().size(11).color_signal(neutral_8()))
+                    .child("Operands name selected variables by the end of their scope path; Verilog operators, slices and literals are allowed."),
+            )
+            .item_signal(derived.error_message.signal_cloned().map(|message| {
+                message.map(|message| {
+                    El::new()
+                        .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                        .child(message)
+                        .into_raw()
+                })
+            }))
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(
+                        button()
+                            .label("Close")
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let derived = derived.clone();
+                                move || derived.dialog_visible.set(false)
+                            })
+                            .build(),
+                    )
+                    .item(
+                        button()
+                            .label("Add")
+                            .size(ButtonSize::Small)
+                            .on_press(move || derived.define(&selected_variables))
+                            .build(),
+                    ),
+            ),
+    )
+}
+
+fn condition_search_dialog(
+    selected_variables: crate::selected_variables::SelectedVariables,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let search = app_config.condition_search.clone();
+    let close_search = search.clone();
+    let search_button = |label: &'static str, direction: shared::SearchDirection| {
+        let search = search.clone();
+        let selected_variables = selected_variables.clone();
+        let timeline = timeline.clone();
+        button()
+            .label(label)
+            .variant(ButtonVariant::Ghost)
+            .size(ButtonSize::Small)
+            .on_press(move || search.start(direction, &selected_variables, &timeline))
+            .build()
+    };
+
+    centered_modal(
+        move || close_search.dialog_visible.set(false),
+        Column::new()
+            .s(Width::exact(480))
+            .s(Height::exact(420))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child("Find Condition"),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder("Condition, e.g. rising(clk) && en")
+                    .value_signal(search.expression_input.signal_cloned())
+                    .on_change({
+                        let search = search.clone();
+                        move |text| search.expression_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                El::new()
+                    .s(Font::new().size(11).color_signal(neutral_8()))
+                    .child("Matches are the times the condition starts to hold; rising(x), falling(x) and 'a becomes b' mark changes."),
+            )
+            .item_signal(search.request.error_message.signal_cloned().map(|message| {
+                message.map(|message| {
+                    El::new()
+                        .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                        .child(message)
+                        .into_raw()
+                })
+            }))
+            .item(
+                El::new()
+                    .s(Width::fill())
+                    .s(Height::fill())
+                    .s(Scrollbars::both())
+                    .child_signal(
+                        map_ref! {
+                            let running = search.request.running_signal(),
+                            let matches = search.matches.signal_cloned()
+                            => (*running, matches.clone())
+                        }
+                        .map({
+                            let timeline = timeline.clone();
+                            move |(running, matches)| {
+                                if running {
+                                    return Some(
+                                        El::new()
+                                            .s(Font::new().size(12).color_signal(neutral_8()))
+                                            .child("Searching...")
+                                            .into_raw(),
+                                    );
+                                }
+                                matches.map(|matches| {
+                                    search_matches_view(matches, timeline.clone()).into_raw()
+                                })
+                            }
+                        }),
+                    ),
+            )
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(search_button("Previous", shared::SearchDirection::Previous))
+                    .item(search_button("Next", shared::SearchDirection::Next))
+                    .item(search_button("All", shared::SearchDirection::All))
+                    .item(
+                        button()
+                            .label("Add markers")
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let search = search.clone();
+                                move || search.add_markers(&timeline, &app_config)
+                            })
+                            .build(),
+                    ),
+            ),
+    )
+}
+
+fn value_search_dialog(
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let search = app_config.value_search.clone();
+    let target = search.target.get_cloned();
+    let variable_name = target
+        .as_ref()
+        .and_then(|variable| variable.variable_name())
+        .unwrap_or_default();
+    let placeholder = match target
+        .and_then(|variable| variable.formatter)
+        .unwrap_or_default()
+    {
+        shared::VarFormat::ASCII => "Text as shown in the row",
+        shared::VarFormat::Binary | shared::VarFormat::BinaryWithGroups => {
+            "Binary value, e.g. 1010_xx01"
+        }
+        shared::VarFormat::Hexadecimal => "Hexadecimal value, e.g. 0xa5 or 3x",
+        shared::VarFormat::Octal => "Octal value, e.g. 17",
+        shared::VarFormat::Signed => "Decimal value, e.g. -12",
+        shared::VarFormat::Unsigned => "Decimal value, e.g. 42",
+    };
+    let step_button = |label: &'static str, forward: bool| {
+        let search = search.clone();
+        let timeline = timeline.clone();
+        button()
+            .label(label)
+            .variant(ButtonVariant::Ghost)
+            .size(ButtonSize::Small)
+            .on_press(move || search.step(forward, &timeline))
+            .build()
+    };
+
+    centered_modal(
+        {
+            let search = search.clone();
+            let timeline = timeline.clone();
+            move || search.close(&timeline)
+        },
+        Column::new()
+            .s(Width::exact(420))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child(format!("Find Value in {}", variable_name)),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder(placeholder)
+                    .value_signal(search.value_input.signal_cloned())
+                    .on_change({
+                        let search = search.clone();
+                        move |text| search.value_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                El::new()
+                    .s(Font::new().size(11).color_signal(neutral_8()))
+                    .child("The value is read in the row's format; x digits match any bits."),
+            )
+            .item_signal(search.request.error_message.signal_cloned().map(|message| {
+                message.map(|message| {
+                    El::new()
+                        .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                        .child(message)
+                        .into_raw()
+                })
+            }))
+            .item(
+                El::new()
+                    .s(Font::new().size(12).color_signal(neutral_8()))
+                    .child_signal(map_ref! {
+                        let running = search.request.running_signal(),
+                        let matches = search.matches.signal_cloned(),
+                        let current = search.current.signal()
+                        => value_search_status(*running, matches.as_ref(), *current)
+                    }),
+            )
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(step_button("Previous", false))
+                    .item(step_button("Next", true))
+                    .item(
+                        button()
+                            .label("Find")
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let search = search.clone();
+                                move || search.start()
+                            })
+                            .build(),
+                    ),
+            ),
+    )
+}
+
+fn value_search_status(
+    running: bool,
+    matches: Option<&crate::value_search::ValueMatches>,
+    current: Option<usize>,
+) -> Option<String> {
+    if running {
+        return Some("Searching...".to_string());
+    }
+    let matches = matches?;
+    let highlighted = if matches.intervals.len() < matches.match_count {
+        format!(" (first {} highlighted)", matches.intervals.len())
+    } else {
+        String::new()
+    };
+    Some(match (matches.match_count, current) {
+        (0, _) => "The signal never holds this value.".to_string(),
+        (count, Some(index)) => format!("Match {} of {}{}", index + 1, count, highlighted),
+        (count, None) => format!("{} matches{}", count, highlighted),
+    })
+}
+
+/// Button cycling `choice` through the markers; `None` stands for the viewport edge
+fn measurement_bound_button(
+    role: &'static str,
+    edge_label: &'static str,
+    choice: Mutable<Option<usize>>,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+) -> impl Element {
+    button()
+        .label_signal(map_ref! {
+            let choice = choice.signal(),
+            let markers = timeline.markers_snapshot.signal_cloned()
+            => match choice.and_then(|index| markers.get(index)) {
+                Some(marker) => format!("{role}: {}", marker.name),
+                None => format!("{role}: {edge_label}"),
+            }
+        })
+        .variant(ButtonVariant::Ghost)
+        .size(ButtonSize::Small)
+        .on_press({
+            let choice = choice.clone();
+            move || {
+                let marker_count = timeline.markers_snapshot.lock_ref().len();
+                choice.set(match choice.get() {
+                    None if marker_count > 0 => Some(0),
+                    Some(index) if index + 1 < marker_count => Some(index + 1),
+                    _ => None,
+                });
+            }
+        })
+        .build()
+}
+
+fn signal_measurement_dialog(
+    selected_variables: crate::selected_variables::SelectedVariables,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    app_config: crate::config::AppConfig,
+) -> impl Element {
+    let measurements = app_config.signal_measurements.clone();
+    let follow_task = measurements.follow_range(&timeline);
+    let close_measurements = measurements.clone();
+
+    centered_modal(
+        move || close_measurements.dialog_visible.set(false),
+        Column::new()
+            .s(Width::exact(440))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .after_remove(move |_| drop(follow_task))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child("Measure Signal"),
+            )
+            .item(
+                button()
+                    .label_signal(measurements.target.signal_cloned().map(|target| {
+                        let name = target
+                            .and_then(|variable| variable.variable_name())
+                            .unwrap_or_else(|| "none".to_string());
+                        format!("Signal: {name}")
+                    }))
+                    .variant(ButtonVariant::Ghost)
+                    .size(ButtonSize::Small)
+                    .on_press({
+                        let measurements = measurements.clone();
+                        move || measurements.cycle_target(&selected_variables)
+                    })
+                    .build(),
+            )
+            .item(
+                Row::new()
+                    .s(Gap::new().x(SPACING_8))
+                    .item(measurement_bound_button(
+                        "From",
+                        "view start",
+                        measurements.start_marker.clone(),
+                        timeline.clone(),
+                    ))
+                    .item(measurement_bound_button(
+                        "To",
+                        "view end",
+                        measurements.end_marker.clone(),
+                        timeline.clone(),
+                    )),
+            )
+            .item_signal(
+                measurements
+                    .request
+                    .error_message
+                    .signal_cloned()
+                    .map(|message| {
+                        message.map(|message| {
+                            El::new()
+                                .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                                .child(message)
+                                .into_raw()
+                        })
+                    }),
+            )
+            .item_signal(
+                measurements
+                    .result
+                    .signal_cloned()
+                    .map(|result| result.map(|result| measurement_view(result).into_raw())),
+            ),
+    )
+}
+
+fn measurement_view(result: crate::signal_measurement::MeasuredRange) -> impl Element {
+    use crate::visualizer::timeline::time_domain::{DurationPs, TimePs};
+
+    let duration = |ps: f64| DurationPs::from_picoseconds(ps.round() as u64).to_string();
+    let spread = |stats: Option<shared::DurationStats>| match stats {
+        Some(stats) => format!(
+            "{} to {}, mean {} ({})",
+            duration(stats.min_ps as f64),
+            duration(stats.max_ps as f64),
+            duration(stats.mean_ps),
+            stats.count
+        ),
+        None => "–".to_string(),
+    };
+    let measurement = &result.measurement;
+    let rows = [
+        (
+            "Range",
+            format!(
+                "{} to {}",
+                TimePs::from_picoseconds(result.start_ps),
+                TimePs::from_picoseconds(result.end_ps)
+            ),
+        ),
+        (
+            "Edges",
+            format!(
+                "{} rising, {} falling",
+                measurement.rising_edges, measurement.falling_edges
+            ),
+        ),
+        ("Period", spread(measurement.period)),
+        (
+            "Frequency",
+            measurement
+                .frequency_hz()
+                .map_or_else(|| "–".to_string(), format_frequency),
+        ),
+        (
+            "Duty cycle",
+            measurement
+                .duty_cycle
+                .map_or_else(|| "–".to_string(), |duty| format!("{:.1} %", duty * 100.0)),
+        ),
+        ("High pulses", spread(measurement.high_pulses)),
+        ("Low pulses", spread(measurement.low_pulses)),
+        (
+            "Period jitter",
+            measurement.period.map_or_else(
+                || "–".to_string(),
+                |period| {
+                    format!(
+                        "{} peak-to-peak, {} RMS",
+                        duration(period.peak_to_peak_ps() as f64),
+                        duration(period.std_dev_ps)
+                    )
+                },
+            ),
+        ),
+    ];
+
+    Column::new()
+        .s(Width::fill())
+        .s(Gap::new().y(SPACING_4))
+        .item(
+            El::new()
+                .s(Font::new().size(12).color_signal(neutral_8()))
+                .child(result.variable_name),
+        )
+        .items(rows.into_iter().map(|(label, value)| {
+            Row::new()
+                .s(Width::fill())
+                .s(Gap::new().x(SPACING_8))
+                .item(
+                    El::new()
+                        .s(Width::exact(100))
+                        .s(Font::new().size(12).color_signal(neutral_8()).no_wrap())
+                        .child(label),
+                )
+                .item(
+                    El::new()
+                        .s(Font::new().size(12).color_signal(neutral_11()))
+                        .child(value),
+                )
+        }))
+}
+
+fn format_frequency(hz: f64) -> String {
+    let (value, unit) = if hz >= 1e9 {
+        (hz / 1e9, "GHz")
+    } else if hz >= 1e6 {
+        (hz / 1e6, "MHz")
+    } else if hz >= 1e3 {
+        (hz / 1e3, "kHz")
+    } else {
+        (hz, "Hz")
+    };
+    format!("{}{}", format_axis_number(value), unit)
+}
+
+/// Times of the last search for all matches
+fn search_matches_view(
+    matches: crate::condition_search::SearchMatches,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+) -> impl Element {
+    use crate::visualizer::timeline::time_domain::TimePs;
+
+    let note = match (matches.times_ps.len(), matches.truncated) {
+        (0, _) => format!("'{}' never holds.", matches.expression),
+        (count, false) => format!("{} matches of '{}'.", count, matches.expression),
+        (count, true) => format!("First {} matches of '{}'.", count, matches.expression),
+    };
+
+    Column::new()
+        .s(Width::fill())
+        .s(Gap::new().y(SPACING_8))
+        .item(
+            El::new()
+                .s(Font::new().size(12).color_signal(neutral_8()))
+                .child(note),
+        )
+        .items(matches.times_ps.into_iter().map(move |time_ps| {
+            let time = TimePs::from_picoseconds(time_ps);
+            Row::new()
+                .s(Width::fill())
+                .s(Align::new().center_y())
+                .s(Gap::new().x(SPACING_8))
+                .item(
+                    El::new()
+                        .s(Width::fill())
+                        .s(Font::new().size(12).color_signal(neutral_11()).no_wrap())
+                        .child(time.to_string()),
+                )
+                .item(
+                    button()
+                        .label("Jump")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let timeline = timeline.clone();
+                            move || timeline.jump_to_time(time)
+                        })
+                        .build(),
+                )
+        }))
+}
+
+/// Mismatching signals of the last comparison, earliest divergence first
+fn diff_summary_view(
+    summary: shared::WaveformDiffSummary,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+) -> impl Element {
+    use crate::visualizer::timeline::time_domain::TimePs;
+
+    let mut notes = vec![format!(
+        "{} of {} signals differ up to {}.",
+        summary.mismatches.len(),
+        summary.compared_signals,
+        TimePs::from_picoseconds(summary.compared_until_ps)
+    )];
+    if !summary.missing_in_candidate.is_empty() {
+        notes.push(format!(
+            "{} signals exist only in the reference.",
+            summary.missing_in_candidate.len()
+        ));
+    }
+    if !summary.missing_in_reference.is_empty() {
+        notes.push(format!(
+            "{} signals exist only in the candidate.",
+            summary.missing_in_reference.len()
+        ));
+    }
+    notes.extend(summary.failed_signals.iter().map(|failure| {
+        format!(
+            "{} was not compared: {}",
+            failure.reference_key.replace('|', "."),
+            failure.error
+        )
+    }));
+
+    Column::new()
+        .s(Width::fill())
+        .s(Gap::new().y(SPACING_8))
+        .items(notes.into_iter().map(|note| {
+            El::new()
+                .s(Font::new().size(12).color_signal(neutral_8()))
+                .child(note)
+        }))
+        .items(summary.mismatches.into_iter().map(move |mismatch| {
+            let signal = mismatch.reference_key.replace('|', ".");
+            let renamed = (mismatch.candidate_key != mismatch.reference_key)
+                .then(|| format!(" (as {})", mismatch.candidate_key.replace('|', ".")));
+            let divergence = TimePs::from_picoseconds(mismatch.first_divergence_ps);
+            Row::new()
+                .s(Width::fill())
+                .s(Align::new().center_y())
+                .s(Gap::new().x(SPACING_8))
+                .item(
+                    El::new()
+                        .s(Width::fill())
+                        .s(Font::new().size(12).color_signal(neutral_11()).no_wrap())
+                        .child(format!("{}{}", signal, renamed.unwrap_or_default())),
+                )
+                .item(
+                    El::new()
+                        .s(Font::new().size(12).color_signal(neutral_8()).no_wrap())
+                        .child(format!(
+                            "from {} · {} {}",
+                            divergence,
+                            mismatch.mismatch_intervals,
+                            if mismatch.mismatch_intervals == 1 {
+                                "interval"
+                            } else {
+                                "intervals"
+                            }
+                        )),
+                )
+                .item(
+                    button()
+                        .label("Jump")
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let timeline = timeline.clone();
+                            move || timeline.jump_to_time(divergence)
+                        })
+                        .build(),
+                )
+        }))
+}
+
+/// Source files covered by the export the dialog currently describes
+fn export_source_files(
+    selected_variables: &crate::selected_variables::SelectedVariables,
+    dialog: &ExportDialogState,
+) -> Vec<String> {
+    if dialog.whole_scope.get() {
+        return selected_variables
+            .selected_scope
+            .get_cloned()
+            .and_then(|scope_id| {
+                scope_id
+                    .rsplit_once('|')
+                    .map(|(file_path, _)| file_path.to_string())
+            })
+            .into_iter()
+            .collect();
+    }
+    let mut files: Vec<String> = Vec::new();
+    let variables = selected_variables.variables.lock_ref();
+    for file_path in variables.iter().filter_map(|variable| variable.file_path()) {
+        if file_path != shared::DERIVED_SIGNALS_FILE && !files.contains(&file_path) {
+            files.push(file_path);
+        }
+    }
+    files
+}
+
+/// One export request per source file; a clipboard copy covers exactly one file
+fn build_export_requests(
+    selected_variables: &crate::selected_variables::SelectedVariables,
+    dialog: &ExportDialogState,
+    start_ps: u64,
+    end_ps: u64,
+    copy_to_clipboard: bool,
+) -> Result<Vec<WaveformExportRequest>, String> {
+    let format = dialog.format.get();
+    let sources = export_source_files(selected_variables, dialog);
+    if sources.is_empty() {
+        return Err(if dialog.whole_scope.get() {
+            "Select a scope in the Files panel first.".to_string()
+        } else {
+            "Add variables to export first.".to_string()
+        });
+    }
+    let output_input = dialog.output_input.get_cloned().trim().to_string();
+    if copy_to_clipboard && sources.len() > 1 {
+        return Err("Copy signals from a single file to the clipboard.".to_string());
+    }
+    if !copy_to_clipboard && sources.len() == 1 && output_input.is_empty() {
+        return Err("Enter an output path.".to_string());
+    }
+    let rows = if format.is_tabular() {
+        dialog.rows.get()
+    } else {
+        TabularRows::Changes
+    };
+    let layout = match rows {
+        TabularRows::Changes => TabularLayout::Changes,
+        TabularRows::Period => {
+            let period_ps = shared::parse_duration_ps(&dialog.period_input.get_cloned())
+                .filter(|period_ps| *period_ps > 0)
+                .ok_or_else(|| "Enter a sampling period such as 10ns.".to_string())?;
+            TabularLayout::SampledPeriod { period_ps }
+        }
+        TabularRows::ClockEdges => {
+            let clock = dialog
+                .clock
+                .get_cloned()
+                .and_then(|unique_id| {
+                    let (file_path, key) = unique_id.split_once('|')?;
+                    (sources.len() == 1 && sources[0] == file_path).then(|| key.to_string())
+                })
+                .ok_or_else(|| {
+                    "Choose a clock from the same file as the exported signals.".to_string()
+                })?;
+            TabularLayout::ClockEdges { clock }
+        }
+    };
+
+    let scope = selected_variables.selected_scope.get_cloned();
+    let variables = selected_variables.variables.lock_ref();
+    let batch_id = js_sys::Date::now() as u64;
+    Ok(sources
+        .iter()
+        .enumerate()
+        .map(|(index, file_path)| {
+            let mut variable_formats = std::collections::BTreeMap::new();
+            let (variables, scopes) = if dialog.whole_scope.get() {
+                let scope_path = scope
+                    .as_deref()
+                    .and_then(|scope_id| scope_id.rsplit_once('|'))
+                    .map(|(_, scope_path)| scope_path.to_string());
+                (Vec::new(), scope_path.into_iter().collect())
+            } else {
+                let variables = variables
+                    .iter()
+                    .filter(|variable| variable.file_path().as_deref() == Some(file_path.as_str()))
+                    .filter_map(|variable| {
+                        let key =
+                            format!("{}|{}", variable.scope_path()?, variable.variable_name()?);
+                        variable_formats
+                            .insert(key.clone(), variable.formatter.unwrap_or_default());
+                        Some(key)
+                    })
+                    .collect();
+                (variables, Vec::new())
+            };
+            WaveformExportRequest {
+                export_id: format!("export_{batch_id}_{index}"),
+                file_path: file_path.clone(),
+                variables,
+                scopes,
+                start_ps,
+                end_ps,
+                format,
+                output_path: if sources.len() == 1 {
+                    output_input.clone()
+                } else {
+                    shared::default_export_path(file_path, format)
+                },
+                layout: layout.clone(),
+                variable_formats,
+                copy_to_clipboard,
+            }
+        })
+        .collect())
+}
+
+/// Picture of the current waveform view, saved by the backend
+fn build_image_export(
+    selected_variables: &crate::selected_variables::SelectedVariables,
+    waveform_canvas: &crate::visualizer::canvas::waveform_canvas::WaveformCanvas,
+    dialog: &ExportDialogState,
+) -> Result<shared::UpMsg, String> {
+    let width_px = dialog
+        .width_input
+        .get_cloned()
+        .trim()
+        .parse::<u32>()
+        .ok()
+        .filter(|width_px| (100..=20_000).contains(width_px))
+        .ok_or_else(|| "Enter an image width between 100 and 20000 px.".to_string())?;
+    let mut output_path = dialog.output_input.get_cloned().trim().to_string();
+    if output_path.is_empty() {
+        output_path = export_source_files(selected_variables, dialog)
+            .first()
+            .map(|source| shared::default_export_path(source, ExportFormat::Svg))
+            .ok_or_else(|| "Enter an output path.".to_string())?;
+    }
+    let content = waveform_canvas
+        .export_svg(width_px)
+        .ok_or_else(|| "The waveform view is not ready yet.".to_string())?;
+    Ok(shared::UpMsg::SaveWaveformImage {
+        export_id: format!("export_{}_image", js_sys::Date::now() as u64),
+        output_path,
+        content,
+        row_count: selected_variables.variables.lock_ref().len(),
+    })
+}
+
+fn export_dialog_view(
+    selected_variables: crate::selected_variables::SelectedVariables,
+    timeline: crate::visualizer::timeline::timeline_actor::WaveformTimeline,
+    waveform_canvas: crate::visualizer::canvas::waveform_canvas::WaveformCanvas,
+    dialog: ExportDialogState,
+) -> impl Element {
+    let close_dialog = dialog.clone();
+    let confirm_action = {
+        let selected_variables = selected_variables.clone();
+        let timeline = timeline.clone();
+        let dialog = dialog.clone();
+        Rc::new(move |copy_to_clipboard: bool| {
+            if dialog.format.get().is_image() {
+                match build_image_export(&selected_variables, &waveform_canvas, &dialog) {
+                    Ok(msg) => {
+                        Task::start(send_export_message(msg));
+                        dialog.error_message.set(None);
+                        dialog.visible.set(false);
+                    }
+                    Err(error) => dialog.error_message.set(Some(error)),
+                }
+                return;
+            }
+            let (start_ps, end_ps) = if dialog.between_markers.get() {
+                let mut times: Vec<u64> = timeline
+                    .markers
+                    .lock_ref()
+                    .iter()
+                    .map(|marker| marker.time_ps)
+                    .collect();
+                times.sort_unstable();
+                match times.as_slice() {
+                    [first, second, ..] => (*first, *second),
+                    _ => {
+                        dialog.error_message.set(Some(
+                            "Add two markers to export the range between them.".to_string(),
+                        ));
+                        return;
+                    }
+                }
+            } else {
+                let viewport = timeline.viewport_actor().get();
+                (viewport.start.picoseconds(), viewport.end.picoseconds())
+            };
+
+            match build_export_requests(
+                &selected_variables,
+                &dialog,
+                start_ps,
+                end_ps,
+                copy_to_clipboard,
+            ) {
+                Ok(requests) => {
+                    for request in requests {
+                        Task::start(send_export_request(request));
+                    }
+                    dialog.error_message.set(None);
+                    dialog.visible.set(false);
+                }
+                Err(error) => dialog.error_message.set(Some(error)),
+            }
+        })
+    };
+
+    centered_modal(
+        move || close_dialog.visible.set(false),
+        Column::new()
+            .s(Width::exact(420))
+            .s(Padding::all(20))
+            .s(Gap::new().y(16))
+            .item(
+                El::new()
+                    .s(Font::new()
+                        .size(14)
+                        .weight(FontWeight::SemiBold)
+                        .color_signal(neutral_11()))
+                    .child("Export Signals"),
+            )
+            .item(
+                Row::new()
+                    .s(Align::new().center_y())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(
+                        button()
+                            .label_signal(
+                                dialog
+                                    .format
+                                    .signal()
+                                    .map(|format| format!("Format: {}", format.label())),
+                            )
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let selected_variables = selected_variables.clone();
+                                let dialog = dialog.clone();
+                                move || {
+                                    let current = dialog.format.get();
+                                    let position = ExportFormat::ALL
+                                        .iter()
+                                        .position(|format| *format == current)
+                                        .unwrap_or(0);
+                                    let next =
+                                        ExportFormat::ALL[(position + 1) % ExportFormat::ALL.len()];
+                                    dialog.format.set(next);
+                                    let sources = export_source_files(&selected_variables, &dialog);
+                                    if let [source] = sources.as_slice() {
+                                        dialog
+                                            .output_input
+                                            .set(shared::default_export_path(source, next));
+                                    }
+                                }
+                            })
+                            .build(),
+                    )
+                    .item(
+                        button()
+                            .label_signal(dialog.between_markers.signal().map(|between| {
+                                if between {
+                                    "Range: between markers".to_string()
+                                } else {
+                                    "Range: visible".to_string()
+                                }
+                            }))
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let dialog = dialog.clone();
+                                move || {
+                                    let next = !dialog.between_markers.get();
+                                    dialog.between_markers.set(next);
+                                    dialog.error_message.set(None);
+                                }
+                            })
+                            .build(),
+                    )
+                    .item(
+                        button()
+                            .label_signal(dialog.whole_scope.signal().map(|whole_scope| {
+                                if whole_scope {
+                                    "Signals: selected scope".to_string()
+                                } else {
+                                    "Signals: selected variables".to_string()
+                                }
+                            }))
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let selected_variables = selected_variables.clone();
+                                let dialog = dialog.clone();
+                                move || {
+                                    let next = !dialog.whole_scope.get();
+                                    dialog.whole_scope.set(next);
+                                    dialog.error_message.set(None);
+                                    let sources = export_source_files(&selected_variables, &dialog);
+                                    if let [source] = sources.as_slice() {
+                                        dialog.output_input.set(shared::default_export_path(
+                                            source,
+                                            dialog.format.get(),
+                                        ));
+                                    }
+                                }
+                            })
+                            .build(),
+                    ),
+            )
+            .item_signal(
+                dialog
+                    .format
+                    .signal()
+                    .map(|format| format.is_tabular())
+                    .dedupe()
+                    .map({
+                        let selected_variables = selected_variables.clone();
+                        let dialog = dialog.clone();
+                        move |tabular| {
+                            tabular.then(|| {
+                                tabular_layout_row(selected_variables.clone(), dialog.clone())
+                            })
+                        }
+                    }),
+            )
+            .item_signal(
+                dialog
+                    .format
+                    .signal()
+                    .map(|format| format.is_image())
+                    .dedupe()
+                    .map({
+                        let dialog = dialog.clone();
+                        move |image| image.then(|| image_size_row(dialog.clone()))
+                    }),
+            )
+            .item(
+                input()
+                    .size(InputSize::Small)
+                    .placeholder("Output file")
+                    .value_signal(dialog.output_input.signal_cloned())
+                    .on_change({
+                        let dialog = dialog.clone();
+                        move |text| dialog.output_input.set(text)
+                    })
+                    .build(),
+            )
+            .item(
+                El::new()
+                    .s(Font::new().size(11).color_signal(neutral_8()))
+                    .child("Variables from several files are exported next to each source file."),
+            )
+            .item_signal(dialog.error_message.signal_cloned().map(|message| {
+                message.map(|message| {
+                    El::new()
+                        .s(Font::new().size(12).color("oklch(57% 0.2 27)"))
+                        .child(message)
+                        .into_raw()
+                })
+            }))
+            .item(
+                Row::new()
+                    .s(Align::new().right())
+                    .s(Gap::new().x(SPACING_8))
+                    .item(
+                        button()
+                            .label("Cancel")
+                            .variant(ButtonVariant::Ghost)
+                            .size(ButtonSize::Small)
+                            .on_press({
+                                let dialog = dialog.clone();
+                                move || dialog.visible.set(false)
+                            })
+                            .build(),
+                    )
+                    .item_signal(
+                        dialog
+                            .format
+                            .signal()
+                            .map(|format| format.is_tabular())
+                            .dedupe()
+                            .map({
+                                let confirm_action = confirm_action.clone();
+                                move |tabular| {
+                                    let confirm_action = confirm_action.clone();
+                                    tabular.then(|| {
+                                        button()
+                                            .label("Copy")
+                                            .variant(ButtonVariant::Ghost)
+                                            .size(ButtonSize::Small)
+                                            .on_press(move || confirm_action(true))
+                                            .build()
+                                    })
+                                }
+                            }),
+                    )
+                    .item(
+                        button()
+                            .label("Export")
+                            .size(ButtonSize::Small)
+                            .on_press(move || confirm_action(false))
+                            .build(),
+                    ),
+            ),
+    )
+}
+
+/// Row layout controls shown for CSV / JSON exports
+fn tabular_layout_row(
+    selected_variables: crate::selected_variables::SelectedVariables,
+    dialog: ExportDialogState,
+) -> impl Element {
+    Row::new()
+        .s(Align::new().center_y())
+        .s(Gap::new().x(SPACING_8))
+        .item(
+            button()
+                .label_signal(dialog.rows.signal().map(|rows| rows.label().to_string()))
+                .variant(ButtonVariant::Ghost)
+                .size(ButtonSize::Small)
+                .on_press({
+                    let dialog = dialog.clone();
+                    move || {
+                        dialog.rows.set(dialog.rows.get().next());
+                        dialog.error_message.set(None);
+                    }
+                })
+                .build(),
+        )
+        .item_signal(dialog.rows.signal().map(move |rows| {
+            match rows {
+                TabularRows::Changes => None,
+                TabularRows::Period => Some(
+                    input()
+                        .size(InputSize::Small)
+                        .placeholder("Period, e.g. 10ns")
+                        .value_signal(dialog.period_input.signal_cloned())
+                        .on_change({
+                            let dialog = dialog.clone();
+                            move |text| dialog.period_input.set(text)
+                        })
+                        .build()
+                        .into_raw(),
+                ),
+                TabularRows::ClockEdges => Some(
+                    button()
+                        .label_signal(dialog.clock.signal_cloned().map(|clock| {
+                            match clock.as_deref().and_then(|id| id.rsplit('|').next()) {
+                                Some(name) => format!("Clock: {name}"),
+                                None => "Choose clock".to_string(),
+                            }
+                        }))
+                        .variant(ButtonVariant::Ghost)
+                        .size(ButtonSize::Small)
+                        .on_press({
+                            let selected_variables = selected_variables.clone();
+                            let dialog = dialog.clone();
+                            move || {
+                                let candidates: Vec<String> = selected_variables
+                                    .variables
+                                    .lock_ref()
+                                    .iter()
+                                    .map(|variable| variable.unique_id.clone())
+                                    .collect();
+                                let current = dialog.clock.get_cloned();
+                                let next = current
+                                    .and_then(|clock| candidates.iter().position(|id| *id == clock))
+                                    .map_or(0, |position| position + 1);
+                                dialog
+                                    .clock
+                                    .set(candidates.get(next % candidates.len().max(1)).cloned());
+                                dialog.error_message.set(None);
+                            }
+                        })
+                        .build()
+                        .into_raw(),
+                ),
+            }
+        }))
+}
+
+/// Width control shown for image exports, which always picture the visible range
+fn image_size_row(dialog: ExportDialogState) -> impl Element {
+    Row::new()
+        .s(Align::new().center_y())
+        .s(Gap::new().x(SPACING_8))
+        .item(
+            El::new()
+                .s(Font::new().size(12).color_signal(neutral_8()))
+                .child("Width (px)"),
+        )
+        .item(
+            input()
+                .size(InputSize::Small)
+                .placeholder("1600")
+                .value_signal(dialog.width_input.signal_cloned())
+                .on_change({
+                    let dialog = dialog.clone();
+                    move |text| dialog.width_input.set(text)
+                })
+                .build(),
+        )
+        .item(
+            El::new()
+                .s(Font::new().size(11).color_signal(neutral_8()))
+                .child("Pictures the visible range of all rows."),
+        )
+}
+
+async fn send_export_request(request: WaveformExportRequest) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    let file_path = request.file_path.clone();
+    if let Err(e) = CurrentPlatform::send_message(shared::UpMsg::ExportWaveform(request)).await {
+        zoon::eprintln!("🚨 Export: Failed to send export request for {file_path}: {e}");
+    }
+}
+
+async fn send_export_message(msg: shared::UpMsg) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    if let Err(e) = CurrentPlatform::send_message(msg).await {
+        zoon::eprintln!("🚨 Export: Failed to send image export: {e}");
+    }
+}
+
 fn centered_modal(
     close_action: impl Fn() + 'static,
     content: impl Element + 'static,
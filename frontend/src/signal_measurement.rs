@@ -0,0 +1,156 @@
+//! Signal measurements (`UpMsg::MeasureSignal`) of one selected 1-bit variable.
+//!
+//! The range runs between two markers, or the viewport edges where no marker is picked.
+//! While the dialog is open the measurement is repeated whenever the range moves, so
+//! panning and zooming keep the numbers in step with the visible waveform.
+
+use crate::pending_request::PendingRequest;
+use crate::visualizer::timeline::WaveformTimeline;
+use shared::{SelectedVariable, SignalMeasurement, SignalMeasurementRequest};
+use zoon::*;
+
+/// Least time between two measurements while the viewport moves
+const REMEASURE_INTERVAL_MS: u32 = 150;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeasuredRange {
+    pub variable_name: String,
+    pub start_ps: u64,
+    pub end_ps: u64,
+    pub measurement: SignalMeasurement,
+}
+
+#[derive(Clone)]
+pub struct SignalMeasurements {
+    pub dialog_visible: Mutable<bool>,
+    pub target: Mutable<Option<SelectedVariable>>,
+    /// Marker indices bounding the range; `None` stands for the viewport edge
+    pub start_marker: Mutable<Option<usize>>,
+    pub end_marker: Mutable<Option<usize>>,
+    /// Measurement in flight, with its range
+    pub request: PendingRequest<(u64, u64)>,
+    pub result: Mutable<Option<MeasuredRange>>,
+}
+
+impl SignalMeasurements {
+    pub fn new() -> Self {
+        Self {
+            dialog_visible: Mutable::new(false),
+            target: Mutable::new(None),
+            start_marker: Mutable::new(None),
+            end_marker: Mutable::new(None),
+            request: PendingRequest::new(),
+            result: Mutable::new(None),
+        }
+    }
+
+    /// Open the dialog, keeping the previous target while it is still selected
+    pub fn open(&self, selected_variables: &crate::selected_variables::SelectedVariables) {
+        let selected = selected_variables.variables_vec_actor.get_cloned();
+        let keep_target = self.target.get_cloned().is_some_and(|target| {
+            selected
+                .iter()
+                .any(|variable| variable.unique_id == target.unique_id)
+        });
+        if !keep_target {
+            self.target.set(selected.into_iter().next());
+            self.result.set(None);
+        }
+        self.request.error_message.set(None);
+        self.dialog_visible.set(true);
+    }
+
+    /// Move the target on to the next selected variable
+    pub fn cycle_target(&self, selected_variables: &crate::selected_variables::SelectedVariables) {
+        let selected = selected_variables.variables_vec_actor.get_cloned();
+        let next = self
+            .target
+            .get_cloned()
+            .and_then(|target| {
+                selected
+                    .iter()
+                    .position(|variable| variable.unique_id == target.unique_id)
+            })
+            .map_or(0, |position| position + 1);
+        self.target
+            .set(selected.get(next % selected.len().max(1)).cloned());
+        self.result.set(None);
+    }
+
+    /// Repeat the measurement whenever the target or its range changes; runs until the
+    /// returned handle is dropped
+    pub fn follow_range(&self, timeline: &WaveformTimeline) -> TaskHandle {
+        let markers_signal = timeline.markers_snapshot.signal_cloned().map(|markers| {
+            markers
+                .iter()
+                .map(|marker| marker.time_ps)
+                .collect::<Vec<u64>>()
+        });
+        let range_signal = map_ref! {
+            let target = self.target.signal_cloned(),
+            let start_marker = self.start_marker.signal(),
+            let end_marker = self.end_marker.signal(),
+            let viewport = timeline.viewport_actor().signal(),
+            let marker_times = markers_signal
+            => target.clone().map(|target| {
+                let bound = |marker: &Option<usize>, edge: u64| {
+                    marker.and_then(|index| marker_times.get(index).copied()).unwrap_or(edge)
+                };
+                let start_ps = bound(start_marker, viewport.start.picoseconds());
+                let end_ps = bound(end_marker, viewport.end.picoseconds());
+                (target, start_ps.min(end_ps), start_ps.max(end_ps))
+            })
+        };
+        let measurements = self.clone();
+        Task::start_droppable(range_signal.dedupe_cloned().for_each(move |range| {
+            let measurements = measurements.clone();
+            async move {
+                if let Some((target, start_ps, end_ps)) = range {
+                    measurements.request(&target, start_ps, end_ps);
+                }
+                Timer::sleep(REMEASURE_INTERVAL_MS).await;
+            }
+        }))
+    }
+
+    fn request(&self, target: &SelectedVariable, start_ps: u64, end_ps: u64) {
+        let Some((file_path, scope_path, variable_name)) = target.parse_unique_id() else {
+            return;
+        };
+        self.request
+            .send("measure", (start_ps, end_ps), |measurement_id| {
+                shared::UpMsg::MeasureSignal(SignalMeasurementRequest {
+                    measurement_id,
+                    file_path,
+                    scope_path,
+                    variable_name,
+                    start_ps,
+                    end_ps,
+                })
+            });
+    }
+
+    pub fn apply_measurement(&self, measurement_id: &str, measurement: SignalMeasurement) {
+        let Some((start_ps, end_ps)) = self.request.finish(measurement_id) else {
+            return;
+        };
+        let variable_name = self
+            .target
+            .get_cloned()
+            .and_then(|target| target.variable_name())
+            .unwrap_or_default();
+        self.request.error_message.set(None);
+        self.result.set(Some(MeasuredRange {
+            variable_name,
+            start_ps,
+            end_ps,
+            measurement,
+        }));
+    }
+
+    pub fn apply_failure(&self, measurement_id: &str, error: String) {
+        if self.request.fail(measurement_id, error) {
+            self.result.set(None);
+        }
+    }
+}
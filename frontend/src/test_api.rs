@@ -4,7 +4,7 @@ use crate::tracked_files::TrackedFiles;
 use crate::visualizer::canvas::rendering::canvas_render_debug_snapshot;
 use crate::visualizer::timeline::TimePs;
 use crate::visualizer::timeline::timeline_actor::WaveformTimeline;
-use shared::AnalogLimits;
+use shared::{AnalogLimits, SignalPayloadEncoding};
 use std::cell::RefCell;
 use std::sync::Arc;
 use wasm_bindgen::prelude::*;
@@ -410,6 +410,16 @@ pub fn expose_novywave_test_api() {
     .ok();
     delete_group_closure.forget();
 
+    let set_signal_payload_encoding_closure =
+        Closure::wrap(Box::new(set_signal_payload_encoding_impl) as Box<dyn Fn(String) -> bool>);
+    js_sys::Reflect::set(
+        &api,
+        &"setSignalPayloadEncoding".into(),
+        set_signal_payload_encoding_closure.as_ref().unchecked_ref(),
+    )
+    .ok();
+    set_signal_payload_encoding_closure.forget();
+
     js_sys::Reflect::set(&window, &"__novywave_test_api".into(), &api).ok();
 
     zoon::println!("[NovyWave] Test API exposed on window.__novywave_test_api");
@@ -499,6 +509,12 @@ fn get_timeline_state_impl() -> JsValue {
             &JsValue::from_f64(debug_metrics.request_deduped_count as f64),
         )
         .ok();
+        js_sys::Reflect::set(
+            &obj,
+            &"requestCancelledCount".into(),
+            &JsValue::from_f64(debug_metrics.request_cancelled_count as f64),
+        )
+        .ok();
         js_sys::Reflect::set(
             &obj,
             &"startupInitialQuerySendCount".into(),
@@ -1112,18 +1128,18 @@ fn get_render_variables_debug_impl() -> JsValue {
             .ok();
 
             let actual = js_sys::Array::new();
-            if let Some((start, end)) = variable.actual_time_range_ns {
+            if let Some((start, end)) = variable.actual_time_range_ps {
                 actual.push(&JsValue::from_f64(start as f64));
                 actual.push(&JsValue::from_f64(end as f64));
             }
-            js_sys::Reflect::set(&obj, &"actualTimeRangeNs".into(), &actual).ok();
+            js_sys::Reflect::set(&obj, &"actualTimeRangePs".into(), &actual).ok();
 
             let covered = js_sys::Array::new();
-            if let Some((start, end)) = variable.covered_time_range_ns {
+            if let Some((start, end)) = variable.covered_time_range_ps {
                 covered.push(&JsValue::from_f64(start as f64));
                 covered.push(&JsValue::from_f64(end as f64));
             }
-            js_sys::Reflect::set(&obj, &"coveredTimeRangeNs".into(), &covered).ok();
+            js_sys::Reflect::set(&obj, &"coveredTimeRangePs".into(), &covered).ok();
 
             items.push(&obj);
         }
@@ -1282,6 +1298,20 @@ fn set_cursor_ps_impl(time_ps: f64) -> bool {
     .unwrap_or(false)
 }
 
+fn set_signal_payload_encoding_impl(encoding: String) -> bool {
+    let encoding = match encoding.to_ascii_lowercase().as_str() {
+        "json" => SignalPayloadEncoding::Json,
+        "binary" => SignalPayloadEncoding::Binary,
+        _ => return false,
+    };
+
+    with_state(|state| {
+        state.waveform_timeline.set_payload_encoding(encoding);
+        true
+    })
+    .unwrap_or(false)
+}
+
 fn set_pointer_hover_impl(normalized_x: f64, normalized_y: f64) -> bool {
     if !normalized_x.is_finite() || !normalized_y.is_finite() {
         return false;
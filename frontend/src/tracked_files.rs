@@ -5,7 +5,8 @@
 
 use futures::StreamExt;
 use shared::{
-    CanonicalPathPayload, FileState, LoadingStatus, TrackedFile, WaveformFile, create_tracked_file,
+    CanonicalPathPayload, FileState, LoadPhase, LoadProgress, LoadingStatus, TrackedFile,
+    WaveformFile, create_tracked_file,
 };
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
@@ -38,6 +39,8 @@ pub struct TrackedFiles {
     loading_start_times: Mutable<HashMap<String, f64>>,
     pub file_reload_completed: Mutable<Option<String>>,
     pub file_reload_started: Mutable<Option<String>>,
+    /// Running body loads by file id (body loads start with the first signal query)
+    pub load_progress: Mutable<HashMap<String, LoadProgress>>,
     pub backend_messages: BackendMessages,
     parse_request_sender: futures::channel::mpsc::UnboundedSender<(String, String)>,
     _parse_task: Arc<TaskHandle>,
@@ -215,6 +218,7 @@ impl TrackedFiles {
             loading_start_times,
             file_reload_completed,
             file_reload_started: Mutable::new(None),
+            load_progress: Mutable::new(HashMap::new()),
             backend_messages,
             parse_request_sender,
             _parse_task,
@@ -291,11 +295,27 @@ impl TrackedFiles {
         }
     }
 
+    /// Remove a file and release its backend data; a file that is still loading gets its load cancelled
     pub fn remove_file(&self, file_id: String) {
+        let removed = self
+            .files_vec_signal
+            .get_cloned()
+            .into_iter()
+            .find(|f| f.id == file_id);
+        if let Some(file) = removed {
+            let up_msg = if matches!(file.state, FileState::Loading(_)) {
+                shared::UpMsg::CancelLoad(file.path)
+            } else {
+                shared::UpMsg::UnloadFile(file.path)
+            };
+            Task::start(send_release_request_to_backend(up_msg));
+        }
+
         self.files.lock_mut().retain(|f| f.id != file_id);
         let mut current = self.files_vec_signal.get_cloned();
         current.retain(|f| f.id != file_id);
         self.files_vec_signal.set_neq(current);
+        self.load_progress.lock_mut().remove(&file_id);
         self.cancel_watchdog(&file_id);
     }
 
@@ -383,6 +403,15 @@ impl TrackedFiles {
         self.files_vec_signal.set_neq(current);
     }
 
+    pub fn apply_load_progress(&self, file_id: String, progress: LoadProgress) {
+        let mut load_progress = self.load_progress.lock_mut();
+        if progress.phase == LoadPhase::Done {
+            load_progress.remove(&file_id);
+        } else {
+            load_progress.insert(file_id, progress);
+        }
+    }
+
     pub fn notify_loading_started(&self, file_id: String, filename: String) {
         let mut loading_file = create_tracked_file(
             payload_from_string(file_id.clone()),
@@ -408,6 +437,7 @@ impl TrackedFiles {
     pub fn clear_all_files(&self) {
         self.files.lock_mut().clear();
         self.files_vec_signal.set_neq(Vec::new());
+        self.load_progress.lock_mut().clear();
         self.loading_start_times.lock_mut().clear();
         self.file_reload_completed.set(None);
         self.file_reload_started.set(None);
@@ -472,6 +502,15 @@ async fn send_parse_request_to_backend(file_path: String) -> Result<(), String>
     }
 }
 
+async fn send_release_request_to_backend(up_msg: shared::UpMsg) {
+    use crate::platform::{CurrentPlatform, Platform};
+
+    let description = format!("{up_msg:?}");
+    if let Err(e) = CurrentPlatform::send_message(up_msg).await {
+        zoon::eprintln!("🚨 TrackedFiles: Failed to send {description}: {e}");
+    }
+}
+
 fn payload_from_string(path: String) -> CanonicalPathPayload {
     CanonicalPathPayload::new(path)
 }
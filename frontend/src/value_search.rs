@@ -0,0 +1,177 @@
+//! Value search within one selected variable (`UpMsg::SearchSignalValue`).
+//!
+//! Right-clicking a variable opens the Find Value dialog for it. The value is typed in the
+//! row's current format; the backend returns the intervals holding it, which are highlighted
+//! on the canvas until the dialog closes, and Previous / Next step the cursor through them.
+
+use crate::pending_request::PendingRequest;
+use crate::visualizer::timeline::time_domain::TimePs;
+use shared::{SelectedVariable, ValueSearchRequest};
+use std::sync::Arc;
+use zoon::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueMatches {
+    pub unique_id: String,
+    pub match_count: usize,
+    pub intervals: Arc<Vec<(u64, u64)>>,
+}
+
+#[derive(Clone)]
+pub struct ValueSearch {
+    pub dialog_visible: Mutable<bool>,
+    /// Variable the dialog searches
+    pub target: Mutable<Option<SelectedVariable>>,
+    pub value_input: Mutable<String>,
+    pub request: PendingRequest<()>,
+    pub matches: Mutable<Option<ValueMatches>>,
+    /// Index of the interval the cursor was last moved to
+    pub current: Mutable<Option<usize>>,
+}
+
+impl ValueSearch {
+    pub fn new() -> Self {
+        Self {
+            dialog_visible: Mutable::new(false),
+            target: Mutable::new(None),
+            value_input: Mutable::new(String::new()),
+            request: PendingRequest::new(),
+            matches: Mutable::new(None),
+            current: Mutable::new(None),
+        }
+    }
+
+    pub fn open(
+        &self,
+        variable: SelectedVariable,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+    ) {
+        let same_target = self
+            .target
+            .get_cloned()
+            .is_some_and(|target| target.unique_id == variable.unique_id);
+        if !same_target {
+            self.value_input.set(String::new());
+            self.request.abandon();
+            self.clear_matches(timeline);
+        }
+        self.target.set(Some(variable));
+        self.request.error_message.set(None);
+        self.dialog_visible.set(true);
+    }
+
+    pub fn close(&self, timeline: &crate::visualizer::timeline::WaveformTimeline) {
+        self.dialog_visible.set(false);
+        self.request.abandon();
+        self.clear_matches(timeline);
+    }
+
+    fn clear_matches(&self, timeline: &crate::visualizer::timeline::WaveformTimeline) {
+        if self.matches.get_cloned().is_some() {
+            timeline.set_value_matches(None);
+        }
+        self.matches.set(None);
+        self.current.set(None);
+    }
+
+    pub fn start(&self) {
+        let Some(target) = self.target.get_cloned() else {
+            return;
+        };
+        let Some((file_path, scope_path, variable_name)) = target.parse_unique_id() else {
+            self.request
+                .error_message
+                .set(Some("This variable cannot be searched.".to_string()));
+            return;
+        };
+        let value = self.value_input.get_cloned();
+        self.request.send("value_search", (), |search_id| {
+            shared::UpMsg::SearchSignalValue(ValueSearchRequest {
+                search_id,
+                file_path,
+                scope_path,
+                variable_name,
+                format: target.formatter.unwrap_or_default(),
+                value,
+            })
+        });
+    }
+
+    pub fn apply_matches(
+        &self,
+        search_id: &str,
+        match_count: usize,
+        intervals: Vec<(u64, u64)>,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+    ) {
+        if self.request.finish(search_id).is_none() {
+            return;
+        }
+        let Some(target) = self.target.get_cloned() else {
+            return;
+        };
+        let intervals = Arc::new(intervals);
+        timeline.set_value_matches(Some((target.unique_id.clone(), Arc::clone(&intervals))));
+        self.current.set(None);
+        self.matches.set(Some(ValueMatches {
+            unique_id: target.unique_id,
+            match_count,
+            intervals,
+        }));
+    }
+
+    pub fn apply_failure(&self, search_id: &str, error: String) {
+        self.request.fail(search_id, error);
+    }
+
+    /// Move the cursor to the start of the next (or previous) interval holding the value
+    pub fn step(&self, forward: bool, timeline: &crate::visualizer::timeline::WaveformTimeline) {
+        let Some(matches) = self.matches.get_cloned() else {
+            return;
+        };
+        let cursor_ps = timeline.cursor_actor().get().picoseconds();
+        match step_target(&matches.intervals, cursor_ps, forward) {
+            Some(index) => {
+                self.current.set(Some(index));
+                timeline.jump_to_time(TimePs::from_picoseconds(matches.intervals[index].0));
+            }
+            None => self.request.error_message.set(Some(
+                if forward {
+                    "No later occurrence."
+                } else {
+                    "No earlier occurrence."
+                }
+                .to_string(),
+            )),
+        }
+    }
+}
+
+/// Index of the first interval starting after `cursor_ps`, or of the last one starting
+/// before it
+fn step_target(intervals: &[(u64, u64)], cursor_ps: u64, forward: bool) -> Option<usize> {
+    let after = intervals.partition_point(|&(start_ps, _)| start_ps <= cursor_ps);
+    if forward {
+        (after < intervals.len()).then_some(after)
+    } else {
+        intervals[..after]
+            .iter()
+            .rposition(|&(start_ps, _)| start_ps < cursor_ps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step_target;
+
+    #[test]
+    fn steps_go_to_interval_starts_around_the_cursor() {
+        let intervals = [(10, 20), (40, 50), (70, u64::MAX)];
+        assert_eq!(step_target(&intervals, 0, true), Some(0));
+        assert_eq!(step_target(&intervals, 40, true), Some(2));
+        assert_eq!(step_target(&intervals, 45, false), Some(1));
+        assert_eq!(step_target(&intervals, 40, false), Some(0));
+        assert_eq!(step_target(&intervals, 70, true), None);
+        assert_eq!(step_target(&intervals, 10, false), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod rendering;
+pub mod shapes;
 pub mod waveform_canvas;
 
 #[allow(unused_imports)]
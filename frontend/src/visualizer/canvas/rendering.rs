@@ -1,11 +1,11 @@
+use super::shapes::{Shape, TextShape};
 use crate::visualizer::timeline::time_domain::{PS_PER_MS, PS_PER_NS, PS_PER_SECOND, PS_PER_US};
-use fast2d::{CanvasWrapper as Fast2DCanvas, Family, Line, Object2d, Rectangle, Text};
+use fast2d::{CanvasWrapper as Fast2DCanvas, Object2d};
 use moonzoon_novyui::tokens::theme::Theme as NovyUITheme;
-use shared::{AnalogLimits, SignalTransition, SignalValue, VarFormat};
+use shared::{AnalogLimits, PackedTransitions, SignalValue, TransitionBucket, VarFormat};
 use std::cell::RefCell;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
 use std::sync::Arc;
 use zoon::Mutable;
 
@@ -20,8 +20,13 @@ enum TimeLabelUnit {
 
 #[derive(Clone, Debug, PartialEq)]
 enum PixelValue {
-    Single(Rc<String>),
+    /// Index of the transition holding the value
+    Single(usize),
     Mixed,
+    /// Span collapsed by backend LOD reduction; flags whether X/Z occurred inside it
+    Busy {
+        has_unknown: bool,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -34,6 +39,7 @@ struct ThemeColors {
     separator_color: (u8, u8, u8, f32),
     #[allow(dead_code)]
     cursor_color: (u8, u8, u8, f32),
+    secondary_cursor_color: (u8, u8, u8, f32),
     segment_divider_color: (u8, u8, u8, f32),
     value_low_color: (u8, u8, u8, f32),
     value_high_color: (u8, u8, u8, f32),
@@ -43,6 +49,8 @@ struct ThemeColors {
     state_uninitialized: (u8, u8, u8, f32),
     segment_alt_multiplier: f32,
     value_analog_color: (u8, u8, u8, f32),
+    difference_color: (u8, u8, u8, f32),
+    value_match_color: (u8, u8, u8, f32),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -85,14 +93,19 @@ pub fn canvas_render_debug_snapshot() -> CanvasRenderDebug {
 pub struct VariableRenderSnapshot {
     pub unique_id: String,
     pub formatter: VarFormat,
-    pub transitions: Arc<Vec<SignalTransition>>,
+    pub transitions: Arc<PackedTransitions>,
+    pub busy_buckets: Arc<Vec<TransitionBucket>>,
     #[allow(dead_code)]
     pub cursor_value: Option<SignalValue>,
-    pub actual_time_range_ns: Option<(u64, u64)>,
-    pub covered_time_range_ns: Option<(u64, u64)>,
+    pub actual_time_range_ps: Option<(u64, u64)>,
+    pub covered_time_range_ps: Option<(u64, u64)>,
     pub signal_type: Option<String>,
     pub row_height: u32,
     pub analog_limits: Option<AnalogLimits>,
+    /// Intervals where the counterpart in another file holds a different value, shaded
+    pub differences: Option<Arc<Vec<(u64, u64)>>>,
+    /// Intervals holding a searched value, highlighted
+    pub value_matches: Option<Arc<Vec<(u64, u64)>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -114,6 +127,7 @@ pub struct RenderingParameters {
     pub viewport_start_ps: u64,
     pub viewport_end_ps: u64,
     pub cursor_position_ps: Option<u64>,
+    pub secondary_cursor_ps: Option<u64>,
     pub zoom_center_ps: Option<u64>,
     pub theme: NovyUITheme,
     pub rows: Vec<RenderRowSnapshot>,
@@ -207,8 +221,8 @@ impl StaticRenderKey {
                     variable.formatter.hash(&mut hasher);
                     variable.row_height.hash(&mut hasher);
                     variable.signal_type.hash(&mut hasher);
-                    variable.actual_time_range_ns.hash(&mut hasher);
-                    variable.covered_time_range_ns.hash(&mut hasher);
+                    variable.actual_time_range_ps.hash(&mut hasher);
+                    variable.covered_time_range_ps.hash(&mut hasher);
                     if let Some(limits) = &variable.analog_limits {
                         limits.auto.hash(&mut hasher);
                         limits.min.to_bits().hash(&mut hasher);
@@ -216,6 +230,18 @@ impl StaticRenderKey {
                     }
                     let ptr = Arc::as_ptr(&variable.transitions) as usize;
                     ptr.hash(&mut hasher);
+                    let buckets_ptr = Arc::as_ptr(&variable.busy_buckets) as usize;
+                    buckets_ptr.hash(&mut hasher);
+                    let differences_ptr = variable
+                        .differences
+                        .as_ref()
+                        .map(|intervals| Arc::as_ptr(intervals) as usize);
+                    differences_ptr.hash(&mut hasher);
+                    let matches_ptr = variable
+                        .value_matches
+                        .as_ref()
+                        .map(|intervals| Arc::as_ptr(intervals) as usize);
+                    matches_ptr.hash(&mut hasher);
                 }
             }
         }
@@ -276,7 +302,11 @@ impl WaveformRenderer {
             let start_time = Self::get_current_time_ms();
             let theme_colors = Self::get_theme_colors(params.theme);
             let static_skip_reason = Self::static_skip_reason(&params);
-            let overlay_objects = Self::build_overlay_objects(&params, &theme_colors);
+            let overlay_objects: Vec<Object2d> =
+                Self::build_overlay_objects(&params, &theme_colors)
+                    .iter()
+                    .map(Shape::to_object2d)
+                    .collect();
 
             let mut state = self.rendering_state.lock_mut();
             let static_key = StaticRenderKey::from_params(&params);
@@ -292,7 +322,10 @@ impl WaveformRenderer {
                 .unwrap_or(true);
 
             let mut static_objects = if static_changed {
-                let objects = Self::build_static_objects(&params, &theme_colors);
+                let objects: Vec<Object2d> = Self::build_static_objects(&params, &theme_colors)
+                    .iter()
+                    .map(Shape::to_object2d)
+                    .collect();
                 static_count = objects.len();
                 Some(objects)
             } else {
@@ -374,6 +407,18 @@ impl WaveformRenderer {
         }
     }
 
+    /// The frame described by `params` as a standalone SVG document, `width_px` wide
+    /// and tall enough for all rows regardless of the on-screen canvas size
+    pub fn render_svg(mut params: RenderingParameters, width_px: u32) -> String {
+        params.canvas_width = width_px.max(1);
+        params.canvas_height =
+            crate::selected_variables_layout::total_content_height(&row_metrics(&params.rows));
+        let theme_colors = Self::get_theme_colors(params.theme);
+        let mut shapes = Self::build_static_objects(&params, &theme_colors);
+        shapes.extend(Self::build_overlay_objects(&params, &theme_colors));
+        super::shapes::svg_document(&shapes, params.canvas_width, params.canvas_height)
+    }
+
     fn static_skip_reason(params: &RenderingParameters) -> Option<&'static str> {
         if params.canvas_width == 0 || params.canvas_height == 0 {
             return Some("zero_canvas");
@@ -387,7 +432,7 @@ impl WaveformRenderer {
     fn build_static_objects(
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
-    ) -> Vec<Object2d> {
+    ) -> Vec<Shape> {
         if params.canvas_width == 0 || params.canvas_height == 0 {
             return Vec::new();
         }
@@ -404,7 +449,7 @@ impl WaveformRenderer {
     fn build_overlay_objects(
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
-    ) -> Vec<Object2d> {
+    ) -> Vec<Shape> {
         let mut objects = Vec::new();
         if params.canvas_width == 0 || params.canvas_height == 0 {
             return objects;
@@ -425,7 +470,7 @@ impl WaveformRenderer {
     }
 
     fn add_waveforms(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
     ) {
@@ -445,34 +490,31 @@ impl WaveformRenderer {
                 theme_colors.row_odd_bg
             };
 
-            objects.push(
-                Rectangle::new()
-                    .position(0.0, row_top)
-                    .size(params.canvas_width as f32, row_height)
-                    .color(row_color.0, row_color.1, row_color.2, row_color.3)
-                    .into(),
-            );
+            objects.push(Shape::rect(
+                0.0,
+                row_top,
+                params.canvas_width as f32,
+                row_height,
+                row_color,
+            ));
 
             match row {
                 RenderRowSnapshot::GroupHeader { name, .. } => {
-                    objects.push(
-                        Text::new()
-                            .text(name.clone())
-                            .position(10.0, row_top + (row_height / 2.0) - 6.0)
-                            .size(
-                                (params.canvas_width as f32 - 20.0).max(20.0),
-                                row_height.max(12.0),
-                            )
-                            .color(
-                                theme_colors.neutral_12.0,
-                                theme_colors.neutral_12.1,
-                                theme_colors.neutral_12.2,
-                                0.62,
-                            )
-                            .font_size(12.0)
-                            .family(Family::name("Inter"))
-                            .into(),
-                    );
+                    objects.push(Shape::Text(TextShape {
+                        text: name.clone(),
+                        x: 10.0,
+                        y: row_top + (row_height / 2.0) - 6.0,
+                        width: (params.canvas_width as f32 - 20.0).max(20.0),
+                        height: row_height.max(12.0),
+                        color: (
+                            theme_colors.neutral_12.0,
+                            theme_colors.neutral_12.1,
+                            theme_colors.neutral_12.2,
+                            0.62,
+                        ),
+                        font_size: 12.0,
+                        family: "Inter",
+                    }));
                 }
                 RenderRowSnapshot::Variable(variable) => {
                     if Self::is_analog_signal(variable) {
@@ -494,30 +536,53 @@ impl WaveformRenderer {
                             theme_colors,
                         );
                     }
+                    if let Some(differences) = &variable.differences {
+                        Self::add_interval_shading(
+                            objects,
+                            Self::visible_intervals(
+                                differences,
+                                params.viewport_start_ps,
+                                params.viewport_end_ps,
+                            ),
+                            row_top,
+                            row_height,
+                            params,
+                            theme_colors.difference_color,
+                        );
+                    }
+                    if let Some(value_matches) = &variable.value_matches {
+                        Self::add_interval_shading(
+                            objects,
+                            Self::visible_intervals(
+                                value_matches,
+                                params.viewport_start_ps,
+                                params.viewport_end_ps,
+                            ),
+                            row_top,
+                            row_height,
+                            params,
+                            theme_colors.value_match_color,
+                        );
+                    }
                 }
             }
 
             if divider_height > 0.0 {
                 let separator_y = (row_top + row_height + (divider_height / 2.0))
                     .min(params.canvas_height as f32);
-                objects.push(
-                    Rectangle::new()
-                        .position(0.0, separator_y - 0.5)
-                        .size(params.canvas_width as f32, 1.0)
-                        .color(
-                            theme_colors.separator_color.0,
-                            theme_colors.separator_color.1,
-                            theme_colors.separator_color.2,
-                            theme_colors.separator_color.3,
-                        )
-                        .into(),
-                );
+                objects.push(Shape::rect(
+                    0.0,
+                    separator_y - 0.5,
+                    params.canvas_width as f32,
+                    1.0,
+                    theme_colors.separator_color,
+                ));
             }
         }
     }
 
     fn add_signal_segments(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         variable: &VariableRenderSnapshot,
         row_top: f32,
         row_height: f32,
@@ -541,25 +606,19 @@ impl WaveformRenderer {
         let start_ps = params.viewport_start_ps;
         let end_ps = params.viewport_end_ps;
         let ps_per_pixel = range_ps as f64 / params.canvas_width.max(1) as f64;
-        let covered_end_ps = variable
-            .covered_time_range_ns
-            .map(|(_, end_ns)| end_ns.saturating_mul(PS_PER_NS));
+        let covered_end_ps = variable.covered_time_range_ps.map(|(_, end_ps)| end_ps);
         let mut pixel_states: Vec<Option<PixelValue>> = vec![None; width_px];
-        let transition_values: Vec<Rc<String>> = variable
-            .transitions
-            .iter()
-            .map(|t| Rc::new(t.value.clone()))
-            .collect();
+        let transitions = variable.transitions.as_ref();
+        // Transitions before the one holding the value at the viewport start are hidden
+        let first_visible = transitions.index_at_or_before(start_ps).unwrap_or(0);
 
-        for (index, transition) in variable.transitions.iter().enumerate() {
-            let mut segment_start = transition.time_ns.saturating_mul(PS_PER_NS);
+        for index in first_visible..transitions.len() {
+            let mut segment_start = transitions.time_ps(index);
             if segment_start >= end_ps {
                 break;
             }
-            let next_time = if index + 1 < variable.transitions.len() {
-                variable.transitions[index + 1]
-                    .time_ns
-                    .saturating_mul(PS_PER_NS)
+            let next_time = if index + 1 < transitions.len() {
+                transitions.time_ps(index + 1)
             } else {
                 covered_end_ps.unwrap_or(end_ps)
             };
@@ -580,7 +639,6 @@ impl WaveformRenderer {
 
             let start_px = ((segment_start - start_ps) as f64 / ps_per_pixel).floor() as isize;
             let end_px = ((segment_end - start_ps) as f64 / ps_per_pixel).ceil() as isize;
-            let value_rc = transition_values[index].clone();
 
             for px in start_px..end_px {
                 if px < 0 || px as usize >= width_px {
@@ -589,18 +647,46 @@ impl WaveformRenderer {
                 let entry = &mut pixel_states[px as usize];
                 match entry {
                     None => {
-                        *entry = Some(PixelValue::Single(value_rc.clone()));
+                        *entry = Some(PixelValue::Single(index));
                     }
                     Some(PixelValue::Single(existing)) => {
-                        if existing.as_ref() != value_rc.as_ref() {
+                        if !transitions.value_eq(*existing, index) {
                             *entry = Some(PixelValue::Mixed);
                         }
                     }
-                    Some(PixelValue::Mixed) => {}
+                    Some(PixelValue::Mixed) | Some(PixelValue::Busy { .. }) => {}
                 }
             }
         }
 
+        // Busy buckets hide transitions the backend folded away; never let them look idle
+        for bucket in variable.busy_buckets.iter() {
+            if bucket.end_ps < start_ps || bucket.start_ps >= end_ps {
+                continue;
+            }
+            let bucket_start = bucket.start_ps.max(start_ps);
+            let bucket_end = bucket.end_ps.min(end_ps);
+            let start_px = ((bucket_start - start_ps) as f64 / ps_per_pixel).floor() as isize;
+            let end_px =
+                (((bucket_end - start_ps) as f64 / ps_per_pixel).ceil() as isize).max(start_px + 1);
+            let has_unknown = bucket.has_unknown_states();
+
+            for px in start_px..end_px {
+                if px < 0 || px as usize >= width_px {
+                    continue;
+                }
+                let entry = &mut pixel_states[px as usize];
+                *entry = match entry {
+                    Some(PixelValue::Busy {
+                        has_unknown: existing,
+                    }) => Some(PixelValue::Busy {
+                        has_unknown: *existing || has_unknown,
+                    }),
+                    _ => Some(PixelValue::Busy { has_unknown }),
+                };
+            }
+        }
+
         let mut run_start = 0usize;
         let mut absolute_segment_index = 0usize;
         let mut current_state = if width_px > 0 {
@@ -616,11 +702,12 @@ impl WaveformRenderer {
                 None
             };
 
-            if !Self::pixel_state_equal(current_state.as_ref(), state.as_ref()) {
+            if !Self::pixel_state_equal(current_state.as_ref(), state.as_ref(), transitions) {
                 if let Some(pixel_state) = current_state.clone() {
                     Self::draw_pixel_run(
                         objects,
                         pixel_state.clone(),
+                        transitions,
                         run_start,
                         idx,
                         row_top,
@@ -629,7 +716,7 @@ impl WaveformRenderer {
                         absolute_segment_index,
                         variable.formatter,
                     );
-                    if !matches!(pixel_state, PixelValue::Mixed) {
+                    if matches!(pixel_state, PixelValue::Single(_)) {
                         absolute_segment_index += 1;
                     }
                 }
@@ -639,12 +726,62 @@ impl WaveformRenderer {
         }
     }
 
-    fn pixel_state_equal(a: Option<&PixelValue>, b: Option<&PixelValue>) -> bool {
+    /// Shade intervals that lie within the viewport across the row
+    fn add_interval_shading(
+        objects: &mut Vec<Shape>,
+        intervals: Vec<(u64, u64)>,
+        row_top: f32,
+        row_height: f32,
+        params: &RenderingParameters,
+        color: (u8, u8, u8, f32),
+    ) {
+        if params.viewport_end_ps <= params.viewport_start_ps {
+            return;
+        }
+        let start_ps = params.viewport_start_ps;
+        let ps_per_pixel =
+            (params.viewport_end_ps - start_ps) as f64 / params.canvas_width.max(1) as f64;
+
+        for (interval_start, interval_end) in intervals {
+            let start_x = ((interval_start - start_ps) as f64 / ps_per_pixel) as f32;
+            let end_x = ((interval_end - start_ps) as f64 / ps_per_pixel) as f32;
+            // Keep glitches narrower than a pixel visible
+            objects.push(Shape::rect(
+                start_x,
+                row_top + 1.0,
+                (end_x - start_x).max(1.0),
+                (row_height - 2.0).max(1.0),
+                color,
+            ));
+        }
+    }
+
+    /// Sorted, disjoint intervals clipped to `start_ps..end_ps`
+    fn visible_intervals(intervals: &[(u64, u64)], start_ps: u64, end_ps: u64) -> Vec<(u64, u64)> {
+        let first = intervals.partition_point(|&(_, interval_end)| interval_end <= start_ps);
+        intervals[first..]
+            .iter()
+            .take_while(|&&(interval_start, _)| interval_start < end_ps)
+            .map(|&(interval_start, interval_end)| {
+                (interval_start.max(start_ps), interval_end.min(end_ps))
+            })
+            .collect()
+    }
+
+    fn pixel_state_equal(
+        a: Option<&PixelValue>,
+        b: Option<&PixelValue>,
+        transitions: &PackedTransitions,
+    ) -> bool {
         match (a, b) {
             (None, None) => true,
             (Some(PixelValue::Mixed), Some(PixelValue::Mixed)) => true,
+            (
+                Some(PixelValue::Busy { has_unknown: a }),
+                Some(PixelValue::Busy { has_unknown: b }),
+            ) => a == b,
             (Some(PixelValue::Single(av)), Some(PixelValue::Single(bv))) => {
-                Rc::ptr_eq(av, bv) || av.as_ref() == bv.as_ref()
+                av == bv || transitions.value_eq(*av, *bv)
             }
             _ => false,
         }
@@ -652,8 +789,9 @@ impl WaveformRenderer {
 
     #[allow(clippy::too_many_arguments)]
     fn draw_pixel_run(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         pixel_state: PixelValue,
+        transitions: &PackedTransitions,
         start_px: usize,
         end_px: usize,
         row_top: f32,
@@ -673,35 +811,34 @@ impl WaveformRenderer {
         }
 
         match pixel_state {
-            PixelValue::Mixed => {
+            PixelValue::Mixed | PixelValue::Busy { .. } => {
                 let rect_top = row_top + 2.0;
                 let rect_height = row_height - 4.0;
-                let highlight = (226, 119, 40, 0.58);
-                objects.push(
-                    Rectangle::new()
-                        .position(rect_start_x, rect_top)
-                        .size(rect_width, rect_height.max(1.5))
-                        .color(highlight.0, highlight.1, highlight.2, highlight.3)
-                        .into(),
-                );
+                let highlight = match pixel_state {
+                    PixelValue::Busy { has_unknown: true } => theme_colors.state_unknown,
+                    _ => (226, 119, 40, 0.58),
+                };
+                objects.push(Shape::rect(
+                    rect_start_x,
+                    rect_top,
+                    rect_width,
+                    rect_height.max(1.5),
+                    highlight,
+                ));
 
                 if rect_start_x > 0.5 {
-                    objects.push(
-                        Rectangle::new()
-                            .position(rect_start_x, rect_top)
-                            .size(1.0, rect_height.max(1.5))
-                            .color(
-                                theme_colors.segment_divider_color.0,
-                                theme_colors.segment_divider_color.1,
-                                theme_colors.segment_divider_color.2,
-                                theme_colors.segment_divider_color.3,
-                            )
-                            .into(),
-                    );
+                    objects.push(Shape::rect(
+                        rect_start_x,
+                        rect_top,
+                        1.0,
+                        rect_height.max(1.5),
+                        theme_colors.segment_divider_color,
+                    ));
                 }
             }
-            PixelValue::Single(value) => {
-                let value_str = value.as_ref();
+            PixelValue::Single(index) => {
+                let value = transitions.value_string(index);
+                let value_str = value.as_str();
                 let state = Self::classify_signal_state(value_str);
                 if state == SignalState::Missing {
                     return;
@@ -735,66 +872,74 @@ impl WaveformRenderer {
                     Self::tint_color(base_color, theme_colors.segment_alt_multiplier)
                 };
 
-                objects.push(
-                    Rectangle::new()
-                        .position(rect_start_x, rect_top)
-                        .size(rect_width, rect_height.max(1.5))
-                        .color(color.0, color.1, color.2, color.3)
-                        .into(),
-                );
+                objects.push(Shape::rect(
+                    rect_start_x,
+                    rect_top,
+                    rect_width,
+                    rect_height.max(1.5),
+                    color,
+                ));
 
                 if rect_start_x > 0.5 {
-                    objects.push(
-                        Rectangle::new()
-                            .position(rect_start_x, rect_top)
-                            .size(1.0, rect_height.max(1.5))
-                            .color(
-                                theme_colors.segment_divider_color.0,
-                                theme_colors.segment_divider_color.1,
-                                theme_colors.segment_divider_color.2,
-                                theme_colors.segment_divider_color.3,
-                            )
-                            .into(),
-                    );
+                    objects.push(Shape::rect(
+                        rect_start_x,
+                        rect_top,
+                        1.0,
+                        rect_height.max(1.5),
+                        theme_colors.segment_divider_color,
+                    ));
                 }
 
                 if rect_width > 18.0 && row_height > 14.0 {
                     let text_color = theme_colors.neutral_12;
-                    let formatted_value =
-                        SignalValue::Present(value_str.clone()).get_formatted(&formatter);
+                    let formatted_value = Self::packed_value_text(transitions, index, formatter);
                     let text = Self::truncate_value_text(&formatted_value, rect_width as usize / 7);
                     let text_top = rect_top + rect_height / 2.0 - 6.0;
-                    objects.push(
-                        Text::new()
-                            .text(text)
-                            .position(rect_start_x + 4.0, text_top.max(row_top + 2.0))
-                            .size(rect_width - 8.0, rect_height.max(12.0))
-                            .color(text_color.0, text_color.1, text_color.2, text_color.3)
-                            .font_size(13.0)
-                            .family(Family::name("Fira Code"))
-                            .into(),
-                    );
+                    objects.push(Shape::Text(TextShape {
+                        text,
+                        x: rect_start_x + 4.0,
+                        y: text_top.max(row_top + 2.0),
+                        width: rect_width - 8.0,
+                        height: rect_height.max(12.0),
+                        color: text_color,
+                        font_size: 13.0,
+                        family: "Fira Code",
+                    }));
                 }
             }
         }
     }
 
+    /// Display text of transition `index`. Vectors are formatted straight from the packed
+    /// bits; single positions and text keep the special X / Z / U renderings.
+    fn packed_value_text(
+        transitions: &PackedTransitions,
+        index: usize,
+        formatter: VarFormat,
+    ) -> String {
+        if transitions.encoding.is_logic() && transitions.width > 1 {
+            formatter.format_packed(transitions, index)
+        } else {
+            SignalValue::present(transitions.value_string(index)).get_formatted(&formatter)
+        }
+    }
+
     fn is_analog_signal(variable: &VariableRenderSnapshot) -> bool {
         variable.signal_type.as_deref() == Some("Real")
     }
 
-    fn parse_analog_value(value_str: &str) -> Option<f64> {
-        let v: f64 = value_str.trim().parse().ok()?;
-        if v.is_nan() || v.is_infinite() {
-            None
-        } else {
-            Some(v)
-        }
+    /// Finite value of transition `index`; reals are read from the column without parsing
+    fn analog_value(transitions: &PackedTransitions, index: usize) -> Option<f64> {
+        transitions
+            .real_value(index)
+            .or_else(|| transitions.value_string(index).trim().parse().ok())
+            .filter(|value: &f64| value.is_finite())
     }
 
     fn compute_analog_range(
-        transitions: &[SignalTransition],
-        actual_time_range_ns: Option<(u64, u64)>,
+        transitions: &PackedTransitions,
+        busy_buckets: &[TransitionBucket],
+        actual_time_range_ps: Option<(u64, u64)>,
         viewport_start_ps: u64,
         viewport_end_ps: u64,
         analog_limits: Option<&AnalogLimits>,
@@ -812,13 +957,13 @@ impl WaveformRenderer {
         let mut min = f64::MAX;
         let mut max = f64::MIN;
         let mut found = false;
-        let actual_end_ps =
-            actual_time_range_ns.map(|(_, end_ns)| end_ns.saturating_mul(PS_PER_NS));
-        for (index, t) in transitions.iter().enumerate() {
-            let start_ps = t.time_ns.saturating_mul(PS_PER_NS);
+        let actual_end_ps = actual_time_range_ps.map(|(_, end_ps)| end_ps);
+        for index in 0..transitions.len() {
+            let start_ps = transitions.time_ps(index);
             let end_ps = transitions
+                .times_ps
                 .get(index + 1)
-                .map(|next| next.time_ns.saturating_mul(PS_PER_NS))
+                .copied()
                 .or(actual_end_ps)
                 .unwrap_or(viewport_end_ps);
 
@@ -828,7 +973,7 @@ impl WaveformRenderer {
             if start_ps >= viewport_end_ps {
                 break;
             }
-            if let Some(v) = Self::parse_analog_value(&t.value) {
+            if let Some(v) = Self::analog_value(transitions, index) {
                 if v < min {
                     min = v;
                 }
@@ -838,11 +983,21 @@ impl WaveformRenderer {
                 found = true;
             }
         }
+        for bucket in busy_buckets {
+            if bucket.end_ps < viewport_start_ps || bucket.start_ps >= viewport_end_ps {
+                continue;
+            }
+            if let (Some(bucket_min), Some(bucket_max)) = (bucket.min_value, bucket.max_value) {
+                min = min.min(bucket_min);
+                max = max.max(bucket_max);
+                found = true;
+            }
+        }
         if found { Some((min, max)) } else { None }
     }
 
     fn add_analog_signal(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         variable: &VariableRenderSnapshot,
         row_top: f32,
         row_height: f32,
@@ -851,7 +1006,7 @@ impl WaveformRenderer {
     ) {
         if Self::analog_visible_span_width_px(
             &variable.transitions,
-            variable.actual_time_range_ns,
+            variable.actual_time_range_ps,
             params.viewport_start_ps,
             params.viewport_end_ps,
             params.canvas_width,
@@ -864,7 +1019,8 @@ impl WaveformRenderer {
 
         let range = match Self::compute_analog_range(
             &variable.transitions,
-            variable.actual_time_range_ns,
+            &variable.busy_buckets,
+            variable.actual_time_range_ps,
             params.viewport_start_ps,
             params.viewport_end_ps,
             variable.analog_limits.as_ref(),
@@ -894,32 +1050,24 @@ impl WaveformRenderer {
             }
         };
 
-        let time_to_x = |time_ns: u64| -> f32 {
-            let time_ps = time_ns.saturating_mul(PS_PER_NS);
-            let ratio = (time_ps.saturating_sub(start_ps)) as f64 / range_ps;
-            (ratio * params.canvas_width as f64) as f32
-        };
         let time_ps_to_x = |time_ps: u64| -> f32 {
             let ratio = (time_ps.saturating_sub(start_ps)) as f64 / range_ps;
             (ratio * params.canvas_width as f64) as f32
         };
-        let actual_end_ps = variable
-            .actual_time_range_ns
-            .map(|(_, end_ns)| end_ns.saturating_mul(PS_PER_NS));
+        let actual_end_ps = variable.actual_time_range_ps.map(|(_, end_ps)| end_ps);
 
         let mut points: Vec<(f32, f32)> = Vec::new();
 
-        for (i, transition) in variable.transitions.iter().enumerate() {
-            let time_ps = transition.time_ns.saturating_mul(PS_PER_NS);
-            let val = match Self::parse_analog_value(&transition.value) {
+        let transitions = variable.transitions.as_ref();
+        for i in 0..transitions.len() {
+            let time_ps = transitions.time_ps(i);
+            let val = match Self::analog_value(transitions, i) {
                 Some(v) => v,
                 None => continue,
             };
 
-            let next_time_ps = if i + 1 < variable.transitions.len() {
-                variable.transitions[i + 1]
-                    .time_ns
-                    .saturating_mul(PS_PER_NS)
+            let next_time_ps = if i + 1 < transitions.len() {
+                transitions.time_ps(i + 1)
             } else {
                 actual_end_ps.unwrap_or(end_ps)
             };
@@ -937,11 +1085,11 @@ impl WaveformRenderer {
                 let x_start = if time_ps < start_ps {
                     0.0
                 } else {
-                    time_to_x(transition.time_ns)
+                    time_ps_to_x(time_ps)
                 };
                 points.push((x_start, y));
             } else {
-                let x = time_to_x(transition.time_ns);
+                let x = time_ps_to_x(time_ps);
                 points.push((x, points.last().unwrap().1));
                 points.push((x, y));
             }
@@ -956,19 +1104,39 @@ impl WaveformRenderer {
 
         if points.len() >= 2 {
             let color = theme_colors.value_analog_color;
-            objects.push(
-                Line::new()
-                    .points(&points)
-                    .width(1.5)
-                    .color(color.0, color.1, color.2, color.3)
-                    .into(),
-            );
+            objects.push(Shape::Polyline {
+                points,
+                width: 1.5,
+                color,
+            });
+        }
+
+        // Min/max envelope for spans the backend folded into LOD buckets
+        for bucket in variable.busy_buckets.iter() {
+            if bucket.end_ps < start_ps || bucket.start_ps >= end_ps {
+                continue;
+            }
+            let (Some(bucket_min), Some(bucket_max)) = (bucket.min_value, bucket.max_value) else {
+                continue;
+            };
+            let x_start = time_ps_to_x(bucket.start_ps.max(start_ps));
+            let x_end = time_ps_to_x(bucket.end_ps.min(end_ps)).max(x_start + 1.0);
+            let y_top = value_to_y(bucket_max.min(max_val));
+            let y_bottom = value_to_y(bucket_min.max(min_val));
+            let color = theme_colors.value_analog_color;
+            objects.push(Shape::rect(
+                x_start,
+                y_top,
+                x_end - x_start,
+                (y_bottom - y_top).max(1.5),
+                (color.0, color.1, color.2, color.3 * 0.6),
+            ));
         }
     }
 
     fn analog_visible_span_width_px(
-        transitions: &[SignalTransition],
-        actual_time_range_ns: Option<(u64, u64)>,
+        transitions: &PackedTransitions,
+        actual_time_range_ps: Option<(u64, u64)>,
         viewport_start_ps: u64,
         viewport_end_ps: u64,
         canvas_width_px: u32,
@@ -980,14 +1148,14 @@ impl WaveformRenderer {
         let mut visible_start_ps = u64::MAX;
         let mut visible_end_ps = 0_u64;
         let mut found_segment = false;
-        let actual_end_ps =
-            actual_time_range_ns.map(|(_, end_ns)| end_ns.saturating_mul(PS_PER_NS));
+        let actual_end_ps = actual_time_range_ps.map(|(_, end_ps)| end_ps);
 
-        for (index, transition) in transitions.iter().enumerate() {
-            let segment_start_ps = transition.time_ns.saturating_mul(PS_PER_NS);
+        for index in 0..transitions.len() {
+            let segment_start_ps = transitions.time_ps(index);
             let segment_end_ps = transitions
+                .times_ps
                 .get(index + 1)
-                .map(|next| next.time_ns.saturating_mul(PS_PER_NS))
+                .copied()
                 .or(actual_end_ps)
                 .unwrap_or(viewport_end_ps);
 
@@ -1019,30 +1187,27 @@ impl WaveformRenderer {
     }
 
     fn add_analog_zoom_hint(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         row_top: f32,
         row_height: f32,
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
     ) {
-        objects.push(
-            Text::new()
-                .text("Zoom in to inspect analog waveform")
-                .position(10.0, row_top + (row_height / 2.0) - 5.0)
-                .size(
-                    (params.canvas_width as f32 - 20.0).max(20.0),
-                    row_height.max(12.0),
-                )
-                .color(
-                    theme_colors.neutral_12.0,
-                    theme_colors.neutral_12.1,
-                    theme_colors.neutral_12.2,
-                    0.5,
-                )
-                .font_size(10.0)
-                .family(Family::name("Inter"))
-                .into(),
-        );
+        objects.push(Shape::Text(TextShape {
+            text: "Zoom in to inspect analog waveform".to_string(),
+            x: 10.0,
+            y: row_top + (row_height / 2.0) - 5.0,
+            width: (params.canvas_width as f32 - 20.0).max(20.0),
+            height: row_height.max(12.0),
+            color: (
+                theme_colors.neutral_12.0,
+                theme_colors.neutral_12.1,
+                theme_colors.neutral_12.2,
+                0.5,
+            ),
+            font_size: 10.0,
+            family: "Inter",
+        }));
     }
 
     fn classify_signal_state(value: &str) -> SignalState {
@@ -1123,7 +1288,7 @@ impl WaveformRenderer {
     }
 
     fn add_cursor_lines(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
     ) {
@@ -1136,14 +1301,27 @@ impl WaveformRenderer {
             if (params.viewport_start_ps..=params.viewport_end_ps).contains(&cursor_ps) {
                 let ratio = (cursor_ps - params.viewport_start_ps) as f64 / range_ps;
                 let x = (ratio * params.canvas_width as f64) as f32;
-                let (r, g, b, a) = theme_colors.cursor_color;
-                objects.push(
-                    Rectangle::new()
-                        .position(x - 1.0, 0.0)
-                        .size(3.0, params.canvas_height as f32)
-                        .color(r, g, b, a)
-                        .into(),
-                );
+                objects.push(Shape::rect(
+                    x - 1.0,
+                    0.0,
+                    3.0,
+                    params.canvas_height as f32,
+                    theme_colors.cursor_color,
+                ));
+            }
+        }
+
+        if let Some(secondary_ps) = params.secondary_cursor_ps {
+            if (params.viewport_start_ps..=params.viewport_end_ps).contains(&secondary_ps) {
+                let ratio = (secondary_ps - params.viewport_start_ps) as f64 / range_ps;
+                let x = (ratio * params.canvas_width as f64) as f32;
+                objects.push(Shape::rect(
+                    x - 1.0,
+                    0.0,
+                    2.0,
+                    params.canvas_height as f32,
+                    theme_colors.secondary_cursor_color,
+                ));
             }
         }
 
@@ -1158,20 +1336,14 @@ impl WaveformRenderer {
                 while y < params.canvas_height as f32 {
                     let remaining = params.canvas_height as f32 - y;
                     let segment_height = remaining.min(dash_height);
-                    objects.push(
-                        Rectangle::new()
-                            .position(x - 1.0, y)
-                            .size(2.0, segment_height)
-                            .color(color.0, color.1, color.2, color.3)
-                            .into(),
-                    );
+                    objects.push(Shape::rect(x - 1.0, y, 2.0, segment_height, color));
                     y += dash_height + gap_height;
                 }
             }
         }
     }
 
-    fn add_marker_lines(objects: &mut Vec<Object2d>, params: &RenderingParameters) {
+    fn add_marker_lines(objects: &mut Vec<Shape>, params: &RenderingParameters) {
         if params.viewport_end_ps <= params.viewport_start_ps || params.markers.is_empty() {
             return;
         }
@@ -1200,18 +1372,7 @@ impl WaveformRenderer {
             let ratio = (marker.time_ps - params.viewport_start_ps) as f64 / range_ps;
             let x = (ratio * params.canvas_width as f64) as f32;
 
-            objects.push(
-                Rectangle::new()
-                    .position(x - 0.5, 0.0)
-                    .size(1.0, canvas_height)
-                    .color(
-                        marker_color.0,
-                        marker_color.1,
-                        marker_color.2,
-                        marker_color.3,
-                    )
-                    .into(),
-            );
+            objects.push(Shape::rect(x - 0.5, 0.0, 1.0, canvas_height, marker_color));
 
             let label_width = (marker.name.chars().count() as f32 * 6.5 + 16.0).clamp(36.0, 180.0);
             let label_x = (x + 4.0).min((params.canvas_width as f32 - label_width - 2.0).max(0.0));
@@ -1223,16 +1384,16 @@ impl WaveformRenderer {
                 let label_y = (canvas_height - lane_height - 2.0)
                     - lane_index as f32 * (lane_height + lane_gap);
                 if label_y >= timeline_y && label_y + lane_height <= canvas_height {
-                    objects.push(
-                        Text::new()
-                            .text(marker.name.clone())
-                            .position(label_x, label_y)
-                            .size(label_width, lane_height)
-                            .color(marker_color.0, marker_color.1, marker_color.2, 1.0)
-                            .font_size(11.0)
-                            .family(Family::name("Inter"))
-                            .into(),
-                    );
+                    objects.push(Shape::Text(TextShape {
+                        text: marker.name.clone(),
+                        x: label_x,
+                        y: label_y,
+                        width: label_width,
+                        height: lane_height,
+                        color: (marker_color.0, marker_color.1, marker_color.2, 1.0),
+                        font_size: 11.0,
+                        family: "Inter",
+                    }));
                     *lane_end = label_x + label_width + 6.0;
                 }
             }
@@ -1240,7 +1401,7 @@ impl WaveformRenderer {
     }
 
     fn add_timeline_row(
-        objects: &mut Vec<Object2d>,
+        objects: &mut Vec<Shape>,
         params: &RenderingParameters,
         theme_colors: &ThemeColors,
     ) {
@@ -1255,18 +1416,13 @@ impl WaveformRenderer {
             .unwrap_or(0.0);
         let timeline_height = (params.canvas_height as f32 - timeline_y).max(1.0);
 
-        objects.push(
-            Rectangle::new()
-                .position(0.0, timeline_y)
-                .size(params.canvas_width as f32, timeline_height)
-                .color(
-                    theme_colors.timeline_row_bg.0,
-                    theme_colors.timeline_row_bg.1,
-                    theme_colors.timeline_row_bg.2,
-                    theme_colors.timeline_row_bg.3,
-                )
-                .into(),
-        );
+        objects.push(Shape::rect(
+            0.0,
+            timeline_y,
+            params.canvas_width as f32,
+            timeline_height,
+            theme_colors.timeline_row_bg,
+        ));
 
         let start_s = params.viewport_start_ps as f64 / PS_PER_SECOND as f64;
         let end_s = params.viewport_end_ps as f64 / PS_PER_SECOND as f64;
@@ -1314,52 +1470,37 @@ impl WaveformRenderer {
         let minimum_label_gap = 56.0;
 
         for (x, label) in &ticks {
-            objects.push(
-                Rectangle::new()
-                    .position(*x, timeline_y)
-                    .size(1.0, 8.0)
-                    .color(
-                        theme_colors.neutral_12.0,
-                        theme_colors.neutral_12.1,
-                        theme_colors.neutral_12.2,
-                        theme_colors.neutral_12.3,
-                    )
-                    .into(),
-            );
+            objects.push(Shape::rect(
+                *x,
+                timeline_y,
+                1.0,
+                8.0,
+                theme_colors.neutral_12,
+            ));
             if timeline_y > 0.0 {
-                objects.push(
-                    Rectangle::new()
-                        .position(*x, 0.0)
-                        .size(1.0, timeline_y)
-                        .color(
-                            theme_colors.grid_color.0,
-                            theme_colors.grid_color.1,
-                            theme_colors.grid_color.2,
-                            theme_colors.grid_color.3,
-                        )
-                        .into(),
-                );
+                objects.push(Shape::rect(
+                    *x,
+                    0.0,
+                    1.0,
+                    timeline_y,
+                    theme_colors.grid_color,
+                ));
             }
 
             if let Some(text) = label {
                 let approx_width = (text.len() as f32 * 6.5).max(35.0);
                 let left_edge = x - approx_width / 2.0;
                 if left_edge > last_label_right + minimum_label_gap {
-                    objects.push(
-                        Text::new()
-                            .text(text.clone())
-                            .position(left_edge, timeline_y + 15.0)
-                            .size(approx_width, timeline_height - 15.0)
-                            .color(
-                                theme_colors.neutral_12.0,
-                                theme_colors.neutral_12.1,
-                                theme_colors.neutral_12.2,
-                                theme_colors.neutral_12.3,
-                            )
-                            .font_size(11.0)
-                            .family(Family::name("Inter"))
-                            .into(),
-                    );
+                    objects.push(Shape::Text(TextShape {
+                        text: text.clone(),
+                        x: left_edge,
+                        y: timeline_y + 15.0,
+                        width: approx_width,
+                        height: timeline_height - 15.0,
+                        color: theme_colors.neutral_12,
+                        font_size: 11.0,
+                        family: "Inter",
+                    }));
                     last_label_right = left_edge + approx_width;
                 }
             }
@@ -1448,6 +1589,7 @@ impl WaveformRenderer {
                 grid_color: (36, 50, 72, 0.35),
                 separator_color: (42, 48, 58, 0.6),
                 cursor_color: (59, 130, 246, 0.8),
+                secondary_cursor_color: (249, 115, 22, 0.85),
                 segment_divider_color: (3, 4, 9, 1.0),
                 value_low_color: (18, 50, 140, 1.0),
                 value_high_color: (16, 96, 72, 1.0),
@@ -1457,6 +1599,8 @@ impl WaveformRenderer {
                 state_uninitialized: (220, 38, 38, 0.65),
                 segment_alt_multiplier: 0.45,
                 value_analog_color: (40, 170, 200, 0.95),
+                difference_color: (236, 72, 153, 0.38),
+                value_match_color: (250, 204, 21, 0.35),
             },
             NovyUITheme::Light => ThemeColors {
                 row_even_bg: (248, 250, 255, 1.0),
@@ -1466,6 +1610,7 @@ impl WaveformRenderer {
                 grid_color: (158, 173, 194, 0.35),
                 separator_color: (135, 148, 170, 0.6),
                 cursor_color: (37, 99, 235, 0.8),
+                secondary_cursor_color: (234, 88, 12, 0.85),
                 segment_divider_color: (206, 212, 224, 1.0),
                 value_low_color: (110, 148, 255, 1.0),
                 value_high_color: (54, 200, 160, 1.0),
@@ -1475,6 +1620,8 @@ impl WaveformRenderer {
                 state_uninitialized: (220, 38, 38, 0.6),
                 segment_alt_multiplier: 1.1,
                 value_analog_color: (20, 140, 180, 0.95),
+                difference_color: (219, 39, 119, 0.3),
+                value_match_color: (234, 179, 8, 0.35),
             },
         }
     }
@@ -1509,7 +1656,7 @@ impl TimeLabelUnit {
 #[cfg(test)]
 mod tests {
     use super::WaveformRenderer;
-    use shared::{AnalogLimits, SignalTransition};
+    use shared::{AnalogLimits, PackedTransitions, TransitionBucket};
 
     #[test]
     fn rounds_small_values_to_friendly_steps() {
@@ -1528,13 +1675,11 @@ mod tests {
 
     #[test]
     fn manual_analog_limits_override_visible_window_range() {
-        let transitions = vec![
-            SignalTransition::new(0, "1.0".to_string()),
-            SignalTransition::new(10, "3.0".to_string()),
-        ];
+        let transitions = PackedTransitions::from_pairs(&[(0, "1.0"), (10, "3.0")]);
 
         let range = WaveformRenderer::compute_analog_range(
             &transitions,
+            &[],
             None,
             0,
             20_000,
@@ -1546,14 +1691,12 @@ mod tests {
 
     #[test]
     fn auto_analog_range_uses_only_visible_window() {
-        let transitions = vec![
-            SignalTransition::new(0, "1.0".to_string()),
-            SignalTransition::new(10, "10.0".to_string()),
-            SignalTransition::new(20, "100.0".to_string()),
-        ];
+        let transitions =
+            PackedTransitions::from_pairs(&[(0, "1.0"), (10_000, "10.0"), (20_000, "100.0")]);
 
         let range = WaveformRenderer::compute_analog_range(
             &transitions,
+            &[],
             None,
             10_000,
             20_000,
@@ -1563,16 +1706,40 @@ mod tests {
         assert_eq!(range, Some((10.0, 10.0)));
     }
 
+    #[test]
+    fn auto_analog_range_includes_busy_bucket_extremes() {
+        let transitions = PackedTransitions::from_pairs(&[(0, "1.0"), (10_000, "2.0")]);
+        let buckets = vec![TransitionBucket {
+            start_ps: 0,
+            end_ps: 10_000,
+            transition_count: 50,
+            first_value: "1.0".to_string(),
+            last_value: "2.0".to_string(),
+            has_unknown: false,
+            has_high_impedance: false,
+            min_value: Some(-4.0),
+            max_value: Some(9.0),
+        }];
+
+        let range = WaveformRenderer::compute_analog_range(
+            &transitions,
+            &buckets,
+            None,
+            0,
+            20_000,
+            Some(&AnalogLimits::auto()),
+        );
+
+        assert_eq!(range, Some((-4.0, 9.0)));
+    }
+
     #[test]
     fn analog_visible_span_width_tracks_compressed_segments() {
-        let transitions = vec![
-            SignalTransition::new(0, "0.0".to_string()),
-            SignalTransition::new(1, "1.0".to_string()),
-        ];
+        let transitions = PackedTransitions::from_pairs(&[(0, "0.0"), (1_000, "1.0")]);
 
         let visible_width = WaveformRenderer::analog_visible_span_width_px(
             &transitions,
-            Some((0, 1)),
+            Some((0, 1_000)),
             0,
             1_000_000,
             100,
@@ -1581,4 +1748,17 @@ mod tests {
         assert!(visible_width.is_some());
         assert!(visible_width.unwrap() < 2.0);
     }
+
+    #[test]
+    fn value_matches_are_clipped_to_the_view() {
+        let matches = [(0, 10), (20, 30), (50, u64::MAX)];
+        assert_eq!(
+            WaveformRenderer::visible_intervals(&matches, 25, 60),
+            vec![(25, 30), (50, 60)]
+        );
+        assert_eq!(
+            WaveformRenderer::visible_intervals(&matches, 10, 20),
+            Vec::<(u64, u64)>::new()
+        );
+    }
 }
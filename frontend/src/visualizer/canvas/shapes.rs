@@ -0,0 +1,178 @@
+//! Drawing primitives of the waveform view.
+//!
+//! `WaveformRenderer` describes a frame as a list of `Shape`s. The canvas turns them into
+//! Fast2D objects and the vector export writes them out as SVG, so both show the same picture.
+
+use fast2d::{Family, Line, Object2d, Rectangle, Text};
+use std::fmt::Write;
+
+pub type Rgba = (u8, u8, u8, f32);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        color: Rgba,
+    },
+    Polyline {
+        points: Vec<(f32, f32)>,
+        width: f32,
+        color: Rgba,
+    },
+    Text(TextShape),
+}
+
+/// Single-line label anchored at the top-left corner of its box
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextShape {
+    pub text: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub color: Rgba,
+    pub font_size: f32,
+    pub family: &'static str,
+}
+
+impl Shape {
+    pub fn rect(x: f32, y: f32, width: f32, height: f32, color: Rgba) -> Self {
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            color,
+        }
+    }
+
+    pub fn to_object2d(&self) -> Object2d {
+        match self {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => Rectangle::new()
+                .position(*x, *y)
+                .size(*width, *height)
+                .color(color.0, color.1, color.2, color.3)
+                .into(),
+            Shape::Polyline {
+                points,
+                width,
+                color,
+            } => Line::new()
+                .points(points)
+                .width(*width)
+                .color(color.0, color.1, color.2, color.3)
+                .into(),
+            Shape::Text(text) => Text::new()
+                .text(text.text.clone())
+                .position(text.x, text.y)
+                .size(text.width, text.height)
+                .color(text.color.0, text.color.1, text.color.2, text.color.3)
+                .font_size(text.font_size)
+                .family(Family::name(text.family))
+                .into(),
+        }
+    }
+}
+
+/// Standalone SVG document of `shapes` drawn on a `width` x `height` px area
+pub fn svg_document(shapes: &[Shape], width: u32, height: u32) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    for shape in shapes {
+        let _ = match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" {}/>"#,
+                svg_paint("fill", *color)
+            ),
+            Shape::Polyline {
+                points,
+                width,
+                color,
+            } => {
+                let points: Vec<String> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+                writeln!(
+                    svg,
+                    r#"<polyline points="{}" fill="none" stroke-width="{width}" {}/>"#,
+                    points.join(" "),
+                    svg_paint("stroke", *color)
+                )
+            }
+            Shape::Text(text) => writeln!(
+                svg,
+                r#"<text x="{}" y="{}" font-family="{}" font-size="{}" {}>{}</text>"#,
+                text.x,
+                // SVG places text on its baseline, Fast2D at the top of the box
+                text.y + text.font_size,
+                text.family,
+                text.font_size,
+                svg_paint("fill", text.color),
+                escape_xml(&text.text)
+            ),
+        };
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn svg_paint(attribute: &str, (r, g, b, a): Rgba) -> String {
+    if a >= 1.0 {
+        format!(r#"{attribute}="rgb({r},{g},{b})""#)
+    } else {
+        format!(r#"{attribute}="rgb({r},{g},{b})" {attribute}-opacity="{a}""#)
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Shape, TextShape, svg_document};
+
+    #[test]
+    fn svg_document_writes_shapes_in_order() {
+        let shapes = vec![
+            Shape::rect(0.0, 0.0, 100.0, 30.0, (6, 9, 14, 1.0)),
+            Shape::Text(TextShape {
+                text: "a<b".to_string(),
+                x: 4.0,
+                y: 9.0,
+                width: 92.0,
+                height: 12.0,
+                color: (253, 253, 253, 0.5),
+                font_size: 13.0,
+                family: "Fira Code",
+            }),
+        ];
+        assert_eq!(
+            svg_document(&shapes, 100, 60),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"60\" viewBox=\"0 0 100 60\">\n\
+             <rect x=\"0\" y=\"0\" width=\"100\" height=\"30\" fill=\"rgb(6,9,14)\"/>\n\
+             <text x=\"4\" y=\"22\" font-family=\"Fira Code\" font-size=\"13\" fill=\"rgb(253,253,253)\" fill-opacity=\"0.5\">a&lt;b</text>\n\
+             </svg>\n"
+        );
+    }
+}
@@ -246,6 +246,13 @@ impl WaveformCanvas {
         self.canvas_ready.set_neq(true);
     }
 
+    /// SVG picture of the current view; `None` until the timeline produced a render state
+    pub fn export_svg(&self, width_px: u32) -> Option<String> {
+        let state = self.render_state_store.get_cloned()?;
+        let params = Self::render_params_from_state(&state, self.current_theme.get_cloned());
+        Some(WaveformRenderer::render_svg(params, width_px))
+    }
+
     fn render_params_from_state(state: &TimelineRenderState, theme: Theme) -> RenderingParameters {
         RenderingParameters {
             canvas_width: state.canvas_width_px,
@@ -253,6 +260,7 @@ impl WaveformCanvas {
             viewport_start_ps: state.viewport_start.picoseconds(),
             viewport_end_ps: state.viewport_end.picoseconds(),
             cursor_position_ps: Some(state.cursor.picoseconds()),
+            secondary_cursor_ps: state.secondary_cursor.map(|time| time.picoseconds()),
             zoom_center_ps: Some(state.zoom_center.picoseconds()),
             theme: Self::map_theme(theme),
             rows: state
@@ -273,12 +281,15 @@ impl WaveformCanvas {
                         unique_id: series.unique_id.clone(),
                         formatter: series.formatter,
                         transitions: Arc::clone(&series.transitions),
+                        busy_buckets: Arc::clone(&series.busy_buckets),
                         cursor_value: series.cursor_value.clone(),
-                        actual_time_range_ns: series.actual_time_range_ns,
-                        covered_time_range_ns: series.covered_time_range_ns,
+                        actual_time_range_ps: series.actual_time_range_ps,
+                        covered_time_range_ps: series.covered_time_range_ps,
                         signal_type: series.signal_type.clone(),
                         row_height: series.row_height,
                         analog_limits: series.analog_limits.clone(),
+                        differences: series.differences.clone(),
+                        value_matches: series.value_matches.clone(),
                     }),
                 }
                 })
@@ -401,7 +412,11 @@ pub fn waveform_canvas(
                                 let time = crate::visualizer::timeline::time_domain::TimePs::from_picoseconds(
                                     time_ps,
                                 );
-                                timeline_for_click.set_cursor_clamped(time);
+                                if event.alt_key() {
+                                    timeline_for_click.set_secondary_cursor(Some(time));
+                                } else {
+                                    timeline_for_click.set_cursor_clamped(time);
+                                }
                                 timeline_for_hover.set_pointer_hover(Some(TimelinePointerHover {
                                     normalized_x,
                                     normalized_y,
@@ -37,10 +37,7 @@ impl MaximumTimelineRange {
             .map(|file| FileRangeData {
                 path: file.path.clone(),
                 time_range: match &file.state {
-                    FileState::Loaded(wf) => match (wf.min_time_ns, wf.max_time_ns) {
-                        (Some(min), Some(max)) => Some((min, max)),
-                        _ => None,
-                    },
+                    FileState::Loaded(wf) => wf.time_range_ps(),
                     _ => None,
                 },
             })
@@ -88,14 +85,14 @@ impl MaximumTimelineRange {
                 continue;
             }
 
-            if let Some((start_ns, end_ns)) = file_data.time_range {
-                let start = TimePs::from_nanos(start_ns);
+            if let Some((start_ps, end_ps)) = file_data.time_range {
+                let start = TimePs::from_picoseconds(start_ps);
                 min_time = Some(match min_time {
                     Some(current) => current.min(start),
                     None => start,
                 });
 
-                let end = TimePs::from_nanos(end_ns);
+                let end = TimePs::from_picoseconds(end_ps);
                 max_time = Some(match max_time {
                     Some(current) => current.max(end),
                     None => end,
@@ -9,7 +9,8 @@ use crate::visualizer::timeline::time_domain::{
 use gloo_timers::callback::Timeout;
 use js_sys::Date;
 use shared::{
-    SignalTransition, SignalValue, UnifiedSignalData, UnifiedSignalRequest, UpMsg, VarFormat,
+    PackedTransitions, SignalDifferenceRequest, SignalPayloadEncoding, SignalValue,
+    TransitionBucket, UnifiedSignalData, UnifiedSignalRequest, UpMsg, VarFormat,
 };
 use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashSet, VecDeque};
@@ -28,19 +29,29 @@ const CURSOR_STEP_RATIO: f64 = 0.04;
 const CURSOR_FAST_MULTIPLIER: u64 = 4;
 const CACHE_HIT_THRESHOLD: f64 = 0.8;
 const CACHE_MAX_SEGMENTS_PER_VARIABLE: usize = 2;
+/// Least time between two difference shading requests while the viewport moves
+const DIFFERENCE_REQUEST_INTERVAL_MS: u32 = 150;
+/// Id prefix of the queries for second cursor values, told apart from timeline requests
+const SECONDARY_CURSOR_REQUEST_PREFIX: &str = "secondary-cursor-";
 
 #[derive(Clone, Debug)]
 pub struct TimelineVariableSeries {
     pub unique_id: String,
     pub formatter: VarFormat,
-    pub transitions: Arc<Vec<SignalTransition>>,
+    pub transitions: Arc<PackedTransitions>,
+    pub busy_buckets: Arc<Vec<TransitionBucket>>,
     pub total_transitions: usize,
-    pub actual_time_range_ns: Option<(u64, u64)>,
-    pub covered_time_range_ns: Option<(u64, u64)>,
+    pub actual_time_range_ps: Option<(u64, u64)>,
+    pub covered_time_range_ps: Option<(u64, u64)>,
     pub cursor_value: Option<SignalValue>,
     pub signal_type: Option<String>,
     pub row_height: u32,
     pub analog_limits: Option<shared::AnalogLimits>,
+    /// Intervals where the variable differs from its counterpart in
+    /// `SelectedVariable::compare_file`
+    pub differences: Option<Arc<Vec<(u64, u64)>>>,
+    /// Intervals holding the value searched for in this variable
+    pub value_matches: Option<Arc<Vec<(u64, u64)>>>,
 }
 
 impl TimelineVariableSeries {
@@ -48,14 +59,17 @@ impl TimelineVariableSeries {
         Self {
             unique_id,
             formatter,
-            transitions: Arc::new(Vec::new()),
+            transitions: Arc::new(PackedTransitions::default()),
+            busy_buckets: Arc::new(Vec::new()),
             total_transitions: 0,
-            actual_time_range_ns: None,
-            covered_time_range_ns: None,
+            actual_time_range_ps: None,
+            covered_time_range_ps: None,
             cursor_value: None,
             signal_type: None,
             row_height: 30,
             analog_limits: None,
+            differences: None,
+            value_matches: None,
         }
     }
 }
@@ -64,13 +78,16 @@ impl TimelineVariableSeries {
 struct TimelineVariableStructure {
     unique_id: String,
     formatter: VarFormat,
-    transitions: Arc<Vec<SignalTransition>>,
+    transitions: Arc<PackedTransitions>,
+    busy_buckets: Arc<Vec<TransitionBucket>>,
     total_transitions: usize,
-    actual_time_range_ns: Option<(u64, u64)>,
-    covered_time_range_ns: Option<(u64, u64)>,
+    actual_time_range_ps: Option<(u64, u64)>,
+    covered_time_range_ps: Option<(u64, u64)>,
     cursor_value: Option<SignalValue>,
     signal_type: Option<String>,
     analog_limits: Option<shared::AnalogLimits>,
+    differences: Option<Arc<Vec<(u64, u64)>>>,
+    value_matches: Option<Arc<Vec<(u64, u64)>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -97,6 +114,7 @@ struct TimelineLayoutSnapshot {
     viewport_start: TimePs,
     viewport_end: TimePs,
     cursor: TimePs,
+    secondary_cursor: Option<TimePs>,
     zoom_center: TimePs,
     canvas_width_px: u32,
     canvas_height_px: u32,
@@ -110,6 +128,7 @@ impl Default for TimelineLayoutSnapshot {
             viewport_start: TimePs::ZERO,
             viewport_end: TimePs::from_nanos(1_000_000_000),
             cursor: TimePs::ZERO,
+            secondary_cursor: None,
             zoom_center: TimePs::ZERO,
             canvas_width_px: 1,
             canvas_height_px: 1,
@@ -139,6 +158,7 @@ pub struct TimelineRenderState {
     pub viewport_start: TimePs,
     pub viewport_end: TimePs,
     pub cursor: TimePs,
+    pub secondary_cursor: Option<TimePs>,
     pub zoom_center: TimePs,
     pub canvas_width_px: u32,
     pub canvas_height_px: u32,
@@ -154,6 +174,7 @@ impl Default for TimelineRenderState {
             viewport_start: TimePs::ZERO,
             viewport_end: TimePs::from_nanos(1_000_000_000),
             cursor: TimePs::ZERO,
+            secondary_cursor: None,
             zoom_center: TimePs::ZERO,
             canvas_width_px: 1,
             canvas_height_px: 1,
@@ -224,15 +245,24 @@ struct RequestContext {
 
 #[derive(Clone, Debug, Default)]
 struct VariableSeriesData {
-    transitions: Arc<Vec<SignalTransition>>,
+    transitions: Arc<PackedTransitions>,
+    busy_buckets: Arc<Vec<TransitionBucket>>,
     total_transitions: usize,
-    actual_time_range_ns: Option<(u64, u64)>,
-    covered_time_range_ns: Option<(u64, u64)>,
+    actual_time_range_ps: Option<(u64, u64)>,
+    covered_time_range_ps: Option<(u64, u64)>,
+}
+
+/// Backend lookup of second cursor values outside the loaded windows
+#[derive(Clone, Debug)]
+struct SecondaryCursorQuery {
+    request_id: String,
+    time_ps: u64,
+    unique_ids: HashSet<String>,
 }
 
 #[derive(Clone, Debug)]
 struct RequestedWindow {
-    range_ns: (u64, u64),
+    range_ps: (u64, u64),
     lod_bucket: u64,
 }
 
@@ -241,7 +271,7 @@ struct RequestFingerprintSignal {
     file_path: String,
     scope_path: String,
     variable_name: String,
-    time_range_ns: Option<(u64, u64)>,
+    time_range_ps: Option<(u64, u64)>,
     max_transitions: Option<usize>,
     format: VarFormat,
 }
@@ -249,7 +279,21 @@ struct RequestFingerprintSignal {
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct RequestFingerprint {
     signals: Vec<RequestFingerprintSignal>,
-    cursor_time_ns: Option<u64>,
+    cursor_time_ps: Option<u64>,
+}
+
+impl RequestFingerprint {
+    /// True when this request asks for every signal of `older`, so the older response
+    /// would only be discarded or overwritten
+    fn supersedes(&self, older: &RequestFingerprint) -> bool {
+        older.signals.iter().all(|old| {
+            self.signals.iter().any(|new| {
+                new.file_path == old.file_path
+                    && new.scope_path == old.scope_path
+                    && new.variable_name == old.variable_name
+            })
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -270,25 +314,27 @@ pub struct TimelineDebugMetrics {
     pub layout_render_count: u64,
     pub request_send_count: u64,
     pub request_deduped_count: u64,
+    pub request_cancelled_count: u64,
     pub startup_initial_query_send_count: u64,
 }
 
 #[derive(Clone, Debug)]
 struct TimelineCacheEntry {
     lod_bucket: u64,
-    range_ns: (u64, u64),
-    transitions: Arc<Vec<SignalTransition>>,
+    range_ps: (u64, u64),
+    transitions: Arc<PackedTransitions>,
+    busy_buckets: Arc<Vec<TransitionBucket>>,
     total_transitions: usize,
 }
 
 impl TimelineCacheEntry {
-    fn coverage_ratio(&self, range_ns: (u64, u64)) -> f64 {
-        let requested = range_ns.1.saturating_sub(range_ns.0);
+    fn coverage_ratio(&self, range_ps: (u64, u64)) -> f64 {
+        let requested = range_ps.1.saturating_sub(range_ps.0);
         if requested == 0 {
             return 0.0;
         }
-        let overlap_start = self.range_ns.0.max(range_ns.0);
-        let overlap_end = self.range_ns.1.min(range_ns.1);
+        let overlap_start = self.range_ps.0.max(range_ps.0);
+        let overlap_end = self.range_ps.1.min(range_ps.1);
         if overlap_end <= overlap_start {
             return 0.0;
         }
@@ -307,7 +353,7 @@ impl TimelineWindowCache {
         &self,
         unique_id: &str,
         lod_bucket: u64,
-        range_ns: (u64, u64),
+        range_ps: (u64, u64),
     ) -> Option<(TimelineCacheEntry, f64)> {
         let slots = self.entries.get(unique_id)?;
         let mut best: Option<(TimelineCacheEntry, f64)> = None;
@@ -315,7 +361,7 @@ impl TimelineWindowCache {
             if entry.lod_bucket != lod_bucket {
                 continue;
             }
-            let coverage = entry.coverage_ratio(range_ns);
+            let coverage = entry.coverage_ratio(range_ps);
             if coverage >= CACHE_HIT_THRESHOLD {
                 match &mut best {
                     Some((_, best_cov)) if coverage <= *best_cov => {}
@@ -341,6 +387,17 @@ impl TimelineWindowCache {
     }
 }
 
+/// Difference shading of a variable compared with `SelectedVariable::compare_file`
+#[derive(Clone, Debug)]
+struct SignalDifferences {
+    compare_unique_id: String,
+    /// Range and resolution of the latest request; answers for another range are stale
+    range_ps: (u64, u64),
+    resolution_ps: u64,
+    intervals: Option<Arc<Vec<(u64, u64)>>>,
+    error: Option<String>,
+}
+
 fn ranges_overlap(a: (u64, u64), b: (u64, u64)) -> bool {
     a.0 < b.1 && b.0 < a.1
 }
@@ -349,85 +406,111 @@ fn range_contains(container: (u64, u64), inner: (u64, u64)) -> bool {
     container.0 <= inner.0 && container.1 >= inner.1
 }
 
+/// Merge two packed columns in time order; at equal times the later column wins.
+/// Columns of different layouts (e.g. after a reload changed the signal) are not merged.
 fn merge_signal_transitions(
-    existing: &[SignalTransition],
-    new_data: &[SignalTransition],
-) -> Vec<SignalTransition> {
-    let mut merged = Vec::with_capacity(existing.len() + new_data.len());
+    existing: &PackedTransitions,
+    new_data: &PackedTransitions,
+) -> PackedTransitions {
+    if !same_layout(existing, new_data) {
+        return new_data.clone();
+    }
+    let mut merged = new_data.empty_like();
     let mut i = 0;
     let mut j = 0;
 
     while i < existing.len() && j < new_data.len() {
-        if existing[i].time_ns <= new_data[j].time_ns {
-            push_transition(&mut merged, &existing[i]);
+        if existing.time_ps(i) <= new_data.time_ps(j) {
+            push_transition(&mut merged, existing, i);
             i += 1;
         } else {
-            push_transition(&mut merged, &new_data[j]);
+            push_transition(&mut merged, new_data, j);
             j += 1;
         }
     }
 
     while i < existing.len() {
-        push_transition(&mut merged, &existing[i]);
+        push_transition(&mut merged, existing, i);
         i += 1;
     }
 
     while j < new_data.len() {
-        push_transition(&mut merged, &new_data[j]);
+        push_transition(&mut merged, new_data, j);
         j += 1;
     }
 
     merged
 }
 
-fn push_transition(target: &mut Vec<SignalTransition>, transition: &SignalTransition) {
-    if let Some(last) = target.last() {
-        if last.time_ns == transition.time_ns {
-            if last.value == transition.value {
-                return;
-            } else {
-                target.pop();
-            }
-        }
+fn same_layout(a: &PackedTransitions, b: &PackedTransitions) -> bool {
+    a.encoding == b.encoding && a.width == b.width
+}
+
+fn push_transition(target: &mut PackedTransitions, source: &PackedTransitions, index: usize) {
+    let time_ps = source.time_ps(index);
+    if target.times_ps.last() == Some(&time_ps) {
+        target.pop();
     }
-    target.push(transition.clone());
+    target.push_from(source, index, time_ps);
+}
+
+/// Combine LOD busy buckets; fresh buckets replace older ones inside the range they were fetched for.
+fn merge_busy_buckets(
+    existing: &[TransitionBucket],
+    new_data: Vec<TransitionBucket>,
+    replaced_range: Option<(u64, u64)>,
+) -> Vec<TransitionBucket> {
+    if existing.is_empty() {
+        return new_data;
+    }
+    let mut merged: Vec<TransitionBucket> = existing
+        .iter()
+        .filter(|bucket| match replaced_range {
+            Some((start, end)) => bucket.end_ps < start || bucket.start_ps > end,
+            None => !new_data.contains(bucket),
+        })
+        .cloned()
+        .collect();
+    merged.extend(new_data);
+    merged.sort_by_key(|bucket| bucket.start_ps);
+    merged
 }
 
 fn ensure_leading_transition(
-    transitions: &mut Vec<SignalTransition>,
-    range_start_ns: u64,
-    previous: Option<&[SignalTransition]>,
+    transitions: &mut PackedTransitions,
+    range_start_ps: u64,
+    previous: Option<&PackedTransitions>,
 ) {
     if transitions.is_empty() {
         return;
     }
 
-    if transitions.first().map(|t| t.time_ns).unwrap_or(u64::MAX) <= range_start_ns {
+    if transitions.time_ps(0) <= range_start_ps {
         return;
     }
 
-    if let Some(prev_list) = previous {
-        if let Some(previous_transition) = prev_list
-            .iter()
-            .rev()
-            .find(|transition| transition.time_ns <= range_start_ns)
-        {
-            let mut synthetic = previous_transition.clone();
-            synthetic.time_ns = range_start_ns;
-            transitions.insert(0, synthetic);
-            return;
-        }
+    // The value held at the range start comes from the previous series when it knows it,
+    // otherwise the first value is stretched back
+    let mut leading = transitions.empty_like();
+    match previous
+        .filter(|previous| same_layout(previous, transitions))
+        .and_then(|previous| Some((previous, previous.index_at_or_before(range_start_ps)?)))
+    {
+        Some((previous, index)) => leading.push_from(previous, index, range_start_ps),
+        None => leading.push_from(transitions, 0, range_start_ps),
     }
-
-    let mut synthetic = transitions.first().cloned().unwrap();
-    synthetic.time_ns = range_start_ns;
-    transitions.insert(0, synthetic);
+    for index in 0..transitions.len() {
+        leading.push_from(transitions, index, transitions.time_ps(index));
+    }
+    *transitions = leading;
 }
 
 /// Primary timeline state coordinating cursor, viewport, zoom and data requests.
 #[derive(Clone)]
 pub struct WaveformTimeline {
     cursor: Mutable<TimePs>,
+    /// Second cursor for A/B measurements
+    secondary_cursor: Mutable<Option<TimePs>>,
     viewport: Mutable<Viewport>,
     zoom_center: Mutable<TimePs>,
     canvas_width: Mutable<f32>,
@@ -438,6 +521,8 @@ pub struct WaveformTimeline {
     layout_snapshot: Mutable<TimelineLayoutSnapshot>,
     series_map: Mutable<BTreeMap<String, VariableSeriesData>>,
     cursor_values: Mutable<BTreeMap<String, SignalValue>>,
+    secondary_cursor_values: Mutable<BTreeMap<String, SignalValue>>,
+    secondary_cursor_query: Rc<RefCell<Option<SecondaryCursorQuery>>>,
     tooltip_state: Mutable<Option<TimelineTooltipData>>,
     request_state: Mutable<RequestContext>,
     window_cache: Mutable<TimelineWindowCache>,
@@ -445,6 +530,9 @@ pub struct WaveformTimeline {
     debug_metrics: Mutable<TimelineDebugMetrics>,
     debug_overlay_enabled: Mutable<bool>,
     tooltip_enabled: Mutable<bool>,
+    payload_encoding: Mutable<SignalPayloadEncoding>,
+    /// Set once the encoding no longer follows what the backend advertises
+    payload_encoding_settled: Rc<Cell<bool>>,
     reload_in_progress: Rc<RefCell<HashSet<String>>>,
     reload_viewport_snapshot: Rc<RefCell<Option<(Viewport, TimePs)>>>,
     reload_restore_pending: Rc<Cell<bool>>,
@@ -472,6 +560,10 @@ pub struct WaveformTimeline {
     _listener_handles: Vec<Arc<TaskHandle>>,
     pub markers: MutableVec<Marker>,
     pub markers_snapshot: Mutable<Vec<Marker>>,
+    /// unique_id of the variable searched for a value and the intervals holding it
+    value_matches: Mutable<Option<(String, Arc<Vec<(u64, u64)>>)>>,
+    /// Difference shading by unique_id of the compared variable
+    signal_differences: Mutable<BTreeMap<String, SignalDifferences>>,
 }
 
 #[derive(Clone, Debug)]
@@ -486,8 +578,8 @@ impl WaveformTimeline {
         let mut expanded_start = start.saturating_sub(margin);
         let mut expanded_end = end.saturating_add(margin);
         if let Some(bounds) = self.bounds() {
-            expanded_start = expanded_start.max(bounds.start.nanos());
-            expanded_end = expanded_end.min(bounds.end.nanos());
+            expanded_start = expanded_start.max(bounds.start.picoseconds());
+            expanded_end = expanded_end.min(bounds.end.picoseconds());
         }
         if expanded_end <= expanded_start {
             expanded_end = expanded_start.saturating_add(1);
@@ -500,8 +592,8 @@ impl WaveformTimeline {
         let mut expanded_start = start.saturating_sub(margin);
         let mut expanded_end = end;
         if let Some(bounds) = self.bounds() {
-            expanded_start = expanded_start.max(bounds.start.nanos());
-            expanded_end = expanded_end.min(bounds.end.nanos());
+            expanded_start = expanded_start.max(bounds.start.picoseconds());
+            expanded_end = expanded_end.min(bounds.end.picoseconds());
         }
         if expanded_end <= expanded_start {
             expanded_end = expanded_start.saturating_add(1);
@@ -514,8 +606,8 @@ impl WaveformTimeline {
         let mut expanded_start = start;
         let mut expanded_end = end.saturating_add(margin);
         if let Some(bounds) = self.bounds() {
-            expanded_start = expanded_start.max(bounds.start.nanos());
-            expanded_end = expanded_end.min(bounds.end.nanos());
+            expanded_start = expanded_start.max(bounds.start.picoseconds());
+            expanded_end = expanded_end.min(bounds.end.picoseconds());
         }
         if expanded_end <= expanded_start {
             expanded_end = expanded_start.saturating_add(1);
@@ -573,6 +665,7 @@ impl WaveformTimeline {
 
         let timeline = Self {
             cursor,
+            secondary_cursor: Mutable::new(None),
             viewport,
             zoom_center,
             canvas_width,
@@ -583,6 +676,8 @@ impl WaveformTimeline {
             layout_snapshot,
             series_map,
             cursor_values,
+            secondary_cursor_values: Mutable::new(BTreeMap::new()),
+            secondary_cursor_query: Rc::new(RefCell::new(None)),
             tooltip_state: tooltip_state.clone(),
             request_state,
             window_cache,
@@ -590,6 +685,8 @@ impl WaveformTimeline {
             debug_metrics,
             debug_overlay_enabled,
             tooltip_enabled,
+            payload_encoding: Mutable::new(SignalPayloadEncoding::Json),
+            payload_encoding_settled: Rc::new(Cell::new(false)),
             reload_in_progress: reload_in_progress.clone(),
             reload_viewport_snapshot: reload_viewport_snapshot.clone(),
             reload_restore_pending: reload_restore_pending.clone(),
@@ -616,6 +713,8 @@ impl WaveformTimeline {
             _listener_handles: Vec::new(),
             markers: MutableVec::new(),
             markers_snapshot: Mutable::new(Vec::new()),
+            value_matches: Mutable::new(None),
+            signal_differences: Mutable::new(BTreeMap::new()),
         };
 
         timeline.initialize_from_config();
@@ -639,6 +738,14 @@ impl WaveformTimeline {
         self.viewport.clone()
     }
 
+    pub fn secondary_cursor_actor(&self) -> Mutable<Option<TimePs>> {
+        self.secondary_cursor.clone()
+    }
+
+    pub fn secondary_cursor_values_actor(&self) -> Mutable<BTreeMap<String, SignalValue>> {
+        self.secondary_cursor_values.clone()
+    }
+
     pub fn zoom_center_actor(&self) -> Mutable<TimePs> {
         self.zoom_center.clone()
     }
@@ -667,6 +774,27 @@ impl WaveformTimeline {
         self.debug_overlay_enabled.clone()
     }
 
+    /// Wire format requested for transition payloads; switch to JSON to inspect responses
+    pub fn set_payload_encoding(&self, encoding: SignalPayloadEncoding) {
+        self.payload_encoding_settled.set(true);
+        self.payload_encoding.set_neq(encoding);
+    }
+
+    /// Move to binary payloads the first time the backend lists them as supported
+    pub fn negotiate_payload_encoding(&self, supported: &[SignalPayloadEncoding]) {
+        if self.payload_encoding_settled.get() {
+            return;
+        }
+        if supported.contains(&SignalPayloadEncoding::Binary) {
+            self.payload_encoding_settled.set(true);
+            self.payload_encoding.set_neq(SignalPayloadEncoding::Binary);
+        }
+    }
+
+    pub fn payload_encoding(&self) -> SignalPayloadEncoding {
+        self.payload_encoding.get()
+    }
+
     pub fn request_bootstrap_pending_debug(&self) -> bool {
         self.request_bootstrap_pending.get_cloned()
     }
@@ -727,6 +855,12 @@ impl WaveformTimeline {
         });
     }
 
+    fn record_request_cancelled(&self) {
+        self.debug_metrics.update_mut(|metrics| {
+            metrics.request_cancelled_count = metrics.request_cancelled_count.saturating_add(1)
+        });
+    }
+
     fn invalidate_request_fingerprints(&self) {
         self.request_state.update_mut(|context| {
             context.latest_request_fingerprint = None;
@@ -766,9 +900,10 @@ impl WaveformTimeline {
     }
 
     fn refresh_cursor_values_from_series(&self) -> bool {
-        let cursor_ns = self.cursor.get_cloned().nanos();
+        self.refresh_secondary_cursor_values();
+        let cursor_ps = self.cursor.get_cloned().picoseconds();
 
-        let snapshot: Vec<(String, Arc<Vec<SignalTransition>>)> = {
+        let snapshot: Vec<(String, Arc<PackedTransitions>)> = {
             let map_ref = self.series_map.lock_ref();
             map_ref
                 .iter()
@@ -783,8 +918,7 @@ impl WaveformTimeline {
         let updates: Vec<(String, SignalValue)> = snapshot
             .into_iter()
             .map(|(unique_id, transitions_arc)| {
-                let value =
-                    Self::cursor_value_from_transitions(transitions_arc.as_slice(), cursor_ns);
+                let value = Self::cursor_value_from_transitions(&transitions_arc, cursor_ps);
                 (unique_id, value)
             })
             .collect();
@@ -807,22 +941,148 @@ impl WaveformTimeline {
         changed
     }
 
-    fn cursor_value_from_transitions(
-        transitions: &[SignalTransition],
-        cursor_ns: u64,
-    ) -> SignalValue {
-        if transitions.is_empty() {
-            return SignalValue::Missing;
+    /// Values at the second cursor. Variables whose loaded window covers it are read from
+    /// the series; the others are asked from the backend like the primary cursor values
+    fn refresh_secondary_cursor_values(&self) {
+        let Some(secondary_ps) = self
+            .secondary_cursor
+            .get_cloned()
+            .map(|time| time.picoseconds())
+        else {
+            self.secondary_cursor_query.replace(None);
+            if !self.secondary_cursor_values.lock_ref().is_empty() {
+                self.secondary_cursor_values.set(BTreeMap::new());
+            }
+            return;
+        };
+        let query = self
+            .secondary_cursor_query
+            .borrow()
+            .clone()
+            .filter(|query| query.time_ps == secondary_ps);
+        let previous = self.secondary_cursor_values.get_cloned();
+        let mut uncovered = Vec::new();
+        let values: BTreeMap<String, SignalValue> = self
+            .series_map
+            .lock_ref()
+            .iter()
+            .map(|(unique_id, data)| {
+                let covered = data
+                    .covered_time_range_ps
+                    .is_none_or(|(start, end)| (start..=end).contains(&secondary_ps));
+                let value = if covered {
+                    Self::cursor_value_from_transitions(&data.transitions, secondary_ps)
+                } else {
+                    uncovered.push(unique_id.clone());
+                    match (&query, previous.get(unique_id)) {
+                        (Some(query), Some(value)) if query.unique_ids.contains(unique_id) => {
+                            value.clone()
+                        }
+                        _ => SignalValue::Loading,
+                    }
+                };
+                (unique_id.clone(), value)
+            })
+            .collect();
+        self.secondary_cursor_values.set_neq(values);
+
+        let already_queried = query.is_some_and(|query| {
+            uncovered
+                .iter()
+                .all(|unique_id| query.unique_ids.contains(unique_id))
+        });
+        if !already_queried {
+            self.send_secondary_cursor_query(secondary_ps, uncovered);
+        }
+    }
+
+    fn send_secondary_cursor_query(&self, time_ps: u64, unique_ids: Vec<String>) {
+        let unique_ids: HashSet<String> = unique_ids.into_iter().collect();
+        let requests: Vec<UnifiedSignalRequest> = self
+            .selected_variables
+            .variables_vec_actor
+            .get_cloned()
+            .into_iter()
+            .filter(|variable| unique_ids.contains(&variable.unique_id))
+            .filter_map(|variable| {
+                let (file_path, scope_path, variable_name) = variable.parse_unique_id()?;
+                Some(UnifiedSignalRequest {
+                    file_path,
+                    scope_path,
+                    variable_name,
+                    // Only the cursor value is wanted; the window just keeps the payload small
+                    time_range_ps: Some((time_ps, time_ps.saturating_add(1))),
+                    max_transitions: Some(1),
+                    format: variable.formatter.unwrap_or(VarFormat::Hexadecimal),
+                })
+            })
+            .collect();
+        if requests.is_empty() {
+            return;
         }
 
-        match transitions.binary_search_by(|transition| transition.time_ns.cmp(&cursor_ns)) {
-            Ok(idx) => SignalValue::present(transitions[idx].value.clone()),
-            Err(0) => SignalValue::Missing,
-            Err(idx) => {
-                let prev = &transitions[idx - 1];
-                SignalValue::present(prev.value.clone())
+        let request_id = format!(
+            "{}{}",
+            SECONDARY_CURSOR_REQUEST_PREFIX,
+            self.request_counter.fetch_add(1, Ordering::SeqCst)
+        );
+        self.secondary_cursor_query
+            .replace(Some(SecondaryCursorQuery {
+                request_id: request_id.clone(),
+                time_ps,
+                unique_ids,
+            }));
+        let connection = self.connection.clone();
+        let payload_encoding = self.payload_encoding.get();
+        Task::start(async move {
+            connection
+                .send_up_msg(UpMsg::UnifiedSignalQuery {
+                    signal_requests: requests,
+                    cursor_time_ps: Some(time_ps),
+                    request_id,
+                    payload_encoding,
+                })
+                .await;
+        });
+    }
+
+    /// Take the answer to a second cursor query, `None` for a failed one; `false` if
+    /// `request_id` is not such a query
+    fn apply_secondary_cursor_response(
+        &self,
+        request_id: &str,
+        cursor_values: Option<&BTreeMap<String, SignalValue>>,
+    ) -> bool {
+        if !request_id.starts_with(SECONDARY_CURSOR_REQUEST_PREFIX) {
+            return false;
+        }
+        let query = self.secondary_cursor_query.borrow().clone();
+        let Some(query) = query.filter(|query| query.request_id == request_id) else {
+            return true;
+        };
+        {
+            let mut values_map = self.secondary_cursor_values.lock_mut();
+            for unique_id in &query.unique_ids {
+                if values_map.contains_key(unique_id) {
+                    let value = cursor_values
+                        .and_then(|values| values.get(unique_id))
+                        .cloned()
+                        .unwrap_or(SignalValue::Missing);
+                    values_map.insert(unique_id.clone(), value);
+                }
             }
         }
+        true
+    }
+
+    fn cursor_value_from_transitions(
+        transitions: &PackedTransitions,
+        cursor_ps: u64,
+    ) -> SignalValue {
+        match transitions.index_at_or_before(cursor_ps) {
+            Some(index) => SignalValue::present(transitions.value_string(index)),
+            None => SignalValue::Missing,
+        }
     }
 
     pub fn set_cursor_clamped(&self, time: TimePs) {
@@ -834,6 +1094,14 @@ impl WaveformTimeline {
         self.schedule_request();
     }
 
+    /// Place the second cursor, or remove it with `None`
+    pub fn set_secondary_cursor(&self, time: Option<TimePs>) {
+        let clamped = time.map(|time| self.clamp_to_bounds(time));
+        self.secondary_cursor.set_neq(clamped);
+        self.refresh_secondary_cursor_values();
+        self.update_render_state_layout_only();
+    }
+
     pub fn move_cursor_left(&self) {
         let faster = self.shift_active.get_cloned();
         let step = self.cursor_step_ps(faster);
@@ -865,9 +1133,9 @@ impl WaveformTimeline {
         if times.is_empty() {
             return;
         }
-        let cursor_ns = self.cursor.get_cloned().nanos();
-        if let Some(prev) = times.iter().rev().find(|&&t| t < cursor_ns).copied() {
-            self.set_cursor_clamped(TimePs::from_nanos(prev));
+        let cursor_ps = self.cursor.get_cloned().picoseconds();
+        if let Some(prev) = times.iter().rev().find(|&&t| t < cursor_ps).copied() {
+            self.set_cursor_clamped(TimePs::from_picoseconds(prev));
         }
     }
 
@@ -876,9 +1144,9 @@ impl WaveformTimeline {
         if times.is_empty() {
             return;
         }
-        let cursor_ns = self.cursor.get_cloned().nanos();
-        if let Some(next) = times.iter().find(|&&t| t > cursor_ns).copied() {
-            self.set_cursor_clamped(TimePs::from_nanos(next));
+        let cursor_ps = self.cursor.get_cloned().picoseconds();
+        if let Some(next) = times.iter().find(|&&t| t > cursor_ps).copied() {
+            self.set_cursor_clamped(TimePs::from_picoseconds(next));
         }
     }
 
@@ -886,9 +1154,7 @@ impl WaveformTimeline {
         let map = self.series_map.lock_ref();
         let mut times = Vec::new();
         for series in map.values() {
-            for transition in series.transitions.iter() {
-                times.push(transition.time_ns);
-            }
+            times.extend_from_slice(&series.transitions.times_ps);
         }
         drop(map);
         times.sort_unstable();
@@ -1071,24 +1337,152 @@ impl WaveformTimeline {
             markers
         };
         if let Some(marker) = sorted_markers.get(index) {
-            let time = TimePs::from_picoseconds(marker.time_ps);
-            let viewport_duration = self.viewport.get_cloned().duration().picoseconds();
-            self.set_pointer_hover(None);
-            let half_duration = viewport_duration / 2;
-            let centered_start =
-                TimePs::from_picoseconds(time.picoseconds().saturating_sub(half_duration));
-            let centered_end = TimePs::from_picoseconds(
-                centered_start
-                    .picoseconds()
-                    .saturating_add(viewport_duration.max(1)),
-            );
-            self.clear_zoom_anchor_ratio();
-            self.set_viewport_clamped(centered_start, centered_end);
-            self.set_zoom_center_follow(Some(time));
-            self.set_cursor_clamped(time);
+            self.jump_to_time(TimePs::from_picoseconds(marker.time_ps));
         }
     }
 
+    /// Center the viewport on `time` at the current zoom and put the cursor there
+    pub fn jump_to_time(&self, time: TimePs) {
+        let viewport_duration = self.viewport.get_cloned().duration().picoseconds();
+        self.set_pointer_hover(None);
+        let half_duration = viewport_duration / 2;
+        let centered_start =
+            TimePs::from_picoseconds(time.picoseconds().saturating_sub(half_duration));
+        let centered_end = TimePs::from_picoseconds(
+            centered_start
+                .picoseconds()
+                .saturating_add(viewport_duration.max(1)),
+        );
+        self.clear_zoom_anchor_ratio();
+        self.set_viewport_clamped(centered_start, centered_end);
+        self.set_zoom_center_follow(Some(time));
+        self.set_cursor_clamped(time);
+    }
+
+    /// Highlight the intervals where a variable holds a searched value, or nothing
+    pub fn set_value_matches(&self, value_matches: Option<(String, Arc<Vec<(u64, u64)>>)>) {
+        self.value_matches.set(value_matches);
+        self.update_render_state();
+    }
+
+    /// Ask the backend where compared variables differ from their counterparts, unless the
+    /// latest answer already covers the viewport at this zoom level
+    fn request_signal_differences(&self) {
+        let viewport = self.viewport.get_cloned();
+        let (start_ps, end_ps) = (viewport.start.picoseconds(), viewport.end.picoseconds());
+        if end_ps <= start_ps {
+            return;
+        }
+        let width_px = self.canvas_width.get_cloned().max(1.0) as u64;
+        let resolution_ps = ((end_ps - start_ps) / width_px).max(1);
+        let range_ps = self.expand_range_with_margin((start_ps, end_ps), (end_ps - start_ps) / 4);
+
+        let pairs: Vec<(String, String)> = self
+            .selected_variables
+            .variables_vec_actor
+            .get_cloned()
+            .into_iter()
+            .filter_map(|variable| {
+                let compare_unique_id = variable.compare_unique_id()?;
+                Some((variable.unique_id, compare_unique_id))
+            })
+            .collect();
+        let mut requests = Vec::new();
+        let dropped_shading = {
+            let mut differences = self.signal_differences.lock_mut();
+            let previous_len = differences.len();
+            differences.retain(|unique_id, entry| {
+                pairs.iter().any(|(id, compare_id)| {
+                    id == unique_id && *compare_id == entry.compare_unique_id
+                })
+            });
+            let dropped_shading = differences.len() != previous_len;
+            for (unique_id, compare_unique_id) in pairs {
+                let covered = differences.get(&unique_id).is_some_and(|entry| {
+                    entry.resolution_ps == resolution_ps
+                        && range_contains(entry.range_ps, (start_ps, end_ps))
+                });
+                if covered {
+                    continue;
+                }
+                // The previous shading stays until the answer arrives
+                let intervals = differences
+                    .remove(&unique_id)
+                    .and_then(|entry| entry.intervals);
+                differences.insert(
+                    unique_id.clone(),
+                    SignalDifferences {
+                        compare_unique_id: compare_unique_id.clone(),
+                        range_ps,
+                        resolution_ps,
+                        intervals,
+                        error: None,
+                    },
+                );
+                requests.push(SignalDifferenceRequest {
+                    unique_id,
+                    compare_unique_id,
+                    range_ps,
+                    resolution_ps,
+                });
+            }
+            dropped_shading
+        };
+        if dropped_shading {
+            self.update_render_state();
+        }
+        if requests.is_empty() {
+            return;
+        }
+        let connection = self.connection.clone();
+        Task::start(async move {
+            for request in requests {
+                connection.send_up_msg(UpMsg::CompareSignal(request)).await;
+            }
+        });
+    }
+
+    /// Shade the intervals where a variable differs from its counterpart
+    pub fn apply_signal_differences(
+        &self,
+        unique_id: &str,
+        compare_unique_id: &str,
+        range_ps: (u64, u64),
+        result: Result<Vec<(u64, u64)>, String>,
+    ) {
+        {
+            let mut differences = self.signal_differences.lock_mut();
+            let Some(entry) = differences.get_mut(unique_id).filter(|entry| {
+                entry.compare_unique_id == compare_unique_id && entry.range_ps == range_ps
+            }) else {
+                return;
+            };
+            match result {
+                Ok(intervals) => {
+                    entry.intervals = Some(Arc::new(intervals));
+                    entry.error = None;
+                }
+                Err(error) => {
+                    entry.intervals = None;
+                    entry.error = Some(error);
+                }
+            }
+        }
+        self.update_render_state();
+    }
+
+    /// Why the variable could not be compared with its counterpart, if it could not
+    pub fn signal_difference_error_signal(
+        &self,
+        unique_id: String,
+    ) -> impl Signal<Item = Option<String>> + use<> {
+        self.signal_differences.signal_ref(move |differences| {
+            differences
+                .get(&unique_id)
+                .and_then(|entry| entry.error.clone())
+        })
+    }
+
     pub fn restore_markers(&self, configs: Vec<shared::MarkerConfig>) {
         let markers: Vec<Marker> = configs
             .into_iter()
@@ -1496,6 +1890,7 @@ impl WaveformTimeline {
                 || previous.formatter != next.formatter
                 || previous.signal_type != next.signal_type
                 || previous.analog_limits != next.analog_limits
+                || previous.compare_file != next.compare_file
             {
                 return false;
             }
@@ -1673,24 +2068,14 @@ impl WaveformTimeline {
             return;
         }
 
-        let start_ns = start_ps / PS_PER_NS;
-        let mut end_ns = if end_ps == 0 {
-            0
-        } else {
-            (end_ps + PS_PER_NS - 1) / PS_PER_NS
-        };
-        if end_ns <= start_ns {
-            end_ns = start_ns.saturating_add(1);
-        }
-
         let width_px = self.canvas_width.get_cloned().max(1.0) as u32;
         if width_px == 0 {
             return;
         }
 
         let max_transitions = (width_px as usize).saturating_mul(4).max(1);
-        let request_range = (start_ns, end_ns);
-        let margin = (end_ns - start_ns).saturating_div(4).max(1);
+        let request_range = (start_ps, end_ps);
+        let margin = (end_ps - start_ps).saturating_div(4).max(1);
         let expanded_range = self.expand_range_with_margin(request_range, margin);
         let duration_ps = end_ps.saturating_sub(start_ps).max(1);
         let time_per_pixel = TimePerPixel::from_duration_and_width(duration_ps, width_px);
@@ -1730,12 +2115,12 @@ impl WaveformTimeline {
                 {
                     cache_hits += 1;
                     best_coverage = best_coverage.max(coverage);
-                    let missing_left = request_range.0 < entry.range_ns.0;
-                    let missing_right = request_range.1 > entry.range_ns.1;
+                    let missing_left = request_range.0 < entry.range_ps.0;
+                    let missing_right = request_range.1 > entry.range_ps.1;
 
                     plan.needs_request = missing_left || missing_right;
                     if missing_left && !missing_right {
-                        let missing_range = (request_range.0, entry.range_ns.0);
+                        let missing_range = (request_range.0, entry.range_ps.0);
                         if missing_range.1 > missing_range.0 {
                             let fetch_range = self.expand_left_range(missing_range, margin);
                             plan.request_range_override = Some(fetch_range);
@@ -1743,7 +2128,7 @@ impl WaveformTimeline {
                             plan.needs_request = false;
                         }
                     } else if missing_right && !missing_left {
-                        let missing_range = (entry.range_ns.1, request_range.1);
+                        let missing_range = (entry.range_ps.1, request_range.1);
                         if missing_range.1 > missing_range.0 {
                             let fetch_range = self.expand_right_range(missing_range, margin);
                             plan.request_range_override = Some(fetch_range);
@@ -1768,9 +2153,10 @@ impl WaveformTimeline {
                         plan.unique_id.clone(),
                         VariableSeriesData {
                             transitions: Arc::clone(&entry.transitions),
+                            busy_buckets: Arc::clone(&entry.busy_buckets),
                             total_transitions: entry.total_transitions,
-                            actual_time_range_ns: Some(entry.range_ns),
-                            covered_time_range_ns: Some(entry.range_ns),
+                            actual_time_range_ps: Some(entry.range_ps),
+                            covered_time_range_ps: Some(entry.range_ps),
                         },
                     );
                 }
@@ -1792,7 +2178,7 @@ impl WaveformTimeline {
                     request_windows.insert(
                         plan.unique_id.clone(),
                         RequestedWindow {
-                            range_ns: range_to_request,
+                            range_ps: range_to_request,
                             lod_bucket,
                         },
                     );
@@ -1800,7 +2186,7 @@ impl WaveformTimeline {
                         file_path: file_path.clone(),
                         scope_path: scope_path.clone(),
                         variable_name: variable_name.clone(),
-                        time_range_ns: Some(range_to_request),
+                        time_range_ps: Some(range_to_request),
                         max_transitions: Some(max_transitions),
                         format: plan.formatter,
                     });
@@ -1819,7 +2205,7 @@ impl WaveformTimeline {
             self.schedule_cursor_loading_indicator(unique_id);
         }
 
-        let cursor_ns = self.cursor.get_cloned().nanos();
+        let cursor_ps = self.cursor.get_cloned().picoseconds();
         let request_fingerprint = RequestFingerprint {
             signals: requests
                 .iter()
@@ -1827,12 +2213,12 @@ impl WaveformTimeline {
                     file_path: request.file_path.clone(),
                     scope_path: request.scope_path.clone(),
                     variable_name: request.variable_name.clone(),
-                    time_range_ns: request.time_range_ns,
+                    time_range_ps: request.time_range_ps,
                     max_transitions: request.max_transitions,
                     format: request.format,
                 })
                 .collect(),
-            cursor_time_ns: Some(cursor_ns),
+            cursor_time_ps: Some(cursor_ps),
         };
 
         {
@@ -1858,6 +2244,18 @@ impl WaveformTimeline {
             self.request_counter.fetch_add(1, Ordering::SeqCst)
         );
         let mut context = self.request_state.get_cloned();
+        // The fingerprint is cleared when a response arrives, so one still set means in flight
+        let superseded_request_id = match (
+            &context.latest_request_id,
+            &context.latest_request_fingerprint,
+        ) {
+            (Some(previous_id), Some(previous_fingerprint))
+                if request_fingerprint.supersedes(previous_fingerprint) =>
+            {
+                Some(previous_id.clone())
+            }
+            _ => None,
+        };
         context.latest_request_id = Some(request_id.clone());
         context.latest_request_started_ms = Some(Date::now());
         context.latest_request_windows = request_windows;
@@ -1866,13 +2264,23 @@ impl WaveformTimeline {
         self.request_state.set(context);
 
         let connection = self.connection.clone();
+        let payload_encoding = self.payload_encoding.get();
         self.record_request_sent(mode);
+        if superseded_request_id.is_some() {
+            self.record_request_cancelled();
+        }
         let handle = Task::start_droppable(async move {
+            if let Some(request_id) = superseded_request_id {
+                connection
+                    .send_up_msg(UpMsg::CancelSignalQuery { request_id })
+                    .await;
+            }
             connection
                 .send_up_msg(UpMsg::UnifiedSignalQuery {
                     signal_requests: requests,
-                    cursor_time_ns: Some(cursor_ns),
+                    cursor_time_ps: Some(cursor_ps),
                     request_id,
+                    payload_encoding,
                 })
                 .await;
         });
@@ -1885,6 +2293,9 @@ impl WaveformTimeline {
         signal_data: Vec<UnifiedSignalData>,
         cursor_values: BTreeMap<String, SignalValue>,
     ) {
+        if self.apply_secondary_cursor_response(request_id, Some(&cursor_values)) {
+            return;
+        }
         let mut current_request = self.request_state.get_cloned();
         let is_latest_response = current_request
             .latest_request_id
@@ -1941,15 +2352,28 @@ impl WaveformTimeline {
 
         let mut cache = self.window_cache.lock_mut();
         let mut series_map = self.series_map.lock_mut();
-
-        for UnifiedSignalData {
-            unique_id,
-            transitions,
-            total_transitions: _,
-            actual_time_range_ns,
-            ..
-        } in signal_data
-        {
+        let mut payload_decode_failed = false;
+
+        for mut data in signal_data {
+            // Decode the wire form once, at the boundary; the columns stay packed from here on
+            let transitions = match data.take_packed() {
+                Ok(transitions) => transitions,
+                Err(error) => {
+                    zoon::eprintln!(
+                        "[TIMELINE] Failed to decode transitions for {}: {}",
+                        data.unique_id,
+                        error
+                    );
+                    payload_decode_failed = true;
+                    continue;
+                }
+            };
+            let UnifiedSignalData {
+                unique_id,
+                actual_time_range_ps,
+                busy_buckets,
+                ..
+            } = data;
             if !is_latest_response && series_map.contains_key(&unique_id) {
                 continue;
             }
@@ -1958,21 +2382,26 @@ impl WaveformTimeline {
             let existing_series = series_map
                 .get(&unique_id)
                 .map(|series| series.transitions.clone());
+            let existing_buckets = series_map
+                .get(&unique_id)
+                .map(|series| series.busy_buckets.clone());
 
             let mut transitions_vec = transitions;
+            let response_range = requested_window.as_ref().map(|window| window.range_ps);
+            let mut buckets_vec = busy_buckets;
 
             let mut covered_range = requested_window
                 .as_ref()
-                .map(|window| window.range_ns)
+                .map(|window| window.range_ps)
                 .or_else(|| {
-                    let start = transitions_vec.first()?.time_ns;
-                    let end = transitions_vec.last()?.time_ns;
+                    let start = *transitions_vec.times_ps.first()?;
+                    let end = *transitions_vec.times_ps.last()?;
                     Some((start, end))
                 })
                 .unwrap_or((0, 0));
-            let mut actual_range = actual_time_range_ns.or_else(|| {
-                let start = transitions_vec.first()?.time_ns;
-                let end = transitions_vec.last()?.time_ns;
+            let mut actual_range = actual_time_range_ps.or_else(|| {
+                let start = *transitions_vec.times_ps.first()?;
+                let end = *transitions_vec.times_ps.last()?;
                 Some((start, end))
             });
 
@@ -1986,66 +2415,76 @@ impl WaveformTimeline {
                     .or_insert_with(VecDeque::new);
 
                 if let Some(position) = slots.iter().position(|entry| {
-                    entry.lod_bucket == lod_bucket && ranges_overlap(entry.range_ns, covered_range)
+                    entry.lod_bucket == lod_bucket && ranges_overlap(entry.range_ps, covered_range)
                 }) {
                     let existing_entry = slots.remove(position).unwrap();
-                    transitions_vec = merge_signal_transitions(
-                        existing_entry.transitions.as_ref(),
-                        transitions_vec.as_slice(),
+                    transitions_vec =
+                        merge_signal_transitions(&existing_entry.transitions, &transitions_vec);
+                    buckets_vec = merge_busy_buckets(
+                        existing_entry.busy_buckets.as_ref(),
+                        buckets_vec,
+                        response_range,
                     );
                     covered_range = (
-                        existing_entry.range_ns.0.min(covered_range.0),
-                        existing_entry.range_ns.1.max(covered_range.1),
+                        existing_entry.range_ps.0.min(covered_range.0),
+                        existing_entry.range_ps.1.max(covered_range.1),
                     );
                     slots.retain(|entry| {
                         !(entry.lod_bucket == lod_bucket
-                            && range_contains(covered_range, entry.range_ns))
+                            && range_contains(covered_range, entry.range_ps))
                     });
                 }
 
                 cache_slot_action = Some(lod_bucket);
             }
 
-            let leading_start_ns = requested_window
+            let leading_start_ps = requested_window
                 .as_ref()
-                .map(|window| window.range_ns.0)
-                .or_else(|| actual_time_range_ns.map(|range| range.0))
+                .map(|window| window.range_ps.0)
+                .or_else(|| actual_time_range_ps.map(|range| range.0))
                 .unwrap_or(covered_range.0);
 
-            let previous_slice = existing_series.as_ref().map(|arc| arc.as_slice());
-            ensure_leading_transition(&mut transitions_vec, leading_start_ns, previous_slice);
-            covered_range.0 = covered_range.0.min(leading_start_ns);
+            ensure_leading_transition(
+                &mut transitions_vec,
+                leading_start_ps,
+                existing_series.as_deref(),
+            );
+            covered_range.0 = covered_range.0.min(leading_start_ps);
 
-            if let Some(existing_arc) = &existing_series {
-                let existing_slice = existing_arc.as_ref();
-                if !existing_slice.is_empty() {
-                    transitions_vec =
-                        merge_signal_transitions(existing_slice, transitions_vec.as_slice());
-                    if let Some(first) = existing_slice.first() {
+            if let Some(existing) = &existing_series {
+                if !existing.is_empty() {
+                    transitions_vec = merge_signal_transitions(existing, &transitions_vec);
+                    if let Some(&first) = existing.times_ps.first() {
                         actual_range = Some(match actual_range {
-                            Some((start, end)) => (start.min(first.time_ns), end),
-                            None => (first.time_ns, first.time_ns),
+                            Some((start, end)) => (start.min(first), end),
+                            None => (first, first),
                         });
                     }
-                    if let Some(last) = existing_slice.last() {
+                    if let Some(&last) = existing.times_ps.last() {
                         actual_range = Some(match actual_range {
-                            Some((start, end)) => (start, end.max(last.time_ns)),
-                            None => (last.time_ns, last.time_ns),
+                            Some((start, end)) => (start, end.max(last)),
+                            None => (last, last),
                         });
                     }
                 }
             }
 
-            if let Some(first) = transitions_vec.first() {
-                if let Some(last) = transitions_vec.last() {
+            if let Some(&first) = transitions_vec.times_ps.first() {
+                if let Some(&last) = transitions_vec.times_ps.last() {
                     actual_range = Some(match actual_range {
-                        Some((start, end)) => (start.min(first.time_ns), end.max(last.time_ns)),
-                        None => (first.time_ns, last.time_ns),
+                        Some((start, end)) => (start.min(first), end.max(last)),
+                        None => (first, last),
                     });
                 }
             }
 
+            if let Some(existing_arc) = &existing_buckets {
+                buckets_vec =
+                    merge_busy_buckets(existing_arc.as_ref(), buckets_vec, response_range);
+            }
+
             let transitions_arc = Arc::new(transitions_vec);
+            let buckets_arc = Arc::new(buckets_vec);
             let transition_count = transitions_arc.len();
 
             if let Some(lod_bucket) = cache_slot_action {
@@ -2055,8 +2494,9 @@ impl WaveformTimeline {
                     .or_insert_with(VecDeque::new);
                 slots.push_front(TimelineCacheEntry {
                     lod_bucket,
-                    range_ns: covered_range,
+                    range_ps: covered_range,
                     transitions: Arc::clone(&transitions_arc),
+                    busy_buckets: Arc::clone(&buckets_arc),
                     total_transitions: transition_count,
                 });
                 while slots.len() > CACHE_MAX_SEGMENTS_PER_VARIABLE {
@@ -2068,9 +2508,10 @@ impl WaveformTimeline {
                 unique_id,
                 VariableSeriesData {
                     transitions: transitions_arc,
+                    busy_buckets: buckets_arc,
                     total_transitions: transition_count,
-                    actual_time_range_ns: actual_range,
-                    covered_time_range_ns: Some(covered_range),
+                    actual_time_range_ps: actual_range,
+                    covered_time_range_ps: Some(covered_range),
                 },
             );
         }
@@ -2078,6 +2519,15 @@ impl WaveformTimeline {
         drop(series_map);
         drop(cache);
 
+        if payload_decode_failed {
+            // Fall back to JSON payloads for good and fetch the skipped signals again
+            self.set_payload_encoding(SignalPayloadEncoding::Json);
+            self.request_state
+                .lock_mut()
+                .latest_completed_request_fingerprint = None;
+            self.schedule_request();
+        }
+
         self.refresh_cursor_values_from_series();
 
         {
@@ -2123,6 +2573,7 @@ impl WaveformTimeline {
     fn sync_state_to_config(&self) {
         let viewport = self.viewport.get_cloned();
         let cursor = self.cursor.get_cloned();
+        let secondary_cursor = self.secondary_cursor.get_cloned();
         let zoom_center = self.zoom_center.get_cloned();
         let tooltip_enabled = self.tooltip_enabled.get_cloned();
 
@@ -2132,6 +2583,7 @@ impl WaveformTimeline {
 
         let timeline_state = TimelineState {
             cursor_position: Some(cursor),
+            secondary_cursor,
             visible_range: Some(TimeRange {
                 start: viewport.start,
                 end: viewport.end,
@@ -2144,6 +2596,10 @@ impl WaveformTimeline {
     }
 
     pub fn handle_unified_signal_error(&self, request_id: &str, error: &str) {
+        if self.apply_secondary_cursor_response(request_id, None) {
+            zoon::println!("Second cursor value request failed: {}", error);
+            return;
+        }
         let mut current_request = self.request_state.get_cloned();
         if current_request
             .latest_request_id
@@ -2271,6 +2727,8 @@ impl WaveformTimeline {
 
         let series_guard = self.series_map.lock_ref();
         let values_guard = self.cursor_values.lock_ref();
+        let differences_guard = self.signal_differences.lock_ref();
+        let value_matches = self.value_matches.get_cloned();
         let mut variables_by_id = BTreeMap::new();
 
         for variable in variables_snapshot {
@@ -2281,34 +2739,48 @@ impl WaveformTimeline {
             let cursor_value = values_guard.get(&variable.unique_id).cloned();
             let signal_type = variable.signal_type.clone();
             let analog_limits = variable.analog_limits.clone();
+            let differences = differences_guard
+                .get(&variable.unique_id)
+                .and_then(|differences| differences.intervals.clone());
+            let value_matches = value_matches
+                .as_ref()
+                .filter(|(unique_id, _)| unique_id == &variable.unique_id)
+                .map(|(_, intervals)| Arc::clone(intervals));
             let structure = match series_guard.get(&variable.unique_id) {
                 Some(series) => TimelineVariableStructure {
                     unique_id: variable.unique_id.clone(),
                     formatter,
                     transitions: Arc::clone(&series.transitions),
+                    busy_buckets: Arc::clone(&series.busy_buckets),
                     total_transitions: series.total_transitions,
-                    actual_time_range_ns: series.actual_time_range_ns,
-                    covered_time_range_ns: series.covered_time_range_ns,
+                    actual_time_range_ps: series.actual_time_range_ps,
+                    covered_time_range_ps: series.covered_time_range_ps,
                     cursor_value,
                     signal_type,
                     analog_limits,
+                    differences,
+                    value_matches,
                 },
                 None => TimelineVariableStructure {
                     unique_id: variable.unique_id.clone(),
                     formatter,
-                    transitions: Arc::new(Vec::new()),
+                    transitions: Arc::new(PackedTransitions::default()),
+                    busy_buckets: Arc::new(Vec::new()),
                     total_transitions: 0,
-                    actual_time_range_ns: None,
-                    covered_time_range_ns: None,
+                    actual_time_range_ps: None,
+                    covered_time_range_ps: None,
                     cursor_value,
                     signal_type,
                     analog_limits,
+                    differences,
+                    value_matches,
                 },
             };
             variables_by_id.insert(variable.unique_id.clone(), structure);
         }
         drop(series_guard);
         drop(values_guard);
+        drop(differences_guard);
 
         let rows = visible_items
             .into_iter()
@@ -2338,6 +2810,7 @@ impl WaveformTimeline {
     fn rebuild_layout_snapshot(&self) {
         let viewport = self.viewport.get_cloned();
         let cursor = self.cursor.get_cloned();
+        let secondary_cursor = self.secondary_cursor.get_cloned();
         let zoom_center = self.zoom_center.get_cloned();
         let width = self.canvas_width.get_cloned().max(1.0) as u32;
         let height = self.canvas_height.get_cloned().max(1.0) as u32;
@@ -2365,6 +2838,7 @@ impl WaveformTimeline {
             viewport_start: viewport.start,
             viewport_end: viewport.end,
             cursor,
+            secondary_cursor,
             zoom_center,
             canvas_width_px: width,
             canvas_height_px: height,
@@ -2400,13 +2874,16 @@ impl WaveformTimeline {
                             unique_id: series.unique_id.clone(),
                             formatter: series.formatter,
                             transitions: Arc::clone(&series.transitions),
+                            busy_buckets: Arc::clone(&series.busy_buckets),
                             total_transitions: series.total_transitions,
-                            actual_time_range_ns: series.actual_time_range_ns,
-                            covered_time_range_ns: series.covered_time_range_ns,
+                            actual_time_range_ps: series.actual_time_range_ps,
+                            covered_time_range_ps: series.covered_time_range_ps,
                             cursor_value: series.cursor_value.clone(),
                             signal_type: series.signal_type.clone(),
                             row_height: *row_height,
                             analog_limits: series.analog_limits.clone(),
+                            differences: series.differences.clone(),
+                            value_matches: series.value_matches.clone(),
                         };
                         variables.push(render_series.clone());
                         rows.push(TimelineRenderRow::Variable(render_series));
@@ -2420,6 +2897,7 @@ impl WaveformTimeline {
             viewport_start: layout.viewport_start,
             viewport_end: layout.viewport_end,
             cursor: layout.cursor,
+            secondary_cursor: layout.secondary_cursor,
             zoom_center: layout.zoom_center,
             canvas_width_px: layout.canvas_width_px,
             canvas_height_px: layout.canvas_height_px,
@@ -2512,9 +2990,9 @@ impl WaveformTimeline {
             return;
         };
         let target_time = self.hover_time_from_snapshot(&snapshot);
-        let target_ns = target_time.picoseconds() / PS_PER_NS;
+        let target_ps = target_time.picoseconds();
 
-        let value = Self::cursor_value_from_transitions(series.transitions.as_ref(), target_ns);
+        let value = Self::cursor_value_from_transitions(&series.transitions, target_ps);
         let formatted_value = crate::format_selection::format_signal_value_for_display(
             &value,
             series.signal_type.as_deref(),
@@ -2784,6 +3262,29 @@ impl WaveformTimeline {
                     t.schedule_request();
                 })
             })),
+            // Difference shading follows the compared variables and the viewport
+            Arc::new(Task::start_droppable({
+                let t = t.clone();
+                let compared_signal = t
+                    .selected_variables
+                    .variables_vec_actor
+                    .signal_ref(|variables| {
+                        variables
+                            .iter()
+                            .filter_map(|variable| variable.compare_unique_id())
+                            .collect::<Vec<_>>()
+                    })
+                    .dedupe_cloned();
+                map_ref! {
+                    let _viewport = t.viewport.signal_cloned(),
+                    let _canvas_width = t.canvas_width.signal_cloned(),
+                    let _compared = compared_signal => ()
+                }
+                .for_each(move |_| {
+                    t.request_signal_differences();
+                    Timer::sleep(DIFFERENCE_REQUEST_INTERVAL_MS)
+                })
+            })),
             Arc::new(Task::start_droppable({
                 let t = t.clone();
                 t.canvas_height.signal_cloned().for_each_sync(move |_| {
@@ -2799,6 +3300,7 @@ impl WaveformTimeline {
                 map_ref! {
                     let _viewport = t.viewport.signal_cloned(),
                     let _cursor = t.cursor.signal_cloned(),
+                    let _secondary_cursor = t.secondary_cursor.signal_cloned(),
                     let _zoom_center = t.zoom_center.signal_cloned(),
                     let _tooltip = t.tooltip_enabled.signal_cloned() => {}
                 }
@@ -2855,6 +3357,12 @@ impl WaveformTimeline {
             }
         }
 
+        let secondary_target = state
+            .secondary_cursor
+            .map(|time| self.clamp_to_bounds(time));
+        let secondary_changed = self.secondary_cursor.get_cloned() != secondary_target;
+        self.secondary_cursor.set_neq(secondary_target);
+
         let mut zoom_target = state
             .zoom_center
             .or_else(|| state.visible_range.map(|range| range.start))
@@ -2883,10 +3391,10 @@ impl WaveformTimeline {
             self.config_restored.set_neq(true);
         }
 
-        if viewport_changed || cursor_changed {
+        if viewport_changed || cursor_changed || secondary_changed {
             self.refresh_cursor_values_from_series();
         }
-        if zoom_changed && !viewport_changed && !cursor_changed {
+        if (zoom_changed || secondary_changed) && !viewport_changed && !cursor_changed {
             self.update_render_state();
         }
 
@@ -2964,10 +3472,16 @@ impl WaveformTimeline {
         drop(set);
         self.reload_restore_pending.set(false);
 
+        // Derived signals may read any file, so they are reloaded with each one
         {
             let mut series_map = self.series_map.lock_mut();
             let prefix = format!("{}|", file_path);
-            series_map.retain(|key, _| !key.starts_with(&prefix));
+            let derived_prefix = format!("{}|", shared::DERIVED_SIGNALS_FILE);
+            series_map
+                .retain(|key, _| !key.starts_with(&prefix) && !key.starts_with(&derived_prefix));
+            self.signal_differences.lock_mut().retain(|key, entry| {
+                !key.starts_with(&prefix) && !entry.compare_unique_id.starts_with(&prefix)
+            });
         }
 
         let variables_snapshot = self.selected_variables.variables_vec_actor.get_cloned();
@@ -2976,7 +3490,7 @@ impl WaveformTimeline {
             .iter()
             .filter_map(|var| {
                 var.file_path().and_then(|path| {
-                    if path == file_path {
+                    if path == file_path || path == shared::DERIVED_SIGNALS_FILE {
                         Some(var.unique_id.clone())
                     } else {
                         None
@@ -2990,20 +3504,31 @@ impl WaveformTimeline {
         }
 
         let affected_list: Vec<String> = affected_ids.iter().cloned().collect();
+        self.reload_variables(&affected_list);
+    }
 
+    /// Drop the data of one variable and query it again, e.g. after a derived signal
+    /// was redefined
+    pub fn refresh_variable_data(&self, unique_id: &str) {
+        self.invalidate_request_fingerprints();
+        self.series_map.lock_mut().remove(unique_id);
+        self.reload_variables(&[unique_id.to_string()]);
+    }
+
+    fn reload_variables(&self, affected_list: &[String]) {
         {
             let mut cursor_values = self.cursor_values.lock_mut();
-            for unique_id in &affected_list {
+            for unique_id in affected_list {
                 cursor_values.insert(unique_id.clone(), SignalValue::Loading);
             }
         }
 
         {
             let mut window_cache = self.window_cache.lock_mut();
-            window_cache.invalidate_ids(&affected_list);
+            window_cache.invalidate_ids(affected_list);
         }
 
-        for unique_id in &affected_list {
+        for unique_id in affected_list {
             self.cancel_cursor_loading_indicator(unique_id);
             self.schedule_cursor_loading_indicator(unique_id.clone());
         }
@@ -3023,6 +3548,7 @@ impl WaveformTimeline {
                 self.reload_restore_pending.set(true);
             }
             self.schedule_request();
+            self.request_signal_differences();
         }
     }
 
@@ -3041,6 +3567,9 @@ impl WaveformTimeline {
             let Some(file_path) = variable.file_path() else {
                 return true;
             };
+            if file_path == shared::DERIVED_SIGNALS_FILE {
+                return true;
+            }
             tracked_files.iter().any(|tracked_file| {
                 (tracked_file.canonical_path == file_path || tracked_file.path == file_path)
                     && matches!(
@@ -3104,6 +3633,26 @@ mod tests {
     use super::*;
     use std::collections::BTreeMap;
 
+    #[test]
+    fn packed_series_merge_and_gain_a_leading_value() {
+        let column = PackedTransitions::from_pairs;
+        // The string tables of the two columns differ
+        let existing = column(&[(0, "IDLE"), (10, "BUSY")]);
+        let fresh = column(&[(10, "DONE"), (20, "IDLE")]);
+        let merged = merge_signal_transitions(&existing, &fresh);
+        assert_eq!(
+            merged.to_transitions(),
+            column(&[(0, "IDLE"), (10, "DONE"), (20, "IDLE")]).to_transitions()
+        );
+
+        let mut window = column(&[(30, "BUSY")]);
+        ensure_leading_transition(&mut window, 25, Some(&merged));
+        assert_eq!(
+            window.to_transitions(),
+            column(&[(25, "IDLE"), (30, "BUSY")]).to_transitions()
+        );
+    }
+
     #[test]
     fn test_cursor_values_actor_signal_propagation() {
         let cursor_values: Mutable<BTreeMap<String, SignalValue>> = Mutable::new(BTreeMap::new());
@@ -0,0 +1,223 @@
+//! Signal-by-signal comparison of two loaded files (`UpMsg::CompareWaveforms`).
+//!
+//! The backend matches signals by scope path, using the remaps edited in the Compare dialog,
+//! and reports the signals whose values differ. Each first divergence gets a timeline marker;
+//! a new comparison replaces the markers of the previous one.
+
+use crate::pending_request::PendingRequest;
+use shared::{PathRemap, WaveformDiffRequest, WaveformDiffSummary};
+use zoon::*;
+
+/// Name prefix of the markers placed at divergences
+const DIFF_MARKER_PREFIX: &str = "≠ ";
+
+/// Only the earliest divergences get markers, so a broken run does not bury the timeline
+const MAX_DIFF_MARKERS: usize = 32;
+
+#[derive(Clone)]
+pub struct WaveformDiff {
+    pub dialog_visible: Mutable<bool>,
+    /// Canonical path of the golden run
+    pub reference_file: Mutable<Option<String>>,
+    pub candidate_file: Mutable<Option<String>>,
+    /// Remaps as typed, see `parse_path_remaps`
+    pub remaps_input: Mutable<String>,
+    pub request: PendingRequest<()>,
+    pub progress: Mutable<f32>,
+    pub summary: Mutable<Option<WaveformDiffSummary>>,
+}
+
+impl WaveformDiff {
+    pub fn new() -> Self {
+        Self {
+            dialog_visible: Mutable::new(false),
+            reference_file: Mutable::new(None),
+            candidate_file: Mutable::new(None),
+            remaps_input: Mutable::new(String::new()),
+            request: PendingRequest::new(),
+            progress: Mutable::new(0.0),
+            summary: Mutable::new(None),
+        }
+    }
+
+    /// Show the dialog, choosing the first two loaded files unless a choice is still valid
+    pub fn open(&self, file_paths: &[String], remaps: &[PathRemap]) {
+        let still_loaded = |choice: &Mutable<Option<String>>| {
+            choice
+                .get_cloned()
+                .is_some_and(|path| file_paths.contains(&path))
+        };
+        if !still_loaded(&self.reference_file) {
+            self.reference_file.set(file_paths.first().cloned());
+        }
+        if !still_loaded(&self.candidate_file) {
+            self.candidate_file.set(file_paths.get(1).cloned());
+        }
+        self.remaps_input.set(format_path_remaps(remaps));
+        self.request.error_message.set(None);
+        self.dialog_visible.set(true);
+    }
+
+    pub fn start(&self, app_config: &crate::config::AppConfig) {
+        let (Some(reference_file), Some(candidate_file)) = (
+            self.reference_file.get_cloned(),
+            self.candidate_file.get_cloned(),
+        ) else {
+            self.request
+                .error_message
+                .set(Some("Load two files to compare them.".to_string()));
+            return;
+        };
+        if reference_file == candidate_file {
+            self.request
+                .error_message
+                .set(Some("Choose two different files.".to_string()));
+            return;
+        }
+        let path_remaps = match parse_path_remaps(&self.remaps_input.get_cloned()) {
+            Ok(path_remaps) => path_remaps,
+            Err(error) => {
+                self.request.error_message.set(Some(error));
+                return;
+            }
+        };
+        app_config.diff_path_remaps_config.set(path_remaps.clone());
+
+        self.progress.set(0.0);
+        self.summary.set(None);
+        self.request.send("diff", (), |diff_id| {
+            shared::UpMsg::CompareWaveforms(WaveformDiffRequest {
+                diff_id,
+                reference_file,
+                candidate_file,
+                path_remaps,
+            })
+        });
+    }
+
+    pub fn apply_progress(&self, diff_id: &str, progress: f32) {
+        if self.request.is_running(diff_id) {
+            self.progress.set(progress);
+        }
+    }
+
+    pub fn apply_summary(
+        &self,
+        diff_id: &str,
+        summary: WaveformDiffSummary,
+        timeline: &crate::visualizer::timeline::WaveformTimeline,
+        app_config: &crate::config::AppConfig,
+    ) {
+        if self.request.finish(diff_id).is_none() {
+            return;
+        }
+        app_config.replace_prefixed_markers(
+            timeline,
+            DIFF_MARKER_PREFIX,
+            divergence_markers(&summary),
+        );
+        self.summary.set(Some(summary));
+    }
+
+    pub fn apply_failure(&self, diff_id: &str, error: String) {
+        self.request.fail(diff_id, error);
+    }
+}
+
+/// One marker per distinct first-divergence time, named after a signal that diverges there
+fn divergence_markers(summary: &WaveformDiffSummary) -> Vec<shared::MarkerConfig> {
+    // (time, first variable name, signals diverging at that time)
+    let mut groups: Vec<(u64, &str, usize)> = Vec::new();
+    for mismatch in &summary.mismatches {
+        match groups.last_mut() {
+            Some((time_ps, _, count)) if *time_ps == mismatch.first_divergence_ps => *count += 1,
+            _ => {
+                let variable_name = mismatch
+                    .reference_key
+                    .rsplit('|')
+                    .next()
+                    .unwrap_or(&mismatch.reference_key);
+                groups.push((mismatch.first_divergence_ps, variable_name, 1));
+            }
+        }
+    }
+    groups
+        .into_iter()
+        .take(MAX_DIFF_MARKERS)
+        .map(|(time_ps, variable_name, count)| shared::MarkerConfig {
+            time_ps,
+            name: if count > 1 {
+                format!("{}{} +{}", DIFF_MARKER_PREFIX, variable_name, count - 1)
+            } else {
+                format!("{}{}", DIFF_MARKER_PREFIX, variable_name)
+            },
+        })
+        .collect()
+}
+
+/// `tb.dut => top.core; tb => top`; remaps may also be given one per line
+pub fn parse_path_remaps(text: &str) -> Result<Vec<PathRemap>, String> {
+    text.split([';', '\n'])
+        .map(str::trim)
+        .filter(|remap| !remap.is_empty())
+        .map(|remap| {
+            let (from, to) = remap
+                .split_once("=>")
+                .map(|(from, to)| (from.trim(), to.trim()))
+                .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+                .ok_or_else(|| format!("Remap '{}' needs the form 'from => to'", remap))?;
+            Ok(PathRemap {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+        })
+        .collect()
+}
+
+pub fn format_path_remaps(remaps: &[PathRemap]) -> String {
+    remaps
+        .iter()
+        .map(|remap| format!("{} => {}", remap.from, remap.to))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{divergence_markers, format_path_remaps, parse_path_remaps};
+    use shared::{SignalMismatch, WaveformDiffSummary};
+
+    #[test]
+    fn path_remaps_round_trip_through_text() {
+        let remaps = parse_path_remaps(" tb.dut => top.core ;\ntb=>top;").unwrap();
+        assert_eq!(remaps.len(), 2);
+        assert_eq!(remaps[1].to, "top");
+        assert_eq!(format_path_remaps(&remaps), "tb.dut => top.core; tb => top");
+        assert!(parse_path_remaps("tb.dut top.core").is_err());
+        assert!(parse_path_remaps("tb =>").is_err());
+    }
+
+    #[test]
+    fn signals_diverging_together_share_a_marker() {
+        let mismatch = |key: &str, time_ps| SignalMismatch {
+            reference_key: key.to_string(),
+            candidate_key: key.to_string(),
+            first_divergence_ps: time_ps,
+            mismatch_intervals: 1,
+        };
+        let summary = WaveformDiffSummary {
+            mismatches: vec![
+                mismatch("tb.dut|state", 100),
+                mismatch("tb.dut|next", 100),
+                mismatch("tb.dut|ack", 100),
+                mismatch("tb|done", 250),
+            ],
+            ..WaveformDiffSummary::default()
+        };
+        let markers = divergence_markers(&summary);
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].name, "≠ state +2");
+        assert_eq!(markers[1].time_ps, 250);
+        assert_eq!(markers[1].name, "≠ done");
+    }
+}
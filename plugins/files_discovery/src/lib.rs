@@ -281,14 +281,25 @@ impl DiscoveryState {
     }
 
     fn extension_allowed(&self, path: &Path) -> bool {
-        path.extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| ext.to_ascii_lowercase())
+        waveform_extension(path)
             .map(|ext| self.allow_extensions.contains(&ext))
             .unwrap_or(false)
     }
 }
 
+const COMPRESSION_EXTENSIONS: &[&str] = &["gz", "zst", "xz"];
+
+/// Lowercase extension a path is filtered by; compressed dumps such as
+/// `run.vcd.gz` count as the format they wrap.
+fn waveform_extension(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !COMPRESSION_EXTENSIONS.contains(&ext.as_str()) {
+        return Some(ext);
+    }
+    let inner = Path::new(path.file_stem()?).extension()?.to_str()?;
+    Some(inner.to_ascii_lowercase())
+}
+
 impl Guest for DiscoveryPlugin {
     fn init() {
         host::log_info("files_discovery: init");
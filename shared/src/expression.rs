@@ -0,0 +1,885 @@
+//! Expression language of derived signals (`DerivedSignal`) and conditional searches
+//! (`ConditionSearchRequest`).
+//!
+//! Operands are selected variables, written as their scope path and name (or any
+//! unambiguous dot-separated tail of it, see `resolve_signal`), and Verilog literals such
+//! as `12`, `4'hA` or `8'b1010_xxxx`. Names with other characters use Verilog escapes:
+//! `\data[7:0] ` runs up to the next whitespace.
+//!
+//! Operators and their precedence follow Verilog: unary `! ~ -`, then `*`, `+ -`,
+//! `<< >>`, `< <= > >=`, `== !=`, `&`, `^`, `|`, `&&`, `||`, plus slices `x[7:4]`, `x[3]`
+//! and concatenation `{a, b}`. Vectors are unsigned; arithmetic and bitwise results take
+//! the width of the wider operand, comparisons and logical operators give one bit.
+//!
+//! Values are 4-state. X and Z make arithmetic and comparisons unknown, while the bitwise
+//! and logical operators resolve what they can (`0 & x` is `0`).
+//!
+//! `rising(x)` and `falling(x)` hold at the time step where the lowest bit of `x` changes
+//! from 0 to 1 or 1 to 0, and `a becomes b` holds where `a == b` starts to hold. They
+//! compare against the values before the time step, see `Expr::evaluate_step`.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bit {
+    Zero,
+    One,
+    X,
+    Z,
+}
+
+impl Bit {
+    /// VCD value character; weak levels count as their strong ones
+    fn from_char(c: char) -> Bit {
+        match c {
+            '0' | 'l' | 'L' => Bit::Zero,
+            '1' | 'h' | 'H' => Bit::One,
+            'z' | 'Z' => Bit::Z,
+            _ => Bit::X,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Bit::Zero => '0',
+            Bit::One => '1',
+            Bit::X => 'x',
+            Bit::Z => 'z',
+        }
+    }
+
+    fn from_bool(value: bool) -> Bit {
+        if value { Bit::One } else { Bit::Zero }
+    }
+
+    fn not(self) -> Bit {
+        match self {
+            Bit::Zero => Bit::One,
+            Bit::One => Bit::Zero,
+            _ => Bit::X,
+        }
+    }
+
+    fn and(self, other: Bit) -> Bit {
+        match (self, other) {
+            (Bit::Zero, _) | (_, Bit::Zero) => Bit::Zero,
+            (Bit::One, Bit::One) => Bit::One,
+            _ => Bit::X,
+        }
+    }
+
+    fn or(self, other: Bit) -> Bit {
+        match (self, other) {
+            (Bit::One, _) | (_, Bit::One) => Bit::One,
+            (Bit::Zero, Bit::Zero) => Bit::Zero,
+            _ => Bit::X,
+        }
+    }
+
+    fn xor(self, other: Bit) -> Bit {
+        match (self, other) {
+            (Bit::Zero | Bit::One, Bit::Zero | Bit::One) => Bit::from_bool(self != other),
+            _ => Bit::X,
+        }
+    }
+}
+
+/// Bit vector, least significant bit first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bits(Vec<Bit>);
+
+impl Bits {
+    /// Parse a VCD bit string, most significant bit first
+    pub fn parse(value: &str) -> Bits {
+        Bits(value.chars().rev().map(Bit::from_char).collect())
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    fn unknown(width: usize) -> Bits {
+        Bits(vec![Bit::X; width])
+    }
+
+    fn from_bools(bits: Vec<bool>) -> Bits {
+        Bits(bits.into_iter().map(Bit::from_bool).collect())
+    }
+
+    fn from_u128(value: u128, width: usize) -> Bits {
+        Bits::from_bools(
+            (0..width)
+                .map(|index| index < 128 && value >> index & 1 == 1)
+                .collect(),
+        )
+    }
+
+    /// Zero-extended or truncated copy; an X or Z in the top bit extends as itself
+    fn resized(&self, width: usize) -> Bits {
+        let fill = match self.0.last() {
+            Some(Bit::X) => Bit::X,
+            Some(Bit::Z) => Bit::Z,
+            _ => Bit::Zero,
+        };
+        let mut bits = self.0.clone();
+        bits.resize(width, fill);
+        Bits(bits)
+    }
+
+    fn zero_extended(&self, width: usize) -> Bits {
+        let mut bits = self.0.clone();
+        bits.resize(width.max(bits.len()), Bit::Zero);
+        Bits(bits)
+    }
+
+    fn known(&self) -> Option<Vec<bool>> {
+        self.0
+            .iter()
+            .map(|bit| match bit {
+                Bit::Zero => Some(false),
+                Bit::One => Some(true),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// One when any bit is set, zero when all are clear, unknown otherwise
+    fn truth(&self) -> Bit {
+        self.0.iter().fold(Bit::Zero, |truth, bit| truth.or(*bit))
+    }
+
+    /// Some bit is set; unknown values are not true
+    pub fn is_true(&self) -> bool {
+        self.truth() == Bit::One
+    }
+
+    fn map(&self, f: impl Fn(Bit) -> Bit) -> Bits {
+        Bits(self.0.iter().map(|bit| f(*bit)).collect())
+    }
+
+    fn zip(&self, other: &Bits, f: impl Fn(Bit, Bit) -> Bit) -> Bits {
+        let width = self.width().max(other.width());
+        let (a, b) = (self.zero_extended(width), other.zero_extended(width));
+        Bits(a.0.iter().zip(&b.0).map(|(a, b)| f(*a, *b)).collect())
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0
+            .iter()
+            .rev()
+            .try_for_each(|bit| write!(f, "{}", bit.to_char()))
+    }
+}
+
+fn add(a: &[bool], b: &[bool], carry_in: bool) -> Vec<bool> {
+    let mut carry = carry_in;
+    a.iter()
+        .zip(b)
+        .map(|(&a, &b)| {
+            let sum = a ^ b ^ carry;
+            carry = (a && b) || (carry && (a ^ b));
+            sum
+        })
+        .collect()
+}
+
+fn subtract(a: &[bool], b: &[bool]) -> Vec<bool> {
+    let inverted: Vec<bool> = b.iter().map(|bit| !bit).collect();
+    add(a, &inverted, true)
+}
+
+fn multiply(a: &[bool], b: &[bool]) -> Vec<bool> {
+    let width = a.len();
+    let mut product = vec![false; width];
+    for (shift, _) in b.iter().enumerate().filter(|(_, bit)| **bit) {
+        let mut shifted = vec![false; shift.min(width)];
+        shifted.extend_from_slice(&a[..width - shift.min(width)]);
+        product = add(&product, &shifted, false);
+    }
+    product
+}
+
+fn compare(a: &[bool], b: &[bool]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+/// Shift distance, saturating for amounts wider than `usize`
+fn to_usize(bits: &[bool]) -> usize {
+    let mut value = 0usize;
+    for (index, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+        if index >= usize::BITS as usize {
+            return usize::MAX;
+        }
+        value |= 1 << index;
+    }
+    value
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnaryOp {
+    LogicalNot,
+    BitNot,
+    Negate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    LogicalOr,
+    LogicalAnd,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Shl,
+    Shr,
+    Add,
+    Sub,
+    Mul,
+}
+
+impl BinaryOp {
+    fn from_token(token: &str) -> Option<(BinaryOp, u8)> {
+        Some(match token {
+            "||" => (BinaryOp::LogicalOr, 1),
+            "&&" => (BinaryOp::LogicalAnd, 2),
+            "|" => (BinaryOp::BitOr, 3),
+            "^" => (BinaryOp::BitXor, 4),
+            "&" => (BinaryOp::BitAnd, 5),
+            "==" => (BinaryOp::Eq, 6),
+            "!=" => (BinaryOp::Ne, 6),
+            "<" => (BinaryOp::Lt, 7),
+            "<=" => (BinaryOp::Le, 7),
+            ">" => (BinaryOp::Gt, 7),
+            ">=" => (BinaryOp::Ge, 7),
+            "<<" => (BinaryOp::Shl, 8),
+            ">>" => (BinaryOp::Shr, 8),
+            "+" => (BinaryOp::Add, 9),
+            "-" => (BinaryOp::Sub, 9),
+            "*" => (BinaryOp::Mul, 10),
+            _ => return None,
+        })
+    }
+
+    fn apply(self, a: &Bits, b: &Bits) -> Bits {
+        let width = a.width().max(b.width());
+        match self {
+            BinaryOp::LogicalOr => Bits(vec![a.truth().or(b.truth())]),
+            BinaryOp::LogicalAnd => Bits(vec![a.truth().and(b.truth())]),
+            BinaryOp::BitOr => a.zip(b, Bit::or),
+            BinaryOp::BitXor => a.zip(b, Bit::xor),
+            BinaryOp::BitAnd => a.zip(b, Bit::and),
+            BinaryOp::Shl | BinaryOp::Shr => match (a.known(), b.known()) {
+                (Some(value), Some(amount)) => {
+                    let amount = to_usize(&amount).min(value.len());
+                    let mut shifted = vec![false; value.len()];
+                    if self == BinaryOp::Shl {
+                        shifted[amount..].copy_from_slice(&value[..value.len() - amount]);
+                    } else {
+                        shifted[..value.len() - amount].copy_from_slice(&value[amount..]);
+                    }
+                    Bits::from_bools(shifted)
+                }
+                _ => Bits::unknown(a.width()),
+            },
+            _ => {
+                let (Some(a), Some(b)) = (
+                    a.zero_extended(width).known(),
+                    b.zero_extended(width).known(),
+                ) else {
+                    return match self {
+                        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => Bits::unknown(width),
+                        _ => Bits::unknown(1),
+                    };
+                };
+                let ordering = compare(&a, &b);
+                let truth = |value: bool| Bits::from_bools(vec![value]);
+                match self {
+                    BinaryOp::Eq => truth(ordering == Ordering::Equal),
+                    BinaryOp::Ne => truth(ordering != Ordering::Equal),
+                    BinaryOp::Lt => truth(ordering == Ordering::Less),
+                    BinaryOp::Le => truth(ordering != Ordering::Greater),
+                    BinaryOp::Gt => truth(ordering == Ordering::Greater),
+                    BinaryOp::Ge => truth(ordering != Ordering::Less),
+                    BinaryOp::Add => Bits::from_bools(add(&a, &b, false)),
+                    BinaryOp::Sub => Bits::from_bools(subtract(&a, &b)),
+                    _ => Bits::from_bools(multiply(&a, &b)),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Rising,
+    Falling,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    /// Operand as written in the expression, see `resolve_signal`
+    Signal(String),
+    Literal(Bits),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Slice {
+        value: Box<Expr>,
+        msb: usize,
+        lsb: usize,
+    },
+    /// Parts from the most significant one
+    Concat(Vec<Expr>),
+    Edge(Edge, Box<Expr>),
+    /// `a becomes b`
+    Becomes(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Operand names, each once
+    pub fn signals(&self) -> BTreeSet<String> {
+        let mut signals = BTreeSet::new();
+        self.collect_signals(&mut signals);
+        signals
+    }
+
+    fn collect_signals(&self, signals: &mut BTreeSet<String>) {
+        match self {
+            Expr::Signal(name) => {
+                signals.insert(name.clone());
+            }
+            Expr::Literal(_) => {}
+            Expr::Unary(_, operand) | Expr::Edge(_, operand) => operand.collect_signals(signals),
+            Expr::Binary(_, a, b) | Expr::Becomes(a, b) => {
+                a.collect_signals(signals);
+                b.collect_signals(signals);
+            }
+            Expr::Slice { value, .. } => value.collect_signals(signals),
+            Expr::Concat(parts) => parts.iter().for_each(|part| part.collect_signals(signals)),
+        }
+    }
+
+    /// Value of the expression given the current value of every operand. Edges and
+    /// `becomes` never hold, as there is no earlier value to compare with.
+    pub fn evaluate(&self, values: &HashMap<String, Bits>) -> Result<Bits, String> {
+        self.evaluate_step(values, values)
+    }
+
+    /// Value of the expression at a time step, given the operand values from that step on
+    /// and the values just before it
+    pub fn evaluate_step(
+        &self,
+        values: &HashMap<String, Bits>,
+        previous: &HashMap<String, Bits>,
+    ) -> Result<Bits, String> {
+        Ok(match self {
+            Expr::Signal(name) => values
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("No value for '{}'", name))?,
+            Expr::Literal(bits) => bits.clone(),
+            Expr::Unary(op, operand) => {
+                let value = operand.evaluate_step(values, previous)?;
+                match op {
+                    UnaryOp::LogicalNot => Bits(vec![value.truth().not()]),
+                    UnaryOp::BitNot => value.map(Bit::not),
+                    UnaryOp::Negate => match value.known() {
+                        Some(bits) => Bits::from_bools(subtract(&vec![false; bits.len()], &bits)),
+                        None => Bits::unknown(value.width()),
+                    },
+                }
+            }
+            Expr::Binary(op, a, b) => op.apply(
+                &a.evaluate_step(values, previous)?,
+                &b.evaluate_step(values, previous)?,
+            ),
+            Expr::Slice { value, msb, lsb } => {
+                let value = value.evaluate_step(values, previous)?;
+                if *msb >= value.width() {
+                    return Err(format!(
+                        "Bit {} is outside a {}-bit value",
+                        msb,
+                        value.width()
+                    ));
+                }
+                Bits(value.0[*lsb..=*msb].to_vec())
+            }
+            Expr::Concat(parts) => {
+                let mut bits = Vec::new();
+                for part in parts.iter().rev() {
+                    bits.extend(part.evaluate_step(values, previous)?.0);
+                }
+                Bits(bits)
+            }
+            Expr::Edge(edge, operand) => {
+                let lowest_bit = |bits: Bits| bits.0.first().copied().unwrap_or(Bit::X);
+                let now = lowest_bit(operand.evaluate_step(values, previous)?);
+                // No edge where an operand had no value before the step
+                let before = operand.evaluate(previous).map(lowest_bit).ok();
+                let (from, to) = match edge {
+                    Edge::Rising => (Bit::Zero, Bit::One),
+                    Edge::Falling => (Bit::One, Bit::Zero),
+                };
+                Bits(vec![Bit::from_bool(before == Some(from) && now == to)])
+            }
+            Expr::Becomes(a, b) => {
+                let condition = Expr::Binary(BinaryOp::Eq, a.clone(), b.clone());
+                let now = condition.evaluate_step(values, previous)?.is_true();
+                let before = condition
+                    .evaluate(previous)
+                    .is_ok_and(|value| value.is_true());
+                Bits(vec![Bit::from_bool(now && !before)])
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Bits),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 25] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<<", ">>", "!", "~", "-", "+", "*", "&", "|", "^", "<",
+    ">", "(", ")", "[", "]", "{", "}", ":",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        let rest: String = chars[pos..chars.len().min(pos + 2)].iter().collect();
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c == '\\' {
+            let start = pos + 1;
+            pos = start;
+            while pos < chars.len() && !chars[pos].is_whitespace() {
+                pos += 1;
+            }
+            if pos == start {
+                return Err("Escaped name after '\\' is empty".to_string());
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = pos;
+            while pos < chars.len()
+                && (chars[pos].is_ascii_alphanumeric() || matches!(chars[pos], '_' | '$' | '.'))
+            {
+                pos += 1;
+            }
+            tokens.push(Token::Ident(chars[start..pos].iter().collect()));
+        } else if c.is_ascii_digit() || c == '\'' {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            if pos < chars.len() && chars[pos] == '\'' {
+                pos += 1;
+                while pos < chars.len()
+                    && (chars[pos].is_ascii_alphanumeric() || matches!(chars[pos], '_' | '?'))
+                {
+                    pos += 1;
+                }
+            }
+            let literal: String = chars[start..pos].iter().collect();
+            tokens.push(Token::Literal(parse_literal(&literal)?));
+        } else if c == ',' {
+            tokens.push(Token::Symbol(","));
+            pos += 1;
+        } else if let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) {
+            tokens.push(Token::Symbol(symbol));
+            pos += symbol.len();
+        } else {
+            return Err(format!("Unexpected '{}' in expression", c));
+        }
+    }
+    Ok(tokens)
+}
+
+/// `12`, `4'hA`, `'b1x0`, `8'd200`, `0xDEADBEEF`, `0b1010`, `0o17`
+fn parse_literal(literal: &str) -> Result<Bits, String> {
+    let invalid = || format!("Invalid literal '{}'", literal);
+    let Some((size, based)) = literal.split_once('\'') else {
+        // C-style prefixes read like the unsized `'h`, `'b` and `'o` literals
+        let prefixed = literal.get(..2).map(str::to_ascii_lowercase);
+        if let Some(base) = prefixed.as_deref().and_then(|prefix| match prefix {
+            "0x" => Some('h'),
+            "0b" => Some('b'),
+            "0o" => Some('o'),
+            _ => None,
+        }) {
+            return parse_literal(&format!("'{}{}", base, &literal[2..])).map_err(|_| invalid());
+        }
+        let value: u128 = literal.replace('_', "").parse().map_err(|_| invalid())?;
+        let width = (128 - value.leading_zeros() as usize).max(1);
+        return Ok(Bits::from_u128(value, width));
+    };
+    let size: Option<usize> = match size {
+        "" => None,
+        size => Some(
+            size.parse()
+                .ok()
+                .filter(|size| *size > 0)
+                .ok_or_else(invalid)?,
+        ),
+    };
+    let based = based.strip_prefix(['s', 'S']).unwrap_or(based);
+    let mut based_chars = based.chars();
+    let base = based_chars.next().ok_or_else(invalid)?.to_ascii_lowercase();
+    let digits: String = based_chars.filter(|c| *c != '_').collect();
+    if digits.is_empty() {
+        return Err(invalid());
+    }
+
+    let bits = if base == 'd' {
+        let value: u128 = digits.parse().map_err(|_| invalid())?;
+        Bits::from_u128(value, (128 - value.leading_zeros() as usize).max(1))
+    } else {
+        let bits_per_digit = match base {
+            'b' => 1,
+            'o' => 3,
+            'h' => 4,
+            _ => return Err(invalid()),
+        };
+        let mut bits = Vec::new();
+        for digit in digits.chars().rev() {
+            let digit_bits = match digit.to_ascii_lowercase() {
+                'x' => vec![Bit::X; bits_per_digit],
+                'z' | '?' => vec![Bit::Z; bits_per_digit],
+                digit => {
+                    let value = digit.to_digit(1 << bits_per_digit).ok_or_else(invalid)?;
+                    (0..bits_per_digit)
+                        .map(|index| Bit::from_bool(value >> index & 1 == 1))
+                        .collect()
+                }
+            };
+            bits.extend(digit_bits);
+        }
+        Bits(bits)
+    };
+    Ok(match size {
+        Some(size) => bits.resized(size),
+        None => bits,
+    })
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_symbol(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Symbol(symbol)) => Some(symbol),
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), String> {
+        if self.peek_symbol() == Some(symbol) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!("Expected '{}' {}", symbol, self.position()))
+        }
+    }
+
+    fn position(&self) -> String {
+        match self.tokens.get(self.pos) {
+            Some(Token::Ident(name)) => format!("before '{}'", name),
+            Some(Token::Literal(bits)) => format!("before the literal {}", bits),
+            Some(Token::Symbol(symbol)) => format!("before '{}'", symbol),
+            None => "at the end of the expression".to_string(),
+        }
+    }
+
+    /// `a becomes b` binds more loosely than every operator
+    fn condition(&mut self) -> Result<Expr, String> {
+        let value = self.binary(0)?;
+        if !matches!(self.tokens.get(self.pos), Some(Token::Ident(word)) if word == "becomes") {
+            return Ok(value);
+        }
+        self.pos += 1;
+        let target = self.binary(0)?;
+        Ok(Expr::Becomes(Box::new(value), Box::new(target)))
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        while let Some((op, precedence)) = self.peek_symbol().and_then(BinaryOp::from_token) {
+            if precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            let right = self.binary(precedence + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let op = match self.peek_symbol() {
+            Some("!") => UnaryOp::LogicalNot,
+            Some("~") => UnaryOp::BitNot,
+            Some("-") => UnaryOp::Negate,
+            _ => return self.postfix(),
+        };
+        self.pos += 1;
+        Ok(Expr::Unary(op, Box::new(self.unary()?)))
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut value = self.primary()?;
+        while self.peek_symbol() == Some("[") {
+            self.pos += 1;
+            let msb = self.index()?;
+            let lsb = if self.peek_symbol() == Some(":") {
+                self.pos += 1;
+                self.index()?
+            } else {
+                msb
+            };
+            self.expect("]")?;
+            if lsb > msb {
+                return Err(format!(
+                    "Slice [{}:{}] must run from high to low bit",
+                    msb, lsb
+                ));
+            }
+            value = Expr::Slice {
+                value: Box::new(value),
+                msb,
+                lsb,
+            };
+        }
+        Ok(value)
+    }
+
+    fn index(&mut self) -> Result<usize, String> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Literal(bits)) => {
+                let index = bits
+                    .known()
+                    .map(|bits| to_usize(&bits))
+                    .ok_or_else(|| "Slice bounds must not contain x or z".to_string())?;
+                self.pos += 1;
+                Ok(index)
+            }
+            _ => Err(format!("Expected a bit index {}", self.position())),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Ident(name)) if self.peek_symbol() == Some("(") => {
+                let edge = match name.as_str() {
+                    "rising" => Edge::Rising,
+                    "falling" => Edge::Falling,
+                    _ => return Err(format!("Unknown function '{}'", name)),
+                };
+                self.pos += 1;
+                let operand = self.condition()?;
+                self.expect(")")?;
+                Ok(Expr::Edge(edge, Box::new(operand)))
+            }
+            Some(Token::Ident(name)) => Ok(Expr::Signal(name)),
+            Some(Token::Literal(bits)) => Ok(Expr::Literal(bits)),
+            Some(Token::Symbol("(")) => {
+                let inner = self.condition()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(Token::Symbol("{")) => {
+                let mut parts = vec![self.binary(0)?];
+                while self.peek_symbol() == Some(",") {
+                    self.pos += 1;
+                    parts.push(self.binary(0)?);
+                }
+                self.expect("}")?;
+                Ok(Expr::Concat(parts))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(format!("Expected a signal or value {}", self.position()))
+            }
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    if parser.tokens.is_empty() {
+        return Err("Expression is empty".to_string());
+    }
+    let expr = parser.condition()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(format!("Unexpected input {}", parser.position()));
+    }
+    Ok(expr)
+}
+
+/// unique_id of the variable an operand names: the variable whose "scope.name" path is
+/// the operand or ends with "." followed by it
+pub fn resolve_signal(operand: &str, unique_ids: &[String]) -> Result<String, String> {
+    let suffix = format!(".{}", operand);
+    let matches: Vec<&String> = unique_ids
+        .iter()
+        .filter(|unique_id| {
+            let mut parts = unique_id.splitn(3, '|').skip(1);
+            let (Some(scope), Some(name)) = (parts.next(), parts.next()) else {
+                return false;
+            };
+            let path = format!("{}.{}", scope, name);
+            path == operand || path.ends_with(&suffix)
+        })
+        .collect();
+    match matches.as_slice() {
+        [unique_id] => Ok((*unique_id).clone()),
+        [] => Err(format!("'{}' is not a selected variable", operand)),
+        _ => Err(format!(
+            "'{}' matches several selected variables; write more of its scope path",
+            operand
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bits, parse, resolve_signal};
+    use std::collections::HashMap;
+
+    fn evaluate(text: &str, values: &[(&str, &str)]) -> String {
+        let values: HashMap<String, Bits> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), Bits::parse(value)))
+            .collect();
+        parse(text).unwrap().evaluate(&values).unwrap().to_string()
+    }
+
+    #[test]
+    fn operators_follow_verilog_precedence_and_widths() {
+        let handshake = [("valid", "1"), ("ready", "0"), ("addr", "1010000000000001")];
+        assert_eq!(evaluate("valid && ready", &handshake), "0");
+        assert_eq!(evaluate("valid && !ready || ready", &handshake), "1");
+        assert_eq!(evaluate("addr[15:12] == 4'hA", &handshake), "1");
+        assert_eq!(evaluate("{addr[3:0], valid, 2'b01}", &handshake), "0001101");
+        assert_eq!(evaluate("addr[3:0] + 1 * 2", &handshake), "0011");
+        assert_eq!(evaluate("addr[3:0] - 2", &handshake), "1111");
+        assert_eq!(evaluate("addr[15:12] >> 1 << 2", &handshake), "0100");
+        assert_eq!(evaluate("~addr[1:0] & 2'b10", &handshake), "10");
+        assert_eq!(evaluate("-addr[2:0]", &handshake), "111");
+        assert_eq!(evaluate("addr[15:8] > 8'd159", &handshake), "1");
+    }
+
+    #[test]
+    fn c_style_prefixes_read_like_based_literals() {
+        let values = [("data", "11011110101011011011111011101111")];
+        assert_eq!(evaluate("data == 0xDEADBEEF", &values), "1");
+        assert_eq!(evaluate("data[3:0] == 0b1111", &values), "1");
+        assert_eq!(evaluate("data[5:0] == 0o57", &values), "1");
+        assert_eq!(evaluate("0x1_F", &values), "00011111");
+        assert!(
+            parse("0xG1")
+                .unwrap_err()
+                .contains("Invalid literal '0xG1'")
+        );
+    }
+
+    #[test]
+    fn unknown_bits_propagate_only_where_they_matter() {
+        let values = [("state", "1x"), ("prev_state", "10"), ("en", "0")];
+        assert_eq!(evaluate("state != prev_state", &values), "x");
+        assert_eq!(evaluate("state & 2'b10", &values), "10");
+        assert_eq!(evaluate("state + 1", &values), "xx");
+        assert_eq!(evaluate("en && state[0]", &values), "0");
+        assert_eq!(evaluate("4'bx1 | 4'b0001", &values), "xxx1");
+    }
+
+    #[test]
+    fn parse_errors_name_the_problem() {
+        assert!(parse("a &&").unwrap_err().contains("end of the expression"));
+        assert!(parse("(a | b").unwrap_err().contains("Expected ')'"));
+        assert!(parse("a[3:5]").unwrap_err().contains("high to low"));
+        assert!(parse("a = b").unwrap_err().contains("Unexpected '='"));
+        assert!(parse("4'q1").is_err());
+        let values = HashMap::from([("a".to_string(), Bits::parse("101"))]);
+        assert!(parse("a[3]").unwrap().evaluate(&values).is_err());
+        assert_eq!(
+            parse(r"\data[7:0]  & a")
+                .unwrap()
+                .signals()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["a".to_string(), "data[7:0]".to_string()]
+        );
+    }
+
+    #[test]
+    fn edges_and_becomes_compare_with_the_previous_step() {
+        let step = |text: &str, before: &[(&str, &str)], now: &[(&str, &str)]| {
+            let values = |values: &[(&str, &str)]| -> HashMap<String, Bits> {
+                values
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), Bits::parse(value)))
+                    .collect()
+            };
+            parse(text)
+                .unwrap()
+                .evaluate_step(&values(now), &values(before))
+                .unwrap()
+                .is_true()
+        };
+        let before = [("clk", "0"), ("en", "1"), ("irq", "0")];
+        let now = [("clk", "1"), ("en", "1"), ("irq", "1")];
+        assert!(step("rising(clk) && en", &before, &now));
+        assert!(!step("rising(clk) && en", &now, &now));
+        assert!(!step("falling(clk)", &before, &now));
+        assert!(step("irq becomes 1", &before, &now));
+        assert!(!step("irq becomes 1", &now, &now));
+        assert!(step("(irq becomes 1) && en", &before, &now));
+        // No edge without a value before the step
+        assert!(!step("rising(clk)", &[], &now));
+        assert!(!evaluate("rising(clk)", &now).contains('1'));
+        assert!(parse("ramp(clk)").unwrap_err().contains("Unknown function"));
+    }
+
+    #[test]
+    fn operands_resolve_by_unambiguous_path_tail() {
+        let selected = vec![
+            "/w/run.vcd|tb.dut|valid".to_string(),
+            "/w/run.vcd|tb.dut.fifo|valid".to_string(),
+            "/w/run.vcd|tb.dut|ready".to_string(),
+        ];
+        assert_eq!(resolve_signal("ready", &selected).unwrap(), selected[2]);
+        assert_eq!(resolve_signal("dut.valid", &selected).unwrap(), selected[0]);
+        assert_eq!(
+            resolve_signal("fifo.valid", &selected).unwrap(),
+            selected[1]
+        );
+        assert!(
+            resolve_signal("valid", &selected)
+                .unwrap_err()
+                .contains("several")
+        );
+        assert!(resolve_signal("ut.ready", &selected).is_err());
+    }
+}
@@ -1,9 +1,12 @@
+use base64::prelude::{BASE64_STANDARD, Engine as _};
 use convert_base;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::str::FromStr;
 use toml::value::Table as TomlTable;
 
+pub mod expression;
+
 // ===== TIME TYPES =====
 
 // ===== MESSAGE TYPES =====
@@ -11,6 +14,10 @@ use toml::value::Table as TomlTable;
 #[derive(Serialize, Deserialize, Debug)]
 pub enum UpMsg {
     LoadWaveformFile(String),
+    /// Abort an in-progress header or body load of the given file path
+    CancelLoad(String),
+    /// Drop all backend data of the given file path (waveform body, metadata, cached signals)
+    UnloadFile(String),
     GetParsingProgress(String),
     LoadConfig,
     SelectWorkspace {
@@ -40,8 +47,50 @@ pub enum UpMsg {
     /// Unified signal data query - serves both timeline and cursor value needs
     UnifiedSignalQuery {
         signal_requests: Vec<UnifiedSignalRequest>,
-        cursor_time_ns: Option<u64>,
+        cursor_time_ps: Option<u64>,
         request_id: String, // For deduplication and tracking
+        #[serde(default)]
+        payload_encoding: SignalPayloadEncoding,
+    },
+    /// Abandon an in-flight `UnifiedSignalQuery`; no response is sent for it
+    CancelSignalQuery {
+        request_id: String,
+    },
+    /// Write an excerpt of a loaded waveform file into a new standalone file
+    ExportWaveform(WaveformExportRequest),
+    /// Compare the signals of two loaded files
+    CompareWaveforms(WaveformDiffRequest),
+    /// Register (or replace) a derived signal; answered by `DerivedSignalDefined` or
+    /// `DerivedSignalFailed`
+    DefineDerivedSignal(DerivedSignal),
+    /// Times where an expression starts to hold, searched over whole files; answered by
+    /// `ConditionSearchCompleted` or `ConditionSearchFailed`
+    SearchCondition(ConditionSearchRequest),
+    /// Intervals where one signal holds a value; answered by `ValueSearchCompleted` or
+    /// `ValueSearchFailed`
+    SearchSignalValue(ValueSearchRequest),
+    /// Edge and pulse statistics of a 1-bit signal over a time range; answered by
+    /// `SignalMeasured` or `SignalMeasurementFailed`
+    MeasureSignal(SignalMeasurementRequest),
+    /// Intervals where a variable differs from its counterpart in another file; answered by
+    /// `SignalDifferencesCompleted` or `SignalDifferencesFailed`
+    CompareSignal(SignalDifferenceRequest),
+    /// Time offset and timescale override of a file, used from its next load on
+    SetFileTimeAlignment {
+        file_path: String,
+        alignment: FileTimeAlignment,
+    },
+    /// Import settings for a logic-analyzer CSV file, used from its next load on
+    ConfigureCsvImport {
+        file_path: String,
+        settings: CsvImportSettings,
+    },
+    /// Save a rendered picture of the waveform view; answered like `ExportWaveform`
+    SaveWaveformImage {
+        export_id: String,
+        output_path: String,
+        content: String,
+        row_count: usize,
     },
     /// Debug: Trigger test notifications to demonstrate notification system
     TriggerTestNotifications,
@@ -58,6 +107,11 @@ pub enum DownMsg {
         file_id: String,
         progress: f32,
     },
+    /// Progress of loading a waveform body for the first signal query
+    LoadProgress {
+        file_id: String,
+        progress: LoadProgress,
+    },
     FileLoaded {
         file_id: String,
         hierarchy: FileHierarchy,
@@ -66,6 +120,17 @@ pub enum DownMsg {
         file_id: String,
         error: String,
     },
+    /// A load was aborted by `UpMsg::CancelLoad` or `UpMsg::UnloadFile`
+    LoadCancelled {
+        file_id: String,
+    },
+    FileUnloaded {
+        file_id: String,
+        /// Estimated bytes released from the signal cache and waveform time table
+        freed_bytes: u64,
+        /// Whether a parsed waveform body was held and dropped
+        body_released: bool,
+    },
     ConfigLoaded(AppConfig),
     WorkspaceLoaded {
         root: String,
@@ -116,14 +181,99 @@ pub enum DownMsg {
         request_id: String,
         signal_data: Vec<UnifiedSignalData>,
         cursor_values: BTreeMap<String, SignalValue>,
-        cached_time_range_ns: Option<(u64, u64)>, // What's available in backend cache
+        cached_time_range_ps: Option<(u64, u64)>, // What's available in backend cache
         statistics: Option<SignalStatistics>,
+        /// Payload encodings the backend can answer in; empty from backends that predate them
+        #[serde(default)]
+        payload_encodings: Vec<SignalPayloadEncoding>,
     },
     UnifiedSignalError {
         request_id: String,
         error: String,
     },
     PlatformRoots(Vec<PlatformRoot>),
+    ExportProgress {
+        export_id: String,
+        /// Fraction of the value changes written, 0.0..=1.0
+        progress: f32,
+    },
+    ExportCompleted {
+        export_id: String,
+        output_path: String,
+        signal_count: usize,
+        bytes_written: u64,
+        /// Export content for requests with `copy_to_clipboard`; nothing was written to disk
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        clipboard_text: Option<String>,
+    },
+    ExportFailed {
+        export_id: String,
+        error: String,
+    },
+    WaveformDiffProgress {
+        diff_id: String,
+        /// Fraction of the reference signals compared, 0.0..=1.0
+        progress: f32,
+    },
+    WaveformDiffCompleted {
+        diff_id: String,
+        summary: WaveformDiffSummary,
+    },
+    WaveformDiffFailed {
+        diff_id: String,
+        error: String,
+    },
+    DerivedSignalDefined {
+        name: String,
+    },
+    DerivedSignalFailed {
+        name: String,
+        error: String,
+    },
+    ConditionSearchCompleted {
+        search_id: String,
+        /// Ascending; at most one for `SearchDirection::Next` and `Previous`
+        matches: Vec<u64>,
+        /// More matches exist than `MAX_CONDITION_MATCHES`
+        truncated: bool,
+    },
+    ConditionSearchFailed {
+        search_id: String,
+        error: String,
+    },
+    ValueSearchCompleted {
+        search_id: String,
+        /// Number of intervals holding the value
+        match_count: usize,
+        /// The earliest `MAX_VALUE_MATCH_INTERVALS` of them as (start, end) in timeline
+        /// picoseconds; the last interval of a signal ends at `u64::MAX`
+        intervals: Vec<(u64, u64)>,
+    },
+    ValueSearchFailed {
+        search_id: String,
+        error: String,
+    },
+    SignalMeasured {
+        measurement_id: String,
+        measurement: SignalMeasurement,
+    },
+    SignalMeasurementFailed {
+        measurement_id: String,
+        error: String,
+    },
+    SignalDifferencesCompleted {
+        unique_id: String,
+        compare_unique_id: String,
+        /// The requested range, which `intervals` are limited to
+        range_ps: (u64, u64),
+        intervals: Vec<(u64, u64)>,
+    },
+    SignalDifferencesFailed {
+        unique_id: String,
+        compare_unique_id: String,
+        range_ps: (u64, u64),
+        error: String,
+    },
     /// Debug: Test notification from backend
     TestNotification {
         variant: String, // "error", "info", "success"
@@ -351,88 +501,1212 @@ pub struct SignalValueQuery {
     pub format: VarFormat,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SignalValueResult {
-    pub scope_path: String,
-    pub variable_name: String,
-    pub time_seconds: f64,
-    pub raw_value: Option<String>, // Raw binary value from waveform
-    pub formatted_value: Option<String>, // Formatted according to requested format
-    pub format: VarFormat,         // Format used for this result
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignalValueResult {
+    pub scope_path: String,
+    pub variable_name: String,
+    pub time_seconds: f64,
+    pub raw_value: Option<String>, // Raw binary value from waveform
+    pub formatted_value: Option<String>, // Formatted according to requested format
+    pub format: VarFormat,         // Format used for this result
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileSignalQueries {
+    pub file_path: String,
+    pub queries: Vec<SignalValueQuery>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FileSignalResults {
+    pub file_path: String,
+    pub results: Vec<SignalValueResult>,
+}
+
+// ===== SIGNAL TRANSITION QUERY TYPES =====
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignalTransitionQuery {
+    pub scope_path: String,
+    pub variable_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SignalTransitionResult {
+    pub scope_path: String,
+    pub variable_name: String,
+    pub transitions: Vec<SignalTransition>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalTransition {
+    /// Transition time in picoseconds, independent of the file's native timescale
+    pub time_ps: u64,
+    pub value: String,
+}
+
+impl SignalTransition {
+    /// Create new signal transition with picosecond precision
+    pub fn new(time_ps: u64, value: String) -> Self {
+        Self { time_ps, value }
+    }
+}
+
+// ===== PACKED SIGNAL VALUES =====
+
+/// Logic states in code order; a packed vector position stores an index into this table
+const LOGIC_STATE_CHARS: [char; 9] = ['0', '1', 'x', 'z', 'u', 'w', 'l', 'h', '-'];
+
+fn logic_state_code(state: char) -> Option<u8> {
+    match state {
+        '0' => Some(0),
+        '1' => Some(1),
+        'x' | 'X' => Some(2),
+        'z' | 'Z' => Some(3),
+        'u' | 'U' => Some(4),
+        'w' | 'W' => Some(5),
+        'l' | 'L' => Some(6),
+        'h' | 'H' => Some(7),
+        '-' => Some(8),
+        _ => None,
+    }
+}
+
+const PACKED_MAGIC: &[u8; 4] = b"NWPT";
+
+/// JSON has no NaN or infinity, so non-finite reals travel as strings ("NaN", "inf", "-inf")
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum WireReal {
+    Finite(f64),
+    NonFinite(String),
+}
+
+fn serialize_reals<S>(reals: &[f64], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(reals.iter().map(|&real| {
+        if real.is_finite() {
+            WireReal::Finite(real)
+        } else {
+            WireReal::NonFinite(real.to_string())
+        }
+    }))
+}
+
+fn deserialize_reals<'de, D>(deserializer: D) -> Result<Vec<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    use serde::de::Error;
+    Vec::<WireReal>::deserialize(deserializer)?
+        .into_iter()
+        .map(|real| match real {
+            WireReal::Finite(real) => Ok(real),
+            WireReal::NonFinite(text) => text
+                .parse()
+                .map_err(|_| D::Error::custom(format!("Invalid real value '{}'", text))),
+        })
+        .collect()
+}
+const PACKED_FORMAT_VERSION: u8 = 1;
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .offset
+            .checked_add(length)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or("Packed payload is truncated")?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Malformed varint in packed payload".to_string())
+    }
+}
+
+/// Storage layout of a `PackedTransitions` column, ordered from most to least compact
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum PackedEncoding {
+    /// 1 bit per position: 0/1
+    #[default]
+    TwoState,
+    /// 2 bits per position: 0/1/X/Z
+    FourState,
+    /// 4 bits per position: the full VHDL std_logic set
+    NineState,
+    /// One f64 per transition
+    Real,
+    /// One interned string id per transition
+    Text,
+}
+
+impl PackedEncoding {
+    pub fn bits_per_position(self) -> usize {
+        match self {
+            PackedEncoding::TwoState => 1,
+            PackedEncoding::FourState => 2,
+            PackedEncoding::NineState => 4,
+            PackedEncoding::Real | PackedEncoding::Text => 0,
+        }
+    }
+
+    pub fn is_logic(self) -> bool {
+        self.bits_per_position() > 0
+    }
+
+    /// Smallest logic encoding able to hold every state of `bits`, or None for non-logic text
+    pub fn for_bit_string(bits: &str) -> Option<Self> {
+        let mut encoding = PackedEncoding::TwoState;
+        for state in bits.chars() {
+            match logic_state_code(state)? {
+                0 | 1 => {}
+                2 | 3 => {
+                    if encoding == PackedEncoding::TwoState {
+                        encoding = PackedEncoding::FourState;
+                    }
+                }
+                _ => encoding = PackedEncoding::NineState,
+            }
+        }
+        Some(encoding)
+    }
+}
+
+/// Column-oriented transitions of one signal.
+///
+/// Vectors are stored as bit-planes (`words_per_value` words per transition, MSB position
+/// first), reals as plain f64 and strings as ids into an interned table, so a cached or
+/// transmitted signal costs a few bytes per transition instead of one `String` each.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PackedTransitions {
+    pub encoding: PackedEncoding,
+    pub width: u32, // Positions per vector value; 0 for Real and Text
+    pub times_ps: Vec<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_reals",
+        deserialize_with = "deserialize_reals"
+    )]
+    pub reals: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub string_ids: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strings: Vec<String>,
+    #[serde(skip)]
+    string_lookup: HashMap<String, u32>,
+}
+
+impl PackedTransitions {
+    pub fn new(encoding: PackedEncoding, width: u32) -> Self {
+        let width = if encoding.is_logic() { width } else { 0 };
+        Self {
+            encoding,
+            width,
+            ..Self::default()
+        }
+    }
+
+    /// Empty column sharing this one's layout and string table, for building subsets
+    pub fn empty_like(&self) -> Self {
+        Self {
+            encoding: self.encoding,
+            width: self.width,
+            strings: self.strings.clone(),
+            string_lookup: self.string_lookup.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Column built from `(time_ps, value)` pairs in VCD notation; mostly for fixtures
+    pub fn from_pairs(changes: &[(u64, &str)]) -> Self {
+        let transitions: Vec<SignalTransition> = changes
+            .iter()
+            .map(|(time_ps, value)| SignalTransition::new(*time_ps, value.to_string()))
+            .collect();
+        Self::from_transitions(&transitions)
+    }
+
+    /// Pack loose transitions, choosing the most compact encoding that holds all values
+    pub fn from_transitions(transitions: &[SignalTransition]) -> Self {
+        let mut logic_encoding = Some(PackedEncoding::TwoState);
+        let mut width = 0;
+        let mut all_real = !transitions.is_empty();
+        for transition in transitions {
+            logic_encoding = match (
+                logic_encoding,
+                PackedEncoding::for_bit_string(&transition.value),
+            ) {
+                (Some(current), Some(needed)) => Some(current.max(needed)),
+                _ => None,
+            };
+            width = width.max(transition.value.len() as u32);
+            all_real &= transition.value.contains('.') && transition.value.parse::<f64>().is_ok();
+        }
+
+        let encoding = match logic_encoding {
+            Some(encoding) if width > 0 => encoding,
+            _ if all_real => PackedEncoding::Real,
+            _ => PackedEncoding::Text,
+        };
+        let mut packed = Self::new(encoding, width);
+        for transition in transitions {
+            packed.push_value(transition.time_ps, &transition.value);
+        }
+        packed
+    }
+
+    pub fn len(&self) -> usize {
+        self.times_ps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.times_ps.is_empty()
+    }
+
+    pub fn time_ps(&self, index: usize) -> u64 {
+        self.times_ps[index]
+    }
+
+    /// Index of the transition holding the value at `time_ps` (last transition at or before it)
+    pub fn index_at_or_before(&self, time_ps: u64) -> Option<usize> {
+        self.times_ps
+            .partition_point(|&time| time <= time_ps)
+            .checked_sub(1)
+    }
+
+    pub fn words_per_value(&self) -> usize {
+        (self.width as usize * self.encoding.bits_per_position()).div_ceil(64)
+    }
+
+    /// Append a value given in its textual form (bit string, real or arbitrary text)
+    pub fn push_value(&mut self, time_ps: u64, value: &str) {
+        match self.encoding {
+            PackedEncoding::Real => self.push_real(time_ps, value.parse().unwrap_or(f64::NAN)),
+            PackedEncoding::Text => self.push_text(time_ps, value),
+            _ => self.push_bits(time_ps, value),
+        }
+    }
+
+    /// Append a vector value. Short values are extended on the left the way VCD does it
+    /// (with X/Z when the leftmost state is X/Z, otherwise with 0). A value with states the
+    /// encoding cannot hold widens the whole column first; characters that are no logic
+    /// state are stored as X.
+    pub fn push_bits(&mut self, time_ps: u64, bits: &str) {
+        let needed = bits
+            .chars()
+            .map(|state| match logic_state_code(state) {
+                Some(0 | 1) => PackedEncoding::TwoState,
+                Some(2 | 3) | None => PackedEncoding::FourState,
+                Some(_) => PackedEncoding::NineState,
+            })
+            .max()
+            .unwrap_or_default();
+        if self.encoding.is_logic() && needed > self.encoding {
+            self.widen(needed);
+        }
+        let bits_per_position = self.encoding.bits_per_position();
+        let width = self.width as usize;
+        let first_word = self.words.len();
+        self.words.resize(first_word + self.words_per_value(), 0);
+
+        let codes: Vec<u8> = bits
+            .chars()
+            .map(|state| logic_state_code(state).unwrap_or(2))
+            .collect();
+        let skipped = codes.len().saturating_sub(width);
+        let padding = width.saturating_sub(codes.len());
+        let pad_code = match codes.first() {
+            Some(&code) if code == 2 || code == 3 => code,
+            _ => 0,
+        };
+
+        for position in 0..width {
+            let code = if position < padding {
+                pad_code
+            } else {
+                codes[skipped + position - padding]
+            };
+            if code != 0 {
+                let bit = position * bits_per_position;
+                self.words[first_word + bit / 64] |= (code as u64) << (bit % 64);
+            }
+        }
+        self.times_ps.push(time_ps);
+    }
+
+    /// Re-pack every value into a logic encoding with more states per position
+    fn widen(&mut self, encoding: PackedEncoding) {
+        let bits_per_position = encoding.bits_per_position();
+        let words_per_value = (self.width as usize * bits_per_position).div_ceil(64);
+        let mut words = vec![0; self.len() * words_per_value];
+        for index in 0..self.len() {
+            for (position, code) in self.logic_codes(index).enumerate() {
+                let bit = position * bits_per_position;
+                words[index * words_per_value + bit / 64] |= (code as u64) << (bit % 64);
+            }
+        }
+        self.encoding = encoding;
+        self.words = words;
+    }
+
+    pub fn push_real(&mut self, time_ps: u64, value: f64) {
+        self.times_ps.push(time_ps);
+        self.reals.push(value);
+    }
+
+    pub fn push_text(&mut self, time_ps: u64, value: &str) {
+        if self.string_lookup.len() != self.strings.len() {
+            // Table came over the wire without its lookup index
+            self.string_lookup = self
+                .strings
+                .iter()
+                .enumerate()
+                .map(|(id, string)| (string.clone(), id as u32))
+                .collect();
+        }
+        let id = match self.string_lookup.get(value) {
+            Some(&id) => id,
+            None => {
+                let id = self.strings.len() as u32;
+                self.strings.push(value.to_string());
+                self.string_lookup.insert(value.to_string(), id);
+                id
+            }
+        };
+        self.times_ps.push(time_ps);
+        self.string_ids.push(id);
+    }
+
+    /// Copy transition `index` of `source` (same encoding and width) with a new timestamp.
+    /// Text is interned again, so the two string tables may differ.
+    pub fn push_from(&mut self, source: &PackedTransitions, index: usize, time_ps: u64) {
+        match self.encoding {
+            PackedEncoding::Real => self.reals.push(source.reals[index]),
+            PackedEncoding::Text => {
+                self.push_text(time_ps, &source.strings[source.string_ids[index] as usize]);
+                return;
+            }
+            _ => {
+                let words = self.words_per_value();
+                self.words
+                    .extend_from_slice(&source.words[index * words..(index + 1) * words]);
+            }
+        }
+        self.times_ps.push(time_ps);
+    }
+
+    /// Remove the last transition
+    pub fn pop(&mut self) {
+        if self.times_ps.pop().is_none() {
+            return;
+        }
+        match self.encoding {
+            PackedEncoding::Real => {
+                self.reals.pop();
+            }
+            PackedEncoding::Text => {
+                self.string_ids.pop();
+            }
+            _ => {
+                let words = self.words_per_value();
+                self.words.truncate(self.words.len() - words);
+            }
+        }
+    }
+
+    fn value_words(&self, index: usize) -> &[u64] {
+        let words = self.words_per_value();
+        &self.words[index * words..(index + 1) * words]
+    }
+
+    fn logic_codes(&self, index: usize) -> impl Iterator<Item = u8> + '_ {
+        let bits_per_position = self.encoding.bits_per_position();
+        let mask = (1u64 << bits_per_position) - 1;
+        let words = self.value_words(index);
+        (0..self.width as usize).map(move |position| {
+            let bit = position * bits_per_position;
+            ((words[bit / 64] >> (bit % 64)) & mask) as u8
+        })
+    }
+
+    /// Whether transitions `a` and `b` carry the same value
+    pub fn value_eq(&self, a: usize, b: usize) -> bool {
+        match self.encoding {
+            PackedEncoding::Real => self.reals[a].to_bits() == self.reals[b].to_bits(),
+            PackedEncoding::Text => self.string_ids[a] == self.string_ids[b],
+            _ => self.value_words(a) == self.value_words(b),
+        }
+    }
+
+    /// Value of transition `index` in the textual form used by `SignalTransition`
+    pub fn value_string(&self, index: usize) -> String {
+        match self.encoding {
+            PackedEncoding::Real => format!("{:.6}", self.reals[index]),
+            PackedEncoding::Text => self.strings[self.string_ids[index] as usize].clone(),
+            _ => self
+                .logic_codes(index)
+                .map(|code| LOGIC_STATE_CHARS[code as usize])
+                .collect(),
+        }
+    }
+
+    pub fn real_value(&self, index: usize) -> Option<f64> {
+        match self.encoding {
+            PackedEncoding::Real => Some(self.reals[index]),
+            _ => None,
+        }
+    }
+
+    /// Value of a two-state vector as an integer, when it fits
+    pub fn unsigned_value(&self, index: usize) -> Option<u128> {
+        if self.encoding != PackedEncoding::TwoState || self.width > 128 {
+            return None;
+        }
+        Some(
+            self.logic_codes(index)
+                .fold(0u128, |value, code| (value << 1) | code as u128),
+        )
+    }
+
+    /// Any X, U, W or don't-care position in transition `index`
+    pub fn has_unknown(&self, index: usize) -> bool {
+        self.encoding.is_logic()
+            && self
+                .logic_codes(index)
+                .any(|code| matches!(code, 2 | 4 | 5 | 8))
+    }
+
+    /// Any Z position in transition `index`
+    pub fn has_high_impedance(&self, index: usize) -> bool {
+        self.encoding.is_logic() && self.logic_codes(index).any(|code| code == 3)
+    }
+
+    pub fn to_transitions(&self) -> Vec<SignalTransition> {
+        (0..self.len())
+            .map(|index| SignalTransition::new(self.times_ps[index], self.value_string(index)))
+            .collect()
+    }
+
+    /// Columnar binary form: a header, delta-encoded varint times, then the value blob
+    /// (little-endian words or reals, or a string table followed by varint ids).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.size_bytes());
+        bytes.extend_from_slice(PACKED_MAGIC);
+        bytes.push(PACKED_FORMAT_VERSION);
+        bytes.push(self.encoding as u8);
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&(self.len() as u64).to_le_bytes());
+
+        let mut previous = 0;
+        for &time in &self.times_ps {
+            write_varint(&mut bytes, time.wrapping_sub(previous));
+            previous = time;
+        }
+
+        match self.encoding {
+            PackedEncoding::Real => {
+                for real in &self.reals {
+                    bytes.extend_from_slice(&real.to_le_bytes());
+                }
+            }
+            PackedEncoding::Text => {
+                write_varint(&mut bytes, self.strings.len() as u64);
+                for string in &self.strings {
+                    write_varint(&mut bytes, string.len() as u64);
+                    bytes.extend_from_slice(string.as_bytes());
+                }
+                for &id in &self.string_ids {
+                    write_varint(&mut bytes, id as u64);
+                }
+            }
+            _ => {
+                for word in &self.words {
+                    bytes.extend_from_slice(&word.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = ByteReader { bytes, offset: 0 };
+        if reader.take(PACKED_MAGIC.len())? != PACKED_MAGIC {
+            return Err("Not a packed transition payload".to_string());
+        }
+        let version = reader.take(1)?[0];
+        if version != PACKED_FORMAT_VERSION {
+            return Err(format!("Unsupported packed payload version {}", version));
+        }
+        let encoding = match reader.take(1)?[0] {
+            0 => PackedEncoding::TwoState,
+            1 => PackedEncoding::FourState,
+            2 => PackedEncoding::NineState,
+            3 => PackedEncoding::Real,
+            4 => PackedEncoding::Text,
+            other => return Err(format!("Unknown packed encoding {}", other)),
+        };
+        let width = u32::from_le_bytes(reader.take_array()?);
+        let count = u64::from_le_bytes(reader.take_array()?) as usize;
+        // Every transition needs at least one byte, which bounds allocations from bad input
+        if count > bytes.len() {
+            return Err("Packed payload is truncated".to_string());
+        }
+
+        let mut packed = Self::new(encoding, width);
+        let mut time = 0u64;
+        packed.times_ps.reserve(count);
+        for _ in 0..count {
+            time = time.wrapping_add(reader.varint()?);
+            packed.times_ps.push(time);
+        }
+
+        match encoding {
+            PackedEncoding::Real => {
+                packed.reals = (0..count)
+                    .map(|_| reader.take_array().map(f64::from_le_bytes))
+                    .collect::<Result<_, _>>()?;
+            }
+            PackedEncoding::Text => {
+                let string_count = reader.varint()? as usize;
+                for _ in 0..string_count.min(bytes.len()) {
+                    let length = reader.varint()? as usize;
+                    let string = std::str::from_utf8(reader.take(length)?)
+                        .map_err(|error| format!("Invalid string in packed payload: {}", error))?;
+                    packed.strings.push(string.to_string());
+                }
+                for _ in 0..count {
+                    let id = reader.varint()?;
+                    if id as usize >= packed.strings.len() {
+                        return Err(format!("String id {} out of range", id));
+                    }
+                    packed.string_ids.push(id as u32);
+                }
+            }
+            _ => {
+                let word_count = count
+                    .checked_mul(packed.words_per_value())
+                    .ok_or("Packed payload is too large")?;
+                if word_count > bytes.len() {
+                    return Err("Packed payload is truncated".to_string());
+                }
+                packed.words = (0..word_count)
+                    .map(|_| reader.take_array().map(u64::from_le_bytes))
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+
+        if reader.offset != bytes.len() {
+            return Err("Trailing bytes after packed payload".to_string());
+        }
+        Ok(packed)
+    }
+
+    /// Approximate heap footprint, used for cache budgeting
+    pub fn size_bytes(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.times_ps.len() * std::mem::size_of::<u64>()
+            + self.words.len() * std::mem::size_of::<u64>()
+            + self.reals.len() * std::mem::size_of::<f64>()
+            + self.string_ids.len() * std::mem::size_of::<u32>()
+            + self
+                .strings
+                .iter()
+                .map(|string| string.len() * 2 + std::mem::size_of::<String>() * 2)
+                .sum::<usize>()
+    }
+}
+
+/// Intervals within `start_ps..end_ps` in which two signals hold different values, ascending
+/// and disjoint. Values are compared in their textual form, so a two-state and a four-state
+/// column holding the same bits are equal; a signal without a value yet differs from one
+/// that has a value.
+pub fn difference_intervals(
+    reference: &PackedTransitions,
+    candidate: &PackedTransitions,
+    start_ps: u64,
+    end_ps: u64,
+) -> Vec<(u64, u64)> {
+    let value_at = |packed: &PackedTransitions, index: usize| {
+        index.checked_sub(1).map(|index| packed.value_string(index))
+    };
+    let mut next = (
+        reference.times_ps.partition_point(|&time| time <= start_ps),
+        candidate.times_ps.partition_point(|&time| time <= start_ps),
+    );
+    let mut values = (value_at(reference, next.0), value_at(candidate, next.1));
+    let mut differing_since = (values.0 != values.1).then_some(start_ps);
+    let mut intervals = Vec::new();
+
+    loop {
+        let time_ps = match (
+            reference.times_ps.get(next.0),
+            candidate.times_ps.get(next.1),
+        ) {
+            (Some(&a), Some(&b)) => a.min(b),
+            (Some(&a), None) => a,
+            (None, Some(&b)) => b,
+            (None, None) => break,
+        };
+        if time_ps >= end_ps {
+            break;
+        }
+        // Several transitions at one time step leave the last value
+        if reference.times_ps.get(next.0) == Some(&time_ps) {
+            while reference.times_ps.get(next.0) == Some(&time_ps) {
+                next.0 += 1;
+            }
+            values.0 = value_at(reference, next.0);
+        }
+        if candidate.times_ps.get(next.1) == Some(&time_ps) {
+            while candidate.times_ps.get(next.1) == Some(&time_ps) {
+                next.1 += 1;
+            }
+            values.1 = value_at(candidate, next.1);
+        }
+
+        match (differing_since, values.0 != values.1) {
+            (None, true) => differing_since = Some(time_ps),
+            (Some(since), false) => {
+                intervals.push((since, time_ps));
+                differing_since = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(since) = differing_since.filter(|&since| since < end_ps) {
+        intervals.push((since, end_ps));
+    }
+    intervals
+}
+
+// ===== UNIFIED SIGNAL QUERY TYPES =====
+
+/// Single request for signal data that can include both transitions and cursor values
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnifiedSignalRequest {
+    pub file_path: String,
+    pub scope_path: String,
+    pub variable_name: String,
+    pub time_range_ps: Option<(u64, u64)>, // None = all available data
+    pub max_transitions: Option<usize>,    // For downsampling large datasets
+    pub format: VarFormat,
+}
+
+/// Response containing all requested signal data
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UnifiedSignalData {
+    pub file_path: String,
+    pub scope_path: String,
+    pub variable_name: String,
+    pub unique_id: String, // Computed unique identifier
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<SignalTransition>,
+    /// Compact form sent by the backend instead of `transitions`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packed_transitions: Option<PackedTransitions>,
+    /// Base64 of `PackedTransitions::to_bytes`, sent instead of `packed_transitions`
+    /// when the query asked for `SignalPayloadEncoding::Binary`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packed_binary: Option<String>,
+    pub total_transitions: usize, // Before any downsampling
+    pub actual_time_range_ps: Option<(u64, u64)>, // Actual data boundaries
+    /// Spans collapsed by level-of-detail reduction; `transitions` keeps only their edges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub busy_buckets: Vec<TransitionBucket>,
+}
+
+impl UnifiedSignalData {
+    /// Number of transitions carried in either representation
+    pub fn transition_count(&self) -> usize {
+        self.packed_transitions
+            .as_ref()
+            .map_or(self.transitions.len(), PackedTransitions::len)
+    }
+
+    /// Switch the packed transitions to the requested wire encoding
+    pub fn encode_payload(&mut self, encoding: SignalPayloadEncoding) {
+        if encoding != SignalPayloadEncoding::Binary {
+            return;
+        }
+        if let Some(packed) = self.packed_transitions.take() {
+            self.packed_binary = Some(BASE64_STANDARD.encode(packed.to_bytes()));
+        }
+    }
+
+    /// Move the transitions out in packed form, decoding whichever form the backend sent
+    pub fn take_packed(&mut self) -> Result<PackedTransitions, String> {
+        if let Some(encoded) = self.packed_binary.take() {
+            let bytes = BASE64_STANDARD
+                .decode(encoded)
+                .map_err(|error| format!("Invalid base64 transition payload: {}", error))?;
+            return PackedTransitions::from_bytes(&bytes);
+        }
+        Ok(match self.packed_transitions.take() {
+            Some(packed) => packed,
+            None => PackedTransitions::from_transitions(&std::mem::take(&mut self.transitions)),
+        })
+    }
+}
+
+/// Wire format of transition payloads in `DownMsg::UnifiedSignalResponse`.
+/// Requested per query; backends that predate it ignore the field and answer in JSON,
+/// so frontends ask for `Binary` only once a response lists it in `payload_encodings`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignalPayloadEncoding {
+    /// Packed columns as JSON arrays; readable in devtools
+    #[default]
+    Json,
+    /// Packed columns as one base64 blob
+    Binary,
+}
+
+/// Summary of a span that held more transitions than can be drawn individually.
+/// Glitches inside the span stay visible through the flags and the analog extremes.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TransitionBucket {
+    pub start_ps: u64,
+    pub end_ps: u64,
+    pub transition_count: usize,
+    pub first_value: String,
+    pub last_value: String,
+    pub has_unknown: bool,        // Any X state within the span
+    pub has_high_impedance: bool, // Any Z state within the span
+    pub min_value: Option<f64>,   // Analog signals only
+    pub max_value: Option<f64>,   // Analog signals only
+}
+
+impl TransitionBucket {
+    pub fn has_unknown_states(&self) -> bool {
+        self.has_unknown || self.has_high_impedance
+    }
+}
+
+/// Statistics about signals for performance optimization
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalStatistics {
+    pub total_signals: usize,
+    pub cached_signals: usize,
+    pub query_time_ms: u64,
+    pub cache_hit_ratio: f64,
+    #[serde(default)]
+    pub cache_hits: usize,
+    #[serde(default)]
+    pub cache_evictions: usize,
+    #[serde(default)]
+    pub cache_memory_bytes: usize, // Decoded transitions currently held by the backend
+    #[serde(default)]
+    pub cache_budget_bytes: usize,
+    /// `cache_memory_bytes` split by waveform file
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cache_file_bytes: BTreeMap<String, usize>,
+}
+
+// ===== WAVEFORM EXPORT TYPES =====
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Vcd,
+    Fst,
+    Csv,
+    Json,
+    WaveDrom,
+    Svg,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 6] = [
+        ExportFormat::Vcd,
+        ExportFormat::Fst,
+        ExportFormat::Csv,
+        ExportFormat::Json,
+        ExportFormat::WaveDrom,
+        ExportFormat::Svg,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Vcd => "vcd",
+            ExportFormat::Fst => "fst",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::WaveDrom => "wavedrom.json",
+            ExportFormat::Svg => "svg",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Vcd => "VCD",
+            ExportFormat::Fst => "FST",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::WaveDrom => "WaveDrom",
+            ExportFormat::Svg => "SVG image",
+        }
+    }
+
+    /// CSV / JSON / WaveDrom text formats with values rendered like the UI shows them
+    pub fn is_tabular(&self) -> bool {
+        matches!(
+            self,
+            ExportFormat::Csv | ExportFormat::Json | ExportFormat::WaveDrom
+        )
+    }
+
+    /// Picture of the waveform view, rendered by the frontend and saved via `UpMsg::SaveWaveformImage`
+    pub fn is_image(&self) -> bool {
+        matches!(self, ExportFormat::Svg)
+    }
+}
+
+/// Row layout of CSV / JSON exports. WaveDrom turns rows into diagram steps and samples
+/// clock edges on rising edges only.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum TabularLayout {
+    /// One row per value change of each signal
+    #[default]
+    Changes,
+    /// Values of all signals sampled every `period_ps`, starting at the window start
+    SampledPeriod { period_ps: u64 },
+    /// Values of all signals at every edge of `clock` ("scope_path|variable_name")
+    ClockEdges { clock: String },
+}
+
+/// Signals and time window of one waveform file to export.
+/// The original hierarchy and timescale are kept; timestamps are not shifted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WaveformExportRequest {
+    /// Echoed by `DownMsg::ExportProgress` / `ExportCompleted` / `ExportFailed`
+    pub export_id: String,
+    pub file_path: String,
+    /// "scope_path|variable_name" keys, i.e. `SelectedVariable::unique_id` without the file path
+    pub variables: Vec<String>,
+    /// Full scope paths exported with all variables of their subtree
+    pub scopes: Vec<String>,
+    pub start_ps: u64,
+    pub end_ps: u64,
+    pub format: ExportFormat,
+    pub output_path: String,
+    /// CSV / JSON only
+    #[serde(default)]
+    pub layout: TabularLayout,
+    /// CSV / JSON value formats by "scope_path|variable_name"; missing entries use the default
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variable_formats: BTreeMap<String, VarFormat>,
+    /// Text formats only: return the content in `ExportCompleted` instead of writing `output_path`
+    #[serde(default)]
+    pub copy_to_clipboard: bool,
+}
+
+/// Suggested output next to the source file, e.g. `/dumps/run.vcd.gz` -> `/dumps/run_excerpt.fst`
+pub fn default_export_path(source_path: &str, format: ExportFormat) -> String {
+    let source = std::path::Path::new(strip_compression_extension(source_path));
+    let stem = source
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("waveform");
+    source
+        .with_file_name(format!("{}_excerpt.{}", stem, format.extension()))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Parse a duration such as `10ns`, `2.5 us` or `100` (nanoseconds) into picoseconds
+pub fn parse_duration_ps(text: &str) -> Option<u64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number.trim().parse().ok()?;
+    let ps_per_unit = match unit.trim().to_ascii_lowercase().as_str() {
+        "fs" => 1e-3,
+        "ps" => 1.0,
+        "" | "ns" => 1e3,
+        "us" => 1e6,
+        "ms" => 1e9,
+        "s" => 1e12,
+        _ => return None,
+    };
+    let ps = value * ps_per_unit;
+    (ps.is_finite() && ps >= 0.0).then(|| ps.round() as u64)
+}
+
+/// Rewrites a reference scope path prefix into the candidate's, e.g. `tb.dut` -> `top.core`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PathRemap {
+    pub from: String,
+    pub to: String,
+}
+
+impl PathRemap {
+    /// Candidate scope path of `scope_path` when it lies in `from` or below it
+    pub fn apply(&self, scope_path: &str) -> Option<String> {
+        let rest = scope_path.strip_prefix(self.from.as_str())?;
+        (rest.is_empty() || rest.starts_with('.')).then(|| format!("{}{}", self.to, rest))
+    }
+}
+
+/// "scope_path|variable_name" key of the candidate signal matching a reference signal;
+/// the first remap that applies wins
+pub fn remap_signal_key(key: &str, remaps: &[PathRemap]) -> String {
+    let Some((scope_path, variable_name)) = key.split_once('|') else {
+        return key.to_string();
+    };
+    remaps
+        .iter()
+        .find_map(|remap| remap.apply(scope_path))
+        .map_or_else(
+            || key.to_string(),
+            |scope_path| format!("{}|{}", scope_path, variable_name),
+        )
+}
+
+/// Two loaded files compared signal by signal on the shared timeline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WaveformDiffRequest {
+    /// Echoed by `DownMsg::WaveformDiffProgress` / `WaveformDiffCompleted` / `WaveformDiffFailed`
+    pub diff_id: String,
+    /// The golden run
+    pub reference_file: String,
+    pub candidate_file: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_remaps: Vec<PathRemap>,
+}
+
+/// A signal whose values differ between the two files
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalMismatch {
+    /// "scope_path|variable_name" in the reference file
+    pub reference_key: String,
+    /// "scope_path|variable_name" in the candidate file
+    pub candidate_key: String,
+    pub first_divergence_ps: u64,
+    /// Separate time intervals in which the values differ
+    pub mismatch_intervals: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct WaveformDiffSummary {
+    pub reference_file: String,
+    pub candidate_file: String,
+    /// Signals found in both files
+    pub compared_signals: usize,
+    /// Values are compared up to the end of the shorter file
+    pub compared_until_ps: u64,
+    /// Ordered by first divergence
+    pub mismatches: Vec<SignalMismatch>,
+    /// Reference signals without a counterpart in the candidate file
+    pub missing_in_candidate: Vec<String>,
+    /// Candidate signals without a counterpart in the reference file
+    pub missing_in_reference: Vec<String>,
+    /// Signals that could not be read from either file; they are left out of the comparison
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_signals: Vec<SignalDiffFailure>,
+}
+
+/// A reference signal the comparison skipped because reading it failed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalDiffFailure {
+    /// "scope_path|variable_name" in the reference file
+    pub reference_key: String,
+    pub error: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct FileSignalQueries {
-    pub file_path: String,
-    pub queries: Vec<SignalValueQuery>,
-}
+/// File path part of the unique_id of derived signals; no file is ever loaded from it
+pub const DERIVED_SIGNALS_FILE: &str = "novywave:derived";
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct FileSignalResults {
-    pub file_path: String,
-    pub results: Vec<SignalValueResult>,
+/// Scope path part of the unique_id of derived signals
+pub const DERIVED_SIGNALS_SCOPE: &str = "derived";
+
+/// Signal computed from an expression over selected variables, see `expression`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DerivedSignal {
+    pub name: String,
+    pub expression: String,
+    /// unique_id of the variable each operand of the expression names
+    #[serde(default)]
+    pub inputs: BTreeMap<String, String>,
 }
 
-// ===== SIGNAL TRANSITION QUERY TYPES =====
+impl DerivedSignal {
+    pub fn unique_id(&self) -> String {
+        derived_unique_id(&self.name)
+    }
+}
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SignalTransitionQuery {
-    pub scope_path: String,
-    pub variable_name: String,
+pub fn derived_unique_id(name: &str) -> String {
+    format!(
+        "{}|{}|{}",
+        DERIVED_SIGNALS_FILE, DERIVED_SIGNALS_SCOPE, name
+    )
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SignalTransitionResult {
-    pub scope_path: String,
-    pub variable_name: String,
-    pub transitions: Vec<SignalTransition>,
+/// Name of the derived signal a unique_id refers to
+pub fn derived_signal_name(unique_id: &str) -> Option<&str> {
+    unique_id
+        .strip_prefix(DERIVED_SIGNALS_FILE)?
+        .strip_prefix('|')?
+        .strip_prefix(DERIVED_SIGNALS_SCOPE)?
+        .strip_prefix('|')
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SignalTransition {
-    pub time_ns: u64,
-    pub value: String,
+/// Most matches a `SearchDirection::All` search reports
+pub const MAX_CONDITION_MATCHES: usize = 1000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchDirection {
+    /// First match after `from_ps`
+    Next,
+    /// Last match before `from_ps`
+    Previous,
+    /// Every match, up to `MAX_CONDITION_MATCHES`
+    All,
 }
 
-impl SignalTransition {
-    /// Create new signal transition with nanosecond precision
-    pub fn new(time_ns: u64, value: String) -> Self {
-        Self { time_ns, value }
-    }
+/// Search for the times where a condition over selected variables starts to hold, see
+/// `expression`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConditionSearchRequest {
+    pub search_id: String,
+    pub expression: String,
+    /// unique_id of the variable each operand of the expression names
+    pub inputs: BTreeMap<String, String>,
+    pub from_ps: u64,
+    pub direction: SearchDirection,
 }
 
-// ===== UNIFIED SIGNAL QUERY TYPES =====
+/// Most intervals a `ValueSearchCompleted` carries
+pub const MAX_VALUE_MATCH_INTERVALS: usize = 10_000;
 
-/// Single request for signal data that can include both transitions and cursor values
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct UnifiedSignalRequest {
+/// Search one signal for a value typed in the row's format: hex, octal and binary digits
+/// may be `x` for "don't care", decimal values match numerically and ASCII values match
+/// the characters
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ValueSearchRequest {
+    pub search_id: String,
     pub file_path: String,
     pub scope_path: String,
     pub variable_name: String,
-    pub time_range_ns: Option<(u64, u64)>, // None = all available data
-    pub max_transitions: Option<usize>,    // For downsampling large datasets
     pub format: VarFormat,
+    pub value: String,
 }
 
-/// Response containing all requested signal data
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct UnifiedSignalData {
+/// Compare a variable with the same variable in another loaded file over a time range.
+/// Transitions are compared at full resolution; differing intervals closer together than
+/// `resolution_ps` are merged, so the answer stays about one interval per canvas pixel.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalDifferenceRequest {
+    pub unique_id: String,
+    /// `SelectedVariable::compare_unique_id` of the variable
+    pub compare_unique_id: String,
+    pub range_ps: (u64, u64),
+    pub resolution_ps: u64,
+}
+
+/// Measure a 1-bit signal between two times of the timeline
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignalMeasurementRequest {
+    pub measurement_id: String,
     pub file_path: String,
     pub scope_path: String,
     pub variable_name: String,
-    pub unique_id: String, // Computed unique identifier
-    pub transitions: Vec<SignalTransition>,
-    pub total_transitions: usize, // Before any downsampling
-    pub actual_time_range_ns: Option<(u64, u64)>, // Actual data boundaries
+    pub start_ps: u64,
+    pub end_ps: u64,
 }
 
-/// Statistics about signals for performance optimization
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Copy)]
-pub struct SignalStatistics {
-    pub total_signals: usize,
-    pub cached_signals: usize,
-    pub query_time_ms: u64,
-    pub cache_hit_ratio: f64,
+/// Spread of a set of durations; `max_ps - min_ps` is the peak-to-peak jitter and
+/// `std_dev_ps` the RMS jitter
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DurationStats {
+    pub count: usize,
+    pub min_ps: u64,
+    pub max_ps: u64,
+    pub mean_ps: f64,
+    pub std_dev_ps: f64,
+}
+
+impl DurationStats {
+    pub fn from_durations(durations: &[u64]) -> Option<Self> {
+        let count = durations.len();
+        let min_ps = *durations.iter().min()?;
+        let max_ps = *durations.iter().max()?;
+        let mean_ps = durations.iter().map(|&d| d as f64).sum::<f64>() / count as f64;
+        let variance = durations
+            .iter()
+            .map(|&d| (d as f64 - mean_ps).powi(2))
+            .sum::<f64>()
+            / count as f64;
+        Some(Self {
+            count,
+            min_ps,
+            max_ps,
+            mean_ps,
+            std_dev_ps: variance.sqrt(),
+        })
+    }
+
+    pub fn peak_to_peak_ps(&self) -> u64 {
+        self.max_ps - self.min_ps
+    }
+}
+
+/// Edge and pulse statistics of a 1-bit signal. Pulses and periods count only when both
+/// of their edges lie in the range; `x` and `z` values end them without an edge.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SignalMeasurement {
+    pub rising_edges: usize,
+    pub falling_edges: usize,
+    /// Rising edge to rising edge
+    pub period: Option<DurationStats>,
+    pub high_pulses: Option<DurationStats>,
+    pub low_pulses: Option<DurationStats>,
+    /// Share of the complete periods spent high, 0.0 to 1.0
+    pub duty_cycle: Option<f64>,
+}
+
+impl SignalMeasurement {
+    pub fn frequency_hz(&self) -> Option<f64> {
+        self.period
+            .filter(|period| period.mean_ps > 0.0)
+            .map(|period| 1e12 / period.mean_ps)
+    }
 }
 
 // ===== FILESYSTEM TYPES =====
@@ -465,6 +1739,96 @@ pub struct LoadingFile {
     pub status: LoadingStatus,
 }
 
+/// Stage of an on-demand waveform body load
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadPhase {
+    /// Unpacking a compressed file into the decompression cache
+    Decompressing,
+    Header,
+    Body,
+    Indexing,
+    /// The load finished (successfully or not); no further updates follow
+    Done,
+}
+
+impl LoadPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LoadPhase::Decompressing => "decompressing",
+            LoadPhase::Header => "header",
+            LoadPhase::Body => "body",
+            LoadPhase::Indexing => "indexing",
+            LoadPhase::Done => "done",
+        }
+    }
+}
+
+/// Snapshot of a body load; `total_bytes` is 0 when the size is unknown
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LoadProgress {
+    pub phase: LoadPhase,
+    pub bytes_processed: u64,
+    pub total_bytes: u64,
+    pub elapsed_ms: u64,
+    pub eta_ms: Option<u64>,
+}
+
+impl LoadProgress {
+    /// The ETA extrapolates the byte rate seen since the current phase began
+    /// (`phase_elapsed_ms`) and is only known while bytes are counted
+    pub fn new(
+        phase: LoadPhase,
+        bytes_processed: u64,
+        total_bytes: u64,
+        elapsed_ms: u64,
+        phase_elapsed_ms: u64,
+    ) -> Self {
+        let bytes_processed = bytes_processed.min(total_bytes);
+        let counts_bytes = matches!(phase, LoadPhase::Decompressing | LoadPhase::Body);
+        let eta_ms = if counts_bytes && bytes_processed > 0 && total_bytes > 0 {
+            let remaining = (total_bytes - bytes_processed) as f64;
+            Some((phase_elapsed_ms as f64 * remaining / bytes_processed as f64).round() as u64)
+        } else {
+            None
+        };
+        Self {
+            phase,
+            bytes_processed,
+            total_bytes,
+            elapsed_ms,
+            eta_ms,
+        }
+    }
+
+    /// Fraction of the current phase processed, if the backend can count bytes for it
+    pub fn fraction(&self) -> Option<f32> {
+        match self.phase {
+            LoadPhase::Decompressing | LoadPhase::Body
+                if self.total_bytes > 0 && self.bytes_processed > 0 =>
+            {
+                Some(self.bytes_processed as f32 / self.total_bytes as f32)
+            }
+            LoadPhase::Decompressing | LoadPhase::Header | LoadPhase::Body => None,
+            LoadPhase::Indexing | LoadPhase::Done => Some(1.0),
+        }
+    }
+}
+
+/// Human-readable byte count using binary units, e.g. "1.5 MB"
+pub fn format_byte_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum LoadingStatus {
     Starting,
@@ -486,6 +1850,27 @@ pub struct WaveformFile {
     pub scopes: Vec<ScopeData>,
     pub min_time_ns: Option<u64>,
     pub max_time_ns: Option<u64>,
+    /// Time bounds in picoseconds, exact for files with sub-nanosecond timescales
+    #[serde(default)]
+    pub min_time_ps: Option<u64>,
+    #[serde(default)]
+    pub max_time_ps: Option<u64>,
+    /// Set when the file was cut off (e.g. a crashed simulation) and only loaded up to this time
+    #[serde(default)]
+    pub truncated_at_ns: Option<u64>,
+}
+
+impl WaveformFile {
+    /// Time bounds in picoseconds, falling back to the nanosecond fields of older data
+    pub fn time_range_ps(&self) -> Option<(u64, u64)> {
+        match (self.min_time_ps, self.max_time_ps) {
+            (Some(min), Some(max)) => Some((min, max)),
+            _ => match (self.min_time_ns, self.max_time_ns) {
+                (Some(min), Some(max)) => Some((min * 1000, max * 1000)),
+                _ => None,
+            },
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -493,6 +1878,12 @@ pub enum FileFormat {
     VCD,
     FST,
     GHW,
+    /// WaveDrom timing diagram (JSON), converted on load
+    WaveDrom,
+    /// ngspice / LTspice rawfile with analog vectors, converted on load
+    SpiceRaw,
+    /// Logic-analyzer capture (sigrok, Saleae) as CSV, converted on load
+    LogicCsv,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -528,6 +1919,10 @@ pub struct SelectedVariable {
     pub row_height: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub analog_limits: Option<AnalogLimits>,
+    /// Another loaded file whose variable at the same path is compared with this one;
+    /// the waveform row shades the intervals where the two differ
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compare_file: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -583,6 +1978,7 @@ impl SelectedVariable {
             signal_type: Some(variable.signal_type),
             row_height,
             analog_limits,
+            compare_file: None,
         }
     }
 
@@ -609,6 +2005,7 @@ impl SelectedVariable {
             signal_type: Some(variable.signal_type),
             row_height,
             analog_limits,
+            compare_file: None,
         }
     }
 
@@ -651,6 +2048,13 @@ impl SelectedVariable {
         self.parse_unique_id().map(|(_, _, var)| var)
     }
 
+    /// unique_id of the same scope path and variable in `compare_file`
+    pub fn compare_unique_id(&self) -> Option<String> {
+        let compare_file = self.compare_file.as_ref()?;
+        let (_, scope, var) = self.parse_unique_id()?;
+        Some(format!("{}|{}|{}", compare_file, scope, var))
+    }
+
     /// Create a Signal struct from this SelectedVariable (for backward compatibility)
     pub fn to_signal(&self) -> Option<Signal> {
         let (_, _, variable_name) = self.parse_unique_id()?;
@@ -745,6 +2149,28 @@ impl VarFormat {
         }
     }
 
+    /// Format transition `index` of a packed column. Two-state vectors up to 128 bits are
+    /// formatted straight from their integer value; everything else goes through `format`.
+    pub fn format_packed(&self, packed: &PackedTransitions, index: usize) -> String {
+        if !packed.encoding.is_logic() {
+            return packed.value_string(index);
+        }
+        match (self, packed.unsigned_value(index)) {
+            (VarFormat::Hexadecimal, Some(value)) if packed.width > 0 => format!("{:x}", value),
+            (VarFormat::Octal, Some(value)) if packed.width > 0 => format!("{:o}", value),
+            (VarFormat::Unsigned, Some(value)) if packed.width > 0 => value.to_string(),
+            (VarFormat::Signed, Some(value)) if packed.width > 0 => {
+                let mask = u128::MAX >> (128 - packed.width);
+                if value >> (packed.width - 1) & 1 == 1 {
+                    format!("-{}", (!value).wrapping_add(1) & mask)
+                } else {
+                    value.to_string()
+                }
+            }
+            _ => self.format(&packed.value_string(index)),
+        }
+    }
+
     /// Format a binary string value according to the selected format
     /// Expects the input to be a binary string (e.g., "10110101")
     pub fn format(&self, binary_value: &str) -> String {
@@ -1481,6 +2907,75 @@ pub struct SignalGroupConfig {
     pub collapsed: bool,
 }
 
+/// Placement of a file's times on the shared timeline, for lining up files that do not
+/// start at the same moment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct FileTimeAlignment {
+    /// Added to every time of the file; negative values move it earlier
+    #[serde(default)]
+    pub offset_ps: i64,
+    /// Replaces the timescale declared by the file, e.g. `1ns` or `10ps`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timescale: Option<String>,
+}
+
+impl FileTimeAlignment {
+    pub fn is_identity(&self) -> bool {
+        self.offset_ps == 0 && self.timescale.is_none()
+    }
+
+    /// Seconds per file time unit of the timescale override
+    pub fn timescale_seconds(&self) -> Option<f64> {
+        parse_timescale_seconds(self.timescale.as_deref()?)
+    }
+}
+
+/// Parse a timescale such as `1ns`, `10 ps` or `100fs` into seconds per time unit
+pub fn parse_timescale_seconds(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| c.is_ascii_alphabetic())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let factor: f64 = match number.trim() {
+        "" => 1.0,
+        number => number.parse().ok()?,
+    };
+    let seconds_per_unit = match unit.trim().to_ascii_lowercase().as_str() {
+        "fs" => 1e-15,
+        "ps" => 1e-12,
+        "ns" => 1e-9,
+        "us" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        _ => return None,
+    };
+    let seconds = factor * seconds_per_unit;
+    (seconds.is_finite() && seconds > 0.0).then_some(seconds)
+}
+
+/// How the columns of a logic-analyzer CSV capture become signals
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CsvImportSettings {
+    /// Header of the time column; the first column whose header mentions time when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_column: Option<String>,
+    /// Unit of the time column (`s`, `ms`, `us`, `ns`, `ps` or `fs`); read from the header
+    /// (`Time [s]`) or seconds when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buses: Vec<CsvBusGroup>,
+}
+
+/// 1-bit channels of a CSV capture combined into one bus
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsvBusGroup {
+    pub name: String,
+    /// Channel column headers, most significant bit first
+    pub channels: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct MarkerConfig {
     pub time_ps: u64,
@@ -1488,25 +2983,77 @@ pub struct MarkerConfig {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "TimelineConfigFile")]
 pub struct TimelineConfig {
-    #[serde(default = "default_cursor_position_ps")]
     pub cursor_position_ps: u64,
-    #[serde(default = "default_visible_range_start_ps")]
+    /// Second cursor for A/B time measurements, if placed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary_cursor_ps: Option<u64>,
     pub visible_range_start_ps: u64,
-    #[serde(default = "default_visible_range_end_ps")]
     pub visible_range_end_ps: u64,
-    #[serde(default = "default_zoom_center_ps")]
     pub zoom_center_ps: u64,
-    #[serde(default = "default_tooltip_enabled")]
     pub tooltip_enabled: bool,
-    #[serde(default)]
     pub markers: Vec<MarkerConfig>,
 }
 
+/// On-disk shape of `TimelineConfig`; also accepts the nanosecond keys written by older releases
+#[derive(Deserialize)]
+struct TimelineConfigFile {
+    cursor_position_ps: Option<u64>,
+    #[serde(default)]
+    secondary_cursor_ps: Option<u64>,
+    visible_range_start_ps: Option<u64>,
+    visible_range_end_ps: Option<u64>,
+    zoom_center_ps: Option<u64>,
+    cursor_position_ns: Option<u64>,
+    visible_range_start_ns: Option<u64>,
+    visible_range_end_ns: Option<u64>,
+    zoom_center_ns: Option<u64>,
+    #[serde(default = "default_tooltip_enabled")]
+    tooltip_enabled: bool,
+    #[serde(default)]
+    markers: Vec<MarkerConfig>,
+}
+
+impl From<TimelineConfigFile> for TimelineConfig {
+    fn from(file: TimelineConfigFile) -> Self {
+        let resolve = |ps: Option<u64>, ns: Option<u64>, default: fn() -> u64| {
+            ps.or_else(|| ns.map(|ns| ns.saturating_mul(PS_PER_NS)))
+                .unwrap_or_else(default)
+        };
+        Self {
+            cursor_position_ps: resolve(
+                file.cursor_position_ps,
+                file.cursor_position_ns,
+                default_cursor_position_ps,
+            ),
+            secondary_cursor_ps: file.secondary_cursor_ps,
+            visible_range_start_ps: resolve(
+                file.visible_range_start_ps,
+                file.visible_range_start_ns,
+                default_visible_range_start_ps,
+            ),
+            visible_range_end_ps: resolve(
+                file.visible_range_end_ps,
+                file.visible_range_end_ns,
+                default_visible_range_end_ps,
+            ),
+            zoom_center_ps: resolve(
+                file.zoom_center_ps,
+                file.zoom_center_ns,
+                default_zoom_center_ps,
+            ),
+            tooltip_enabled: file.tooltip_enabled,
+            markers: file.markers,
+        }
+    }
+}
+
 impl Default for TimelineConfig {
     fn default() -> Self {
         Self {
             cursor_position_ps: default_cursor_position_ps(),
+            secondary_cursor_ps: None,
             visible_range_start_ps: default_visible_range_start_ps(),
             visible_range_end_ps: default_visible_range_end_ps(),
             zoom_center_ps: default_zoom_center_ps(),
@@ -1543,6 +3090,9 @@ where
 pub struct WorkspaceSection {
     #[serde(default, deserialize_with = "deserialize_opened_files")]
     pub opened_files: Vec<CanonicalPathPayload>,
+    /// Time offset and timescale override of opened files by canonical file path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_time_alignments: BTreeMap<String, FileTimeAlignment>,
     #[serde(default, deserialize_with = "deserialize_dock_mode")]
     pub dock_mode: DockMode,
     #[serde(default)]
@@ -1565,12 +3115,21 @@ pub struct WorkspaceSection {
     pub signal_groups: Vec<SignalGroupConfig>,
     #[serde(default)]
     pub timeline: TimelineConfig,
+    /// Import settings of logic-analyzer CSV files by canonical file path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub csv_imports: BTreeMap<String, CsvImportSettings>,
+    /// Scope path remaps used when comparing two files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diff_path_remaps: Vec<PathRemap>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived_signals: Vec<DerivedSignal>,
 }
 
 impl Default for WorkspaceSection {
     fn default() -> Self {
         Self {
             opened_files: Vec::new(),
+            file_time_alignments: BTreeMap::new(),
             dock_mode: DockMode::Right,
             expanded_scopes: Vec::new(),
             load_files_expanded_directories: Vec::new(),
@@ -1582,6 +3141,9 @@ impl Default for WorkspaceSection {
             selected_variables: Vec::new(),
             signal_groups: Vec::new(),
             timeline: TimelineConfig::default(),
+            csv_imports: BTreeMap::new(),
+            diff_path_remaps: Vec::new(),
+            derived_signals: Vec::new(),
         }
     }
 }
@@ -2091,14 +3653,51 @@ impl CanonicalPathPayload {
 
 // ===== FILESYSTEM UTILITIES =====
 
+/// Compression wrapping a waveform file, e.g. `dump.vcd.gz`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl CompressionFormat {
+    pub fn from_path(path: &str) -> Option<Self> {
+        match get_file_extension(path)?.as_str() {
+            "gz" => Some(CompressionFormat::Gzip),
+            "zst" => Some(CompressionFormat::Zstd),
+            "xz" => Some(CompressionFormat::Xz),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CompressionFormat::Gzip => "gz",
+            CompressionFormat::Zstd => "zst",
+            CompressionFormat::Xz => "xz",
+        }
+    }
+}
+
+/// Path of the file inside a compressed one (`dump.vcd.gz` -> `dump.vcd`); other paths are returned unchanged
+pub fn strip_compression_extension(path: &str) -> &str {
+    match CompressionFormat::from_path(path) {
+        Some(format) => &path[..path.len() - format.extension().len() - 1],
+        None => path,
+    }
+}
+
 pub fn is_waveform_file(path: &str) -> bool {
-    if let Some(extension) = std::path::Path::new(path)
+    if let Some(extension) = std::path::Path::new(strip_compression_extension(path))
         .extension()
         .and_then(|ext| ext.to_str())
     {
         match extension.to_lowercase().as_str() {
             // Supported waveform formats
             "vcd" | "fst" | "ghw" => true,
+            // WaveDrom diagrams, SPICE rawfiles and logic-analyzer CSV, imported by the backend
+            "json" | "raw" | "csv" => true,
 
             // DISABLED: Additional waveform formats pending testing
             // "vzt" => true,  // GTKWave compressed format
@@ -2617,6 +4216,51 @@ mod tests {
         assert_eq!(section.opened_files[0].display(), "/tmp/sample.vcd");
     }
 
+    #[test]
+    fn timeline_config_accepts_legacy_nanosecond_keys() {
+        let legacy: TimelineConfig = toml::from_str(
+            r#"
+cursor_position_ns = 1500
+visible_range_start_ns = 1000
+visible_range_end_ns = 2000
+"#,
+        )
+        .expect("legacy timeline config");
+
+        assert_eq!(legacy.cursor_position_ps, 1_500_000);
+        assert_eq!(legacy.visible_range_start_ps, 1_000_000);
+        assert_eq!(legacy.visible_range_end_ps, 2_000_000);
+        assert_eq!(legacy.zoom_center_ps, 0);
+        assert!(legacy.tooltip_enabled);
+        assert!(legacy.markers.is_empty());
+
+        let current: TimelineConfig = toml::from_str(
+            r#"
+cursor_position_ps = 1500
+cursor_position_ns = 9
+"#,
+        )
+        .expect("current timeline config");
+        assert_eq!(current.cursor_position_ps, 1500);
+        assert_eq!(current.visible_range_end_ps, DEFAULT_TIMELINE_RANGE_PS);
+        assert_eq!(current.secondary_cursor_ps, None);
+    }
+
+    #[test]
+    fn timeline_config_round_trips_secondary_cursor() {
+        let config = TimelineConfig {
+            secondary_cursor_ps: Some(2_500),
+            ..TimelineConfig::default()
+        };
+        let text = toml::to_string(&config).expect("serialize timeline config");
+        assert!(text.contains("secondary_cursor_ps = 2500"));
+        let restored: TimelineConfig = toml::from_str(&text).expect("timeline config");
+        assert_eq!(restored, config);
+
+        let without = toml::to_string(&TimelineConfig::default()).unwrap();
+        assert!(!without.contains("secondary_cursor_ps"));
+    }
+
     #[test]
     fn cursor_values_btreemap_lookup_with_unique_id() {
         let mut map: BTreeMap<String, SignalValue> = BTreeMap::new();
@@ -2702,4 +4346,300 @@ mod tests {
             "Backend and frontend should produce identical unique_id format"
         );
     }
+
+    #[test]
+    fn packed_transitions_round_trip_each_encoding() {
+        let cases = [
+            (vec!["0000", "1010", "1111"], PackedEncoding::TwoState),
+            (vec!["01xz", "zzzz", "0000"], PackedEncoding::FourState),
+            (vec!["uu01", "-hlw", "0000"], PackedEncoding::NineState),
+            (vec!["1.500000", "-0.250000"], PackedEncoding::Real),
+            (vec!["IDLE", "BUSY", "IDLE"], PackedEncoding::Text),
+        ];
+        for (values, expected_encoding) in cases {
+            let transitions: Vec<SignalTransition> = values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| SignalTransition::new(index as u64 * 10, value.to_string()))
+                .collect();
+            let packed = PackedTransitions::from_transitions(&transitions);
+            assert_eq!(packed.encoding, expected_encoding);
+
+            let json = serde_json::to_string(&packed).unwrap();
+            let decoded: PackedTransitions = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded.to_transitions(), transitions);
+        }
+    }
+
+    #[test]
+    fn packed_transitions_interns_strings_and_wide_vectors() {
+        let mut text = PackedTransitions::new(PackedEncoding::Text, 0);
+        for (time, value) in [(0, "IDLE"), (5, "BUSY"), (9, "IDLE")] {
+            text.push_text(time, value);
+        }
+        assert_eq!(text.strings.len(), 2);
+        assert!(text.value_eq(0, 2));
+
+        let wide = format!("x{}", "1".repeat(99));
+        let mut vector = PackedTransitions::new(PackedEncoding::FourState, 100);
+        vector.push_bits(0, &wide);
+        vector.push_bits(1, "z1");
+        assert_eq!(vector.words_per_value(), 4);
+        assert_eq!(vector.value_string(0), wide);
+        assert_eq!(vector.value_string(1), format!("{}1", "z".repeat(99)));
+        assert!(vector.has_unknown(0) && !vector.has_high_impedance(0));
+        assert!(vector.has_high_impedance(1));
+    }
+
+    #[test]
+    fn two_state_columns_widen_when_a_value_needs_more_states() {
+        let mut bit = PackedTransitions::new(PackedEncoding::TwoState, 1);
+        for (time_ps, value) in [(0, "0"), (10, "x"), (20, "1")] {
+            bit.push_bits(time_ps, value);
+        }
+        assert_eq!(bit.encoding, PackedEncoding::FourState);
+        assert_eq!(
+            bit.to_transitions(),
+            PackedTransitions::from_pairs(&[(0, "0"), (10, "x"), (20, "1")]).to_transitions()
+        );
+
+        let mut bus = PackedTransitions::new(PackedEncoding::TwoState, 2);
+        bus.push_bits(0, "01");
+        bus.push_bits(10, "x1");
+        bus.push_bits(20, "h1");
+        assert_eq!(bus.encoding, PackedEncoding::NineState);
+        let values: Vec<String> = (0..bus.len())
+            .map(|index| bus.value_string(index))
+            .collect();
+        assert_eq!(values, ["01", "x1", "h1"]);
+    }
+
+    #[test]
+    fn unified_signal_data_binary_payload_round_trips() {
+        let transitions = vec![
+            SignalTransition::new(0, "IDLE".to_string()),
+            SignalTransition::new(1_500, "BUSY".to_string()),
+            SignalTransition::new(u64::MAX, "IDLE".to_string()),
+        ];
+        let mut data = UnifiedSignalData {
+            file_path: "a.vcd".to_string(),
+            scope_path: "top".to_string(),
+            variable_name: "state".to_string(),
+            unique_id: "a.vcd|top|state".to_string(),
+            transitions: Vec::new(),
+            packed_transitions: Some(PackedTransitions::from_transitions(&transitions)),
+            packed_binary: None,
+            total_transitions: 3,
+            actual_time_range_ps: Some((0, u64::MAX)),
+            busy_buckets: Vec::new(),
+        };
+        data.encode_payload(SignalPayloadEncoding::Binary);
+        assert!(data.packed_transitions.is_none());
+
+        let json = serde_json::to_string(&data).unwrap();
+        let mut decoded: UnifiedSignalData = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.take_packed().unwrap().to_transitions(), transitions);
+
+        let mut vector = PackedTransitions::new(PackedEncoding::NineState, 20);
+        vector.push_bits(7, "01xzuwlh-");
+        let bytes = vector.to_bytes();
+        assert_eq!(
+            PackedTransitions::from_bytes(&bytes)
+                .unwrap()
+                .to_transitions(),
+            vector.to_transitions()
+        );
+        assert!(PackedTransitions::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn non_finite_reals_survive_json() {
+        let mut packed = PackedTransitions::new(PackedEncoding::Real, 0);
+        packed.push_real(0, 1.5);
+        packed.push_real(10, f64::INFINITY);
+        packed.push_real(20, f64::NEG_INFINITY);
+        packed.push_value(30, "not a number");
+
+        let json = serde_json::to_string(&packed).unwrap();
+        let decoded: PackedTransitions = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.reals[..3], [1.5, f64::INFINITY, f64::NEG_INFINITY]);
+        assert!(decoded.reals[3].is_nan());
+        assert!(
+            serde_json::from_str::<PackedTransitions>(
+                r#"{"encoding":"Real","width":0,"times_ps":[0],"reals":["one"]}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn difference_intervals_cover_every_differing_span() {
+        let packed = PackedTransitions::from_pairs;
+        let golden = packed(&[(0, "0"), (10, "1"), (20, "0"), (30, "1")]);
+        let run = packed(&[
+            (0, "0"),
+            (10, "1"),
+            (12, "0"),
+            (15, "1"),
+            (20, "0"),
+            (28, "1"),
+        ]);
+
+        assert_eq!(
+            difference_intervals(&golden, &run, 0, 40),
+            vec![(12, 15), (28, 30)]
+        );
+        // A range starting inside a difference picks up the values held at its start
+        assert_eq!(
+            difference_intervals(&golden, &run, 13, 29),
+            vec![(13, 15), (28, 29)]
+        );
+        assert!(difference_intervals(&golden, &golden, 0, 40).is_empty());
+
+        // A missing value differs from any value; equal bits in wider encodings match
+        let late = packed(&[(5, "1"), (10, "x"), (11, "0"), (20, "0")]);
+        assert_eq!(difference_intervals(&golden, &late, 0, 25), vec![(0, 20)]);
+        let four_state = packed(&[(0, "0"), (10, "1"), (20, "z"), (25, "0"), (30, "1")]);
+        assert_eq!(
+            difference_intervals(&golden, &four_state, 0, u64::MAX),
+            vec![(20, 25)]
+        );
+    }
+
+    #[test]
+    fn load_progress_extrapolates_eta_from_byte_rate() {
+        let progress = LoadProgress::new(LoadPhase::Body, 250, 1_000, 2_000, 2_000);
+        assert_eq!(progress.eta_ms, Some(6_000));
+        assert_eq!(progress.fraction(), Some(0.25));
+
+        // Time spent on the header does not slow the body's byte rate down
+        let after_header = LoadProgress::new(LoadPhase::Body, 250, 1_000, 10_000, 2_000);
+        assert_eq!(after_header.eta_ms, Some(6_000));
+        assert_eq!(after_header.elapsed_ms, 10_000);
+
+        // FST bodies report no byte counts
+        let unknown = LoadProgress::new(LoadPhase::Body, 0, 1_000, 2_000, 2_000);
+        assert_eq!((unknown.eta_ms, unknown.fraction()), (None, None));
+
+        let overshoot = LoadProgress::new(LoadPhase::Body, 1_200, 1_000, 2_000, 2_000);
+        assert_eq!(overshoot.eta_ms, Some(0));
+        assert_eq!(
+            LoadProgress::new(LoadPhase::Header, 0, 1_000, 10, 10).eta_ms,
+            None
+        );
+        assert_eq!(format_byte_size(512), "512 B");
+        assert_eq!(format_byte_size(3 * 1024 * 1024 / 2), "1.5 MB");
+    }
+
+    #[test]
+    fn default_export_path_sits_next_to_the_source() {
+        assert_eq!(
+            default_export_path("/dumps/run.vcd.gz", ExportFormat::Fst),
+            "/dumps/run_excerpt.fst"
+        );
+        assert_eq!(
+            default_export_path("run.vcd", ExportFormat::Vcd),
+            "run_excerpt.vcd"
+        );
+    }
+
+    #[test]
+    fn durations_parse_to_picoseconds() {
+        assert_eq!(parse_duration_ps("10ns"), Some(10_000));
+        assert_eq!(parse_duration_ps(" 2.5 us "), Some(2_500_000));
+        assert_eq!(parse_duration_ps("100"), Some(100_000));
+        assert_eq!(parse_duration_ps("1s"), Some(1_000_000_000_000));
+        assert_eq!(parse_duration_ps("500fs"), Some(1));
+        assert_eq!(parse_duration_ps("3 cycles"), None);
+        assert_eq!(parse_duration_ps("-1ns"), None);
+    }
+
+    #[test]
+    fn timescale_overrides_parse_to_seconds() {
+        assert_eq!(parse_timescale_seconds("1ns"), Some(1e-9));
+        assert_eq!(parse_timescale_seconds(" 10 ps"), Some(1e-11));
+        assert_eq!(parse_timescale_seconds("us"), Some(1e-6));
+        assert_eq!(parse_timescale_seconds("0ns"), None);
+        assert_eq!(parse_timescale_seconds("10"), None);
+
+        let alignment: WorkspaceSection = toml::from_str(
+            "[file_time_alignments.\"run.vcd\"]\noffset_ps = -2000\ntimescale = \"100fs\"\n",
+        )
+        .unwrap();
+        let alignment = &alignment.file_time_alignments["run.vcd"];
+        assert_eq!(alignment.offset_ps, -2000);
+        assert_eq!(alignment.timescale_seconds(), Some(1e-13));
+        assert!(FileTimeAlignment::default().is_identity());
+    }
+
+    #[test]
+    fn signal_keys_are_remapped_by_scope_prefix() {
+        let remaps = [
+            PathRemap {
+                from: "tb.dut".to_string(),
+                to: "top.core".to_string(),
+            },
+            PathRemap {
+                from: "tb".to_string(),
+                to: "top".to_string(),
+            },
+        ];
+        assert_eq!(
+            remap_signal_key("tb.dut.alu|acc", &remaps),
+            "top.core.alu|acc"
+        );
+        assert_eq!(remap_signal_key("tb.dut|clk", &remaps), "top.core|clk");
+        assert_eq!(remap_signal_key("tb.dut2|clk", &remaps), "top.dut2|clk");
+        assert_eq!(remap_signal_key("other|clk", &remaps), "other|clk");
+    }
+
+    #[test]
+    fn compressed_waveform_files_are_recognised() {
+        assert!(is_waveform_file("/dumps/run.vcd.gz"));
+        assert!(is_waveform_file("run.FST.ZST"));
+        assert!(is_waveform_file("run.vcd.xz"));
+        assert!(!is_waveform_file("notes.txt.gz"));
+        assert!(!is_waveform_file("archive.gz"));
+        assert_eq!(
+            CompressionFormat::from_path("run.vcd.zst"),
+            Some(CompressionFormat::Zstd)
+        );
+        assert_eq!(strip_compression_extension("a/run.vcd.gz"), "a/run.vcd");
+        assert_eq!(strip_compression_extension("a/run.vcd"), "a/run.vcd");
+    }
+
+    #[test]
+    fn format_packed_matches_string_formatting() {
+        let values = [
+            "0",
+            "1",
+            "00000000",
+            "10110101",
+            "01000001",
+            "11111111",
+            "1000000000000001",
+        ];
+        let formats = [
+            VarFormat::ASCII,
+            VarFormat::Binary,
+            VarFormat::BinaryWithGroups,
+            VarFormat::Hexadecimal,
+            VarFormat::Octal,
+            VarFormat::Signed,
+            VarFormat::Unsigned,
+        ];
+        for value in values {
+            let mut packed = PackedTransitions::new(PackedEncoding::TwoState, value.len() as u32);
+            packed.push_bits(0, value);
+            for format in formats {
+                assert_eq!(
+                    format.format_packed(&packed, 0),
+                    format.format(value),
+                    "{:?} of {}",
+                    format,
+                    value
+                );
+            }
+        }
+    }
 }
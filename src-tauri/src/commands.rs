@@ -271,6 +271,9 @@ pub async fn load_waveform_file(path: String, window: tauri::Window) -> Result<(
             }],
             min_time_ns: Some(0),
             max_time_ns: Some(1_000_000_000),
+            min_time_ps: Some(0),
+            max_time_ps: Some(1_000_000_000_000),
+            truncated_at_ns: None,
         }],
     };
 